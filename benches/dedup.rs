@@ -0,0 +1,80 @@
+//! Times deduplicating 100k marks, each given twice, which hashes and
+//! compares every mark's message. Run with `cargo bench --bench dedup`.
+//!
+//! The first pass over marks with cold caches extends each key once; later
+//! passes reuse the cached extended keys. The baseline rebuilds each message
+//! with [`obfuscate`], extending the key every time, as marks did before they
+//! cached it.
+
+use std::{
+    collections::HashSet,
+    hint::black_box,
+    time::{Duration, Instant},
+};
+
+use dcbor::Date;
+use provenance_mark::{crypto_utils::obfuscate, *};
+
+const COUNT: u32 = 100_000;
+
+fn time<T>(label: &str, f: impl FnOnce() -> T) -> Duration {
+    let start = Instant::now();
+    black_box(f());
+    let elapsed = start.elapsed();
+    println!("{label:<28} {:>10.1} ms", elapsed.as_secs_f64() * 1000.0);
+    elapsed
+}
+
+/// Copies of `marks` with nothing cached.
+fn cold(marks: &[ProvenanceMark]) -> Vec<ProvenanceMark> {
+    marks
+        .iter()
+        .map(|mark| {
+            ProvenanceMark::from_fields(
+                mark.res(),
+                mark.key().clone(),
+                mark.hash().clone(),
+                mark.chain_id().clone(),
+                mark.seq().into(),
+                mark.date(),
+                Vec::new(),
+            )
+            .unwrap()
+        })
+        .collect()
+}
+
+fn main() {
+    let mut generator = ProvenanceMarkGenerator::new_with_passphrase(
+        ProvenanceMarkResolution::Medium,
+        "bench",
+    );
+    let date = Date::from_string("2023-06-20").unwrap();
+    let marks: Vec<ProvenanceMark> = (0..COUNT)
+        .map(|_| generator.next(date, None::<String>))
+        .collect();
+    let doubled: Vec<ProvenanceMark> =
+        cold(&marks).into_iter().chain(cold(&marks)).collect();
+
+    let baseline = time("uncached (baseline)", || {
+        doubled
+            .iter()
+            .map(|mark| {
+                [mark.key().to_vec(), obfuscate(mark.key(), mark.payload())]
+                    .concat()
+            })
+            .collect::<HashSet<_>>()
+    });
+    time("cold caches", || {
+        doubled.iter().collect::<HashSet<_>>().len()
+    });
+    let warm = time("warm caches", || {
+        doubled.iter().collect::<HashSet<_>>().len()
+    });
+    println!(
+        "speedup with warm caches: {:.1}x",
+        baseline.as_secs_f64() / warm.as_secs_f64()
+    );
+
+    assert_eq!(doubled.iter().collect::<HashSet<_>>().len(), COUNT as usize);
+}
@@ -0,0 +1,110 @@
+#ifndef PROVENANCE_MARK_H
+#define PROVENANCE_MARK_H
+
+/* Generated by cbindgen from src/ffi.rs. Do not edit by hand. */
+
+#include <stddef.h>
+#include <stdint.h>
+
+/**
+ * An opaque handle to a parsed mark.
+ */
+typedef struct PmMark PmMark;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+/**
+ * Parse a `ur:provenance` string into a mark handle.
+ *
+ * Returns null and records an error if `ur` is null, is not UTF-8, or does
+ * not decode as a mark.
+ *
+ * # Safety
+ *
+ * `ur` must be null or a pointer to a NUL-terminated string.
+ */
+PmMark *pm_mark_parse_ur(const char *ur);
+
+/**
+ * Copy the mark's identifier, its first four Mark ID bytes as upper-case
+ * ByteWords such as `WHAT HANG SOAP SETS`, into `buf`.
+ *
+ * Returns the length of the identifier, or 0 if `mark` is null.
+ *
+ * # Safety
+ *
+ * `mark` must be null or a live handle, and `buf` must be null or valid for
+ * writes of `len` bytes.
+ */
+size_t pm_mark_identifier(const PmMark *mark, char *buf, size_t len);
+
+/**
+ * The mark's sequence number, or 0 if `mark` is null.
+ *
+ * # Safety
+ *
+ * `mark` must be null or a live handle.
+ */
+uint32_t pm_mark_seq(const PmMark *mark);
+
+/**
+ * The mark's date as seconds since the Unix epoch, or 0 if `mark` is null.
+ *
+ * # Safety
+ *
+ * `mark` must be null or a live handle.
+ */
+int64_t pm_mark_date(const PmMark *mark);
+
+/**
+ * Copy up to `len` bytes of the mark's chain ID into `buf`.
+ *
+ * Returns the length of the chain ID, which is between 4 and 32 bytes
+ * depending on the mark's resolution, or 0 if `mark` is null.
+ *
+ * # Safety
+ *
+ * `mark` must be null or a live handle, and `buf` must be null or valid for
+ * writes of `len` bytes.
+ */
+size_t pm_mark_chain_id(const PmMark *mark, uint8_t *buf, size_t len);
+
+/**
+ * Whether `next` directly follows `previous` in the same chain.
+ *
+ * Returns 1 if it does and 0 if it does not, recording the reason as the
+ * last error. Returns -1 if either handle is null.
+ *
+ * # Safety
+ *
+ * `previous` and `next` must each be null or a live handle.
+ */
+int pm_mark_precedes(const PmMark *previous, const PmMark *next);
+
+/**
+ * Release a mark handle. Passing null does nothing.
+ *
+ * # Safety
+ *
+ * `mark` must be null or a live handle, which must not be used afterwards.
+ */
+void pm_mark_free(PmMark *mark);
+
+/**
+ * Copy the message of the last error on this thread into `buf`.
+ *
+ * Returns the length of the message, or 0 if no error has occurred.
+ *
+ * # Safety
+ *
+ * `buf` must be null or valid for writes of `len` bytes.
+ */
+size_t pm_last_error_message(char *buf, size_t len);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* PROVENANCE_MARK_H */
@@ -27,3 +27,24 @@ section "All Default Features"
 cargo test > /dev/null
 
 test_additional_features "envelope"
+test_additional_features "fs"
+test_additional_features "examples"
+test_additional_features "mmap"
+test_additional_features "tracing"
+test_additional_features "components"
+test_additional_features "ffi"
+test_additional_features "rayon"
+test_additional_features "clap"
+test_additional_features "arrow"
+
+test_only_features "core-verify"
+test_only_features "core-verify,url"
+test_only_features "core-verify,json"
+test_only_features "core-verify,markdown,json"
+
+section "core-verify dependencies"
+if cargo tree --no-default-features --features core-verify -e normal \
+    | grep -E '^[^a-z]*(url|serde_json) v'; then
+    echo "core-verify must not depend on url or serde_json"
+    exit 1
+fi
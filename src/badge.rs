@@ -0,0 +1,111 @@
+use crate::ChainReport;
+
+/// The symbol and word ending a badge.
+const VERIFIED: &str = "✓ verified";
+const UNVERIFIED: &str = "✗ unverified";
+
+/// The text of each part of a chain's badge.
+struct BadgeParts {
+    chain_id: String,
+    count: String,
+    latest: Option<String>,
+    verified: bool,
+}
+
+impl ChainReport {
+    /// A one-line summary of the chain for embedding in page footers, such
+    /// as `Provenance: chain b16a7cbd · 42 marks · latest 2024-11-03 · ✓
+    /// verified`.
+    ///
+    /// The chain is shown by the first four bytes of its ID, and the latest
+    /// date is that of its most recently dated mark, as `YYYY-MM-DD` in UTC.
+    /// The badge ends `✓ verified` if the chain has its genesis mark and no
+    /// issue that breaks its continuity, and `✗ unverified` otherwise.
+    /// Warnings, such as future-dated marks, do not affect the badge.
+    pub fn badge(&self) -> String {
+        let parts = self.badge_parts();
+        let mut fields =
+            vec![format!("Provenance: chain {}", parts.chain_id), parts.count];
+        fields.extend(parts.latest.map(|date| format!("latest {}", date)));
+        fields.push(
+            if parts.verified { VERIFIED } else { UNVERIFIED }.to_string(),
+        );
+        fields.join(" · ")
+    }
+
+    /// The [`badge`](Self::badge) as an HTML fragment: a
+    /// `<span class="provenance-badge">` with the chain ID in `<code>`, the
+    /// latest date in `<time>`, and the verdict in a span of class
+    /// `provenance-verified` or `provenance-unverified`. All text is
+    /// escaped.
+    pub fn badge_html(&self) -> String {
+        let parts = self.badge_parts();
+        let mut fields = vec![
+            format!(
+                "Provenance: chain <code>{}</code>",
+                escape_html(&parts.chain_id)
+            ),
+            escape_html(&parts.count),
+        ];
+        fields.extend(parts.latest.map(|date| {
+            let date = escape_html(&date);
+            format!("latest <time datetime=\"{}\">{}</time>", date, date)
+        }));
+        let (class, verdict) = if parts.verified {
+            ("provenance-verified", VERIFIED)
+        } else {
+            ("provenance-unverified", UNVERIFIED)
+        };
+        fields.push(format!(
+            "<span class=\"{}\">{}</span>",
+            class,
+            escape_html(verdict)
+        ));
+        format!(
+            "<span class=\"provenance-badge\">{}</span>",
+            fields.join(" · ")
+        )
+    }
+
+    fn badge_parts(&self) -> BadgeParts {
+        let chain_id = &self.chain_id()[..self.chain_id().len().min(4)];
+        let count = match self.marks().len() {
+            1 => "1 mark".to_string(),
+            count => format!("{} marks", count),
+        };
+        let latest = self
+            .marks()
+            .iter()
+            .map(|mark| mark.date())
+            .max_by_key(|date| date.datetime())
+            .map(|date| date.datetime().format("%Y-%m-%d").to_string());
+        let verified = self.has_genesis()
+            && self
+                .sequences()
+                .iter()
+                .flat_map(|sequence| sequence.marks())
+                .flat_map(|flagged_mark| flagged_mark.issues())
+                .all(|issue| !issue.breaks_sequence());
+        BadgeParts {
+            chain_id: hex::encode(chain_id),
+            count,
+            latest,
+            verified,
+        }
+    }
+}
+
+fn escape_html(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
@@ -0,0 +1,121 @@
+//! A compact binary archive of provenance marks, readable lazily from a
+//! memory-mapped file.
+//!
+//! ```text
+//! magic     8 bytes   "PMARCHV1"
+//! frame*
+//!   res     1 byte    ProvenanceMarkResolution
+//!   length  4 bytes   big-endian length of the message
+//!   message length bytes, as returned by ProvenanceMark::message()
+//! ```
+//!
+//! Reading an archive only touches the pages of the file that hold the frames
+//! being decoded, so multi-gigabyte archives can be validated without first
+//! reading them into memory.
+
+use std::{fs::File, io::Write, path::Path};
+
+use memmap2::Mmap;
+
+use crate::{Error, ProvenanceMark, ProvenanceMarkResolution, Result};
+
+/// The bytes every archive begins with.
+pub const ARCHIVE_MAGIC: &[u8; 8] = b"PMARCHV1";
+
+const FRAME_HEADER_LENGTH: usize = 5;
+
+/// Writes `marks` to `w` in archive format.
+pub fn write_archive<'a>(
+    mut w: impl Write,
+    marks: impl IntoIterator<Item = &'a ProvenanceMark>,
+) -> Result<()> {
+    w.write_all(ARCHIVE_MAGIC)?;
+    for mark in marks {
+        let message = mark.message();
+        w.write_all(&[mark.res().into()])?;
+        w.write_all(&(message.len() as u32).to_be_bytes())?;
+        w.write_all(&message)?;
+    }
+    w.flush()?;
+    Ok(())
+}
+
+/// Memory-maps the archive at `path` for lazy decoding.
+///
+/// The archive must not be modified while it is mapped.
+pub fn open_mmap(path: impl AsRef<Path>) -> Result<ArchiveReader> {
+    let file = File::open(path)?;
+    // SAFETY: the map is read-only, and callers are required not to modify
+    // the archive while it is open.
+    let map = unsafe { Mmap::map(&file)? };
+    ArchiveReader::new(map)
+}
+
+/// Iterates over the marks in a memory-mapped archive, decoding each frame as
+/// it is reached.
+///
+/// Each decoded [`ProvenanceMark`] owns a copy of its message bytes, so marks
+/// may outlive the reader. Iteration stops after the first error.
+pub struct ArchiveReader {
+    map: Mmap,
+    offset: usize,
+}
+
+impl ArchiveReader {
+    fn new(map: Mmap) -> Result<Self> {
+        if !map.starts_with(ARCHIVE_MAGIC) {
+            return Err(Error::InvalidArchive {
+                offset: 0,
+                details: "missing archive header".to_string(),
+            });
+        }
+        Ok(Self { map, offset: ARCHIVE_MAGIC.len() })
+    }
+
+    /// The byte offset of the next frame.
+    pub fn offset(&self) -> usize { self.offset }
+
+    fn read_frame(&self) -> Result<(ProvenanceMark, usize)> {
+        let offset = self.offset;
+        let invalid = |details: &str| Error::InvalidArchive {
+            offset,
+            details: details.to_string(),
+        };
+        let header = self
+            .map
+            .get(offset..offset + FRAME_HEADER_LENGTH)
+            .ok_or_else(|| invalid("truncated frame header"))?;
+        let res = ProvenanceMarkResolution::try_from(header[0])
+            .map_err(|e| invalid(&e.to_string()))?;
+        let length =
+            u32::from_be_bytes(header[1..].try_into().unwrap()) as usize;
+        let start = offset + FRAME_HEADER_LENGTH;
+        let message = self
+            .map
+            .get(start..start + length)
+            .ok_or_else(|| invalid("truncated message"))?;
+        let mark = ProvenanceMark::from_message(res, message.to_vec())
+            .map_err(|e| invalid(&e.to_string()))?;
+        Ok((mark, start + length))
+    }
+}
+
+impl Iterator for ArchiveReader {
+    type Item = Result<ProvenanceMark>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.offset >= self.map.len() {
+            return None;
+        }
+        match self.read_frame() {
+            Ok((mark, next_offset)) => {
+                self.offset = next_offset;
+                Some(Ok(mark))
+            }
+            Err(error) => {
+                self.offset = self.map.len();
+                Some(Err(error))
+            }
+        }
+    }
+}
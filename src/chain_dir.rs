@@ -0,0 +1,137 @@
+//! Reading and writing the chain directory layout used by the reference
+//! tooling:
+//!
+//! ```text
+//! <path>/
+//!     generator.json
+//!     marks/
+//!         0000.json
+//!         0001.json
+//!         ...
+//! ```
+//!
+//! `generator.json` holds the serialized [`ProvenanceMarkGenerator`], and each
+//! file in `marks/` holds a [`ProvenanceMarkInfo`] named by the zero-padded
+//! sequence number of its mark.
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use crate::{Error, ProvenanceMarkGenerator, ProvenanceMarkInfo, Result};
+
+pub const GENERATOR_FILE_NAME: &str = "generator.json";
+pub const MARKS_DIR_NAME: &str = "marks";
+
+/// The path of the file for the mark with the given sequence number.
+pub fn mark_file_path(path: impl AsRef<Path>, seq: u32) -> PathBuf {
+    path.as_ref()
+        .join(MARKS_DIR_NAME)
+        .join(format!("{:04}.json", seq))
+}
+
+/// Saves the generator and marks to a chain directory, creating it if needed.
+///
+/// The generator file is always rewritten. A mark file that already exists
+/// is left alone if its contents are unchanged, and otherwise is only
+/// overwritten if `force` is set.
+pub fn save(
+    path: impl AsRef<Path>,
+    generator: &ProvenanceMarkGenerator,
+    infos: &[ProvenanceMarkInfo],
+    force: bool,
+) -> Result<()> {
+    let path = path.as_ref();
+    fs::create_dir_all(path.join(MARKS_DIR_NAME))?;
+
+    for info in infos {
+        let file_path = mark_file_path(path, info.mark().seq());
+        let json = serde_json::to_string_pretty(info)?;
+        if file_path.exists() && !force {
+            let existing = fs::read_to_string(&file_path)?;
+            if existing == json {
+                continue;
+            }
+            return Err(Error::FileExists {
+                path: file_path.display().to_string(),
+            });
+        }
+        fs::write(&file_path, json)?;
+    }
+
+    let json = serde_json::to_string_pretty(generator)?;
+    fs::write(path.join(GENERATOR_FILE_NAME), json)?;
+
+    Ok(())
+}
+
+/// Loads the generator and marks from a chain directory.
+///
+/// Marks are returned in sequence order. Each mark file's number must match
+/// the sequence number of the mark it contains, and every mark must belong
+/// to the generator's chain.
+pub fn load(
+    path: impl AsRef<Path>,
+) -> Result<(ProvenanceMarkGenerator, Vec<ProvenanceMarkInfo>)> {
+    let path = path.as_ref();
+
+    let generator_path = path.join(GENERATOR_FILE_NAME);
+    let json = fs::read_to_string(&generator_path)?;
+    let generator: ProvenanceMarkGenerator = serde_json::from_str(&json)
+        .map_err(|e| invalid_file(&generator_path, e))?;
+
+    let marks_path = path.join(MARKS_DIR_NAME);
+    let mut entries = Vec::new();
+    if marks_path.is_dir() {
+        for entry in fs::read_dir(&marks_path)? {
+            let file_path = entry?.path();
+            if file_path.extension().is_none_or(|ext| ext != "json") {
+                continue;
+            }
+            let index: u32 = file_path
+                .file_stem()
+                .and_then(|stem| stem.to_str())
+                .and_then(|stem| stem.parse().ok())
+                .ok_or_else(|| {
+                    invalid_file(&file_path, "file name is not a mark number")
+                })?;
+            entries.push((index, file_path));
+        }
+    }
+    entries.sort();
+
+    let mut infos = Vec::with_capacity(entries.len());
+    for (index, file_path) in entries {
+        let json = fs::read_to_string(&file_path)?;
+        let info: ProvenanceMarkInfo = serde_json::from_str(&json)
+            .map_err(|e| invalid_file(&file_path, e))?;
+        let mark = info.mark();
+        if mark.seq() != index {
+            return Err(invalid_file(
+                &file_path,
+                format!(
+                    "file index {} does not match mark seq {}",
+                    index,
+                    mark.seq()
+                ),
+            ));
+        }
+        if mark.chain_id() != generator.chain_id() {
+            return Err(invalid_file(
+                &file_path,
+                "mark does not belong to the generator's chain",
+            ));
+        }
+        infos.push(info);
+    }
+
+    Ok((generator, infos))
+}
+
+fn invalid_file(path: &Path, details: impl ToString) -> Error {
+    Error::InvalidFile {
+        path: path.display().to_string(),
+        details: details.to_string(),
+    }
+}
@@ -0,0 +1,61 @@
+use std::collections::BTreeMap;
+
+use serde::Serialize;
+
+use crate::{ChainAlias, ChainReport};
+
+/// A canonical chain and the chains in a report that are
+/// [aliases](crate::ValidationOptions::with_aliases) of it, from
+/// [`ValidationReport::groups`](crate::ValidationReport::groups).
+///
+/// Grouping is presentational: each chain in a group is validated on its own
+/// and has its own [`ChainReport`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ChainGroup {
+    #[serde(with = "hex")]
+    canonical_chain_id: Vec<u8>,
+    #[serde(serialize_with = "crate::validate::hex_list::serialize")]
+    chain_ids: Vec<Vec<u8>>,
+}
+
+impl ChainGroup {
+    /// The groups of `chains`, ordered by canonical chain ID. There is a
+    /// group for each canonical chain with at least one alias in `chains`.
+    pub(crate) fn from_chains(
+        chains: &[ChainReport],
+        aliases: &ChainAlias,
+    ) -> Vec<Self> {
+        let mut groups: BTreeMap<&[u8], Vec<&[u8]>> = BTreeMap::new();
+        for chain in chains {
+            if let Some(canonical) = aliases.canonical(chain.chain_id()) {
+                groups.entry(canonical).or_default().push(chain.chain_id());
+            }
+        }
+        groups
+            .into_iter()
+            .map(|(canonical, members)| {
+                let canonical_present =
+                    chains.iter().any(|chain| chain.chain_id() == canonical);
+                let chain_ids = canonical_present
+                    .then_some(canonical)
+                    .into_iter()
+                    .chain(members)
+                    .map(<[u8]>::to_vec)
+                    .collect();
+                Self { canonical_chain_id: canonical.to_vec(), chain_ids }
+            })
+            .collect()
+    }
+
+    /// The ID of the chain the group's aliases map to, which need not be in
+    /// the report.
+    pub fn canonical_chain_id(&self) -> &[u8] { &self.canonical_chain_id }
+
+    /// The IDs of the group's chains in the report: the canonical chain first
+    /// if it is present, then its aliases in order of chain ID.
+    pub fn chain_ids(&self) -> &[Vec<u8>] { &self.chain_ids }
+
+    pub fn contains(&self, chain_id: &[u8]) -> bool {
+        self.chain_ids.iter().any(|id| id == chain_id)
+    }
+}
@@ -0,0 +1,197 @@
+//! Resolution of divergent copies of a chain, such as the copies held by two
+//! mirrors of a registry after the chain forked.
+
+use crate::ProvenanceMark;
+
+/// How [`resolve`] chooses between the branches of two diverged copies.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MergeStrategy {
+    /// Choose the branch whose marks link from the common prefix for the
+    /// most marks, or [`A`](MergeBranch::A) if they link for equally many.
+    PreferLongerVerifiedRun,
+    /// Choose the branch whose first mark has the earlier date, falling back
+    /// to [`PreferLongerVerifiedRun`](Self::PreferLongerVerifiedRun) if the
+    /// dates are equal. A branch with no marks is never chosen over one with
+    /// marks.
+    PreferEarlierDates,
+    /// Choose neither branch, leaving both in the outcome for the caller to
+    /// decide between.
+    KeepBoth,
+}
+
+/// One of the two copies passed to [`resolve`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MergeBranch {
+    /// The copy passed as `a`.
+    A,
+    /// The copy passed as `b`.
+    B,
+}
+
+impl std::fmt::Display for MergeBranch {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MergeBranch::A => write!(f, "a"),
+            MergeBranch::B => write!(f, "b"),
+        }
+    }
+}
+
+/// The result of [`resolve`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MergeOutcome {
+    common_prefix: Vec<ProvenanceMark>,
+    branch_a: Vec<ProvenanceMark>,
+    branch_b: Vec<ProvenanceMark>,
+    verified_a: usize,
+    verified_b: usize,
+    chosen: Option<MergeBranch>,
+}
+
+impl MergeOutcome {
+    /// The marks both copies hold, from the start of each copy up to where
+    /// they first differ or a mark fails to link from the one before it.
+    pub fn common_prefix(&self) -> &[ProvenanceMark] { &self.common_prefix }
+
+    /// The marks of the copy labeled `branch` after the common prefix.
+    pub fn branch(&self, branch: MergeBranch) -> &[ProvenanceMark] {
+        match branch {
+            MergeBranch::A => &self.branch_a,
+            MergeBranch::B => &self.branch_b,
+        }
+    }
+
+    /// The number of marks at the start of the [`branch`](Self::branch)
+    /// labeled `branch` that each link from the mark before them, starting
+    /// from the last mark of the common prefix.
+    pub fn verified_run(&self, branch: MergeBranch) -> usize {
+        match branch {
+            MergeBranch::A => self.verified_a,
+            MergeBranch::B => self.verified_b,
+        }
+    }
+
+    /// The sequence number at which the copies diverged, or `None` if either
+    /// copy has no marks after the common prefix.
+    pub fn diverged_at_seq(&self) -> Option<u32> {
+        let a = self.branch_a.first()?;
+        let b = self.branch_b.first()?;
+        Some(a.seq().min(b.seq()))
+    }
+
+    /// The branch the strategy chose, or `None` for
+    /// [`KeepBoth`](MergeStrategy::KeepBoth).
+    pub fn chosen(&self) -> Option<MergeBranch> { self.chosen }
+
+    /// The canonical sequence: the common prefix followed by the verified
+    /// run of the chosen branch, or only the common prefix if no branch was
+    /// chosen.
+    ///
+    /// Marks of the chosen branch after its first unlinked mark are left out,
+    /// so the canonical sequence always links from start to end.
+    pub fn canonical(&self) -> Vec<ProvenanceMark> {
+        let mut canonical = self.common_prefix.clone();
+        if let Some(branch) = self.chosen {
+            canonical.extend(
+                self.branch(branch)[..self.verified_run(branch)]
+                    .iter()
+                    .cloned(),
+            );
+        }
+        canonical
+    }
+
+    /// The last mark of the [canonical](Self::canonical) sequence.
+    pub fn head(&self) -> Option<&ProvenanceMark> {
+        self.chosen
+            .and_then(|branch| {
+                self.branch(branch)[..self.verified_run(branch)].last()
+            })
+            .or_else(|| self.common_prefix.last())
+    }
+}
+
+/// The number of marks at the start of `branch` that each link from the mark
+/// before them, the first linking from `previous` if there is one.
+fn verified_run(
+    previous: Option<&ProvenanceMark>,
+    branch: &[ProvenanceMark],
+) -> usize {
+    let mut previous = previous;
+    let mut count = 0;
+    for mark in branch {
+        if previous.is_some_and(|previous| !previous.precedes(mark)) {
+            break;
+        }
+        previous = Some(mark);
+        count += 1;
+    }
+    count
+}
+
+/// Resolve two divergent copies of the same chain, such as those held by two
+/// mirrors after the chain forked, into a common prefix, the branch each
+/// copy holds after it, and a canonical sequence chosen by `strategy`.
+///
+/// Each copy is sorted by sequence number first, so it may be given in any
+/// order. The common prefix ends where the copies first differ, or at the
+/// first mark that does not link from the one before it, so everything in it
+/// is verified. Copies that do not diverge, where one is a prefix of the
+/// other, leave the shorter copy's branch empty.
+///
+/// To only find out how the copies relate, use
+/// [`ChainReport::compare`](crate::ChainReport::compare).
+pub fn resolve(
+    a: &[ProvenanceMark],
+    b: &[ProvenanceMark],
+    strategy: MergeStrategy,
+) -> MergeOutcome {
+    let mut a = a.to_vec();
+    let mut b = b.to_vec();
+    a.sort_by_key(ProvenanceMark::seq);
+    b.sort_by_key(ProvenanceMark::seq);
+
+    let mut prefix_len = 0;
+    while prefix_len < a.len().min(b.len())
+        && a[prefix_len] == b[prefix_len]
+        && (prefix_len == 0 || a[prefix_len - 1].precedes(&a[prefix_len]))
+    {
+        prefix_len += 1;
+    }
+    let branch_a = a.split_off(prefix_len);
+    let branch_b = b.split_off(prefix_len);
+    let common_prefix = a;
+
+    let verified_a = verified_run(common_prefix.last(), &branch_a);
+    let verified_b = verified_run(common_prefix.last(), &branch_b);
+    let longer_run = if verified_b > verified_a {
+        MergeBranch::B
+    } else {
+        MergeBranch::A
+    };
+    let chosen = match strategy {
+        MergeStrategy::PreferLongerVerifiedRun => Some(longer_run),
+        MergeStrategy::PreferEarlierDates => {
+            match (branch_a.first(), branch_b.first()) {
+                (Some(a), Some(b)) if a.date() < b.date() => {
+                    Some(MergeBranch::A)
+                }
+                (Some(a), Some(b)) if b.date() < a.date() => {
+                    Some(MergeBranch::B)
+                }
+                (None, Some(_)) => Some(MergeBranch::B),
+                _ => Some(longer_run),
+            }
+        }
+        MergeStrategy::KeepBoth => None,
+    };
+
+    MergeOutcome {
+        common_prefix,
+        branch_a,
+        branch_b,
+        verified_a,
+        verified_b,
+        chosen,
+    }
+}
@@ -0,0 +1,64 @@
+use dcbor::Date;
+
+use crate::{ProvenanceMark, ValidationReport};
+
+/// What a report knows about the start of a chain, from
+/// [`ValidationReport::chain_origin_info`].
+///
+/// A chain is identified by the chain ID every one of its marks carries, so
+/// its genesis mark is not needed to know which chain a mark belongs to. The
+/// genesis mark is only needed to know that the report holds the chain from
+/// its start.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ChainOrigin<'a> {
+    genesis: Option<&'a ProvenanceMark>,
+    earliest_known_seq: u32,
+    earliest_known_date: Date,
+}
+
+impl<'a> ChainOrigin<'a> {
+    /// The chain's genesis mark, or `None` if the report does not hold it.
+    pub fn genesis(&self) -> Option<&'a ProvenanceMark> { self.genesis }
+
+    /// The sequence number of the chain's first mark in the report, which is
+    /// 0 if the genesis mark is present.
+    pub fn earliest_known_seq(&self) -> u32 { self.earliest_known_seq }
+
+    /// The date of the chain's first mark in the report.
+    pub fn earliest_known_date(&self) -> Date { self.earliest_known_date }
+}
+
+impl std::fmt::Display for ChainOrigin<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.genesis.is_some() {
+            write!(f, "genesis present, dated {}", self.earliest_known_date)
+        } else {
+            write!(
+                f,
+                "genesis absent; earliest known mark is seq {}, dated {}",
+                self.earliest_known_seq, self.earliest_known_date
+            )
+        }
+    }
+}
+
+impl ValidationReport {
+    /// The origin of the chain with the given ID, or `None` if the report
+    /// has no such chain.
+    pub fn chain_origin_info(
+        &self,
+        chain_id: &[u8],
+    ) -> Option<ChainOrigin<'_>> {
+        let chain = self
+            .chains()
+            .iter()
+            .find(|chain| chain.chain_id() == chain_id)?;
+        // A chain's marks are sorted by sequence number
+        let earliest = chain.marks().first()?;
+        Some(ChainOrigin {
+            genesis: chain.has_genesis().then_some(earliest.as_ref()),
+            earliest_known_seq: earliest.seq(),
+            earliest_known_date: earliest.date(),
+        })
+    }
+}
@@ -0,0 +1,126 @@
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    sync::Arc,
+};
+
+use crate::{ChainReport, ProvenanceMark, ValidationReport};
+
+/// How one copy of a chain relates to another, from [`ChainReport::compare`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ChainRelation {
+    /// Both copies hold exactly the same marks.
+    Equal,
+    /// Every mark in this copy is in the other, which has more.
+    PrefixOfOther,
+    /// Every mark in the other copy is in this one, which has more.
+    ExtensionOfOther,
+    /// The copies share some marks, but each has marks the other lacks.
+    /// `at_seq` is the first sequence number at which they differ.
+    Diverged { at_seq: u32 },
+    /// The copies are of different chains, or share no marks.
+    Disjoint,
+}
+
+impl std::fmt::Display for ChainRelation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ChainRelation::Equal => write!(f, "equal"),
+            ChainRelation::PrefixOfOther => write!(f, "prefix of other"),
+            ChainRelation::ExtensionOfOther => write!(f, "extension of other"),
+            ChainRelation::Diverged { at_seq } => {
+                write!(f, "diverged at seq {}", at_seq)
+            }
+            ChainRelation::Disjoint => write!(f, "disjoint"),
+        }
+    }
+}
+
+/// The sequence number and fingerprint of each mark.
+fn mark_keys(marks: &[Arc<ProvenanceMark>]) -> BTreeSet<(u32, [u8; 32])> {
+    marks
+        .iter()
+        .map(|mark| (mark.seq(), mark.fingerprint()))
+        .collect()
+}
+
+/// Compare two copies of the same chain by the fingerprints of their marks
+/// at each sequence number.
+fn relation(
+    marks: &[Arc<ProvenanceMark>],
+    other_marks: &[Arc<ProvenanceMark>],
+) -> ChainRelation {
+    let ours = mark_keys(marks);
+    let theirs = mark_keys(other_marks);
+    let only_ours = ours.difference(&theirs).next();
+    let only_theirs = theirs.difference(&ours).next();
+    match (only_ours, only_theirs) {
+        (None, None) => ChainRelation::Equal,
+        (None, Some(_)) => ChainRelation::PrefixOfOther,
+        (Some(_), None) => ChainRelation::ExtensionOfOther,
+        (Some((ours_seq, _)), Some((theirs_seq, _))) => {
+            if ours.is_disjoint(&theirs) {
+                ChainRelation::Disjoint
+            } else {
+                ChainRelation::Diverged { at_seq: *ours_seq.min(theirs_seq) }
+            }
+        }
+    }
+}
+
+/// The marks of the chain with `chain_id`, or none if it is absent.
+fn chain_marks<'a>(
+    chains: &BTreeMap<&[u8], &'a ChainReport>,
+    chain_id: &[u8],
+) -> &'a [Arc<ProvenanceMark>] {
+    chains.get(chain_id).map_or(&[], |chain| chain.marks())
+}
+
+impl ChainReport {
+    /// How this copy of a chain relates to `other`, such as a mirror's copy
+    /// of the same chain.
+    ///
+    /// Marks are compared by fingerprint at each sequence number, so copies
+    /// with gaps are compared by the marks they do hold. Chains with
+    /// different chain IDs are [`Disjoint`](ChainRelation::Disjoint).
+    pub fn compare(&self, other: &ChainReport) -> ChainRelation {
+        if self.chain_id() != other.chain_id() {
+            return ChainRelation::Disjoint;
+        }
+        relation(self.marks(), other.marks())
+    }
+}
+
+impl ValidationReport {
+    /// [Compare](ChainReport::compare) each chain in this report with the
+    /// chain of the same ID in `other`, keyed by chain ID.
+    ///
+    /// A chain found in only one of the reports is compared with an empty
+    /// copy: it is an [extension](ChainRelation::ExtensionOfOther) if only
+    /// this report has it, and a [prefix](ChainRelation::PrefixOfOther) if
+    /// only `other` does.
+    pub fn compare_chains(
+        &self,
+        other: &ValidationReport,
+    ) -> BTreeMap<Vec<u8>, ChainRelation> {
+        let ours: BTreeMap<&[u8], &ChainReport> = self
+            .chains()
+            .iter()
+            .map(|chain| (chain.chain_id(), chain))
+            .collect();
+        let theirs: BTreeMap<&[u8], &ChainReport> = other
+            .chains()
+            .iter()
+            .map(|chain| (chain.chain_id(), chain))
+            .collect();
+        ours.keys()
+            .chain(theirs.keys())
+            .map(|&chain_id| {
+                let relation = relation(
+                    chain_marks(&ours, chain_id),
+                    chain_marks(&theirs, chain_id),
+                );
+                (chain_id.to_vec(), relation)
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,175 @@
+//! Conversion of marks to and from Arrow record batches, for loading large
+//! numbers of marks into columnar query engines without going through JSON.
+//!
+//! ```text
+//! res       UInt8                  ProvenanceMarkResolution
+//! seq       UInt32
+//! date      Timestamp(ms, UTC)
+//! chain_id  Binary
+//! key       Binary
+//! hash      Binary
+//! info      Binary, nullable       dCBOR encoding of the info
+//! ur        Utf8
+//! ```
+
+use std::sync::Arc;
+
+/// The version of Arrow that batches are built with.
+pub use arrow;
+use arrow::{
+    array::{
+        Array, ArrayRef, BinaryArray, StringArray, TimestampMillisecondArray,
+        UInt8Array, UInt32Array,
+    },
+    datatypes::{DataType, Field, Schema, SchemaRef, TimeUnit},
+    record_batch::RecordBatch,
+};
+use bc_ur::UREncodable;
+use chrono::TimeZone;
+use dcbor::Date;
+
+use crate::{
+    ChainId, Error, LinkHash, LinkKey, ProvenanceMark,
+    ProvenanceMarkResolution, Result,
+};
+
+/// The schema of the batches built by [`marks_to_record_batch`].
+pub fn mark_schema() -> SchemaRef {
+    Arc::new(Schema::new(vec![
+        Field::new("res", DataType::UInt8, false),
+        Field::new("seq", DataType::UInt32, false),
+        Field::new(
+            "date",
+            DataType::Timestamp(TimeUnit::Millisecond, Some("UTC".into())),
+            false,
+        ),
+        Field::new("chain_id", DataType::Binary, false),
+        Field::new("key", DataType::Binary, false),
+        Field::new("hash", DataType::Binary, false),
+        Field::new("info", DataType::Binary, true),
+        Field::new("ur", DataType::Utf8, false),
+    ]))
+}
+
+/// A record batch with one row per mark, in the [schema](mark_schema) of
+/// this module.
+pub fn marks_to_record_batch(marks: &[ProvenanceMark]) -> Result<RecordBatch> {
+    let columns: Vec<ArrayRef> = vec![
+        Arc::new(UInt8Array::from_iter_values(
+            marks.iter().map(|mark| u8::from(mark.res())),
+        )),
+        Arc::new(UInt32Array::from_iter_values(
+            marks.iter().map(ProvenanceMark::seq),
+        )),
+        Arc::new(
+            TimestampMillisecondArray::from_iter_values(
+                marks
+                    .iter()
+                    .map(|mark| mark.date().datetime().timestamp_millis()),
+            )
+            .with_timezone("UTC"),
+        ),
+        Arc::new(BinaryArray::from_iter_values(
+            marks.iter().map(|mark| mark.chain_id().as_bytes()),
+        )),
+        Arc::new(BinaryArray::from_iter_values(
+            marks.iter().map(|mark| mark.key().as_bytes()),
+        )),
+        Arc::new(BinaryArray::from_iter_values(
+            marks.iter().map(|mark| mark.hash().as_bytes()),
+        )),
+        Arc::new(BinaryArray::from_iter(marks.iter().map(|mark| {
+            Some(mark.info_bytes()).filter(|bytes| !bytes.is_empty())
+        }))),
+        Arc::new(StringArray::from_iter_values(
+            marks.iter().map(ProvenanceMark::ur_string),
+        )),
+    ];
+    Ok(RecordBatch::try_new(mark_schema(), columns)?)
+}
+
+/// The marks in a record batch with the columns of [`mark_schema`], each
+/// reassembled with [`ProvenanceMark::from_fields`].
+///
+/// The `ur` column is not read, and other columns the batch has are ignored.
+/// Fails on the first row whose fields do not make a mark, such as a key of
+/// the wrong length for its resolution.
+pub fn record_batch_to_marks(
+    batch: &RecordBatch,
+) -> Result<Vec<ProvenanceMark>> {
+    let res = column::<UInt8Array>(batch, "res")?;
+    let seq = column::<UInt32Array>(batch, "seq")?;
+    let date = column::<TimestampMillisecondArray>(batch, "date")?;
+    let chain_id = column::<BinaryArray>(batch, "chain_id")?;
+    let key = column::<BinaryArray>(batch, "key")?;
+    let hash = column::<BinaryArray>(batch, "hash")?;
+    let info = nullable_column::<BinaryArray>(batch, "info")?;
+
+    (0..batch.num_rows())
+        .map(|row| {
+            let invalid =
+                |e: Error| Error::InvalidRow { row, details: e.to_string() };
+            let res = ProvenanceMarkResolution::try_from(res.value(row))
+                .map_err(invalid)?;
+            let date = chrono::Utc
+                .timestamp_millis_opt(date.value(row))
+                .single()
+                .map(Date::from_datetime)
+                .ok_or_else(|| Error::InvalidRow {
+                    row,
+                    details: "date out of range".to_string(),
+                })?;
+            let info_bytes = if info.is_null(row) {
+                Vec::new()
+            } else {
+                info.value(row).to_vec()
+            };
+            let key = LinkKey::new(res, key.value(row)).map_err(invalid)?;
+            let hash = LinkHash::new(res, hash.value(row)).map_err(invalid)?;
+            let chain_id =
+                ChainId::new(res, chain_id.value(row)).map_err(invalid)?;
+            ProvenanceMark::from_fields(
+                res,
+                key,
+                hash,
+                chain_id,
+                seq.value(row).into(),
+                date,
+                info_bytes,
+            )
+            .map_err(invalid)
+        })
+        .collect()
+}
+
+/// The column named `name`, which must have type `T` and no nulls.
+fn column<'a, T: Array + 'static>(
+    batch: &'a RecordBatch,
+    name: &str,
+) -> Result<&'a T> {
+    let column = nullable_column::<T>(batch, name)?;
+    if column.null_count() > 0 {
+        return Err(Error::InvalidColumn {
+            column: name.to_string(),
+            details: "contains nulls".to_string(),
+        });
+    }
+    Ok(column)
+}
+
+/// The column named `name`, which must have type `T`.
+fn nullable_column<'a, T: Array + 'static>(
+    batch: &'a RecordBatch,
+    name: &str,
+) -> Result<&'a T> {
+    let invalid = |details: &str| Error::InvalidColumn {
+        column: name.to_string(),
+        details: details.to_string(),
+    };
+    batch
+        .column_by_name(name)
+        .ok_or_else(|| invalid("missing"))?
+        .as_any()
+        .downcast_ref::<T>()
+        .ok_or_else(|| invalid("wrong type"))
+}
@@ -0,0 +1,141 @@
+use dcbor::prelude::*;
+
+use crate::{Error, ProvenanceMarkResolution, Result, RngState};
+
+/// CBOR tag for a [`ChainContinuation`] ("PRVC").
+///
+/// Experimental: not allocated in `bc-tags`, and registered only by
+/// [`register_experimental_tags_in`](crate::register_experimental_tags_in).
+pub const TAG_CHAIN_CONTINUATION: u64 = 1347573315;
+pub const TAG_NAME_CHAIN_CONTINUATION: &str = "provenance-continuation";
+
+/// The state needed to continue issuing marks on an existing chain, without
+/// the chain's original seed.
+///
+/// A continuation is created with
+/// [`ProvenanceMarkGenerator::export_continuation`](crate::ProvenanceMarkGenerator::export_continuation)
+/// and turned back into a generator with
+/// [`ProvenanceMarkGenerator::from_continuation`](crate::ProvenanceMarkGenerator::from_continuation),
+/// allowing a chain to be handed off to a new custodian.
+///
+/// # Trust
+///
+/// The continuation contains the RNG state from which every future key is
+/// derived, so anyone holding it can issue valid marks on the chain. It must
+/// be transported over a confidential channel.
+///
+/// Handing off a chain does not revoke the previous custodian: anyone who
+/// still has the original seed, an older generator state, or a copy of the
+/// continuation can keep issuing marks. If both parties issue marks, the
+/// chain forks, which validation reports as conflicting marks at the same
+/// sequence number. Verifiers should treat marks issued after a hand-off as
+/// vouched for by the new custodian only if the previous custodian is
+/// trusted to have destroyed their state.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChainContinuation {
+    res: ProvenanceMarkResolution,
+    chain_id: Vec<u8>,
+    next_seq: u32,
+    rng_state: RngState,
+}
+
+/// `next_seq` as a `u32`, or [`Error::SequenceOutOfRange`] if it does not
+/// fit.
+///
+/// A generator's next sequence number is only checked against `u32`, not
+/// its resolution's [`max_seq`](ProvenanceMarkResolution::max_seq): a
+/// generator that has issued its last mark is one past it.
+pub(crate) fn next_seq_from_u64(next_seq: u64) -> Result<u32> {
+    u32::try_from(next_seq).map_err(|_| Error::SequenceOutOfRange {
+        value: next_seq,
+        max: u32::MAX,
+    })
+}
+
+impl ChainContinuation {
+    /// Returns [`Error::SequenceOutOfRange`] if `next_seq` does not fit in a
+    /// `u32`.
+    pub fn new(
+        res: ProvenanceMarkResolution,
+        chain_id: Vec<u8>,
+        next_seq: u64,
+        rng_state: RngState,
+    ) -> Result<Self> {
+        if chain_id.len() != res.link_length() {
+            return Err(Error::InvalidChainIdLength {
+                expected: res.link_length(),
+                actual: chain_id.len(),
+            });
+        }
+        let next_seq = next_seq_from_u64(next_seq)?;
+        Ok(Self { res, chain_id, next_seq, rng_state })
+    }
+
+    pub fn res(&self) -> ProvenanceMarkResolution { self.res }
+
+    pub fn chain_id(&self) -> &[u8] { &self.chain_id }
+
+    pub fn next_seq(&self) -> u32 { self.next_seq }
+
+    pub fn rng_state(&self) -> &RngState { &self.rng_state }
+}
+
+impl std::fmt::Display for ChainContinuation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "ChainContinuation(chainID: {}, res: {}, nextSeq: {})",
+            hex::encode(&self.chain_id),
+            self.res,
+            self.next_seq
+        )
+    }
+}
+
+impl CBORTagged for ChainContinuation {
+    fn cbor_tags() -> Vec<Tag> {
+        vec![Tag::new(
+            TAG_CHAIN_CONTINUATION,
+            TAG_NAME_CHAIN_CONTINUATION,
+        )]
+    }
+}
+
+impl From<ChainContinuation> for CBOR {
+    fn from(value: ChainContinuation) -> Self { value.tagged_cbor() }
+}
+
+impl CBORTaggedEncodable for ChainContinuation {
+    fn untagged_cbor(&self) -> CBOR {
+        vec![
+            self.res.to_cbor(),
+            CBOR::to_byte_string(&self.chain_id),
+            self.next_seq.to_cbor(),
+            self.rng_state.clone().into(),
+        ]
+        .to_cbor()
+    }
+}
+
+impl TryFrom<CBOR> for ChainContinuation {
+    type Error = dcbor::Error;
+
+    fn try_from(cbor: CBOR) -> dcbor::Result<Self> {
+        Self::from_tagged_cbor(cbor)
+    }
+}
+
+impl CBORTaggedDecodable for ChainContinuation {
+    fn from_untagged_cbor(cbor: CBOR) -> dcbor::Result<Self> {
+        let v = CBOR::try_into_array(cbor)?;
+        if v.len() != 4 {
+            return Err("Invalid chain continuation length".into());
+        }
+        let res = ProvenanceMarkResolution::try_from(v[0].clone())?;
+        let chain_id = CBOR::try_into_byte_string(v[1].clone())?;
+        let next_seq: u64 = v[2].clone().try_into()?;
+        let rng_state = RngState::try_from(v[3].clone())?;
+        Self::new(res, chain_id, next_seq, rng_state)
+            .map_err(dcbor::Error::from)
+    }
+}
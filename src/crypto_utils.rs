@@ -1,3 +1,5 @@
+use std::io::{self, Read, Write};
+
 use chacha20::{
     ChaCha20,
     cipher::{KeyIvInit, StreamCipher},
@@ -16,6 +18,35 @@ pub fn sha256(data: impl AsRef<[u8]>) -> [u8; SHA256_SIZE] {
     hash
 }
 
+/// Incremental SHA-256 for data that arrives in pieces.
+#[derive(Clone, Default)]
+pub struct Sha256Stream(Sha256);
+
+impl Sha256Stream {
+    pub fn new() -> Self { Self::default() }
+
+    pub fn update(&mut self, data: impl AsRef<[u8]>) { self.0.update(data); }
+
+    pub fn finalize(self) -> [u8; SHA256_SIZE] { self.0.finalize().into() }
+}
+
+impl Write for Sha256Stream {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.update(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> { Ok(()) }
+}
+
+/// Computes the SHA-256 of everything read from `reader` without buffering it
+/// in memory.
+pub fn sha256_reader(mut reader: impl Read) -> io::Result<[u8; SHA256_SIZE]> {
+    let mut stream = Sha256Stream::new();
+    io::copy(&mut reader, &mut stream)?;
+    Ok(stream.finalize())
+}
+
 pub fn sha256_prefix(data: impl AsRef<[u8]>, prefix: usize) -> Vec<u8> {
     let digest = sha256(data);
     digest.iter().take(prefix).copied().collect()
@@ -33,22 +64,71 @@ pub fn hkdf_hmac_sha256(
     key_material: impl AsRef<[u8]>,
     salt: impl AsRef<[u8]>,
     key_len: usize,
+) -> Vec<u8> {
+    hkdf_hmac_sha256_info(key_material, salt, [], key_len)
+}
+
+/// Computes the HKDF-HMAC-SHA-256 for the given key material, using `info` to
+/// separate keys derived from the same material for different purposes.
+pub fn hkdf_hmac_sha256_info(
+    key_material: impl AsRef<[u8]>,
+    salt: impl AsRef<[u8]>,
+    info: impl AsRef<[u8]>,
+    key_len: usize,
 ) -> Vec<u8> {
     let mut key = vec![0u8; key_len];
     let hkdf = Hkdf::<Sha256>::new(Some(salt.as_ref()), key_material.as_ref());
-    hkdf.expand(&[], &mut key).unwrap();
+    hkdf.expand(info.as_ref(), &mut key).unwrap();
     key
 }
 
+/// Computes the HMAC-SHA-256 of `message` under `key`.
+///
+/// This is the HKDF extract step, which is HMAC with the salt as its key.
+pub fn hmac_sha256(
+    key: impl AsRef<[u8]>,
+    message: impl AsRef<[u8]>,
+) -> [u8; SHA256_SIZE] {
+    let (prk, _) =
+        Hkdf::<Sha256>::extract(Some(key.as_ref()), message.as_ref());
+    prk.into()
+}
+
+/// Encrypts or decrypts `message` with the ChaCha20 keystream for `key` and
+/// `nonce`.
+pub fn chacha20(
+    key: &[u8; 32],
+    nonce: &[u8; 12],
+    message: impl AsRef<[u8]>,
+) -> Vec<u8> {
+    let mut cipher = ChaCha20::new(key.into(), nonce.into());
+    let mut buffer = message.as_ref().to_vec();
+    cipher.apply_keystream(&mut buffer);
+    buffer
+}
+
 pub fn obfuscate(key: impl AsRef<[u8]>, message: impl AsRef<[u8]>) -> Vec<u8> {
-    let key = key.as_ref();
     let message = message.as_ref();
 
     if message.is_empty() {
         return message.to_vec();
     }
 
-    let extended_key = extend_key(key);
+    obfuscate_with_extended_key(&extend_key(key), message)
+}
+
+/// [`obfuscate`] with a key already extended by [`extend_key`], for callers
+/// that obfuscate with the same key more than once.
+pub fn obfuscate_with_extended_key(
+    extended_key: &[u8; 32],
+    message: impl AsRef<[u8]>,
+) -> Vec<u8> {
+    let message = message.as_ref();
+
+    if message.is_empty() {
+        return message.to_vec();
+    }
+
     let iv = extended_key
         .iter()
         .rev()
@@ -56,8 +136,5 @@ pub fn obfuscate(key: impl AsRef<[u8]>, message: impl AsRef<[u8]>) -> Vec<u8> {
         .copied()
         .collect::<Vec<u8>>();
     let iv2: [u8; 12] = iv.as_slice().try_into().unwrap();
-    let mut cipher = ChaCha20::new(&extended_key.into(), &iv2.into());
-    let mut buffer = message.to_vec();
-    cipher.apply_keystream(&mut buffer);
-    buffer
+    chacha20(extended_key, &iv2, message)
 }
@@ -61,9 +61,11 @@ impl SerializableDate for Date {
         let reference_date =
             Utc.with_ymd_and_hms(2001, 1, 1, 0, 0, 0).single().unwrap();
         let duration = self.datetime() - reference_date;
-        let seconds = duration.num_seconds();
-        let n = u32::try_from(seconds).map_err(|_| Error::DateOutOfRange {
-            details: "seconds value too large for u32".to_string(),
+        if duration < Duration::zero() {
+            return Err(before_minimum());
+        }
+        let n = u32::try_from(duration.num_seconds()).map_err(|_| {
+            after_maximum(&[0xff; 4], Date::deserialize_4_bytes)
         })?;
         Ok(n.to_be_bytes())
     }
@@ -80,17 +82,15 @@ impl SerializableDate for Date {
         let reference_date =
             Utc.with_ymd_and_hms(2001, 1, 1, 0, 0, 0).single().unwrap();
         let duration = self.datetime() - reference_date;
-        let milliseconds = duration.num_milliseconds();
-        let n =
-            u64::try_from(milliseconds).map_err(|_| Error::DateOutOfRange {
-                details: "milliseconds value too large for u64".to_string(),
-            })?;
-
-        if n > 0xe5940a78a7ff {
-            return Err(Error::DateOutOfRange {
-                details: "date exceeds maximum representable value".to_string(),
-            });
+        if duration < Duration::zero() {
+            return Err(before_minimum());
         }
+        let n = u64::try_from(duration.num_milliseconds())
+            .ok()
+            .filter(|n| *n <= MAX_6_BYTE_MILLISECONDS)
+            .ok_or_else(|| {
+                after_maximum(&MAX_6_BYTES, Date::deserialize_6_bytes)
+            })?;
 
         let bytes = n.to_be_bytes();
         Ok(bytes[2..8].try_into().unwrap())
@@ -101,7 +101,7 @@ impl SerializableDate for Date {
         full_bytes[2..].copy_from_slice(bytes);
         let n = u64::from_be_bytes(full_bytes);
 
-        if n > 0xe5940a78a7ff {
+        if n > MAX_6_BYTE_MILLISECONDS {
             return Err(Error::DateOutOfRange {
                 details: "date exceeds maximum representable value".to_string(),
             });
@@ -114,6 +114,28 @@ impl SerializableDate for Date {
     }
 }
 
+/// Milliseconds after 2001-01-01 of 9999-12-31T23:59:59.999Z, the latest date
+/// the 6-byte encoding represents.
+const MAX_6_BYTE_MILLISECONDS: u64 = 0xe5940a78a7ff;
+const MAX_6_BYTES: [u8; 6] = [0xe5, 0x94, 0x0a, 0x78, 0xa7, 0xff];
+
+fn before_minimum() -> Error {
+    Error::DateOutOfRange {
+        details: "before minimum representable date (2001-01-01)".to_string(),
+    }
+}
+
+/// An out-of-range error naming the latest date, decoded from `max_bytes`.
+fn after_maximum<const N: usize>(
+    max_bytes: &[u8; N],
+    deserialize: fn(&[u8; N]) -> Result<Date>,
+) -> Error {
+    let max = deserialize(max_bytes).unwrap();
+    Error::DateOutOfRange {
+        details: format!("after maximum representable date ({})", max),
+    }
+}
+
 pub fn range_of_days_in_month(year: i32, month: u32) -> std::ops::Range<u32> {
     let next_month = if month == 12 {
         Utc.with_ymd_and_hms(year + 1, 1, 1, 0, 0, 0).unwrap()
@@ -0,0 +1,161 @@
+use bc_rand::{
+    RandomNumberGenerator, SecureRandomNumberGenerator, rng_random_data,
+};
+use dcbor::prelude::*;
+
+use crate::{
+    Error, ProvenanceMark, ProvenanceMarkGenerator, ProvenanceSeed, Result,
+    crypto_utils::{SHA256_SIZE, chacha20, hkdf_hmac_sha256_info, hmac_sha256},
+};
+
+/// CBOR tag for an encrypted info payload ("PRVE").
+///
+/// Experimental: not allocated in `bc-tags`, and registered only by
+/// [`register_experimental_tags_in`](crate::register_experimental_tags_in).
+pub const TAG_ENCRYPTED_INFO: u64 = 1347573317;
+pub const TAG_NAME_ENCRYPTED_INFO: &str = "provenance-encrypted-info";
+
+/// The length of the random nonce that starts each encrypted info payload.
+const NONCE_LENGTH: usize = 12;
+
+/// The keys for encrypting and authenticating info payloads, derived from a
+/// chain's seed.
+struct InfoKeys {
+    encryption: [u8; 32],
+    authentication: [u8; 32],
+}
+
+impl InfoKeys {
+    fn new(seed: &ProvenanceSeed) -> Self {
+        let derive = |info: &str| {
+            let key = hkdf_hmac_sha256_info(seed.to_bytes(), [], info, 32);
+            <[u8; 32]>::try_from(key.as_slice()).unwrap()
+        };
+        Self {
+            encryption: derive("provenance-info-encryption"),
+            authentication: derive("provenance-info-authentication"),
+        }
+    }
+
+    /// The tag authenticating `nonce` and `ciphertext` as the info of the
+    /// mark at `seq` on the chain `chain_id`.
+    fn tag(
+        &self,
+        chain_id: &[u8],
+        seq: u32,
+        nonce: &[u8],
+        ciphertext: &[u8],
+    ) -> [u8; SHA256_SIZE] {
+        let message =
+            [chain_id, seq.to_be_bytes().as_slice(), nonce, ciphertext]
+                .concat();
+        hmac_sha256(self.authentication, message)
+    }
+
+    /// Encrypts `plaintext` under a fresh random nonce. The nonce is not
+    /// derived from the sequence number, since a sequence number can be
+    /// issued more than once, such as by a generator restored from a stale
+    /// backup, and reusing a nonce would reveal the XOR of the plaintexts.
+    fn encrypt(
+        &self,
+        chain_id: &[u8],
+        seq: u32,
+        plaintext: &[u8],
+        rng: &mut impl RandomNumberGenerator,
+    ) -> CBOR {
+        let nonce: [u8; NONCE_LENGTH] =
+            rng_random_data(rng, NONCE_LENGTH).try_into().unwrap();
+        let ciphertext = chacha20(&self.encryption, &nonce, plaintext);
+        let tag = self.tag(chain_id, seq, &nonce, &ciphertext);
+        let payload = [nonce.as_slice(), &ciphertext, &tag].concat();
+        CBOR::to_tagged_value(TAG_ENCRYPTED_INFO, CBOR::to_byte_string(payload))
+    }
+
+    fn decrypt(
+        &self,
+        chain_id: &[u8],
+        seq: u32,
+        payload: &[u8],
+    ) -> Result<Vec<u8>> {
+        if payload.len() < NONCE_LENGTH + SHA256_SIZE {
+            return Err(Error::InfoAuthenticationFailed);
+        }
+        let (nonce, rest) = payload.split_at(NONCE_LENGTH);
+        let (ciphertext, tag) = rest.split_at(rest.len() - SHA256_SIZE);
+        let expected = self.tag(chain_id, seq, nonce, ciphertext);
+        // Compare in constant time.
+        let difference = expected
+            .iter()
+            .zip(tag)
+            .fold(0, |acc, (a, b)| acc | (a ^ b));
+        if difference != 0 {
+            return Err(Error::InfoAuthenticationFailed);
+        }
+        let nonce: &[u8; NONCE_LENGTH] = nonce.try_into().unwrap();
+        Ok(chacha20(&self.encryption, nonce, ciphertext))
+    }
+}
+
+impl ProvenanceMarkGenerator {
+    /// Issues the next mark with `plaintext` encrypted as its info, so that
+    /// only holders of the chain's seed can read it.
+    ///
+    /// The info's obfuscation is keyed by the mark's own public key, so it
+    /// hides the info from casual inspection but not from anyone holding the
+    /// mark. This instead encrypts `plaintext` with ChaCha20 under a key
+    /// derived from the seed and a random 12-byte nonce, and authenticates
+    /// the nonce and ciphertext, bound to the chain ID and sequence number,
+    /// with HMAC-SHA-256. The info is the nonce, the ciphertext, and the
+    /// 32-byte tag, as a byte string tagged [`TAG_ENCRYPTED_INFO`]. Read it
+    /// back with [`ProvenanceSeed::decrypt_info`].
+    ///
+    /// Returns [`Error::MissingSeed`] if the generator was created from a
+    /// continuation. Like [`try_next`](Self::try_next), the generator is left
+    /// unchanged on error.
+    pub fn next_with_encrypted_info(
+        &mut self,
+        date: Date,
+        plaintext: &[u8],
+    ) -> Result<ProvenanceMark> {
+        let mut rng = SecureRandomNumberGenerator;
+        self.next_with_encrypted_info_using(date, plaintext, &mut rng)
+    }
+
+    /// [`next_with_encrypted_info`](Self::next_with_encrypted_info), drawing
+    /// the nonce from `rng`, which must be a secure random number generator.
+    pub fn next_with_encrypted_info_using(
+        &mut self,
+        date: Date,
+        plaintext: &[u8],
+        rng: &mut impl RandomNumberGenerator,
+    ) -> Result<ProvenanceMark> {
+        let seed = self.seed().ok_or(Error::MissingSeed)?;
+        let info = InfoKeys::new(seed).encrypt(
+            self.chain_id(),
+            self.next_seq(),
+            plaintext,
+            rng,
+        );
+        self.try_next(date, Some(info))
+    }
+}
+
+impl ProvenanceSeed {
+    /// Decrypts the info of a mark issued with
+    /// [`ProvenanceMarkGenerator::next_with_encrypted_info`] on the chain
+    /// created from this seed.
+    ///
+    /// Returns [`Error::NoEncryptedInfo`] if the mark's info is missing or is
+    /// not an encrypted payload, and [`Error::InfoAuthenticationFailed`] if
+    /// this is not the chain's seed or the payload has been altered.
+    pub fn decrypt_info(&self, mark: &ProvenanceMark) -> Result<Vec<u8>> {
+        let payload = mark
+            .info()
+            .and_then(|info| {
+                info.try_into_expected_tagged_value(TAG_ENCRYPTED_INFO).ok()
+            })
+            .and_then(|payload| payload.try_into_byte_string().ok())
+            .ok_or(Error::NoEncryptedInfo)?;
+        InfoKeys::new(self).decrypt(mark.chain_id(), mark.seq(), &payload)
+    }
+}
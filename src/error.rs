@@ -30,10 +30,28 @@ pub enum Error {
     #[error("invalid next key length: expected {expected}, got {actual}")]
     InvalidNextKeyLength { expected: usize, actual: usize },
 
+    /// Invalid hash length for the given resolution
+    #[error("invalid hash length: expected {expected}, got {actual}")]
+    InvalidHashLength { expected: usize, actual: usize },
+
     /// Invalid chain ID length for the given resolution
     #[error("invalid chain ID length: expected {expected}, got {actual}")]
     InvalidChainIdLength { expected: usize, actual: usize },
 
+    /// Invalid sequence number length for the given resolution
+    #[error(
+        "invalid sequence number length: expected {expected}, got {actual}"
+    )]
+    InvalidSeqBytesLength { expected: usize, actual: usize },
+
+    /// A sequence number larger than the resolution, or a `u32`, can carry
+    #[error("sequence number {value} out of range (max {max})")]
+    SequenceOutOfRange { value: u64, max: u32 },
+
+    /// Invalid date length for the given resolution
+    #[error("invalid date length: expected {expected}, got {actual}")]
+    InvalidDateBytesLength { expected: usize, actual: usize },
+
     /// Invalid message length for the given resolution
     #[error(
         "invalid message length: expected at least {expected}, got {actual}"
@@ -56,6 +74,35 @@ pub enum Error {
     #[error("missing required URL parameter: {parameter}")]
     MissingUrlParameter { parameter: String },
 
+    /// Refusing to overwrite an existing file
+    #[error("file already exists: {path}")]
+    FileExists { path: String },
+
+    /// A file could not be read as the expected contents
+    #[error("invalid file {path}: {details}")]
+    InvalidFile { path: String, details: String },
+
+    /// A mark archive could not be decoded
+    #[error("invalid archive at byte {offset}: {details}")]
+    InvalidArchive { offset: usize, details: String },
+
+    /// A line of a mark stream could not be decoded
+    #[error("invalid mark on line {line}: {details}")]
+    InvalidLine { line: usize, details: String },
+
+    /// A string could not be parsed as a mark's
+    /// [compact string](crate::ProvenanceMark::to_compact_string)
+    #[error("invalid compact mark string: {details}")]
+    InvalidCompactString { details: String },
+
+    /// A row of a record batch could not be reassembled into a mark
+    #[error("invalid mark in row {row}: {details}")]
+    InvalidRow { row: usize, details: String },
+
+    /// A column of a record batch is missing or cannot be read
+    #[error("invalid {column} column: {details}")]
+    InvalidColumn { column: String, details: String },
+
     /// Year out of range for 2-byte serialization
     #[error(
         "year out of range for 2-byte serialization: must be between 2023-2150, got {year}"
@@ -66,10 +113,115 @@ pub enum Error {
     #[error("invalid month ({month}) or day ({day}) for year {year}")]
     InvalidMonthOrDay { year: i32, month: u32, day: u32 },
 
+    /// A speculative mark does not match the generator's current state
+    #[error(
+        "speculative mark does not match generator state: expected seq {expected_seq}, got seq {actual_seq}"
+    )]
+    SpeculationMismatch { expected_seq: u32, actual_seq: u32 },
+
+    /// A fingerprint prefix could not be parsed
+    #[error("invalid fingerprint prefix {prefix:?}: {details}")]
+    InvalidFingerprintPrefix { prefix: String, details: String },
+
+    /// Words in a Mark ID that are not ByteWords or Bytemoji
+    #[error("unknown words in identifier: {}", .words.join(", "))]
+    UnknownIdentifierWords { words: Vec<String> },
+
+    /// A Mark ID with too few or too many words
+    #[error("identifier must have 4 to 32 words, got {actual}")]
+    InvalidIdentifierLength { actual: usize },
+
+    /// A genesis mark was requested from a generator past genesis
+    #[error(
+        "generator has already issued its genesis mark: next seq is {next_seq}"
+    )]
+    NotAtGenesis { next_seq: u32 },
+
+    /// Two chains derived from the same master seed have the same chain ID
+    #[error("derived chains {first} and {second} have the same chain ID")]
+    DerivedChainIdCollision { first: u32, second: u32 },
+
+    /// A published mark differs from the mark regenerated from the seed
+    #[error(
+        "published mark at seq {seq} does not match the chain regenerated from the seed"
+    )]
+    RecoveryMismatch { seq: u32 },
+
+    /// An info payload is larger than recommended for the resolution
+    #[error(
+        "info payload too large: {size} bytes exceeds the recommended {limit} bytes"
+    )]
+    InfoTooLarge { size: usize, limit: usize },
+
+    /// An info payload is not canonical dCBOR
+    #[error("info payload is not canonical dCBOR")]
+    NonCanonicalInfo,
+
+    /// A stored validation report's digest does not match its marks
+    #[error("digest mismatch at {path}")]
+    DigestMismatch { path: String },
+
+    /// A URL that cannot have path segments appended, such as a `mailto:` URL
+    #[error("URL cannot be a deep link base: {url}")]
+    InvalidDeepLinkBase { url: String },
+
+    /// A deep link whose path and query name different marks
+    #[error("deep link names different marks in its path and query: {url}")]
+    AmbiguousDeepLink { url: String },
+
+    /// Display JSON without a `ur` string to parse the mark from
+    #[error("display JSON has no \"ur\" string")]
+    MissingDisplayJsonUr,
+
+    /// A field of display JSON that disagrees with the mark parsed from its UR
+    #[error("display JSON field {field:?} does not match the mark's UR")]
+    DisplayJsonMismatch { field: String },
+
+    /// A column of a mark record that disagrees with the mark parsed from its
+    /// UR
+    #[error("mark record column {column:?} does not match the mark's UR")]
+    MarkRecordMismatch { column: String },
+
+    /// A validation report format name that is not recognized
+    #[error(
+        "unknown report format {format:?}: expected one of {}",
+        .valid.join(", ")
+    )]
+    UnknownReportFormat {
+        format: String,
+        valid: Vec<&'static str>,
+    },
+
+    /// The generator has no seed, as when it was created from a
+    /// continuation
+    #[error("the generator has no seed")]
+    MissingSeed,
+
+    /// The generator has issued its chain's termination mark
+    #[error("the chain has been terminated")]
+    ChainTerminated,
+
+    /// A mark whose info is not an encrypted info payload
+    #[error("mark has no encrypted info")]
+    NoEncryptedInfo,
+
+    /// An encrypted info payload that does not authenticate under the seed
+    #[error(
+        "encrypted info failed authentication: wrong seed or tampered payload"
+    )]
+    InfoAuthenticationFailed,
+
     /// Resolution serialization error
     #[error("resolution serialization error: {details}")]
     ResolutionError { details: String },
 
+    /// A resolution encoded as anything other than an unsigned integer from
+    /// 0 to 3
+    #[error(
+        "invalid resolution encoding: expected an unsigned integer from 0 to 3, got {found}"
+    )]
+    InvalidResolutionEncoding { found: String },
+
     /// Bytewords encoding/decoding error
     #[error("bytewords error: {0}")]
     Bytewords(#[from] bc_ur::Error),
@@ -79,17 +231,24 @@ pub enum Error {
     Cbor(#[from] dcbor::Error),
 
     /// URL parsing error
+    #[cfg(feature = "url")]
     #[error("URL parsing error: {0}")]
     Url(#[from] url::ParseError),
 
     /// Base64 decoding error
+    #[cfg(feature = "json")]
     #[error("base64 decoding error: {0}")]
     Base64(#[from] base64::DecodeError),
 
     /// JSON serialization error
+    #[cfg(feature = "json")]
     #[error("JSON error: {0}")]
     Json(#[from] serde_json::Error),
 
+    /// I/O error
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+
     /// Integer conversion error
     #[error("integer conversion error: {0}")]
     TryFromInt(#[from] std::num::TryFromIntError),
@@ -98,18 +257,105 @@ pub enum Error {
     #[error("envelope error: {0}")]
     Envelope(#[from] bc_envelope::Error),
 
+    /// Arrow error
+    #[cfg(feature = "arrow")]
+    #[error("Arrow error: {0}")]
+    Arrow(#[from] arrow::error::ArrowError),
+
     /// Validation error
     #[error("validation error: {0}")]
     Validation(#[from] crate::validate::ValidationIssue),
+
+    /// Several errors reported together
+    #[error("{}", format_multiple(.0))]
+    Multiple(Vec<Error>),
+}
+
+fn format_multiple(errors: &[Error]) -> String {
+    let items = errors
+        .iter()
+        .enumerate()
+        .map(|(i, e)| format!("({}) {}", i + 1, e))
+        .collect::<Vec<_>>();
+    format!("{} errors: {}", errors.len(), items.join("; "))
+}
+
+impl Error {
+    /// Collapses nested [`Error::Multiple`] values into a single level.
+    ///
+    /// A `Multiple` holding exactly one error is replaced by that error.
+    /// Other errors are returned unchanged.
+    pub fn flatten(self) -> Error {
+        match self {
+            Error::Multiple(errors) => {
+                let mut flat = Vec::new();
+                for error in errors {
+                    match error.flatten() {
+                        Error::Multiple(inner) => flat.extend(inner),
+                        other => flat.push(other),
+                    }
+                }
+                if flat.len() == 1 {
+                    flat.pop().unwrap()
+                } else {
+                    Error::Multiple(flat)
+                }
+            }
+            other => other,
+        }
+    }
+}
+
+/// Wraps the issues in an [`Error::Multiple`], flattened so that a single
+/// issue becomes a plain [`Error::Validation`].
+///
+/// An empty list gives an empty `Multiple`, which displays as `0 errors: `;
+/// callers should only convert a list that holds at least one issue.
+impl From<Vec<crate::validate::ValidationIssue>> for Error {
+    fn from(issues: Vec<crate::validate::ValidationIssue>) -> Self {
+        Error::Multiple(issues.into_iter().map(Error::Validation).collect())
+            .flatten()
+    }
 }
 
 pub type Result<T> = std::result::Result<T, Error>;
 
+/// Error from
+/// [`ProvenanceMarkGenerator::next_with_hook`](crate::ProvenanceMarkGenerator::next_with_hook)
+#[derive(Debug, Error)]
+pub enum IssueError<E> {
+    /// The hook rejected the candidate mark
+    #[error("mark rejected by issuance hook: {0}")]
+    Rejected(E),
+
+    /// The mark could not be issued
+    #[error(transparent)]
+    Generator(#[from] Error),
+}
+
+/// Error from [`ChainReport::marks_after`](crate::ChainReport::marks_after)
+/// when a subscriber cannot be caught up from the mark it already has.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Error)]
+pub enum SyncError {
+    /// The known mark is not one of the chain's marks
+    #[error("the known mark is not in the chain")]
+    UnknownMark,
+
+    /// The known mark is in the chain, but after a break in its verified
+    /// sequence, so nothing after it can be verified
+    #[error("the known mark is after a break in the chain")]
+    MarkOnBrokenBranch,
+
+    /// The known mark is the last verified mark of the chain
+    #[error("the known mark is the chain's latest verified mark")]
+    AlreadyUpToDate,
+}
+
 impl From<Error> for dcbor::Error {
     fn from(error: Error) -> dcbor::Error {
-        match error {
+        match error.flatten() {
             Error::Cbor(err) => err,
-            _ => dcbor::Error::Custom(error.to_string()),
+            error => dcbor::Error::Custom(error.to_string()),
         }
     }
 }
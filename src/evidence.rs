@@ -0,0 +1,111 @@
+use std::collections::BTreeMap;
+
+use serde::Serialize;
+
+use crate::{ProvenanceMark, ValidationIssue};
+
+/// What a linkage check compared when it flagged a mark, recorded when
+/// [`ValidationPolicy::with_include_evidence`](crate::ValidationPolicy::with_include_evidence)
+/// is on.
+///
+/// Linkage checks compare a mark with the mark before it in its chain, as by
+/// [`ProvenanceMark::precedes_opt`]. Positions are indices into the marks
+/// passed to validation, counting the first occurrence of a duplicated mark.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct IssueEvidence {
+    rule: &'static str,
+    previous_mark: String,
+    previous_position: usize,
+    position: usize,
+    values: BTreeMap<&'static str, String>,
+}
+
+impl IssueEvidence {
+    pub(crate) fn new(
+        issue: &ValidationIssue,
+        previous: (&ProvenanceMark, usize),
+        mark: (&ProvenanceMark, usize),
+    ) -> Self {
+        let values: Vec<(&'static str, String)> = match issue {
+            ValidationIssue::HashMismatch { expected, actual } => vec![
+                ("expected_hash", hex::encode(expected)),
+                ("actual_hash", hex::encode(actual)),
+            ],
+            ValidationIssue::SequenceGap { expected, actual } => vec![
+                ("expected_seq", expected.to_string()),
+                ("actual_seq", actual.to_string()),
+            ],
+            ValidationIssue::DuplicateSeq { seq } => {
+                vec![("seq", seq.to_string())]
+            }
+            ValidationIssue::SequenceRegression { previous, next } => vec![
+                ("previous_seq", previous.to_string()),
+                ("seq", next.to_string()),
+            ],
+            ValidationIssue::DateOrdering { previous, next } => vec![
+                ("previous_date", previous.to_string()),
+                ("date", next.to_string()),
+            ],
+            ValidationIssue::ResolutionMismatch { previous, next } => vec![
+                ("previous_resolution", previous.to_string()),
+                ("resolution", next.to_string()),
+            ],
+            ValidationIssue::NonGenesisAtZero => {
+                vec![("seq", mark.0.seq().to_string())]
+            }
+            ValidationIssue::ChainMismatch { previous_chain, next_chain } => {
+                vec![
+                    ("previous_chain", previous_chain.clone()),
+                    ("chain", next_chain.clone()),
+                ]
+            }
+            ValidationIssue::InvalidGenesisKey => vec![
+                ("key", hex::encode(mark.0.key())),
+                ("chain_id", hex::encode(mark.0.chain_id())),
+            ],
+            _ => Vec::new(),
+        };
+        Self {
+            rule: issue.code(),
+            previous_mark: previous.0.id_hex()[..8].to_string(),
+            previous_position: previous.1,
+            position: mark.1,
+            values: values.into_iter().collect(),
+        }
+    }
+
+    /// The [code](ValidationIssue::code) of the issue the check produced.
+    pub fn rule(&self) -> &'static str { self.rule }
+
+    /// The first 8 hex digits of the ID of the mark this mark was compared
+    /// with.
+    pub fn previous_mark(&self) -> &str { &self.previous_mark }
+
+    /// The position in the input of the mark this mark was compared with.
+    pub fn previous_position(&self) -> usize { self.previous_position }
+
+    /// The position in the input of the flagged mark.
+    pub fn position(&self) -> usize { self.position }
+
+    /// The values the check compared, such as `expected_hash` and
+    /// `actual_hash` in hex, or `previous_date` and `date`.
+    pub fn values(&self) -> &BTreeMap<&'static str, String> { &self.values }
+}
+
+impl std::fmt::Display for IssueEvidence {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} against {} (input {} after input {})",
+            self.rule,
+            self.previous_mark,
+            self.position,
+            self.previous_position
+        )?;
+        for (i, (name, value)) in self.values.iter().enumerate() {
+            let separator = if i == 0 { ": " } else { ", " };
+            write!(f, "{}{} {}", separator, name, value)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,108 @@
+//! Deterministic example chains for documentation and demos.
+//!
+//! Every call produces exactly the same marks, so example output (URs,
+//! identifiers, validation reports) can be shown without hard-coding it.
+
+use chrono::{Duration, TimeZone, Utc};
+use dcbor::Date;
+
+use crate::{
+    LinkKey, ProvenanceMark, ProvenanceMarkGenerator, ProvenanceMarkResolution,
+    ValidationReport,
+};
+
+/// The passphrase used to seed demo chains.
+pub const DEMO_PASSPHRASE: &str = "Wolf";
+
+/// The passphrase used to seed the second chain of
+/// [`DemoScenario::TwoChains`].
+pub const DEMO_SECOND_PASSPHRASE: &str = "Fox";
+
+/// The number of marks in the chains used by [`demo_report`].
+pub const DEMO_REPORT_LENGTH: usize = 5;
+
+/// The date of the mark with the given sequence number in a demo chain:
+/// 2023-06-20T12:00:00Z plus one day per mark.
+pub fn demo_date(seq: u32) -> Date {
+    let start = Utc
+        .with_ymd_and_hms(2023, 6, 20, 12, 0, 0)
+        .single()
+        .unwrap();
+    Date::from_datetime(start + Duration::days(seq as i64))
+}
+
+/// A reproducible chain of `len` marks, starting with the genesis mark.
+pub fn demo_chain(
+    res: ProvenanceMarkResolution,
+    len: usize,
+) -> Vec<ProvenanceMark> {
+    chain_with_passphrase(res, DEMO_PASSPHRASE, len)
+}
+
+/// The situations [`demo_report`] can illustrate.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DemoScenario {
+    /// A single complete chain with no issues.
+    Clean,
+    /// A chain with the mark at seq 2 missing.
+    Gap,
+    /// A chain without its genesis mark.
+    MissingGenesis,
+    /// A chain whose mark at seq 2 does not match the hash of seq 1.
+    HashMismatch,
+    /// Two unrelated complete chains.
+    TwoChains,
+}
+
+/// A reproducible validation report illustrating the given scenario.
+pub fn demo_report(
+    res: ProvenanceMarkResolution,
+    scenario: DemoScenario,
+) -> ValidationReport {
+    let chain = demo_chain(res, DEMO_REPORT_LENGTH);
+    let marks = match scenario {
+        DemoScenario::Clean => chain,
+        DemoScenario::Gap => {
+            chain.into_iter().filter(|mark| mark.seq() != 2).collect()
+        }
+        DemoScenario::MissingGenesis => chain.into_iter().skip(1).collect(),
+        DemoScenario::HashMismatch => {
+            // Replace seq 2 with a mark revealing a different key, so seq 1's
+            // hash no longer matches. It still commits to the key of seq 3.
+            let mut marks = chain.clone();
+            marks[2] = ProvenanceMark::new(
+                res,
+                LinkKey::new(res, chain[0].hash().as_bytes()).unwrap(),
+                chain[3].key().clone(),
+                chain[2].chain_id().clone(),
+                2,
+                chain[2].date(),
+                None::<String>,
+            )
+            .unwrap();
+            marks
+        }
+        DemoScenario::TwoChains => {
+            let mut marks = chain;
+            marks.extend(chain_with_passphrase(
+                res,
+                DEMO_SECOND_PASSPHRASE,
+                DEMO_REPORT_LENGTH,
+            ));
+            marks
+        }
+    };
+    ProvenanceMark::validate(marks)
+}
+
+fn chain_with_passphrase(
+    res: ProvenanceMarkResolution,
+    passphrase: &str,
+    len: usize,
+) -> Vec<ProvenanceMark> {
+    let mut generator =
+        ProvenanceMarkGenerator::new_with_passphrase(res, passphrase);
+    (0..len)
+        .map(|i| generator.next(demo_date(i as u32), None::<String>))
+        .collect()
+}
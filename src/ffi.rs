@@ -0,0 +1,243 @@
+//! A minimal C interface for verifying marks.
+//!
+//! Marks are parsed from UR strings into opaque [`PmMark`] handles, which
+//! must be released with [`pm_mark_free`]. Functions that can fail record a
+//! message that [`pm_last_error_message`] retrieves; the message is kept per
+//! thread and replaced by the next failure.
+//!
+//! Strings are returned by copying into a caller-supplied buffer with
+//! `snprintf` semantics: at most `len - 1` bytes are written followed by a NUL,
+//! and the return value is the length of the whole string, so a return value
+//! of `len` or more means the output was truncated.
+//!
+//! The header `include/provenance_mark.h` declares these functions. It is
+//! regenerated with `cbindgen --config cbindgen.toml src/ffi.rs -o
+//! include/provenance_mark.h`, and the tests check that it is current.
+//!
+//! The crate builds only as a Rust library by default. To build a shared
+//! library for C callers, run:
+//!
+//! ```sh
+//! cargo rustc --release --features ffi --crate-type cdylib
+//! ```
+
+use std::{
+    cell::RefCell,
+    ffi::{CStr, c_char, c_int},
+    ptr, slice,
+};
+
+use crate::ProvenanceMark;
+
+thread_local! {
+    static LAST_ERROR: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+fn set_last_error(message: impl Into<String>) {
+    LAST_ERROR.with(|last| *last.borrow_mut() = Some(message.into()));
+}
+
+/// An opaque handle to a parsed mark.
+pub struct PmMark(ProvenanceMark);
+
+/// Borrow the mark behind a handle, recording an error if it is null.
+///
+/// # Safety
+///
+/// `mark` must be null or a handle returned by [`pm_mark_parse_ur`] that has
+/// not been freed.
+unsafe fn mark_ref<'a>(mark: *const PmMark) -> Option<&'a ProvenanceMark> {
+    // SAFETY: the caller guarantees the pointer is null or valid.
+    let mark = unsafe { mark.as_ref() }.map(|handle| &handle.0);
+    if mark.is_none() {
+        set_last_error("null mark handle");
+    }
+    mark
+}
+
+/// Copy `s` into `buf` with `snprintf` semantics, never splitting a UTF-8
+/// character, and return the full length of `s`.
+///
+/// # Safety
+///
+/// `buf` must be null or valid for writes of `len` bytes.
+unsafe fn copy_to_buffer(s: &str, buf: *mut c_char, len: usize) -> usize {
+    if !buf.is_null() && len > 0 {
+        let mut n = s.len().min(len - 1);
+        while !s.is_char_boundary(n) {
+            n -= 1;
+        }
+        // SAFETY: the caller guarantees `buf` is valid for `len` bytes, and
+        // `n + 1 <= len`.
+        unsafe {
+            ptr::copy_nonoverlapping(s.as_ptr().cast::<c_char>(), buf, n);
+            *buf.add(n) = 0;
+        }
+    }
+    s.len()
+}
+
+/// Parse a `ur:provenance` string into a mark handle.
+///
+/// Returns null and records an error if `ur` is null, is not UTF-8, or does
+/// not decode as a mark.
+///
+/// # Safety
+///
+/// `ur` must be null or a pointer to a NUL-terminated string.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn pm_mark_parse_ur(ur: *const c_char) -> *mut PmMark {
+    if ur.is_null() {
+        set_last_error("null UR string");
+        return ptr::null_mut();
+    }
+    // SAFETY: the caller guarantees `ur` is NUL-terminated.
+    let Ok(ur) = unsafe { CStr::from_ptr(ur) }.to_str() else {
+        set_last_error("UR string is not valid UTF-8");
+        return ptr::null_mut();
+    };
+    match ProvenanceMark::parse_ur_string(ur) {
+        Ok(mark) => Box::into_raw(Box::new(PmMark(mark))),
+        Err(error) => {
+            set_last_error(error.to_string());
+            ptr::null_mut()
+        }
+    }
+}
+
+/// Copy the mark's identifier, its first four Mark ID bytes as upper-case
+/// ByteWords such as `WHAT HANG SOAP SETS`, into `buf`.
+///
+/// Returns the length of the identifier, or 0 if `mark` is null.
+///
+/// # Safety
+///
+/// `mark` must be null or a live handle, and `buf` must be null or valid for
+/// writes of `len` bytes.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn pm_mark_identifier(
+    mark: *const PmMark,
+    buf: *mut c_char,
+    len: usize,
+) -> usize {
+    // SAFETY: forwarded from the caller.
+    match unsafe { mark_ref(mark) } {
+        Some(mark) => unsafe {
+            copy_to_buffer(&mark.id_bytewords(4, false), buf, len)
+        },
+        None => 0,
+    }
+}
+
+/// The mark's sequence number, or 0 if `mark` is null.
+///
+/// # Safety
+///
+/// `mark` must be null or a live handle.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn pm_mark_seq(mark: *const PmMark) -> u32 {
+    // SAFETY: forwarded from the caller.
+    unsafe { mark_ref(mark) }.map_or(0, ProvenanceMark::seq)
+}
+
+/// The mark's date as seconds since the Unix epoch, or 0 if `mark` is null.
+///
+/// # Safety
+///
+/// `mark` must be null or a live handle.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn pm_mark_date(mark: *const PmMark) -> i64 {
+    // SAFETY: forwarded from the caller.
+    unsafe { mark_ref(mark) }
+        .map_or(0, |mark| mark.date().datetime().timestamp())
+}
+
+/// Copy up to `len` bytes of the mark's chain ID into `buf`.
+///
+/// Returns the length of the chain ID, which is between 4 and 32 bytes
+/// depending on the mark's resolution, or 0 if `mark` is null.
+///
+/// # Safety
+///
+/// `mark` must be null or a live handle, and `buf` must be null or valid for
+/// writes of `len` bytes.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn pm_mark_chain_id(
+    mark: *const PmMark,
+    buf: *mut u8,
+    len: usize,
+) -> usize {
+    // SAFETY: forwarded from the caller.
+    let Some(mark) = (unsafe { mark_ref(mark) }) else {
+        return 0;
+    };
+    let chain_id = mark.chain_id();
+    if !buf.is_null() {
+        let n = chain_id.len().min(len);
+        // SAFETY: the caller guarantees `buf` is valid for `len` bytes.
+        unsafe { slice::from_raw_parts_mut(buf, n) }
+            .copy_from_slice(&chain_id[..n]);
+    }
+    chain_id.len()
+}
+
+/// Whether `next` directly follows `previous` in the same chain.
+///
+/// Returns 1 if it does and 0 if it does not, recording the reason as the
+/// last error. Returns -1 if either handle is null.
+///
+/// # Safety
+///
+/// `previous` and `next` must each be null or a live handle.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn pm_mark_precedes(
+    previous: *const PmMark,
+    next: *const PmMark,
+) -> c_int {
+    // SAFETY: forwarded from the caller.
+    let (Some(previous), Some(next)) =
+        (unsafe { mark_ref(previous) }, unsafe { mark_ref(next) })
+    else {
+        return -1;
+    };
+    match previous.precedes_opt(next) {
+        Ok(()) => 1,
+        Err(error) => {
+            set_last_error(error.to_string());
+            0
+        }
+    }
+}
+
+/// Release a mark handle. Passing null does nothing.
+///
+/// # Safety
+///
+/// `mark` must be null or a live handle, which must not be used afterwards.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn pm_mark_free(mark: *mut PmMark) {
+    if !mark.is_null() {
+        // SAFETY: the handle was created by `Box::into_raw` in
+        // `pm_mark_parse_ur` and the caller guarantees it is not reused.
+        drop(unsafe { Box::from_raw(mark) });
+    }
+}
+
+/// Copy the message of the last error on this thread into `buf`.
+///
+/// Returns the length of the message, or 0 if no error has occurred.
+///
+/// # Safety
+///
+/// `buf` must be null or valid for writes of `len` bytes.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn pm_last_error_message(
+    buf: *mut c_char,
+    len: usize,
+) -> usize {
+    LAST_ERROR.with(|last| match last.borrow().as_deref() {
+        // SAFETY: forwarded from the caller.
+        Some(message) => unsafe { copy_to_buffer(message, buf, len) },
+        None => 0,
+    })
+}
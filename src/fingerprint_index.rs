@@ -0,0 +1,100 @@
+use std::{collections::BTreeMap, sync::Arc};
+
+use crate::{Error, ProvenanceMark, Result, crypto_utils::SHA256_SIZE};
+
+/// A lookup table from mark fingerprints back to marks.
+///
+/// Supports exact lookups by full fingerprint as well as resolution of the
+/// short hex prefixes users are likely to type, e.g. `pm show 3fa9`.
+#[derive(Debug, Clone, Default)]
+pub struct FingerprintIndex {
+    marks: BTreeMap<[u8; SHA256_SIZE], Arc<ProvenanceMark>>,
+}
+
+/// The result of resolving a fingerprint prefix against a
+/// [`FingerprintIndex`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PrefixResolution<'a> {
+    /// Exactly one mark has a fingerprint beginning with the prefix.
+    Unique(&'a ProvenanceMark),
+    /// More than one mark matches, in fingerprint order.
+    Ambiguous(Vec<&'a ProvenanceMark>),
+    /// No mark matches.
+    NotFound,
+}
+
+impl FingerprintIndex {
+    pub fn build(marks: &[ProvenanceMark]) -> Self {
+        let marks = marks
+            .iter()
+            .map(|mark| (mark.fingerprint(), Arc::new(mark.clone())))
+            .collect();
+        Self { marks }
+    }
+
+    /// Builds an index that shares the given marks rather than copying them.
+    pub(crate) fn from_shared(marks: &[Arc<ProvenanceMark>]) -> Self {
+        let marks = marks
+            .iter()
+            .map(|mark| (mark.fingerprint(), mark.clone()))
+            .collect();
+        Self { marks }
+    }
+
+    pub fn len(&self) -> usize { self.marks.len() }
+
+    pub fn is_empty(&self) -> bool { self.marks.is_empty() }
+
+    /// Returns the mark with exactly the given fingerprint.
+    pub fn get(
+        &self,
+        fingerprint: &[u8; SHA256_SIZE],
+    ) -> Option<&ProvenanceMark> {
+        self.marks.get(fingerprint).map(Arc::as_ref)
+    }
+
+    /// Resolves a hex fingerprint prefix to the marks it matches.
+    ///
+    /// The prefix must be a non-empty, even-length hex string of at most 64
+    /// characters; case is ignored.
+    pub fn resolve_prefix(
+        &self,
+        hex_prefix: &str,
+    ) -> Result<PrefixResolution<'_>> {
+        let prefix = parse_hex_prefix(hex_prefix)?;
+
+        let mut start = [0u8; SHA256_SIZE];
+        start[..prefix.len()].copy_from_slice(&prefix);
+        let mut matches: Vec<&ProvenanceMark> = self
+            .marks
+            .range(start..)
+            .take_while(|(fingerprint, _)| fingerprint.starts_with(&prefix))
+            .map(|(_, mark)| mark.as_ref())
+            .collect();
+
+        Ok(match matches.len() {
+            0 => PrefixResolution::NotFound,
+            1 => PrefixResolution::Unique(matches.remove(0)),
+            _ => PrefixResolution::Ambiguous(matches),
+        })
+    }
+}
+
+fn parse_hex_prefix(hex_prefix: &str) -> Result<Vec<u8>> {
+    let invalid = |details: &str| Error::InvalidFingerprintPrefix {
+        prefix: hex_prefix.to_string(),
+        details: details.to_string(),
+    };
+    if hex_prefix.is_empty() {
+        return Err(invalid("prefix is empty"));
+    }
+    if !hex_prefix.len().is_multiple_of(2) {
+        return Err(invalid(
+            "odd number of hex digits; prefixes must be whole bytes",
+        ));
+    }
+    if hex_prefix.len() > SHA256_SIZE * 2 {
+        return Err(invalid("longer than a 32-byte fingerprint"));
+    }
+    hex::decode(hex_prefix).map_err(|e| invalid(&e.to_string()))
+}
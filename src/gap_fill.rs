@@ -0,0 +1,77 @@
+use crate::{ChainReport, ProvenanceMark};
+
+/// The outcome of checking a mark obtained out of band against a gap in a
+/// chain, from [`ChainReport::fills_gap`].
+///
+/// Each check is reported separately so callers can tell a mark from another
+/// chain apart from one that claims the right place but does not link.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GapFillResult {
+    chain_id_matches: bool,
+    in_gap: bool,
+    follows_previous: Option<bool>,
+    precedes_next: Option<bool>,
+}
+
+impl GapFillResult {
+    /// Whether the candidate has the chain's ID.
+    pub fn chain_id_matches(&self) -> bool { self.chain_id_matches }
+
+    /// Whether the candidate's sequence number is missing from the chain and
+    /// some later mark is present, so the candidate would sit in a gap rather
+    /// than extend the chain.
+    pub fn in_gap(&self) -> bool { self.in_gap }
+
+    /// Whether the mark directly before the candidate precedes it.
+    ///
+    /// `None` if the candidate is not in a gap or the mark before it is also
+    /// missing.
+    pub fn follows_previous(&self) -> Option<bool> { self.follows_previous }
+
+    /// Whether the candidate precedes the mark directly after it.
+    ///
+    /// `None` if the candidate is not in a gap or the mark after it is also
+    /// missing.
+    pub fn precedes_next(&self) -> Option<bool> { self.precedes_next }
+
+    /// Whether every check that applies passed.
+    ///
+    /// A candidate in the middle of a gap of several marks can pass without
+    /// either linkage check applying; it only links once its neighbours are
+    /// filled in too.
+    pub fn is_fill(&self) -> bool {
+        self.chain_id_matches
+            && self.in_gap
+            && self.follows_previous != Some(false)
+            && self.precedes_next != Some(false)
+    }
+}
+
+impl ChainReport {
+    /// Check whether `candidate` fills a gap in this chain, without
+    /// validating the chain again.
+    ///
+    /// To see the chain with the candidate included, use
+    /// [`ValidationReport::with_additional_marks`](crate::ValidationReport::with_additional_marks).
+    pub fn fills_gap(&self, candidate: &ProvenanceMark) -> GapFillResult {
+        let seq = candidate.seq();
+        let previous = self.marks().iter().rev().find(|mark| mark.seq() < seq);
+        let next = self.marks().iter().find(|mark| mark.seq() > seq);
+        let in_gap = next.is_some()
+            && !self.marks().iter().any(|mark| mark.seq() == seq);
+
+        let follows_previous = previous
+            .filter(|mark| in_gap && mark.seq() + 1 == seq)
+            .map(|mark| mark.precedes_opt(candidate).is_ok());
+        let precedes_next = next
+            .filter(|mark| in_gap && mark.seq() - 1 == seq)
+            .map(|mark| candidate.precedes_opt(mark).is_ok());
+
+        GapFillResult {
+            chain_id_matches: candidate.chain_id() == self.chain_id(),
+            in_gap,
+            follows_previous,
+            precedes_next,
+        }
+    }
+}
@@ -1,4 +1,7 @@
-use std::fmt::Formatter;
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt::Formatter,
+};
 
 #[cfg(feature = "envelope")]
 use bc_envelope::prelude::*;
@@ -6,42 +9,298 @@ use bc_rand::RandomNumberGenerator;
 use dcbor::Date;
 #[cfg(not(feature = "envelope"))]
 use dcbor::prelude::*;
+#[cfg(feature = "json")]
 use serde::{Deserialize, Serialize};
 
+#[cfg(feature = "json")]
+use crate::util::{deserialize_optional_iso8601, serialize_optional_iso8601};
 use crate::{
-    Error, ProvenanceMark, ProvenanceMarkResolution, ProvenanceSeed, Result,
-    RngState,
-    crypto_utils::sha256,
-    util::{deserialize_base64, serialize_base64},
+    ChainContinuation, ChainId, Error, IssueError, LinkKey, ProvenanceMark,
+    ProvenanceMarkResolution, ProvenanceSeed, Result, RngState,
+    continuation::next_seq_from_u64, crypto_utils::sha256,
     xoshiro256starstar::Xoshiro256StarStar,
 };
 
-#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+/// CBOR tag reserved for the proposed generator UR type ("PRVG").
+///
+/// Experimental: not allocated in `bc-tags`, and registered only by
+/// [`register_experimental_tags_in`](crate::register_experimental_tags_in).
+pub const TAG_PROVENANCE_GENERATOR: u64 = 1347573319;
+pub const TAG_NAME_PROVENANCE_GENERATOR: &str = "provenance-generator";
+
+/// CBOR tag wrapping the info of a chain's
+/// [termination mark](ProvenanceMarkGenerator::terminate) ("PRVT").
+///
+/// Experimental: not allocated in `bc-tags`, and registered only by
+/// [`register_experimental_tags_in`](crate::register_experimental_tags_in).
+pub const TAG_CHAIN_TERMINATION: u64 = 1347573332;
+pub const TAG_NAME_CHAIN_TERMINATION: &str = "provenance-termination";
+
+/// Issues the marks of a chain.
+///
+/// Generators are deliberately not `Clone`: issuing marks from two copies of
+/// a generator forks the chain. Use [`snapshot`](Self::snapshot) for a
+/// read-only copy, or
+/// [`dangerous_clone_for_fork`](Self::dangerous_clone_for_fork)
+/// if an independent copy that can issue marks is really needed.
+#[derive(Debug)]
+#[cfg_attr(feature = "json", derive(Serialize, Deserialize))]
 pub struct ProvenanceMarkGenerator {
     res: ProvenanceMarkResolution,
-    seed: ProvenanceSeed,
-    #[serde(rename = "chainID")]
-    #[serde(
-        serialize_with = "serialize_base64",
-        deserialize_with = "deserialize_base64"
+    /// Required in JSON, and `null` for a generator restored from a
+    /// [`ChainContinuation`], so that a missing seed is not mistaken for one.
+    #[cfg_attr(
+        feature = "json",
+        serde(deserialize_with = "Option::deserialize")
     )]
-    chain_id: Vec<u8>,
-    #[serde(rename = "nextSeq")]
+    seed: Option<ProvenanceSeed>,
+    #[cfg_attr(feature = "json", serde(rename = "chainID"))]
+    chain_id: ChainId,
+    #[cfg_attr(feature = "json", serde(rename = "nextSeq"))]
     next_seq: u32,
-    #[serde(rename = "rngState")]
+    #[cfg_attr(feature = "json", serde(rename = "rngState"))]
     rng_state: RngState,
+    #[cfg_attr(
+        feature = "json",
+        serde(
+            rename = "createdAt",
+            default,
+            skip_serializing_if = "Option::is_none",
+            serialize_with = "serialize_optional_iso8601",
+            deserialize_with = "deserialize_optional_iso8601"
+        )
+    )]
+    created_at: Option<Date>,
+    /// `None` for generators loaded from data that does not record it.
+    #[cfg_attr(
+        feature = "json",
+        serde(
+            rename = "marksIssued",
+            default,
+            skip_serializing_if = "Option::is_none"
+        )
+    )]
+    marks_issued: Option<u64>,
+    #[cfg_attr(
+        feature = "json",
+        serde(
+            rename = "lastIssuedAt",
+            default,
+            skip_serializing_if = "Option::is_none",
+            serialize_with = "serialize_optional_iso8601",
+            deserialize_with = "deserialize_optional_iso8601"
+        )
+    )]
+    last_issued_at: Option<Date>,
+    #[cfg_attr(
+        feature = "json",
+        serde(default, skip_serializing_if = "std::ops::Not::not")
+    )]
+    terminated: bool,
+    #[cfg_attr(feature = "json", serde(skip))]
+    info_checks: bool,
+}
+
+/// Generators are equal if they will produce the same marks; the operational
+/// statistics in [`GeneratorStats`] are not compared.
+impl PartialEq for ProvenanceMarkGenerator {
+    fn eq(&self, other: &Self) -> bool {
+        self.res == other.res
+            && self.seed == other.seed
+            && self.chain_id == other.chain_id
+            && self.next_seq == other.next_seq
+            && self.rng_state == other.rng_state
+            && self.terminated == other.terminated
+    }
+}
+
+impl Eq for ProvenanceMarkGenerator {}
+
+/// A read-only copy of a [`ProvenanceMarkGenerator`]'s state, from
+/// [`ProvenanceMarkGenerator::snapshot`], for inspecting or auditing a
+/// generator without being able to issue marks from it.
+///
+/// A snapshot cannot be turned back into a generator, so keeping one never
+/// risks forking the chain. With the `json` feature it serializes exactly as
+/// the generator does, seed included, so stored snapshots need the same
+/// protection as stored generators.
+///
+/// ```compile_fail
+/// # use provenance_mark::*;
+/// let generator = ProvenanceMarkGenerator::new_with_passphrase(
+///     ProvenanceMarkResolution::Low,
+///     "Wolf",
+/// );
+/// let mut snapshot = generator.snapshot();
+/// snapshot.next(dcbor::Date::now(), None::<&str>);
+/// ```
+#[derive(Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "json", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "json", serde(transparent))]
+pub struct GeneratorSnapshot(ProvenanceMarkGenerator);
+
+impl Clone for GeneratorSnapshot {
+    fn clone(&self) -> Self { Self(self.0.dangerous_clone_for_fork()) }
+}
+
+impl GeneratorSnapshot {
+    pub fn res(&self) -> &ProvenanceMarkResolution { self.0.res() }
+    pub fn chain_id(&self) -> &ChainId { self.0.chain_id() }
+    pub fn next_seq(&self) -> u32 { self.0.next_seq() }
+    pub fn is_terminated(&self) -> bool { self.0.is_terminated() }
+    pub fn stats(&self) -> GeneratorStats { self.0.stats() }
+
+    /// The mark the generator would issue next with the given date and info,
+    /// as by [`ProvenanceMarkGenerator::speculate`].
+    pub fn expected_next(
+        &self,
+        date: Date,
+        info: Option<impl CBOREncodable>,
+    ) -> Result<ProvenanceMark> {
+        self.0.speculate(date, info)
+    }
+
+    /// Whether `mark` is the mark the generator would issue next, such as
+    /// one published by another process that is about to be committed.
+    pub fn is_expected_next(&self, mark: &ProvenanceMark) -> bool {
+        self.expected_next(mark.date(), mark.info())
+            .is_ok_and(|expected| expected == *mark)
+    }
+
+    /// As [`ProvenanceMarkGenerator::summary_markdown`].
+    #[cfg(feature = "markdown")]
+    pub fn summary_markdown(&self) -> String { self.0.summary_markdown() }
+}
+
+/// Operational statistics about a [`ProvenanceMarkGenerator`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GeneratorStats {
+    created_at: Option<Date>,
+    marks_issued: u64,
+    last_issued_at: Option<Date>,
+}
+
+impl GeneratorStats {
+    pub fn created_at(&self) -> Option<Date> { self.created_at }
+    pub fn marks_issued(&self) -> u64 { self.marks_issued }
+    pub fn last_issued_at(&self) -> Option<Date> { self.last_issued_at }
 }
 
 impl ProvenanceMarkGenerator {
     pub fn res(&self) -> &ProvenanceMarkResolution { &self.res }
 
-    pub fn seed(&self) -> &ProvenanceSeed { &self.seed }
+    /// The seed the chain was created from, or `None` for a generator
+    /// restored from a [`ChainContinuation`].
+    pub fn seed(&self) -> Option<&ProvenanceSeed> { self.seed.as_ref() }
 
-    pub fn chain_id(&self) -> &[u8] { &self.chain_id }
+    pub fn chain_id(&self) -> &ChainId { &self.chain_id }
 
     pub fn next_seq(&self) -> u32 { self.next_seq }
 
     pub fn rng_state(&self) -> &RngState { &self.rng_state }
+
+    /// When this generator was created, or `None` for a generator loaded from
+    /// an envelope or from JSON that predates this field, neither of which
+    /// records it.
+    pub fn created_at(&self) -> Option<Date> { self.created_at }
+
+    /// The number of marks issued by this generator. Marks issued on the same
+    /// chain by other generators, e.g. before a
+    /// [`ChainContinuation`] hand-off, are not counted. Seeded generators
+    /// loaded from an envelope or from JSON that predates this count are
+    /// taken to have issued every mark before [`next_seq`](Self::next_seq).
+    pub fn marks_issued(&self) -> u64 {
+        self.marks_issued.unwrap_or(u64::from(self.next_seq))
+    }
+
+    /// The date of the most recently issued mark, if any.
+    pub fn last_issued_at(&self) -> Option<Date> { self.last_issued_at }
+
+    /// Whether [`try_next`](Self::try_next) refuses info payloads that fail
+    /// [`check_info`](Self::check_info).
+    pub fn info_checks(&self) -> bool { self.info_checks }
+
+    /// Whether the generator has issued its chain's
+    /// [termination mark](Self::terminate), and so refuses to issue more.
+    pub fn is_terminated(&self) -> bool { self.terminated }
+
+    /// Enables or disables enforcement of [`check_info`](Self::check_info) by
+    /// [`try_next`](Self::try_next). This setting is not persisted.
+    pub fn with_info_checks(mut self, enabled: bool) -> Self {
+        self.info_checks = enabled;
+        self
+    }
+
+    pub fn set_info_checks(&mut self, enabled: bool) {
+        self.info_checks = enabled;
+    }
+
+    pub fn stats(&self) -> GeneratorStats {
+        GeneratorStats {
+            created_at: self.created_at,
+            marks_issued: self.marks_issued(),
+            last_issued_at: self.last_issued_at,
+        }
+    }
+
+    /// A read-only copy of the generator's current state, which cannot
+    /// issue marks.
+    pub fn snapshot(&self) -> GeneratorSnapshot {
+        GeneratorSnapshot(self.dangerous_clone_for_fork())
+    }
+
+    /// An independent copy of the generator, which can issue marks.
+    ///
+    /// Issuing marks from both the copy and the original forks the chain:
+    /// each issues a different mark with the same sequence number, and later
+    /// marks can follow only one of them. Use [`snapshot`](Self::snapshot)
+    /// to inspect a generator instead.
+    pub fn dangerous_clone_for_fork(&self) -> Self {
+        Self {
+            res: self.res,
+            seed: self.seed.clone(),
+            chain_id: self.chain_id.clone(),
+            next_seq: self.next_seq,
+            rng_state: self.rng_state.clone(),
+            created_at: self.created_at,
+            marks_issued: self.marks_issued,
+            last_issued_at: self.last_issued_at,
+            terminated: self.terminated,
+            info_checks: self.info_checks,
+        }
+    }
+
+    /// A Markdown description of the chain and this generator's statistics.
+    #[cfg(feature = "markdown")]
+    pub fn summary_markdown(&self) -> String {
+        let mut lines: Vec<String> = Vec::new();
+
+        lines.push("---".to_string());
+
+        lines.push("".to_string());
+        lines.push(format!("#### Chain `{}`", hex::encode(&self.chain_id)));
+
+        lines.push("".to_string());
+        lines.push(format!("- Resolution: {}", self.res));
+        lines.push(format!("- Next sequence: {}", self.next_seq));
+        lines.push(format!("- Marks issued: {}", self.marks_issued()));
+        lines.push(format!(
+            "- Created: {}",
+            self.created_at
+                .map(|date| date.to_string())
+                .unwrap_or_else(|| "unknown".to_string())
+        ));
+        lines.push(format!(
+            "- Last issued: {}",
+            self.last_issued_at
+                .map(|date| date.to_string())
+                .unwrap_or_else(|| "never".to_string())
+        ));
+
+        lines.push("".to_string());
+
+        lines.join("\n")
+    }
 }
 
 impl ProvenanceMarkGenerator {
@@ -56,6 +315,18 @@ impl ProvenanceMarkGenerator {
         Self::new(res, seed.clone(), chain_id, 0, digest2.into()).unwrap()
     }
 
+    /// Creates a generator from a [`bc_components::Seed`], such as one
+    /// decoded from a `ur:seed`. See
+    /// [`ProvenanceSeed::try_from_bc_seed`].
+    #[cfg(feature = "components")]
+    pub fn new_with_bc_seed(
+        res: ProvenanceMarkResolution,
+        seed: &bc_components::Seed,
+    ) -> Result<Self> {
+        let seed = ProvenanceSeed::try_from_bc_seed(seed)?;
+        Ok(Self::new_with_seed(res, seed))
+    }
+
     pub fn new_with_passphrase(
         res: ProvenanceMarkResolution,
         passphrase: &str,
@@ -64,6 +335,68 @@ impl ProvenanceMarkGenerator {
         Self::new_with_seed(res, seed)
     }
 
+    /// Creates `count` independent chains from the [child
+    /// seeds](ProvenanceSeed::derive_child) of `master`, issuing each chain's
+    /// genesis mark dated `date`.
+    ///
+    /// The chain at index `i` of the result uses child seed `i`, so the same
+    /// master always yields the same chains in the same order. With the
+    /// `rayon` feature the chains are created in parallel.
+    ///
+    /// Returns an error if `date` cannot be represented at `res`, or
+    /// [`Error::DerivedChainIdCollision`] if two chains have the same chain
+    /// ID, which becomes possible at low resolution with many chains. If
+    /// several chains fail, every failure is reported in an
+    /// [`Error::Multiple`].
+    pub fn bulk_new_derived(
+        master: &ProvenanceSeed,
+        res: ProvenanceMarkResolution,
+        count: u32,
+        date: Date,
+    ) -> Result<Vec<(Self, ProvenanceMark)>> {
+        let genesis = |index: u32| -> Result<(Self, ProvenanceMark)> {
+            let mut generator =
+                Self::new_with_seed(res, master.derive_child(index));
+            let mark = generator.try_next(date, None::<CBOR>)?;
+            Ok((generator, mark))
+        };
+
+        #[cfg(feature = "rayon")]
+        let results: Vec<_> = {
+            use rayon::prelude::*;
+            (0..count).into_par_iter().map(genesis).collect()
+        };
+        #[cfg(not(feature = "rayon"))]
+        let results: Vec<_> = (0..count).map(genesis).collect();
+
+        let mut chains = Vec::with_capacity(results.len());
+        let mut errors = Vec::new();
+        for result in results {
+            match result {
+                Ok(chain) => chains.push(chain),
+                Err(error) => errors.push(error),
+            }
+        }
+        if !errors.is_empty() {
+            return Err(Error::Multiple(errors).flatten());
+        }
+
+        let mut indices = HashMap::new();
+        for (index, (generator, _)) in (0..).zip(&chains) {
+            let first = *indices.entry(generator.chain_id()).or_insert(index);
+            if first != index {
+                errors.push(Error::DerivedChainIdCollision {
+                    first,
+                    second: index,
+                });
+            }
+        }
+        if !errors.is_empty() {
+            return Err(Error::Multiple(errors).flatten());
+        }
+        Ok(chains)
+    }
+
     pub fn new_using(
         res: ProvenanceMarkResolution,
         rng: &mut impl RandomNumberGenerator,
@@ -79,20 +412,107 @@ impl ProvenanceMarkGenerator {
         Self::new_with_seed(res, seed)
     }
 
+    /// Returns [`Error::SequenceOutOfRange`] if `next_seq` does not fit in a
+    /// `u32`.
     pub fn new(
         res: ProvenanceMarkResolution,
         seed: ProvenanceSeed,
         chain_id: Vec<u8>,
-        next_seq: u32,
+        next_seq: u64,
         rng_state: RngState,
     ) -> Result<Self> {
-        if chain_id.len() != res.link_length() {
-            return Err(Error::InvalidChainIdLength {
-                expected: res.link_length(),
-                actual: chain_id.len(),
-            });
+        let chain_id = ChainId::new(res, chain_id)?;
+        let next_seq = next_seq_from_u64(next_seq)?;
+        Ok(Self {
+            res,
+            seed: Some(seed),
+            chain_id,
+            next_seq,
+            rng_state,
+            created_at: Some(Date::now()),
+            marks_issued: Some(0),
+            last_issued_at: None,
+            terminated: false,
+            info_checks: false,
+        })
+    }
+
+    /// Exports the state needed to continue issuing marks on this chain,
+    /// without the seed.
+    ///
+    /// See [`ChainContinuation`] for the trust implications of handing off a
+    /// chain.
+    pub fn export_continuation(&self) -> ChainContinuation {
+        ChainContinuation::new(
+            self.res,
+            self.chain_id.to_vec(),
+            self.next_seq.into(),
+            self.rng_state.clone(),
+        )
+        .unwrap()
+    }
+
+    /// Creates a generator that continues a chain from a
+    /// [`ChainContinuation`]. The resulting generator has no seed.
+    pub fn from_continuation(continuation: ChainContinuation) -> Self {
+        Self {
+            res: continuation.res(),
+            seed: None,
+            chain_id: ChainId::new_unchecked(continuation.chain_id().to_vec()),
+            next_seq: continuation.next_seq(),
+            rng_state: continuation.rng_state().clone(),
+            created_at: Some(Date::now()),
+            marks_issued: Some(0),
+            last_issued_at: None,
+            terminated: false,
+            info_checks: false,
+        }
+    }
+
+    /// Recreates the generator for a chain whose state was lost, from the
+    /// chain's seed and the marks it has published.
+    ///
+    /// The chain is regenerated from `seed`, and each mark in `published`
+    /// must match the regenerated mark with the same sequence number. Marks
+    /// missing from `published` are skipped, since their keys are derived
+    /// from the seed. The returned generator issues the mark after the last
+    /// published one, and its [statistics](Self::stats) count the published
+    /// marks.
+    ///
+    /// Returns [`Error::RecoveryMismatch`] with the sequence number of the
+    /// first published mark that differs from the regenerated chain,
+    /// including one from another chain or resolution.
+    pub fn recover(
+        seed: &ProvenanceSeed,
+        res: ProvenanceMarkResolution,
+        published: &[ProvenanceMark],
+    ) -> Result<Self> {
+        let mut by_seq: BTreeMap<u32, &ProvenanceMark> = BTreeMap::new();
+        for mark in published {
+            let previous = by_seq.insert(mark.seq(), mark);
+            if previous.is_some_and(|previous| previous != mark) {
+                return Err(Error::RecoveryMismatch { seq: mark.seq() });
+            }
         }
-        Ok(Self { res, seed, chain_id, next_seq, rng_state })
+
+        let mut generator = Self::new_with_seed(res, seed.clone());
+        for (&seq, &mark) in &by_seq {
+            while generator.next_seq < seq {
+                generator.skip();
+            }
+            let regenerated = generator
+                .advance(mark.date(), mark.info())
+                .ok()
+                .filter(|(expected, _)| {
+                    mark.res() == res
+                        && expected.fingerprint() == mark.fingerprint()
+                });
+            let Some((_, rng_state)) = regenerated else {
+                return Err(Error::RecoveryMismatch { seq });
+            };
+            generator.record_issued(mark, rng_state);
+        }
+        Ok(generator)
     }
 
     pub fn next(
@@ -100,38 +520,304 @@ impl ProvenanceMarkGenerator {
         date: Date,
         info: Option<impl CBOREncodable>,
     ) -> ProvenanceMark {
+        self.issue(date, info).unwrap()
+    }
+
+    /// Issues the next mark like [`next`](Self::next), but returns an error
+    /// instead of panicking, leaving the generator unchanged.
+    ///
+    /// If [info checks](Self::with_info_checks) are enabled, an info payload
+    /// that is larger than recommended for the resolution or not canonical
+    /// dCBOR is refused.
+    pub fn try_next(
+        &mut self,
+        date: Date,
+        info: Option<impl CBOREncodable>,
+    ) -> Result<ProvenanceMark> {
+        let info = info.map(|info| info.to_cbor());
+        self.enforce_info_checks(info.as_ref())?;
+        self.issue(date, info)
+    }
+
+    /// Issues the next mark after an external approval step, such as a
+    /// policy check or signing with a hardware security module.
+    ///
+    /// `hook` is shown the candidate mark before the generator is advanced.
+    /// If it returns an error, the mark is not issued and the generator is
+    /// left unchanged, so the next call produces the same candidate. If it
+    /// returns `Ok(None)`, the candidate is issued as-is. If it returns
+    /// `Ok(Some(extra))`, `extra` is merged into the mark's info and the mark
+    /// is recomputed before it is issued; the hook does not see the
+    /// recomputed mark.
+    ///
+    /// `extra` is merged as follows: if the mark has no info, `extra` becomes
+    /// its info; if both are maps, the entries of `extra` are added to the
+    /// info map, replacing any with the same key; otherwise the info becomes
+    /// the array `[info, extra]`.
+    ///
+    /// If [info checks](Self::with_info_checks) are enabled, they apply to
+    /// both the candidate's info and the merged info.
+    pub fn next_with_hook<E>(
+        &mut self,
+        date: Date,
+        info: Option<impl CBOREncodable>,
+        hook: impl FnOnce(&ProvenanceMark) -> std::result::Result<Option<CBOR>, E>,
+    ) -> std::result::Result<ProvenanceMark, IssueError<E>> {
+        let info = info.map(|info| info.to_cbor());
+        self.enforce_info_checks(info.as_ref())?;
+        let candidate = self.speculate(date, info.clone())?;
+        let info = match hook(&candidate).map_err(IssueError::Rejected)? {
+            Some(extra) => {
+                let merged = merge_info(info, extra);
+                self.enforce_info_checks(Some(&merged))?;
+                Some(merged)
+            }
+            None => info,
+        };
+        Ok(self.issue(date, info)?)
+    }
+
+    /// Issues a final mark that closes the chain, after which the generator
+    /// issues no more marks.
+    ///
+    /// The mark's info is `info`, or null if there is none, wrapped in
+    /// [`TAG_CHAIN_TERMINATION`], so that
+    /// [`ProvenanceMark::is_termination`] recognizes it. Validation flags any
+    /// mark sequenced after a termination mark with
+    /// [`ValidationIssue::MarkAfterTermination`](crate::ValidationIssue::MarkAfterTermination),
+    /// so a later mark claiming to extend the chain is known to be
+    /// fraudulent.
+    ///
+    /// Once the mark is issued, [`try_next`](Self::try_next) and the other
+    /// issuing methods return [`Error::ChainTerminated`], and
+    /// [`next`](Self::next) panics.
+    pub fn terminate(
+        &mut self,
+        date: Date,
+        info: Option<impl CBOREncodable>,
+    ) -> Result<ProvenanceMark> {
+        let info = info.map(|info| info.to_cbor()).unwrap_or(CBOR::null());
+        let info = CBOR::to_tagged_value(TAG_CHAIN_TERMINATION, info);
+        self.try_next(date, Some(info))
+    }
+
+    /// Refuses info that fails the [info checks](Self::with_info_checks), if
+    /// they are enabled.
+    fn enforce_info_checks(&self, info: Option<&CBOR>) -> Result<()> {
+        if let Some(info) = info.filter(|_| self.info_checks) {
+            let check = self.check_info(info)?;
+            if check.exceeds_limit() {
+                return Err(Error::InfoTooLarge {
+                    size: check.encoded_size(),
+                    limit: check.limit(),
+                });
+            }
+            if !check.is_canonical() {
+                return Err(Error::NonCanonicalInfo);
+            }
+        }
+        Ok(())
+    }
+
+    /// Issues the genesis mark and the first content mark of a new chain.
+    ///
+    /// The genesis mark typically marks the announcement of the chain itself,
+    /// and the second mark the first work published on it. Both are issued or
+    /// neither is: if the first work is dated before the genesis mark, an
+    /// error is returned and the generator is left unchanged. Returns an
+    /// error if the generator has already issued its genesis mark.
+    ///
+    /// ```
+    /// use dcbor::{Date, prelude::*};
+    /// use provenance_mark::{
+    ///     ProvenanceMark, ProvenanceMarkGenerator, ProvenanceMarkResolution,
+    /// };
+    ///
+    /// let mut generator = ProvenanceMarkGenerator::new_with_passphrase(
+    ///     ProvenanceMarkResolution::Medium,
+    ///     "essays",
+    /// );
+    /// let (genesis, first_work) = generator
+    ///     .bootstrap(
+    ///         Date::from_string("2024-01-01").unwrap(),
+    ///         Some(CBOR::from("Announcing my essays")),
+    ///         Date::from_string("2024-01-02").unwrap(),
+    ///         Some(CBOR::from("On provenance")),
+    ///     )
+    ///     .unwrap();
+    /// assert!(genesis.is_genesis());
+    /// assert!(ProvenanceMark::is_sequence_valid(&[genesis, first_work]));
+    /// assert_eq!(generator.next_seq(), 2);
+    /// ```
+    pub fn bootstrap(
+        &mut self,
+        genesis_date: Date,
+        genesis_info: Option<CBOR>,
+        first_work_date: Date,
+        first_work_info: Option<CBOR>,
+    ) -> Result<(ProvenanceMark, ProvenanceMark)> {
+        if self.next_seq != 0 {
+            return Err(Error::NotAtGenesis { next_seq: self.next_seq });
+        }
+        // The copy replaces this generator only once both marks are issued,
+        // so the chain is not forked
+        let mut generator = self.dangerous_clone_for_fork();
+        let (genesis, rng_state) =
+            generator.advance(genesis_date, genesis_info)?;
+        generator.record_issued(&genesis, rng_state);
+        let (first_work, rng_state) =
+            generator.advance(first_work_date, first_work_info)?;
+        genesis.precedes_opt(&first_work)?;
+        generator.record_issued(&first_work, rng_state);
+        *self = generator;
+        Ok((genesis, first_work))
+    }
+
+    fn issue(
+        &mut self,
+        date: Date,
+        info: Option<impl CBOREncodable>,
+    ) -> Result<ProvenanceMark> {
+        #[cfg(feature = "tracing")]
+        let _span = tracing::info_span!(
+            "next",
+            chain_id = %hex::encode(&self.chain_id[..4]),
+            seq = self.next_seq,
+            res = %self.res
+        )
+        .entered();
+
+        let (mark, rng_state) = self.advance(date, info)?;
+        self.record_issued(&mark, rng_state);
+
+        #[cfg(feature = "tracing")]
+        tracing::info!(id = %mark.id_hex(), "issued mark");
+
+        Ok(mark)
+    }
+
+    /// Produces the exact mark that [`next`](Self::next) would produce,
+    /// without advancing the generator.
+    pub fn speculate(
+        &self,
+        date: Date,
+        info: Option<impl CBOREncodable>,
+    ) -> Result<ProvenanceMark> {
+        let (mark, _) = self.advance(date, info)?;
+        Ok(mark)
+    }
+
+    /// Advances the generator past a mark previously returned by
+    /// [`speculate`](Self::speculate).
+    ///
+    /// Returns an error, leaving the generator unchanged, if the generator's
+    /// current state would not produce the given mark, for example because
+    /// another mark was issued after the speculation.
+    pub fn commit_speculation(&mut self, mark: &ProvenanceMark) -> Result<()> {
+        let (expected, rng_state) = self.advance(mark.date(), mark.info())?;
+        if expected != *mark {
+            return Err(Error::SpeculationMismatch {
+                expected_seq: self.next_seq,
+                actual_seq: mark.seq(),
+            });
+        }
+        self.record_issued(mark, rng_state);
+        Ok(())
+    }
+
+    fn record_issued(&mut self, mark: &ProvenanceMark, rng_state: RngState) {
+        self.next_seq += 1;
+        self.rng_state = rng_state;
+        self.marks_issued = Some(self.marks_issued() + 1);
+        self.last_issued_at = Some(mark.date());
+        self.terminated |= mark.is_termination();
+    }
+
+    /// Advances past the next mark without producing it, as for a mark that
+    /// is missing during [`recover`](Self::recover).
+    fn skip(&mut self) {
+        if self.next_seq > 0 {
+            let data: [u8; 32] = self.rng_state.clone().into();
+            let mut rng = Xoshiro256StarStar::from_data(&data);
+            rng.next_bytes(self.res.link_length());
+            self.rng_state = rng.to_data().into();
+        }
+        self.next_seq += 1;
+    }
+
+    /// The key the next mark will reveal, without advancing the generator.
+    pub(crate) fn peek_next_key(&self) -> LinkKey {
+        if self.next_seq == 0 {
+            return self.chain_id.genesis_key();
+        }
+        let data: [u8; 32] = self.rng_state.clone().into();
+        let mut rng = Xoshiro256StarStar::from_data(&data);
+        LinkKey::new_unchecked(rng.next_bytes(self.res.link_length()))
+    }
+
+    /// Computes the next mark and the RNG state that follows it, without
+    /// mutating the generator.
+    fn advance(
+        &self,
+        date: Date,
+        info: Option<impl CBOREncodable>,
+    ) -> Result<(ProvenanceMark, RngState)> {
+        if self.terminated {
+            return Err(Error::ChainTerminated);
+        }
         let data: [u8; 32] = self.rng_state.clone().into();
         let mut rng = Xoshiro256StarStar::from_data(&data);
 
         let seq = self.next_seq;
-        self.next_seq += 1;
 
         let key;
+        let rng_state;
         if seq == 0 {
-            key = self.chain_id.clone();
+            key = self.chain_id.genesis_key();
+            rng_state = self.rng_state.clone();
         } else {
             // The randomness generated by the PRNG should be portable across
             // implementations.
-            key = rng.next_bytes(self.res.link_length());
-            self.rng_state = rng.to_data().into();
+            key =
+                LinkKey::new_unchecked(rng.next_bytes(self.res.link_length()));
+            rng_state = rng.to_data().into();
         }
 
         let mut next_rng = rng.clone();
-        let next_key = next_rng.next_bytes(self.res.link_length());
+        let next_key =
+            LinkKey::new_unchecked(next_rng.next_bytes(self.res.link_length()));
 
-        ProvenanceMark::new(
+        let mark = ProvenanceMark::new(
             self.res,
             key,
             next_key,
             self.chain_id.clone(),
-            seq,
+            seq.into(),
             date,
             info,
-        )
-        .unwrap()
+        )?;
+        Ok((mark, rng_state))
     }
 }
 
+/// Merges the `extra` info returned by an issuance hook into a mark's info,
+/// as described in [`ProvenanceMarkGenerator::next_with_hook`].
+fn merge_info(info: Option<CBOR>, extra: CBOR) -> CBOR {
+    let Some(info) = info else {
+        return extra;
+    };
+    if let (CBORCase::Map(info_map), CBORCase::Map(extra_map)) =
+        (info.as_case(), extra.as_case())
+    {
+        let mut merged = info_map.clone();
+        for (key, value) in extra_map.iter() {
+            merged.insert(key.clone(), value.clone());
+        }
+        return merged.into();
+    }
+    vec![info, extra].into()
+}
+
 impl std::fmt::Display for ProvenanceMarkGenerator {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         write!(
@@ -139,7 +825,10 @@ impl std::fmt::Display for ProvenanceMarkGenerator {
             "ProvenanceMarkGenerator(chainID: {}, res: {}, seed: {}, nextSeq: {}, rngState: {:?})",
             hex::encode(&self.chain_id),
             self.res,
-            self.seed.hex(),
+            self.seed
+                .as_ref()
+                .map(ProvenanceSeed::hex)
+                .unwrap_or_else(|| "none".to_string()),
             self.next_seq,
             self.rng_state
         )
@@ -152,9 +841,16 @@ impl From<ProvenanceMarkGenerator> for Envelope {
         Envelope::new(CBOR::to_byte_string(generator.chain_id()))
             .add_type("provenance-generator")
             .add_assertion("res", generator.res().to_cbor())
-            .add_assertion("seed", generator.seed().to_cbor())
+            .add_optional_assertion(
+                "seed",
+                generator.seed().map(|seed| seed.to_cbor()),
+            )
             .add_assertion("next-seq", generator.next_seq())
             .add_assertion("rng-state", generator.rng_state().to_cbor())
+            .add_optional_assertion(
+                "terminated",
+                generator.is_terminated().then_some(true),
+            )
     }
 }
 
@@ -165,20 +861,27 @@ impl TryFrom<Envelope> for ProvenanceMarkGenerator {
     fn try_from(envelope: Envelope) -> Result<Self> {
         envelope.check_type("provenance-generator")?;
         let chain_id: Vec<u8> = envelope.subject().try_byte_string()?;
-        const EXPECTED_KEY_COUNT: usize = 5;
+        let seed: Option<ProvenanceSeed> =
+            match envelope.optional_object_for_predicate("seed")? {
+                Some(object) => Some(object.try_leaf()?.try_into()?),
+                None => None,
+            };
+        let terminated: bool =
+            match envelope.optional_object_for_predicate("terminated")? {
+                Some(object) => object.try_leaf()?.try_into()?,
+                None => false,
+            };
+        let expected_key_count =
+            4 + usize::from(seed.is_some()) + usize::from(terminated);
         let assertion_count = envelope.assertions().len();
-        if assertion_count != EXPECTED_KEY_COUNT {
-            return Err(Error::ExtraKeys(EXPECTED_KEY_COUNT, assertion_count));
+        if assertion_count != expected_key_count {
+            return Err(Error::ExtraKeys(expected_key_count, assertion_count));
         }
         let res: ProvenanceMarkResolution = envelope
             .object_for_predicate("res")?
             .try_leaf()?
             .try_into()?;
-        let seed: ProvenanceSeed = envelope
-            .object_for_predicate("seed")?
-            .try_leaf()?
-            .try_into()?;
-        let next_seq: u32 = envelope
+        let next_seq: u64 = envelope
             .object_for_predicate("next-seq")?
             .try_leaf()?
             .try_into()?;
@@ -187,6 +890,21 @@ impl TryFrom<Envelope> for ProvenanceMarkGenerator {
             .try_leaf()?
             .try_into()?;
 
-        ProvenanceMarkGenerator::new(res, seed, chain_id, next_seq, rng_state)
+        let mut generator = match seed {
+            Some(seed) => {
+                let mut generator = ProvenanceMarkGenerator::new(
+                    res, seed, chain_id, next_seq, rng_state,
+                )?;
+                generator.marks_issued = None;
+                generator
+            }
+            None => ProvenanceMarkGenerator::from_continuation(
+                ChainContinuation::new(res, chain_id, next_seq, rng_state)?,
+            ),
+        };
+        // The envelope does not record the generator's statistics
+        generator.created_at = None;
+        generator.terminated = terminated;
+        Ok(generator)
     }
 }
@@ -0,0 +1,129 @@
+//! The hash that links each mark to the next.
+//!
+//! A mark's `hash` field is the first `link_length` bytes of the SHA-256 of
+//! the concatenation of, in order:
+//!
+//! | Field        | Low | Medium | Quartile | High |
+//! |--------------|-----|--------|----------|------|
+//! | `key`        | 4   | 8      | 16       | 32   |
+//! | `next_key`   | 4   | 8      | 16       | 32   |
+//! | `chain_id`   | 4   | 8      | 16       | 32   |
+//! | `seq_bytes`  | 2   | 4      | 4        | 4    |
+//! | `date_bytes` | 2   | 4      | 6        | 6    |
+//! | `info_bytes` | any | any    | any      | any  |
+//!
+//! where `next_key` is the key revealed by the following mark in the chain,
+//! and the hash itself is `link_length` bytes: 4, 8, 16, or 32. The sequence
+//! number is big-endian. The date is encoded as described in
+//! [`SerializableDate`](crate::date::SerializableDate): for Low, the 16-bit
+//! big-endian value `(year - 2023) << 9 | month << 5 | day`; for Medium, the
+//! 32-bit big-endian count of seconds since 2001-01-01T00:00:00Z; for
+//! Quartile and High, the 48-bit big-endian count of milliseconds since the
+//! same instant. `info_bytes` is the dCBOR encoding of the mark's info, or
+//! empty if it has none.
+//!
+//! # Test Vectors
+//!
+//! Each vector uses `key` = `00 01 02 …`, `next_key` = `40 41 42 …`, and
+//! `chain_id` = `80 81 82 …`, each `link_length` bytes, with sequence number 1
+//! and the date 2023-06-20T12:00:00Z. The Quartile and High vectors carry the
+//! info string `"Hello"`, whose dCBOR encoding is `6548656c6c6f`.
+//!
+//! | Resolution | `seq_bytes` | `date_bytes`   | `info_bytes`   | Hash |
+//! |------------|-------------|----------------|----------------|------|
+//! | Low        | `0001`      | `00d4`         |                | `c971b97b` |
+//! | Medium     | `00000001`  | `2a41cc40`     |                | `7ae9e9500417f171` |
+//! | Quartile   | `00000001`  | `00a51105da00` | `6548656c6c6f` | `e5b76e52f88835b95d21689c0cd703b0` |
+//! | High       | `00000001`  | `00a51105da00` | `6548656c6c6f` | `95596a46dbeaa9c2243f30e8d25c80c10fcd797b42eb5ccc7a0039dc8caf1cc7` |
+//!
+//! ```
+//! use provenance_mark::{ProvenanceMarkResolution, hash::link_hash};
+//!
+//! let hash = link_hash(
+//!     ProvenanceMarkResolution::Low,
+//!     [0x00, 0x01, 0x02, 0x03],
+//!     [0x40, 0x41, 0x42, 0x43],
+//!     [0x80, 0x81, 0x82, 0x83],
+//!     [0x00, 0x01],
+//!     [0x00, 0xd4],
+//!     b"",
+//! )
+//! .unwrap();
+//! assert_eq!(hex::encode(hash), "c971b97b");
+//! ```
+
+use crate::{
+    Error, ProvenanceMarkResolution, Result, crypto_utils::sha256_prefix,
+};
+
+/// Computes the hash a mark at resolution `res` stores to commit to
+/// `next_key`, from the mark's fields in their serialized form.
+///
+/// Returns an error if `key`, `next_key`, `chain_id`, `seq_bytes`, or
+/// `date_bytes` is not the length `res` requires. `info_bytes` may be any
+/// length, and is not checked to be valid CBOR.
+pub fn link_hash(
+    res: ProvenanceMarkResolution,
+    key: impl AsRef<[u8]>,
+    next_key: impl AsRef<[u8]>,
+    chain_id: impl AsRef<[u8]>,
+    seq_bytes: impl AsRef<[u8]>,
+    date_bytes: impl AsRef<[u8]>,
+    info_bytes: impl AsRef<[u8]>,
+) -> Result<Vec<u8>> {
+    if key.as_ref().len() != res.link_length() {
+        return Err(Error::InvalidKeyLength {
+            expected: res.link_length(),
+            actual: key.as_ref().len(),
+        });
+    }
+    if next_key.as_ref().len() != res.link_length() {
+        return Err(Error::InvalidNextKeyLength {
+            expected: res.link_length(),
+            actual: next_key.as_ref().len(),
+        });
+    }
+    if chain_id.as_ref().len() != res.link_length() {
+        return Err(Error::InvalidChainIdLength {
+            expected: res.link_length(),
+            actual: chain_id.as_ref().len(),
+        });
+    }
+    if seq_bytes.as_ref().len() != res.seq_bytes_length() {
+        return Err(Error::InvalidSeqBytesLength {
+            expected: res.seq_bytes_length(),
+            actual: seq_bytes.as_ref().len(),
+        });
+    }
+    if date_bytes.as_ref().len() != res.date_bytes_length() {
+        return Err(Error::InvalidDateBytesLength {
+            expected: res.date_bytes_length(),
+            actual: date_bytes.as_ref().len(),
+        });
+    }
+
+    Ok(link_hash_unchecked(
+        res, key, next_key, chain_id, seq_bytes, date_bytes, info_bytes,
+    ))
+}
+
+/// [`link_hash`] for fields already known to have the right lengths.
+pub(crate) fn link_hash_unchecked(
+    res: ProvenanceMarkResolution,
+    key: impl AsRef<[u8]>,
+    next_key: impl AsRef<[u8]>,
+    chain_id: impl AsRef<[u8]>,
+    seq_bytes: impl AsRef<[u8]>,
+    date_bytes: impl AsRef<[u8]>,
+    info_bytes: impl AsRef<[u8]>,
+) -> Vec<u8> {
+    let mut buf = Vec::new();
+    buf.extend_from_slice(key.as_ref());
+    buf.extend_from_slice(next_key.as_ref());
+    buf.extend_from_slice(chain_id.as_ref());
+    buf.extend_from_slice(seq_bytes.as_ref());
+    buf.extend_from_slice(date_bytes.as_ref());
+    buf.extend_from_slice(info_bytes.as_ref());
+
+    sha256_prefix(&buf, res.link_length())
+}
@@ -0,0 +1,127 @@
+use chrono::Duration;
+use dcbor::Date;
+use serde::Serialize;
+
+use crate::{ChainReport, ValidationIssue};
+
+/// The weight of each of the four components of a chain's health score.
+const COMPONENT_WEIGHT: f32 = 0.25;
+
+/// The components of a chain's [health score](ChainReport::health_score).
+///
+/// The score is
+///
+/// ```text
+/// freshness × (genesis + main_run_fraction + 1 / (1 + error_count)
+///              + 1 / (1 + gap_count)) / 4
+/// ```
+///
+/// where `genesis` is 1 if the chain has its genesis mark and 0 otherwise,
+/// so a complete chain with no breaks scores 1 and every component can only
+/// lower it.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct HealthDetails {
+    score: f32,
+    has_genesis: bool,
+    main_run_fraction: f32,
+    error_count: usize,
+    gap_count: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    freshness: Option<f32>,
+}
+
+impl HealthDetails {
+    /// The overall score, from 0 to 1.
+    pub fn score(&self) -> f32 { self.score }
+    pub fn has_genesis(&self) -> bool { self.has_genesis }
+
+    /// The fraction of the chain's marks in its longest sequence.
+    pub fn main_run_fraction(&self) -> f32 { self.main_run_fraction }
+
+    /// The number of issues that break the chain's continuity, other than
+    /// gaps. Warnings are not counted.
+    pub fn error_count(&self) -> usize { self.error_count }
+
+    /// The number of [`ValidationIssue::SequenceGap`] issues.
+    pub fn gap_count(&self) -> usize { self.gap_count }
+
+    /// 1 if the chain's head mark is no older than the maximum age, and the
+    /// maximum age divided by the head's age otherwise, or `None` if no
+    /// reference time was given.
+    pub fn freshness(&self) -> Option<f32> { self.freshness }
+}
+
+impl ChainReport {
+    /// A single measure of the chain's quality, from 0 to 1, for sorting
+    /// chains on dashboards. See [`HealthDetails`] for the formula.
+    ///
+    /// Staleness is not considered; use
+    /// [`health_details_as_of`](Self::health_details_as_of) to include it.
+    pub fn health_score(&self) -> f32 { self.health_details().score() }
+
+    /// The components of the chain's [health score](Self::health_score).
+    pub fn health_details(&self) -> HealthDetails { self.health(None) }
+
+    /// The chain's health, also penalizing a head mark older than `max_age`
+    /// as of the given date.
+    pub fn health_details_as_of(
+        &self,
+        as_of: &Date,
+        max_age: Duration,
+    ) -> HealthDetails {
+        self.health(Some((as_of, max_age)))
+    }
+
+    fn health(&self, as_of: Option<(&Date, Duration)>) -> HealthDetails {
+        let main_run = self
+            .sequences()
+            .iter()
+            .map(|sequence| sequence.len())
+            .max()
+            .unwrap_or(0);
+        let main_run_fraction = if self.marks().is_empty() {
+            0.0
+        } else {
+            main_run as f32 / self.marks().len() as f32
+        };
+
+        let (mut error_count, mut gap_count) = (0, 0);
+        for issue in self
+            .sequences()
+            .iter()
+            .flat_map(|sequence| sequence.marks())
+            .flat_map(|flagged_mark| flagged_mark.issues())
+        {
+            match issue {
+                ValidationIssue::SequenceGap { .. } => gap_count += 1,
+                issue if issue.breaks_sequence() => error_count += 1,
+                _ => {}
+            }
+        }
+
+        let freshness =
+            as_of.map(|(as_of, max_age)| match self.staleness(as_of) {
+                Some(age) if age > max_age => {
+                    max_age.num_seconds() as f32 / age.num_seconds() as f32
+                }
+                _ => 1.0,
+            });
+
+        let genesis = if self.has_genesis() { 1.0 } else { 0.0 };
+        let score = freshness.unwrap_or(1.0)
+            * COMPONENT_WEIGHT
+            * (genesis
+                + main_run_fraction
+                + 1.0 / (1 + error_count) as f32
+                + 1.0 / (1 + gap_count) as f32);
+
+        HealthDetails {
+            score,
+            has_genesis: self.has_genesis(),
+            main_run_fraction,
+            error_count,
+            gap_count,
+            freshness,
+        }
+    }
+}
@@ -0,0 +1,158 @@
+//! Parsing of the human-readable Mark ID forms produced by
+//! [`ProvenanceMark::id_bytewords`] and [`ProvenanceMark::id_bytemoji`].
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    sync::{Arc, LazyLock},
+};
+
+use bc_ur::bytewords;
+
+use crate::{Error, ProvenanceMark, Result, ValidationReport};
+
+/// The prefix optionally placed before a Mark ID.
+const ID_PREFIX: &str = "🅟";
+
+static WORD_BYTES: LazyLock<HashMap<String, u8>> = LazyLock::new(|| {
+    (0..=u8::MAX)
+        .map(|byte| (bytewords::encode_to_words(&[byte]), byte))
+        .collect()
+});
+
+static BYTEMOJI_BYTES: LazyLock<HashMap<String, u8>> = LazyLock::new(|| {
+    (0..=u8::MAX)
+        .map(|byte| {
+            (
+                normalize_emoji(&bytewords::encode_to_bytemojis(&[byte])),
+                byte,
+            )
+        })
+        .collect()
+});
+
+/// Emoji may arrive with or without variation selectors depending on the
+/// keyboard or font that produced them.
+fn normalize_emoji(emoji: &str) -> String {
+    emoji.chars().filter(|c| *c != '\u{fe0f}').collect()
+}
+
+/// Splits an identifier into its tokens, dropping the optional `🅟` prefix.
+fn tokens(identifier: &str) -> Vec<&str> {
+    let identifier = identifier.trim();
+    let identifier = identifier.strip_prefix(ID_PREFIX).unwrap_or(identifier);
+    identifier
+        .split(|c: char| c.is_whitespace() || c == '-')
+        .filter(|token| !token.is_empty())
+        .collect()
+}
+
+fn parse_tokens(
+    identifier: &str,
+    lookup: impl Fn(&str) -> Option<u8>,
+) -> Result<Vec<u8>> {
+    let tokens = tokens(identifier);
+    let mut bytes = Vec::with_capacity(tokens.len());
+    let mut unknown = Vec::new();
+    for token in tokens {
+        match lookup(token) {
+            Some(byte) => bytes.push(byte),
+            None => unknown.push(token.to_string()),
+        }
+    }
+    if !unknown.is_empty() {
+        return Err(Error::UnknownIdentifierWords { words: unknown });
+    }
+    if !(4..=32).contains(&bytes.len()) {
+        return Err(Error::InvalidIdentifierLength { actual: bytes.len() });
+    }
+    Ok(bytes)
+}
+
+/// Parses a ByteWords Mark ID such as `"🅟 WAVE JUDO LIAR FIGS"` back into
+/// the Mark ID prefix it encodes.
+///
+/// Case is ignored, the `🅟` prefix is optional, and words may be separated
+/// by spaces or hyphens. Identifiers of 4 to 32 words are accepted, matching
+/// the lengths produced by [`ProvenanceMark::id_bytewords`] and
+/// [`ProvenanceMark::disambiguated_id_bytewords`].
+pub fn parse_bytewords_identifier(identifier: &str) -> Result<Vec<u8>> {
+    parse_tokens(identifier, |word| {
+        WORD_BYTES.get(&word.to_lowercase()).copied()
+    })
+}
+
+/// Parses a Bytemoji Mark ID back into the Mark ID prefix it encodes.
+///
+/// The `🅟` prefix is optional, and emoji variation selectors are ignored.
+/// Identifiers of 4 to 32 emoji are accepted.
+pub fn parse_bytemoji_identifier(identifier: &str) -> Result<Vec<u8>> {
+    parse_tokens(identifier, |emoji| {
+        BYTEMOJI_BYTES.get(&normalize_emoji(emoji)).copied()
+    })
+}
+
+impl ValidationReport {
+    /// Finds the marks whose Mark ID begins with the prefix encoded by a
+    /// spoken or typed identifier, in either ByteWords or Bytemoji form.
+    pub fn find_by_spoken_identifier(
+        &self,
+        identifier: &str,
+    ) -> Result<Vec<&ProvenanceMark>> {
+        let is_bytewords = tokens(identifier)
+            .iter()
+            .any(|token| token.chars().any(|c| c.is_ascii_alphabetic()));
+        let prefix = if is_bytewords {
+            parse_bytewords_identifier(identifier)?
+        } else {
+            parse_bytemoji_identifier(identifier)?
+        };
+        Ok(self
+            .marks()
+            .iter()
+            .map(Arc::as_ref)
+            .filter(|mark| mark.id().starts_with(&prefix))
+            .collect())
+    }
+
+    /// The identifiers shared by more than one distinct mark in the report,
+    /// each with the marks that share it in order of first appearance.
+    ///
+    /// Identifiers are the first four bytes of each
+    /// [Mark ID](ProvenanceMark::id) in hex, as shown in text reports, and
+    /// are listed in order.
+    pub fn identifier_collisions(&self) -> Vec<(String, Vec<&ProvenanceMark>)> {
+        self.identifier_groups()
+            .into_iter()
+            .filter(|(_, marks)| marks.len() > 1)
+            .map(|(identifier, marks)| {
+                (identifier, marks.into_iter().map(Arc::as_ref).collect())
+            })
+            .collect()
+    }
+
+    /// The number of leading Mark ID bytes needed to tell every mark in the
+    /// report apart: 4 if no identifiers collide, and at most 32.
+    pub fn minimum_unique_identifier_len(&self) -> usize {
+        let ids: Vec<[u8; 32]> =
+            self.marks().iter().map(|mark| mark.id()).collect();
+        ProvenanceMark::minimal_noncolliding_prefix_lengths(&ids)
+            .into_iter()
+            .max()
+            .unwrap_or(4)
+    }
+
+    /// The report's marks grouped by identifier, in order of identifier.
+    pub(crate) fn identifier_groups(
+        &self,
+    ) -> Vec<(String, Vec<&Arc<ProvenanceMark>>)> {
+        let mut groups: BTreeMap<String, Vec<&Arc<ProvenanceMark>>> =
+            BTreeMap::new();
+        for mark in self.marks() {
+            groups
+                .entry(hex::encode(&mark.id()[..4]))
+                .or_default()
+                .push(mark);
+        }
+        groups.into_iter().collect()
+    }
+}
@@ -0,0 +1,79 @@
+use dcbor::prelude::*;
+
+use crate::{ProvenanceMarkGenerator, ProvenanceMarkResolution, Result};
+
+/// The length of the `ur:provenance/` prefix of a mark's UR string.
+const UR_PREFIX_LENGTH: usize = "ur:provenance/".len();
+
+/// The length of the CRC-32 checksum appended to a UR's payload.
+const UR_CHECKSUM_LENGTH: usize = 4;
+
+/// The outcome of checking a candidate info payload with
+/// [`ProvenanceMarkGenerator::check_info`] before issuing a mark with it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InfoCheck {
+    encoded_size: usize,
+    limit: usize,
+    canonical: bool,
+    ur_length: usize,
+}
+
+impl InfoCheck {
+    /// The size in bytes of the payload's CBOR encoding.
+    pub fn encoded_size(&self) -> usize { self.encoded_size }
+
+    /// The recommended info size for the generator's resolution; see
+    /// [`ProvenanceMarkResolution::recommended_info_size`].
+    pub fn limit(&self) -> usize { self.limit }
+
+    /// Whether the payload is larger than the recommended size.
+    pub fn exceeds_limit(&self) -> bool { self.encoded_size > self.limit }
+
+    /// Whether the payload's encoding is canonical dCBOR, i.e. it survives a
+    /// decode and re-encode unchanged.
+    pub fn is_canonical(&self) -> bool { self.canonical }
+
+    /// The length of the UR string of a mark issued with this payload.
+    pub fn ur_length(&self) -> usize { self.ur_length }
+
+    /// Whether the payload passes every check.
+    pub fn is_ok(&self) -> bool { self.canonical && !self.exceeds_limit() }
+}
+
+impl ProvenanceMarkGenerator {
+    /// Checks a candidate info payload against the limits for this
+    /// generator's resolution, without issuing a mark.
+    ///
+    /// Use [`with_info_checks`](Self::with_info_checks) to have
+    /// [`try_next`](Self::try_next) refuse payloads that fail.
+    pub fn check_info(&self, info: &CBOR) -> Result<InfoCheck> {
+        let data = info.to_cbor_data();
+        let canonical = CBOR::try_from_data(&data)
+            .is_ok_and(|decoded| decoded.to_cbor_data() == data);
+        Ok(InfoCheck {
+            encoded_size: data.len(),
+            limit: self.res().recommended_info_size(),
+            canonical,
+            ur_length: estimate_ur_length(*self.res(), data.len()),
+        })
+    }
+}
+
+/// The length of the minimal ByteWords UR of a mark carrying `info_size`
+/// bytes of info. The mark is encoded as the CBOR array `[res, message]`,
+/// and each byte of the payload and checksum becomes two characters.
+fn estimate_ur_length(
+    res: ProvenanceMarkResolution,
+    info_size: usize,
+) -> usize {
+    let message_length = res.fixed_length() + info_size;
+    let header_length = match message_length {
+        0..24 => 1,
+        24..256 => 2,
+        256..65536 => 3,
+        _ => 5,
+    };
+    // One byte for the array header and one for the resolution.
+    let payload_length = 2 + header_length + message_length;
+    UR_PREFIX_LENGTH + 2 * (payload_length + UR_CHECKSUM_LENGTH)
+}
@@ -0,0 +1,60 @@
+//! Reading and writing streams of marks as lines of base64 CBOR, for
+//! scripting pipelines that pass marks through standard input and output.
+//!
+//! Each line holds one mark as produced by
+//! [`ProvenanceMark::to_b64_cbor`]. Blank lines are ignored when reading.
+
+use std::io::{BufRead, Write};
+
+use base64::Engine as _;
+use dcbor::prelude::*;
+
+use crate::{Error, ProvenanceMark, Result};
+
+impl ProvenanceMark {
+    /// The mark's tagged CBOR in standard, padded base64.
+    pub fn to_b64_cbor(&self) -> String {
+        base64::engine::general_purpose::STANDARD.encode(self.to_cbor_data())
+    }
+
+    /// Parses the base64 tagged CBOR of
+    /// [`to_b64_cbor`](Self::to_b64_cbor). Whitespace anywhere in the input,
+    /// such as from wrapping a long line, is ignored, but the padding must be
+    /// correct.
+    pub fn from_b64_cbor(b64: &str) -> Result<Self> {
+        let b64: String =
+            b64.chars().filter(|c| !c.is_ascii_whitespace()).collect();
+        let data = base64::engine::general_purpose::STANDARD.decode(b64)?;
+        Ok(Self::try_from(CBOR::try_from_data(&data)?)?)
+    }
+}
+
+/// Writes each mark on its own line as base64 CBOR.
+pub fn write_b64_lines<'a>(
+    mut w: impl Write,
+    marks: impl IntoIterator<Item = &'a ProvenanceMark>,
+) -> Result<()> {
+    for mark in marks {
+        writeln!(w, "{}", mark.to_b64_cbor())?;
+    }
+    Ok(())
+}
+
+/// Reads one mark from each non-blank line.
+///
+/// A line that does not decode is reported as [`Error::InvalidLine`] with
+/// its 1-based line number.
+pub fn read_b64_lines(r: impl BufRead) -> Result<Vec<ProvenanceMark>> {
+    let mut marks = Vec::new();
+    for (index, line) in r.lines().enumerate() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let mark = ProvenanceMark::from_b64_cbor(&line).map_err(|e| {
+            Error::InvalidLine { line: index + 1, details: e.to_string() }
+        })?;
+        marks.push(mark);
+    }
+    Ok(marks)
+}
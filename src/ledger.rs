@@ -0,0 +1,130 @@
+use std::sync::Arc;
+
+use bc_ur::UREncodable;
+
+use crate::{
+    ChainReport, ProvenanceMark, ProvenanceMarkInfo, ValidationReport,
+};
+
+impl ChainReport {
+    /// A markdown ledger listing every mark in the chain, suitable for
+    /// keeping alongside the published works.
+    ///
+    /// The ledger is a table with one row per mark, ordered by sequence
+    /// number, giving its date, short ID, ByteWords identifier, and UR (in a
+    /// collapsible block). When `infos` is provided, a comment column is added,
+    /// filled from the info whose mark equals the row's mark. The section
+    /// begins with an anchor named `chain-<chain ID hex>`.
+    pub fn markdown_ledger(
+        &self,
+        infos: Option<&[ProvenanceMarkInfo]>,
+    ) -> String {
+        let mut lines: Vec<String> = Vec::new();
+
+        lines.push(format!("<a id=\"{}\"></a>", self.ledger_anchor()));
+
+        lines.push("".to_string());
+        lines.push(format!("### {}", self.ledger_title()));
+
+        lines.push("".to_string());
+        if infos.is_some() {
+            lines.push(
+                "| Seq | Date | ID | Bytewords | Comment | UR |".to_string(),
+            );
+            lines.push("| ---: | --- | --- | --- | --- | --- |".to_string());
+        } else {
+            lines.push("| Seq | Date | ID | Bytewords | UR |".to_string());
+            lines.push("| ---: | --- | --- | --- | --- |".to_string());
+        }
+
+        let mut marks: Vec<&ProvenanceMark> =
+            self.marks().iter().map(Arc::as_ref).collect();
+        marks.sort_by_key(|mark| (mark.seq(), mark.id()));
+        for mark in marks {
+            let mut cells = vec![
+                mark.seq().to_string(),
+                mark.date_display(),
+                format!("`{}`", &mark.id_hex()[..8]),
+                mark.id_bytewords(4, false),
+            ];
+            if let Some(infos) = infos {
+                let comment = infos
+                    .iter()
+                    .find(|info| info.mark() == mark)
+                    .map(|info| escape_cell(info.comment()))
+                    .unwrap_or_default();
+                cells.push(comment);
+            }
+            cells.push(format!(
+                "<details><summary>UR</summary><code>{}</code></details>",
+                mark.ur_string()
+            ));
+            lines.push(format!("| {} |", cells.join(" | ")));
+        }
+
+        lines.join("\n")
+    }
+
+    fn ledger_anchor(&self) -> String {
+        format!("chain-{}", self.chain_id_hex())
+    }
+
+    fn ledger_title(&self) -> String {
+        let title = format!("Chain `{}`", &self.chain_id_hex()[..8]);
+        match self.label() {
+            Some(label) => format!("{} {}", title, escape_cell(label)),
+            None => title,
+        }
+    }
+}
+
+impl ValidationReport {
+    /// The [markdown ledgers](ChainReport::markdown_ledger) of every chain in
+    /// the report, preceded by a list of links to each.
+    pub fn markdown_ledgers(
+        &self,
+        infos: Option<&[ProvenanceMarkInfo]>,
+    ) -> String {
+        let contents = self
+            .chains()
+            .iter()
+            .map(|chain| {
+                format!(
+                    "- [{}](#{})",
+                    chain.ledger_title(),
+                    chain.ledger_anchor()
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+        let ledgers = self
+            .chains()
+            .iter()
+            .map(|chain| chain.markdown_ledger(infos));
+        std::iter::once(contents)
+            .chain(ledgers)
+            .collect::<Vec<_>>()
+            .join("\n\n")
+    }
+}
+
+/// Escape text for use inside a markdown table cell, so that it renders
+/// literally and cannot break the table.
+fn escape_cell(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.trim().chars() {
+        match c {
+            '\\' | '|' | '`' | '*' | '_' | '[' | ']' | '#' => {
+                escaped.push('\\');
+                escaped.push(c);
+            }
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '\n' => escaped.push_str("<br>"),
+            '\r' => {}
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
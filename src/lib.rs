@@ -1,4 +1,4 @@
-#![doc(html_root_url = "https://docs.rs/provenance-mark/0.24.0")]
+#![doc(html_root_url = "https://docs.rs/provenance-mark/0.25.0")]
 #![warn(rust_2018_idioms)]
 
 //! # Introduction
@@ -19,31 +19,134 @@
 //!
 //! ```toml
 //! [dependencies]
-//! provenance-mark = "0.24.0"
+//! provenance-mark = "0.25.0"
 //! ```
 //!
+//! # Features
+//!
+//! The default features are `envelope`, `url`, `json`, and `markdown`. With
+//! `--no-default-features --features core-verify`, the crate still parses,
+//! generates, and validates marks as URs and formats text reports, without
+//! depending on `url`, `serde_json`, or `base64`.
+//!
+//! - `url`: `ProvenanceMark::to_url` and `from_url`, deep links, and the `web`
+//!   module.
+//! - `json`: serde support for marks, seeds, and generators; JSON reports and
+//!   their schema; PROV export; `ProvenanceMarkInfo` and `MarkRecord`; base64
+//!   CBOR marks and the `io` module.
+//! - `markdown`: Markdown summaries of generators, marks, and receipts, and
+//!   Markdown ledgers (which also need `json`).
+//! - `fs`: the `chain_dir` module. Implies `json`.
+//!
+//! # Thread Safety
+//!
+//! [`ProvenanceMark`], [`ProvenanceMarkResolution`], [`ProvenanceMarkInfo`],
+//! and the reports returned by validation ([`ValidationReport`],
+//! [`ChainReport`], [`SequenceReport`], and [`FlaggedMark`]) are `Send + Sync +
+//! 'static`, so they can be shared between threads or moved across `await`
+//! points. Values they cache on first use are held in `OnceLock`s. The crate's
+//! tests assert this at compile time, so a field that is not thread-safe, such
+//! as a `Cell`, fails the build.
+//!
 //! # Examples
 //!
-//! See the unit tests in the source code for examples of how to use this
-//! library.
+//! The [`prelude`] re-exports the types most programs need, and its
+//! documentation walks through generating, validating, and reporting on a
+//! chain. See the unit tests in the source code for more examples of how to
+//! use this library.
 
 mod validate;
 pub use validate::*;
+mod validation_options;
+pub use validation_options::*;
+mod evidence;
+pub use evidence::*;
 mod error;
-pub use error::{Error, Result};
+pub use error::{Error, IssueError, Result, SyncError};
 mod resolution;
 pub use resolution::*;
 mod mark;
 pub use mark::*;
+#[cfg(feature = "json")]
 mod mark_info;
+#[cfg(feature = "json")]
 pub use mark_info::*;
+#[cfg(feature = "json")]
+mod mark_record;
+#[cfg(feature = "json")]
+pub use mark_record::*;
+mod receipt;
+pub use receipt::*;
+mod signature;
+pub use signature::*;
+mod link_bytes;
+pub use link_bytes::*;
 mod generator;
 pub use generator::*;
+mod info_check;
+pub use info_check::*;
+mod continuation;
+pub use continuation::*;
+mod encrypted_info;
+pub use encrypted_info::*;
 mod seed;
 pub use seed::*;
 mod rng_state;
 pub use rng_state::*;
+mod visual_identity;
+pub use visual_identity::*;
+mod fingerprint_index;
+pub use fingerprint_index::*;
+mod gap_fill;
+pub use gap_fill::*;
+mod chain_relation;
+pub use chain_relation::*;
+mod chain_origin;
+pub use chain_origin::*;
+mod chain_group;
+pub use chain_group::*;
+mod service;
+pub use service::*;
+mod health;
+pub use health::*;
+mod size_statistics;
+pub use size_statistics::*;
+mod sync;
+pub use sync::*;
+mod badge;
+#[cfg(feature = "mmap")]
+pub mod chain_archive;
+#[cfg(feature = "fs")]
+pub mod chain_dir;
+pub mod chain_merge;
+#[cfg(feature = "arrow")]
+pub mod columnar;
 pub mod crypto_utils;
 pub mod date;
+#[cfg(feature = "examples")]
+pub mod examples;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+pub mod hash;
+pub mod identifier;
+#[cfg(feature = "json")]
+pub mod io;
+#[cfg(all(feature = "markdown", feature = "json"))]
+mod ledger;
+mod narrative;
+pub mod prelude;
+pub mod proof;
+pub use proof::{
+    TAG_NAME_OWNERSHIP_CHALLENGE, TAG_NAME_OWNERSHIP_RESPONSE,
+    TAG_OWNERSHIP_CHALLENGE, TAG_OWNERSHIP_RESPONSE,
+};
+#[cfg(feature = "json")]
+mod prov;
+mod report_digest;
+#[cfg(feature = "json")]
+mod report_schema;
+pub mod scan;
 pub mod util;
+#[cfg(feature = "url")]
+pub mod web;
 pub mod xoshiro256starstar;
@@ -0,0 +1,185 @@
+//! Typed byte fields of a mark, so that a key, a hash, and a chain ID cannot
+//! be passed in place of one another.
+
+#[cfg(feature = "json")]
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+#[cfg(feature = "json")]
+use crate::util::{deserialize_base64, serialize_base64};
+use crate::{Error, ProvenanceMarkResolution, Result};
+
+mod sealed {
+    pub trait Sealed {}
+
+    impl Sealed for Vec<u8> {}
+}
+
+/// A field that a mark's constructors accept in place of a [`LinkKey`],
+/// [`LinkHash`], or [`ChainId`]: the typed value itself, or the `Vec<u8>`
+/// that earlier versions took. The constructors check its length either way.
+pub trait IntoLinkBytes<T>: sealed::Sealed {
+    #[doc(hidden)]
+    fn into_link_bytes(self) -> T;
+}
+
+/// Whether `len` is the link length of one of the resolutions.
+#[cfg(feature = "json")]
+fn is_link_length(len: usize) -> bool {
+    use ProvenanceMarkResolution::*;
+    [Low, Medium, Quartile, High]
+        .iter()
+        .any(|res| res.link_length() == len)
+}
+
+macro_rules! link_bytes {
+    ($(#[$doc:meta])* $name:ident, $what:literal, $error:ident) => {
+        $(#[$doc])*
+        ///
+        /// It is `link_length` bytes long for the resolution it was created
+        /// for, displays as hex, and serializes as base64.
+        #[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+        pub struct $name(Vec<u8>);
+
+        impl $name {
+            #[doc = concat!("Wraps ", $what, ", which must be the resolution's")]
+            /// [link length](ProvenanceMarkResolution::link_length).
+            pub fn new(
+                res: ProvenanceMarkResolution,
+                bytes: impl Into<Vec<u8>>,
+            ) -> Result<Self> {
+                let bytes = bytes.into();
+                if bytes.len() != res.link_length() {
+                    return Err(Error::$error {
+                        expected: res.link_length(),
+                        actual: bytes.len(),
+                    });
+                }
+                Ok(Self(bytes))
+            }
+
+            /// Wraps bytes whose length the caller has already checked, or
+            /// which a mark will check.
+            pub(crate) fn new_unchecked(bytes: Vec<u8>) -> Self { Self(bytes) }
+
+            pub fn as_bytes(&self) -> &[u8] { &self.0 }
+
+            pub fn into_bytes(self) -> Vec<u8> { self.0 }
+        }
+
+        impl sealed::Sealed for $name {}
+
+        impl IntoLinkBytes<$name> for $name {
+            fn into_link_bytes(self) -> $name { self }
+        }
+
+        impl IntoLinkBytes<$name> for Vec<u8> {
+            fn into_link_bytes(self) -> $name { $name(self) }
+        }
+
+        impl AsRef<[u8]> for $name {
+            fn as_ref(&self) -> &[u8] { &self.0 }
+        }
+
+        /// Lets code written against the byte slice accessors keep working.
+        impl std::ops::Deref for $name {
+            type Target = [u8];
+
+            fn deref(&self) -> &[u8] { &self.0 }
+        }
+
+        impl PartialEq<[u8]> for $name {
+            fn eq(&self, other: &[u8]) -> bool { self.0 == other }
+        }
+
+        impl PartialEq<Vec<u8>> for $name {
+            fn eq(&self, other: &Vec<u8>) -> bool { self.0 == *other }
+        }
+
+        impl PartialEq<$name> for [u8] {
+            fn eq(&self, other: &$name) -> bool { *self == *other.0 }
+        }
+
+        impl PartialEq<$name> for Vec<u8> {
+            fn eq(&self, other: &$name) -> bool { *self == other.0 }
+        }
+
+        impl From<$name> for Vec<u8> {
+            fn from(value: $name) -> Self { value.0 }
+        }
+
+        impl std::fmt::Display for $name {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                write!(f, "{}", hex::encode(&self.0))
+            }
+        }
+
+        /// The bytes as base64, as in a mark's JSON.
+        #[cfg(feature = "json")]
+        impl Serialize for $name {
+            fn serialize<S>(
+                &self,
+                serializer: S,
+            ) -> std::result::Result<S::Ok, S::Error>
+            where
+                S: Serializer,
+            {
+                serialize_base64(&self.0, serializer)
+            }
+        }
+
+        /// Accepts base64 whose length is the link length of some
+        /// resolution. There is no resolution to check it against, so marks
+        /// check that it matches theirs when they are assembled.
+        #[cfg(feature = "json")]
+        impl<'de> Deserialize<'de> for $name {
+            fn deserialize<D>(
+                deserializer: D,
+            ) -> std::result::Result<Self, D::Error>
+            where
+                D: Deserializer<'de>,
+            {
+                let bytes = deserialize_base64(deserializer)?;
+                if !is_link_length(bytes.len()) {
+                    return Err(serde::de::Error::custom(format!(
+                        concat!(
+                            "invalid ",
+                            stringify!($name),
+                            " length: {} is not the link length of any ",
+                            "resolution"
+                        ),
+                        bytes.len()
+                    )));
+                }
+                Ok(Self(bytes))
+            }
+        }
+    };
+}
+
+link_bytes!(
+    /// The key a mark reveals, which the previous mark in the chain committed
+    /// to in its hash.
+    LinkKey,
+    "a key",
+    InvalidKeyLength
+);
+
+link_bytes!(
+    /// The hash with which a mark commits to its own fields and to the key
+    /// the next mark will reveal.
+    LinkHash,
+    "a hash",
+    InvalidHashLength
+);
+
+link_bytes!(
+    /// The identifier of a chain, shared by all of its marks.
+    ChainId,
+    "a chain ID",
+    InvalidChainIdLength
+);
+
+impl ChainId {
+    /// The key of the chain's genesis mark, which equals the chain ID.
+    pub fn genesis_key(&self) -> LinkKey { LinkKey(self.0.clone()) }
+}
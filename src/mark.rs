@@ -1,61 +1,107 @@
+use std::sync::OnceLock;
 #[cfg(feature = "envelope")]
-use std::sync::Arc;
+use std::sync::{Arc, Once};
 
 #[cfg(feature = "envelope")]
 use bc_envelope::prelude::*;
 #[cfg(feature = "envelope")]
 use bc_envelope::{FormatContext, with_format_context_mut};
-use bc_ur::bytewords;
+use bc_ur::{UR, URDecodable, UREncodable, bytewords};
 #[cfg(not(feature = "envelope"))]
 use dcbor::{Date, prelude::*};
-use serde::{Deserialize, Serialize};
+#[cfg(feature = "json")]
+use serde::{Deserialize, Serialize, ser::SerializeStruct};
+#[cfg(feature = "url")]
 use url::Url;
 
+#[cfg(feature = "json")]
+use crate::util::{deserialize_cbor, deserialize_iso8601, serialize_base64};
 use crate::{
-    Error, ProvenanceMarkResolution, Result,
-    crypto_utils::{SHA256_SIZE, obfuscate, sha256, sha256_prefix},
-    util::{
-        deserialize_base64, deserialize_cbor, deserialize_iso8601,
-        serialize_base64, serialize_cbor, serialize_iso8601,
+    ChainId, DatePrecision, Error, IntoLinkBytes, LinkHash, LinkKey,
+    ProvenanceMarkResolution, Result,
+    crypto_utils::{
+        SHA256_SIZE, extend_key, obfuscate_with_extended_key, sha256,
     },
+    hash::link_hash_unchecked,
 };
 
-// JSON Example:
-// {"chainID":"znwVmQ==","date":"2023-06-20T00:00:00Z","hash":"ZaTfvw==","key":"
-// znwVmQ==","res":0,"seq":0}
-
-#[derive(Serialize, Clone)]
+#[derive(Clone)]
 pub struct ProvenanceMark {
     seq: u32,
-
-    #[serde(serialize_with = "serialize_iso8601")]
     date: Date,
-
     res: ProvenanceMarkResolution,
-
-    #[serde(serialize_with = "serialize_base64")]
-    chain_id: Vec<u8>,
-
-    #[serde(serialize_with = "serialize_base64")]
-    key: Vec<u8>,
-
-    #[serde(serialize_with = "serialize_base64")]
-    hash: Vec<u8>,
-
-    #[serde(
-        default,
-        skip_serializing_if = "Vec::is_empty",
-        serialize_with = "serialize_cbor"
-    )]
+    chain_id: ChainId,
+    key: LinkKey,
+    hash: LinkHash,
     info_bytes: Vec<u8>,
-
-    #[serde(skip)]
     seq_bytes: Vec<u8>,
-
-    #[serde(skip)]
     date_bytes: Vec<u8>,
+    /// The length of the mark's UR, computed on first use. Not part of the
+    /// mark's identity.
+    ur_len: OnceLock<usize>,
+    /// The key extended for obfuscating the payload, computed on first use.
+    /// Not part of the mark's identity.
+    extended_key: OnceLock<[u8; 32]>,
+}
+
+/// Marks serialize with their keys in a fixed, alphabetical order that does
+/// not depend on the order of the struct's fields:
+///
+/// ```json
+/// {"chainID":"znwVmQ==","date":"2023-06-20","hash":"ZaTfvw==","key":"znwVmQ==","res":0,"seq":0}
+/// ```
+///
+/// `infoBytes`, the base64 of the info's dCBOR, follows `hash` if the mark
+/// has info and is omitted otherwise. Deserialization accepts the keys in
+/// any order, and also accepts `chain_id` and `info_bytes`.
+///
+/// Deserialization rejects marks that fail
+/// [`ProvenanceMark::self_consistent`]. JSON carries `seq` and `date` only
+/// as values, so an edited `seq` or `date` on a non-genesis mark produces a
+/// different mark that is still self-consistent and is not detected here; it
+/// only shows up when the mark is validated against its neighbors.
+#[cfg(feature = "json")]
+impl Serialize for ProvenanceMark {
+    fn serialize<S>(
+        &self,
+        serializer: S,
+    ) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        struct Base64<'a>(&'a [u8]);
+
+        impl Serialize for Base64<'_> {
+            fn serialize<S>(
+                &self,
+                serializer: S,
+            ) -> std::result::Result<S::Ok, S::Error>
+            where
+                S: serde::Serializer,
+            {
+                serialize_base64(self.0, serializer)
+            }
+        }
+
+        let has_info = !self.info_bytes.is_empty();
+        let mut state = serializer
+            .serialize_struct("ProvenanceMark", 6 + usize::from(has_info))?;
+        state.serialize_field("chainID", &self.chain_id)?;
+        state.serialize_field("date", &self.date.to_string())?;
+        state.serialize_field("hash", &self.hash)?;
+        if has_info {
+            state.serialize_field("infoBytes", &Base64(&self.info_bytes))?;
+        } else {
+            state.skip_field("infoBytes")?;
+        }
+        state.serialize_field("key", &self.key)?;
+        state.serialize_field("res", &self.res)?;
+        state.serialize_field("seq", &self.seq)?;
+        state.end()
+    }
 }
 
+#[cfg(feature = "json")]
 impl<'de> Deserialize<'de> for ProvenanceMark {
     fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
     where
@@ -64,40 +110,159 @@ impl<'de> Deserialize<'de> for ProvenanceMark {
         #[derive(Deserialize)]
         struct ProvenanceMarkHelper {
             res: ProvenanceMarkResolution,
-            #[serde(deserialize_with = "deserialize_base64")]
-            key: Vec<u8>,
-            #[serde(deserialize_with = "deserialize_base64")]
-            hash: Vec<u8>,
-            #[serde(deserialize_with = "deserialize_base64")]
-            chain_id: Vec<u8>,
+            key: LinkKey,
+            hash: LinkHash,
+            #[serde(rename = "chainID", alias = "chain_id")]
+            chain_id: ChainId,
+            #[serde(rename = "infoBytes", alias = "info_bytes")]
             #[serde(default, deserialize_with = "deserialize_cbor")]
             info_bytes: Vec<u8>,
-            seq: u32,
+            seq: u64,
             #[serde(deserialize_with = "deserialize_iso8601")]
             date: Date,
         }
 
         let helper = ProvenanceMarkHelper::deserialize(deserializer)?;
-        let seq_bytes = helper
-            .res
-            .serialize_seq(helper.seq)
-            .map_err(serde::de::Error::custom)?;
-        let date_bytes = helper
-            .res
-            .serialize_date(helper.date)
-            .map_err(serde::de::Error::custom)?;
-
-        Ok(ProvenanceMark {
-            res: helper.res,
-            key: helper.key,
-            hash: helper.hash,
-            chain_id: helper.chain_id,
-            seq_bytes,
-            date_bytes,
-            info_bytes: helper.info_bytes,
-            seq: helper.seq,
-            date: helper.date,
-        })
+        let mark = ProvenanceMark::from_fields(
+            helper.res,
+            helper.key,
+            helper.hash,
+            helper.chain_id,
+            helper.seq,
+            helper.date,
+            helper.info_bytes,
+        )
+        .map_err(serde::de::Error::custom)?;
+
+        mark.self_consistent().map_err(|inconsistencies| {
+            let details = inconsistencies
+                .iter()
+                .map(|i| i.to_string())
+                .collect::<Vec<_>>()
+                .join("; ");
+            serde::de::Error::custom(format!(
+                "inconsistent provenance mark: {}",
+                details
+            ))
+        })?;
+
+        Ok(mark)
+    }
+}
+
+/// A way in which a mark's stored fields disagree with each other.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InconsistencyKind {
+    /// The stored sequence bytes do not encode the sequence number at the
+    /// mark's resolution.
+    SeqBytes,
+    /// The stored date bytes do not encode the date at the mark's resolution.
+    DateBytes,
+    /// The date carries more precision than the mark's resolution can
+    /// represent.
+    DatePrecision,
+    /// The info bytes are not valid CBOR.
+    InfoCbor,
+    /// The key equals the chain ID, but the sequence number is not 0.
+    GenesisKeyAtNonZeroSeq,
+}
+
+impl std::fmt::Display for InconsistencyKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InconsistencyKind::SeqBytes => {
+                write!(f, "sequence bytes do not match sequence number")
+            }
+            InconsistencyKind::DateBytes => {
+                write!(f, "date bytes do not match date")
+            }
+            InconsistencyKind::DatePrecision => {
+                write!(f, "date is more precise than the resolution allows")
+            }
+            InconsistencyKind::InfoCbor => {
+                write!(f, "info bytes are not valid CBOR")
+            }
+            InconsistencyKind::GenesisKeyAtNonZeroSeq => {
+                write!(f, "key equals chain ID at non-zero sequence number")
+            }
+        }
+    }
+}
+
+/// A way in which a mark fails [`ProvenanceMark::self_check`].
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+#[serde(tag = "type", content = "data")]
+pub enum SelfCheckFailure {
+    /// A key, hash, or chain ID whose length does not match the mark's
+    /// resolution.
+    FieldLength {
+        field: String,
+        expected: usize,
+        actual: usize,
+    },
+    /// The stored sequence bytes do not encode the sequence number.
+    SeqBytes,
+    /// The stored date bytes do not encode the date.
+    DateBytes,
+    /// The date is more precise than the mark's resolution can represent.
+    DatePrecision,
+    /// The info bytes are not valid CBOR.
+    InfoCbor,
+    /// The key equals the chain ID, but the sequence number is not 0.
+    GenesisKeyAtNonZeroSeq,
+    /// The sequence number is 0, but the key does not equal the chain ID.
+    NonGenesisKeyAtZeroSeq,
+    /// Parsing the mark's [message](ProvenanceMark::message) does not yield
+    /// the same fields.
+    MessageRoundTrip,
+}
+
+impl From<InconsistencyKind> for SelfCheckFailure {
+    fn from(kind: InconsistencyKind) -> Self {
+        match kind {
+            InconsistencyKind::SeqBytes => SelfCheckFailure::SeqBytes,
+            InconsistencyKind::DateBytes => SelfCheckFailure::DateBytes,
+            InconsistencyKind::DatePrecision => SelfCheckFailure::DatePrecision,
+            InconsistencyKind::InfoCbor => SelfCheckFailure::InfoCbor,
+            InconsistencyKind::GenesisKeyAtNonZeroSeq => {
+                SelfCheckFailure::GenesisKeyAtNonZeroSeq
+            }
+        }
+    }
+}
+
+impl std::fmt::Display for SelfCheckFailure {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SelfCheckFailure::FieldLength { field, expected, actual } => {
+                write!(
+                    f,
+                    "{} is {} bytes, expected {}",
+                    field, actual, expected
+                )
+            }
+            SelfCheckFailure::SeqBytes => {
+                write!(f, "{}", InconsistencyKind::SeqBytes)
+            }
+            SelfCheckFailure::DateBytes => {
+                write!(f, "{}", InconsistencyKind::DateBytes)
+            }
+            SelfCheckFailure::DatePrecision => {
+                write!(f, "{}", InconsistencyKind::DatePrecision)
+            }
+            SelfCheckFailure::InfoCbor => {
+                write!(f, "{}", InconsistencyKind::InfoCbor)
+            }
+            SelfCheckFailure::GenesisKeyAtNonZeroSeq => {
+                write!(f, "{}", InconsistencyKind::GenesisKeyAtNonZeroSeq)
+            }
+            SelfCheckFailure::NonGenesisKeyAtZeroSeq => {
+                write!(f, "key differs from chain ID at sequence number 0")
+            }
+            SelfCheckFailure::MessageRoundTrip => {
+                write!(f, "message does not parse back to the same mark")
+            }
+        }
     }
 }
 
@@ -118,46 +283,333 @@ impl std::hash::Hash for ProvenanceMark {
 
 impl ProvenanceMark {
     pub fn res(&self) -> ProvenanceMarkResolution { self.res }
-    pub fn key(&self) -> &[u8] { &self.key }
-    pub fn hash(&self) -> &[u8] { &self.hash }
-    pub fn chain_id(&self) -> &[u8] { &self.chain_id }
+    pub fn key(&self) -> &LinkKey { &self.key }
+    pub fn hash(&self) -> &LinkHash { &self.hash }
+    pub fn chain_id(&self) -> &ChainId { &self.chain_id }
     pub fn seq_bytes(&self) -> &[u8] { &self.seq_bytes }
     pub fn date_bytes(&self) -> &[u8] { &self.date_bytes }
 
     pub fn seq(&self) -> u32 { self.seq }
     pub fn date(&self) -> Date { self.date }
 
+    /// How precisely the mark records its date, which depends on its
+    /// resolution: low resolution marks carry no time of day.
+    pub fn date_precision(&self) -> DatePrecision { self.res.date_precision() }
+
+    /// The mark's date rendered to its [precision](Self::date_precision):
+    /// `2023-06-20` for a low resolution mark, `2023-06-20T12:34:56Z` for
+    /// medium, and `2023-06-20T12:34:56.789Z` for quartile and high.
+    pub fn date_display(&self) -> String {
+        self.date_precision().format(&self.date)
+    }
+
+    /// The mark's message: the key followed by the payload obfuscated with
+    /// that key.
     pub fn message(&self) -> Vec<u8> {
-        let payload = [
-            self.chain_id.clone(),
-            self.hash.clone(),
+        [
+            self.key.to_vec(),
+            obfuscate_with_extended_key(self.extended_key(), self.payload()),
+        ]
+        .concat()
+    }
+
+    /// The mark's key extended for obfuscating its payload, computed on
+    /// first use and cached, since every [`message`](Self::message), and so
+    /// every comparison and hash of the mark, needs it.
+    fn extended_key(&self) -> &[u8; 32] {
+        self.extended_key.get_or_init(|| extend_key(&self.key))
+    }
+
+    /// The length in bytes of the mark's [`message`](Self::message),
+    /// without building it.
+    pub fn message_len(&self) -> usize {
+        self.key.len()
+            + self.chain_id.len()
+            + self.hash.len()
+            + self.seq_bytes.len()
+            + self.date_bytes.len()
+            + self.info_bytes.len()
+    }
+
+    /// The length of the mark's UR string, computed on first use and cached.
+    pub fn ur_len(&self) -> usize {
+        *self.ur_len.get_or_init(|| self.ur_string().len())
+    }
+
+    /// The length in bytes of the dCBOR encoding of the mark's info, or zero
+    /// if it has none.
+    pub fn info_len(&self) -> usize { self.info_bytes.len() }
+
+    /// The dCBOR encoding of the mark's info, or empty if it has none.
+    pub(crate) fn info_bytes(&self) -> &[u8] { &self.info_bytes }
+
+    /// The deobfuscated payload carried in the mark's message:
+    /// `chain_id ‖ hash ‖ seq_bytes ‖ date_bytes ‖ info_bytes`.
+    ///
+    /// The key is not part of the payload; it precedes the obfuscated payload
+    /// in the [`message`](Self::message).
+    pub fn payload(&self) -> Vec<u8> {
+        [
+            self.chain_id.to_vec(),
+            self.hash.to_vec(),
             self.seq_bytes.clone(),
             self.date_bytes.clone(),
             self.info_bytes.clone(),
         ]
-        .concat();
-        [self.key.clone(), obfuscate(&self.key, payload)].concat()
+        .concat()
     }
 
+    pub fn message_hex(&self) -> String { hex::encode(self.message()) }
+
+    pub fn payload_hex(&self) -> String { hex::encode(self.payload()) }
+
     pub fn info(&self) -> Option<CBOR> {
         if self.info_bytes.is_empty() {
             None
         } else {
-            CBOR::try_from_data(&self.info_bytes).unwrap().into()
+            CBOR::try_from_data(&self.info_bytes).ok()
+        }
+    }
+
+    /// The CBOR tags present at the top level of the mark's info: those
+    /// wrapping the info itself and, if it is an array or map, those wrapping
+    /// its elements or entries. Tags are listed outermost first, in the order
+    /// they appear, without duplicates.
+    ///
+    /// Lets callers detect info extensions they do not understand, such as
+    /// tags introduced by newer tools.
+    pub fn info_tags(&self) -> Vec<u64> {
+        fn push_tags(cbor: &CBOR, tags: &mut Vec<u64>) -> CBOR {
+            let mut cbor = cbor.clone();
+            while let CBORCase::Tagged(tag, inner) = cbor.as_case() {
+                if !tags.contains(&tag.value()) {
+                    tags.push(tag.value());
+                }
+                cbor = inner.clone();
+            }
+            cbor
+        }
+
+        let mut tags = Vec::new();
+        let Some(info) = self.info() else {
+            return tags;
+        };
+        let untagged = push_tags(&info, &mut tags);
+        match untagged.as_case() {
+            CBORCase::Array(items) => {
+                for item in items {
+                    push_tags(item, &mut tags);
+                }
+            }
+            CBORCase::Map(map) => {
+                for (key, value) in map.iter() {
+                    push_tags(key, &mut tags);
+                    push_tags(value, &mut tags);
+                }
+            }
+            _ => {}
+        }
+        tags
+    }
+
+    /// The diagnostic notation of the mark's info, or `None` if it could not
+    /// be decoded.
+    ///
+    /// The info is rendered without the registered tags, so that a tag
+    /// summarizer that does not accept the tagged value, which can fail or
+    /// panic, is never called. Tags are shown by number, as they are in
+    /// unannotated diagnostic notation anyway.
+    fn info_diagnostic(&self) -> Option<String> {
+        let opts = dcbor::DiagFormatOpts::default()
+            .summarize(false)
+            .tags(dcbor::TagsStoreOpt::None);
+        self.info().map(|info| info.diagnostic_opt(&opts))
+    }
+
+    /// The most characters of info diagnostic notation shown in the mark's
+    /// `Debug` output.
+    pub const DEBUG_INFO_MAX_LEN: usize = 64;
+
+    /// The diagnostic notation of the mark's info, or `None` if it has none.
+    ///
+    /// Diagnostics longer than `max_len` characters are truncated to
+    /// `max_len` characters followed by an ellipsis and the size of the info
+    /// in bytes, such as `… (10003 bytes)`.
+    ///
+    /// If the info cannot be decoded as CBOR, the summary is instead the info
+    /// bytes in hex, truncated the same way, followed by
+    /// `(unrenderable CBOR)`.
+    pub fn info_summary(&self, max_len: usize) -> Option<String> {
+        if self.info_bytes.is_empty() {
+            return None;
+        }
+        let Some(diagnostic) = self.info_diagnostic() else {
+            let hex = truncate_summary(
+                hex::encode(&self.info_bytes),
+                max_len,
+                self.info_bytes.len(),
+            );
+            return Some(format!("{} (unrenderable CBOR)", hex));
+        };
+        Some(truncate_summary(diagnostic, max_len, self.info_bytes.len()))
+    }
+
+    /// The mark's info as shown in text reports: its
+    /// [`info_summary`](Self::info_summary), or only its size if `redact`.
+    pub(crate) fn report_info(&self, redact: bool) -> Option<String> {
+        if redact {
+            (!self.info_bytes.is_empty())
+                .then(|| format!("<info: {} bytes>", self.info_bytes.len()))
+        } else {
+            self.info_summary(Self::DEBUG_INFO_MAX_LEN)
+        }
+    }
+}
+
+/// `text` if it is at most `max_len` characters, otherwise its first
+/// `max_len` characters followed by an ellipsis and `byte_len`.
+fn truncate_summary(text: String, max_len: usize, byte_len: usize) -> String {
+    if text.chars().count() <= max_len {
+        return text;
+    }
+    let truncated: String = text.chars().take(max_len).collect();
+    format!("{}… ({} bytes)", truncated, byte_len)
+}
+
+impl ProvenanceMark {
+    /// Checks that the mark's stored byte fields agree with the values derived
+    /// from them.
+    ///
+    /// The sequence number and date are re-serialized at the mark's resolution
+    /// and compared with the stored bytes, the info bytes must decode as CBOR,
+    /// and a key equal to the chain ID is only allowed at sequence number 0.
+    /// All inconsistencies found are returned.
+    ///
+    /// Marks decoded from a UR or CBOR keep the bytes they were issued with,
+    /// so all of these checks apply. Marks deserialized from JSON have their
+    /// sequence and date bytes re-derived from `seq` and `date`, so the
+    /// [`InconsistencyKind::SeqBytes`] and [`InconsistencyKind::DateBytes`]
+    /// checks cannot fail for them: a `seq` edited on a non-genesis mark
+    /// cannot be detected from the mark alone. Such a mark no longer follows
+    /// its predecessor or matches the hash its successor expects, so
+    /// validating the chain reports it.
+    pub fn self_consistent(
+        &self,
+    ) -> std::result::Result<(), Vec<InconsistencyKind>> {
+        let mut inconsistencies = Vec::new();
+
+        if self.res.serialize_seq(self.seq).ok().as_ref()
+            != Some(&self.seq_bytes)
+        {
+            inconsistencies.push(InconsistencyKind::SeqBytes);
+        }
+        if self.res.serialize_date(self.date).ok().as_ref()
+            != Some(&self.date_bytes)
+        {
+            inconsistencies.push(InconsistencyKind::DateBytes);
+        }
+        if self.res.deserialize_date(&self.date_bytes).ok() != Some(self.date) {
+            inconsistencies.push(InconsistencyKind::DatePrecision);
+        }
+        if !self.info_bytes.is_empty()
+            && CBOR::try_from_data(&self.info_bytes).is_err()
+        {
+            inconsistencies.push(InconsistencyKind::InfoCbor);
+        }
+        if self.key.as_bytes() == self.chain_id.as_bytes() && self.seq != 0 {
+            inconsistencies.push(InconsistencyKind::GenesisKeyAtNonZeroSeq);
+        }
+
+        if inconsistencies.is_empty() {
+            Ok(())
+        } else {
+            Err(inconsistencies)
         }
     }
 }
 
 impl ProvenanceMark {
+    /// Runs every check of the mark's internal invariants, returning all the
+    /// failures found.
+    ///
+    /// In addition to [`self_consistent`](Self::self_consistent), the key,
+    /// hash, and chain ID must have the resolution's link length, the key
+    /// must equal the chain ID exactly when the sequence number is 0, and
+    /// parsing the mark's [message](Self::message) with
+    /// [`from_message`](Self::from_message) must yield the same fields.
+    pub fn self_check(&self) -> std::result::Result<(), Vec<SelfCheckFailure>> {
+        let link_length = self.res.link_length();
+        let mut failures: Vec<SelfCheckFailure> = [
+            ("key", self.key.as_bytes()),
+            ("hash", self.hash.as_bytes()),
+            ("chain_id", self.chain_id.as_bytes()),
+        ]
+        .into_iter()
+        .filter(|(_, bytes)| bytes.len() != link_length)
+        .map(|(field, bytes)| SelfCheckFailure::FieldLength {
+            field: field.to_string(),
+            expected: link_length,
+            actual: bytes.len(),
+        })
+        .collect();
+
+        if let Err(inconsistencies) = self.self_consistent() {
+            failures.extend(inconsistencies.into_iter().map(Into::into));
+        }
+        if self.seq == 0 && self.key.as_bytes() != self.chain_id.as_bytes() {
+            failures.push(SelfCheckFailure::NonGenesisKeyAtZeroSeq);
+        }
+
+        let round_trips = Self::from_message(self.res, self.message())
+            .is_ok_and(|parsed| {
+                parsed.key == self.key
+                    && parsed.hash == self.hash
+                    && parsed.chain_id == self.chain_id
+                    && parsed.seq_bytes == self.seq_bytes
+                    && parsed.date_bytes == self.date_bytes
+                    && parsed.info_bytes == self.info_bytes
+                    && parsed.seq == self.seq
+                    && parsed.date == self.date
+            });
+        if !round_trips {
+            failures.push(SelfCheckFailure::MessageRoundTrip);
+        }
+
+        if failures.is_empty() {
+            Ok(())
+        } else {
+            Err(failures)
+        }
+    }
+
+    /// Whether the mark passes [`self_check`](Self::self_check).
+    pub fn passes_self_check(&self) -> bool { self.self_check().is_ok() }
+}
+
+impl ProvenanceMark {
+    /// Creates a mark, computing its hash from `next_key`, the key the next
+    /// mark in the chain will reveal.
+    ///
+    /// The key, next key, and chain ID must have the resolution's link
+    /// length, which they do if they were created for the same resolution.
+    /// They may also be given as `Vec<u8>`, as in earlier versions, and are
+    /// checked the same way.
+    /// `seq` is taken as a `u64` so that callers storing wider sequence
+    /// numbers need not truncate them; one larger than the resolution's
+    /// [`max_seq`](ProvenanceMarkResolution::max_seq) is reported as
+    /// [`Error::SequenceOutOfRange`].
     pub fn new(
         res: ProvenanceMarkResolution,
-        key: Vec<u8>,
-        next_key: Vec<u8>,
-        chain_id: Vec<u8>,
-        seq: u32,
+        key: impl IntoLinkBytes<LinkKey>,
+        next_key: impl IntoLinkBytes<LinkKey>,
+        chain_id: impl IntoLinkBytes<ChainId>,
+        seq: u64,
         date: Date,
         info: Option<impl CBOREncodable>,
     ) -> Result<Self> {
+        let key = key.into_link_bytes();
+        let next_key = next_key.into_link_bytes();
+        let chain_id = chain_id.into_link_bytes();
         if key.len() != res.link_length() {
             return Err(Error::InvalidKeyLength {
                 expected: res.link_length(),
@@ -177,6 +629,7 @@ impl ProvenanceMark {
             });
         }
 
+        let seq = res.checked_seq(seq)?;
         let date_bytes = res.serialize_date(date)?;
         let seq_bytes = res.serialize_seq(seq)?;
 
@@ -187,7 +640,7 @@ impl ProvenanceMark {
             None => Vec::new(),
         };
 
-        let hash = Self::make_hash(
+        let hash = LinkHash::new_unchecked(Self::make_hash(
             res,
             &key,
             next_key,
@@ -195,7 +648,7 @@ impl ProvenanceMark {
             &seq_bytes,
             &date_bytes,
             &info_bytes,
-        );
+        ));
 
         Ok(Self {
             res,
@@ -208,6 +661,8 @@ impl ProvenanceMark {
 
             seq,
             date,
+            ur_len: OnceLock::new(),
+            extended_key: OnceLock::new(),
         })
     }
 
@@ -222,10 +677,15 @@ impl ProvenanceMark {
             });
         }
 
-        let key = message[res.key_range()].to_vec();
-        let payload = obfuscate(&key, &message[res.link_length()..]);
-        let hash = payload[res.hash_range()].to_vec();
-        let chain_id = payload[res.chain_id_range()].to_vec();
+        let key = LinkKey::new_unchecked(message[res.key_range()].to_vec());
+        let extended_key = extend_key(&key);
+        let payload = obfuscate_with_extended_key(
+            &extended_key,
+            &message[res.link_length()..],
+        );
+        let hash = LinkHash::new_unchecked(payload[res.hash_range()].to_vec());
+        let chain_id =
+            ChainId::new_unchecked(payload[res.chain_id_range()].to_vec());
         let seq_bytes = payload[res.seq_bytes_range()].to_vec();
         let seq = res.deserialize_seq(&seq_bytes)?;
         let date_bytes = payload[res.date_bytes_range()].to_vec();
@@ -246,9 +706,116 @@ impl ProvenanceMark {
 
             seq,
             date,
+            ur_len: OnceLock::new(),
+            extended_key: OnceLock::from(extended_key),
+        })
+    }
+
+    /// Reassemble a mark from its separately stored fields.
+    ///
+    /// Unlike [`new`](Self::new), which computes the hash from the next key,
+    /// the hash is taken as given. The sequence and date bytes are re-derived
+    /// at the given resolution. `info_bytes` is the CBOR encoding of the info,
+    /// or empty if there is none. As with [`new`](Self::new), the key, hash,
+    /// and chain ID may be given as `Vec<u8>`.
+    ///
+    /// A hash of the wrong length is reported as [`Error::InvalidHashLength`].
+    /// A date that cannot be represented exactly at the resolution is
+    /// rejected rather than truncated, and a sequence number larger than
+    /// the resolution can carry as [`Error::SequenceOutOfRange`].
+    pub fn from_fields(
+        res: ProvenanceMarkResolution,
+        key: impl IntoLinkBytes<LinkKey>,
+        hash: impl IntoLinkBytes<LinkHash>,
+        chain_id: impl IntoLinkBytes<ChainId>,
+        seq: u64,
+        date: Date,
+        info_bytes: Vec<u8>,
+    ) -> Result<Self> {
+        let key = key.into_link_bytes();
+        let hash = hash.into_link_bytes();
+        let chain_id = chain_id.into_link_bytes();
+        if key.len() != res.link_length() {
+            return Err(Error::InvalidKeyLength {
+                expected: res.link_length(),
+                actual: key.len(),
+            });
+        }
+        if hash.len() != res.link_length() {
+            return Err(Error::InvalidHashLength {
+                expected: res.link_length(),
+                actual: hash.len(),
+            });
+        }
+        if chain_id.len() != res.link_length() {
+            return Err(Error::InvalidChainIdLength {
+                expected: res.link_length(),
+                actual: chain_id.len(),
+            });
+        }
+
+        let seq = res.checked_seq(seq)?;
+        let seq_bytes = res.serialize_seq(seq)?;
+        let date_bytes = res.serialize_date(date)?;
+        if res.deserialize_date(&date_bytes)? != date {
+            return Err(Error::InvalidDate {
+                details: InconsistencyKind::DatePrecision.to_string(),
+            });
+        }
+
+        if !info_bytes.is_empty() && CBOR::try_from_data(&info_bytes).is_err() {
+            return Err(Error::InvalidInfoCbor);
+        }
+
+        Ok(Self {
+            res,
+            key,
+            hash,
+            chain_id,
+            seq_bytes,
+            date_bytes,
+            info_bytes,
+
+            seq,
+            date,
+            ur_len: OnceLock::new(),
+            extended_key: OnceLock::new(),
         })
     }
 
+    /// [`new`](Self::new) with untyped key, next key, and chain ID.
+    #[deprecated(
+        note = "use `new`, which accepts `Vec<u8>` fields, or wrap them in `LinkKey` and `ChainId`"
+    )]
+    pub fn new_from_bytes(
+        res: ProvenanceMarkResolution,
+        key: Vec<u8>,
+        next_key: Vec<u8>,
+        chain_id: Vec<u8>,
+        seq: u64,
+        date: Date,
+        info: Option<impl CBOREncodable>,
+    ) -> Result<Self> {
+        Self::new(res, key, next_key, chain_id, seq, date, info)
+    }
+
+    /// [`from_fields`](Self::from_fields) with untyped key, hash, and chain
+    /// ID.
+    #[deprecated(
+        note = "use `from_fields`, which accepts `Vec<u8>` fields, or wrap them in `LinkKey`, `LinkHash`, and `ChainId`"
+    )]
+    pub fn from_byte_fields(
+        res: ProvenanceMarkResolution,
+        key: Vec<u8>,
+        hash: Vec<u8>,
+        chain_id: Vec<u8>,
+        seq: u64,
+        date: Date,
+        info_bytes: Vec<u8>,
+    ) -> Result<Self> {
+        Self::from_fields(res, key, hash, chain_id, seq, date, info_bytes)
+    }
+
     fn make_hash(
         res: ProvenanceMarkResolution,
         key: impl AsRef<[u8]>,
@@ -258,15 +825,9 @@ impl ProvenanceMark {
         date_bytes: impl AsRef<[u8]>,
         info_bytes: impl AsRef<[u8]>,
     ) -> Vec<u8> {
-        let mut buf = Vec::new();
-        buf.extend_from_slice(key.as_ref());
-        buf.extend_from_slice(next_key.as_ref());
-        buf.extend_from_slice(chain_id.as_ref());
-        buf.extend_from_slice(seq_bytes.as_ref());
-        buf.extend_from_slice(date_bytes.as_ref());
-        buf.extend_from_slice(info_bytes.as_ref());
-
-        sha256_prefix(&buf, res.link_length())
+        link_hash_unchecked(
+            res, key, next_key, chain_id, seq_bytes, date_bytes, info_bytes,
+        )
     }
 }
 
@@ -346,7 +907,9 @@ impl ProvenanceMark {
     ///
     /// Non-colliding marks get the minimum of 4. Only marks whose 4-byte
     /// prefixes collide are extended.
-    fn minimal_noncolliding_prefix_lengths(ids: &[[u8; 32]]) -> Vec<usize> {
+    pub(crate) fn minimal_noncolliding_prefix_lengths(
+        ids: &[[u8; 32]],
+    ) -> Vec<usize> {
         let n = ids.len();
         let mut lengths = vec![4usize; n];
 
@@ -452,15 +1015,43 @@ impl ProvenanceMark {
     pub fn precedes_opt(&self, next: &ProvenanceMark) -> Result<()> {
         use crate::ValidationIssue;
 
+        // `next` must be at the same resolution, or its fields can't be
+        // compared with this mark's
+        if next.res != self.res {
+            return Err(ValidationIssue::ResolutionMismatch {
+                previous: self.res,
+                next: next.res,
+            }
+            .into());
+        }
+        // `next` must be in the same chain, or its hash can't match
+        if next.chain_id != self.chain_id {
+            return Err(ValidationIssue::ChainMismatch {
+                previous_chain: self.chain_id.to_string()[..8].to_string(),
+                next_chain: next.chain_id.to_string()[..8].to_string(),
+            }
+            .into());
+        }
+        // `next` can't share this mark's sequence number
+        if next.seq == self.seq && next != self {
+            return Err(ValidationIssue::DuplicateSeq { seq: next.seq }.into());
+        }
         // `next` can't be a genesis
         if next.seq == 0 {
             return Err(ValidationIssue::NonGenesisAtZero.into());
         }
-        if next.key == next.chain_id {
+        if next.key.as_bytes() == next.chain_id.as_bytes() {
             return Err(ValidationIssue::InvalidGenesisKey.into());
         }
         // `next` must have the next highest sequence number
-        if self.seq != next.seq - 1 {
+        if next.seq < self.seq {
+            return Err(ValidationIssue::SequenceRegression {
+                previous: self.seq,
+                next: next.seq,
+            }
+            .into());
+        }
+        if next.seq - self.seq > 1 {
             return Err(ValidationIssue::SequenceGap {
                 expected: self.seq + 1,
                 actual: next.seq,
@@ -488,7 +1079,7 @@ impl ProvenanceMark {
         if self.hash != expected_hash {
             return Err(ValidationIssue::HashMismatch {
                 expected: expected_hash,
-                actual: self.hash.clone(),
+                actual: self.hash.to_vec(),
             }
             .into());
         }
@@ -506,7 +1097,16 @@ impl ProvenanceMark {
     }
 
     pub fn is_genesis(&self) -> bool {
-        self.seq == 0 && self.key == self.chain_id
+        self.seq == 0 && self.key.as_bytes() == self.chain_id.as_bytes()
+    }
+
+    /// Whether this is the final mark of its chain, issued by
+    /// [`ProvenanceMarkGenerator::terminate`](crate::ProvenanceMarkGenerator::terminate).
+    pub fn is_termination(&self) -> bool {
+        self.info().is_some_and(|info| {
+            info.try_into_expected_tagged_value(crate::TAG_CHAIN_TERMINATION)
+                .is_ok()
+        })
     }
 }
 
@@ -544,6 +1144,7 @@ impl ProvenanceMark {
 impl ProvenanceMark {
     // Example format:
     // ur:provenance/lfaegdtokebznlahftbsnlaxpsdiwecswsrnlsdsdpghrp
+    #[cfg(feature = "url")]
     pub fn to_url(&self, base: &str) -> Url {
         let mut url = Url::parse(base).unwrap();
         url.query_pairs_mut()
@@ -551,6 +1152,7 @@ impl ProvenanceMark {
         url
     }
 
+    #[cfg(feature = "url")]
     pub fn from_url(url: &Url) -> Result<Self> {
         let query = url.query_pairs().find(|(key, _)| key == "provenance");
         if let Some((_, value)) = query {
@@ -561,6 +1163,117 @@ impl ProvenanceMark {
             })
         }
     }
+
+    /// The mark as a single URL path segment: its minimal ByteWords encoding,
+    /// which consists only of lowercase letters and so never needs
+    /// percent-encoding.
+    pub fn to_path_segment(&self) -> String { self.to_url_encoding() }
+
+    /// Parse a mark from a path segment produced by
+    /// [`to_path_segment`](Self::to_path_segment), ignoring case.
+    pub fn from_path_segment(segment: &str) -> Result<Self> {
+        Self::from_url_encoding(&segment.to_ascii_lowercase())
+    }
+
+    /// A link to this mark formed by appending its
+    /// [path segment](Self::to_path_segment) to `base`, with or without a
+    /// trailing slash. Any query or fragment of `base` is kept.
+    #[cfg(feature = "url")]
+    pub fn to_deep_link(&self, base: &Url) -> Result<Url> {
+        let mut url = base.clone();
+        url.path_segments_mut()
+            .map_err(|_| Error::InvalidDeepLinkBase { url: base.to_string() })?
+            .pop_if_empty()
+            .push(&self.to_path_segment());
+        Ok(url)
+    }
+
+    /// Parse a mark from a link produced by
+    /// [`to_deep_link`](Self::to_deep_link), falling back to the `provenance`
+    /// query parameter read by [`from_url`](Self::from_url) if the last path
+    /// segment is not a mark.
+    ///
+    /// Returns [`Error::AmbiguousDeepLink`] if the path names a mark and the
+    /// query parameter names a different one or cannot be parsed.
+    #[cfg(feature = "url")]
+    pub fn from_deep_link(url: &Url) -> Result<Self> {
+        let from_path = url
+            .path_segments()
+            .and_then(|mut segments| segments.rfind(|s| !s.is_empty()))
+            .and_then(|segment| Self::from_path_segment(segment).ok());
+        let Some(mark) = from_path else {
+            return Self::from_url(url);
+        };
+        let has_query = url.query_pairs().any(|(key, _)| key == "provenance");
+        if has_query && Self::from_url(url).ok().as_ref() != Some(&mark) {
+            return Err(Error::AmbiguousDeepLink { url: url.to_string() });
+        }
+        Ok(mark)
+    }
+}
+
+/// A mark's serde fields alongside the values derived from them, for
+/// [`ProvenanceMark::to_display_json`].
+#[cfg(feature = "json")]
+#[derive(Serialize)]
+struct DisplayMark<'a> {
+    #[serde(flatten)]
+    mark: &'a ProvenanceMark,
+    ur: String,
+    identifier: String,
+    bytewords_identifier: String,
+    bytemoji_identifier: String,
+    resolution_name: String,
+    is_genesis: bool,
+}
+
+#[cfg(feature = "json")]
+impl ProvenanceMark {
+    /// The mark's JSON form extended with the values consumers would
+    /// otherwise derive themselves: `ur`, the hex Mark ID as `identifier`,
+    /// the prefixed four-word `bytewords_identifier` and
+    /// `bytemoji_identifier`, `resolution_name`, and `is_genesis`.
+    pub fn to_display_json(&self) -> serde_json::Value {
+        let display = DisplayMark {
+            mark: self,
+            ur: self.ur_string(),
+            identifier: self.id_hex(),
+            bytewords_identifier: self.id_bytewords(4, true),
+            bytemoji_identifier: self.id_bytemoji(4, true),
+            resolution_name: self.res.to_string(),
+            is_genesis: self.is_genesis(),
+        };
+        // Every field serializes infallibly to a JSON value
+        serde_json::to_value(display).unwrap()
+    }
+
+    /// Parse a mark from JSON produced by
+    /// [`to_display_json`](Self::to_display_json).
+    ///
+    /// Only the `ur` field is trusted. Every other field that is present must
+    /// match the value derived from the UR, or
+    /// [`Error::DisplayJsonMismatch`] names the first that does not; missing
+    /// fields are allowed.
+    pub fn from_display_json(json: &serde_json::Value) -> Result<Self> {
+        let ur = json
+            .get("ur")
+            .and_then(serde_json::Value::as_str)
+            .ok_or(Error::MissingDisplayJsonUr)?;
+        let mark = Self::parse_ur_string(ur)?;
+        let expected = mark.to_display_json();
+        let mismatch = expected
+            .as_object()
+            .into_iter()
+            .flatten()
+            .filter(|(field, _)| *field != "ur")
+            .find(|(field, value)| {
+                json.get(field).is_some_and(|given| given != *value)
+            });
+        if let Some((field, _)) = mismatch {
+            return Err(Error::DisplayJsonMismatch { field: field.clone() });
+        }
+        Ok(mark)
+    }
 }
 
 impl std::fmt::Debug for ProvenanceMark {
@@ -570,11 +1283,11 @@ impl std::fmt::Debug for ProvenanceMark {
             format!("hash: {}", hex::encode(&self.hash)),
             format!("chainID: {}", hex::encode(&self.chain_id)),
             format!("seq: {}", self.seq),
-            format!("date: {}", self.date.to_string()),
+            format!("date: {}", self.date_display()),
         ];
 
-        if let Some(info) = self.info() {
-            components.push(format!("info: {}", info.diagnostic()));
+        if let Some(info) = self.info_summary(Self::DEBUG_INFO_MAX_LEN) {
+            components.push(format!("info: {}", info));
         }
 
         write!(f, "ProvenanceMark({})", components.join(", "))
@@ -601,17 +1314,118 @@ pub fn register_tags_in(context: &mut FormatContext) {
     );
 }
 
+/// Register the tags of this crate's experimental CBOR types, and their
+/// summarizers, in `context`.
+///
+/// These tags are not allocated in `bc-tags` or registered with IANA, so they
+/// may collide with other types and may change. [`register_tags_in`] does
+/// not register them.
+#[cfg(feature = "envelope")]
+pub fn register_experimental_tags_in(context: &mut FormatContext) {
+    context.tags_mut().insert(Tag::new(
+        crate::TAG_PROVENANCE_GENERATOR,
+        crate::TAG_NAME_PROVENANCE_GENERATOR,
+    ));
+
+    context.tags_mut().insert(Tag::new(
+        crate::TAG_CHAIN_TERMINATION,
+        crate::TAG_NAME_CHAIN_TERMINATION,
+    ));
+
+    context.tags_mut().insert(Tag::new(
+        crate::TAG_ENCRYPTED_INFO,
+        crate::TAG_NAME_ENCRYPTED_INFO,
+    ));
+
+    context.tags_mut().insert(Tag::new(
+        crate::TAG_CHAIN_CONTINUATION,
+        crate::TAG_NAME_CHAIN_CONTINUATION,
+    ));
+    context.tags_mut().set_summarizer(
+        crate::TAG_CHAIN_CONTINUATION,
+        Arc::new(move |untagged_cbor: CBOR, _flat: bool| {
+            let continuation =
+                crate::ChainContinuation::from_untagged_cbor(untagged_cbor)?;
+            Ok(continuation.to_string())
+        }),
+    );
+
+    context.tags_mut().insert(Tag::new(
+        crate::TAG_OWNERSHIP_CHALLENGE,
+        crate::TAG_NAME_OWNERSHIP_CHALLENGE,
+    ));
+
+    context.tags_mut().insert(Tag::new(
+        crate::TAG_OWNERSHIP_RESPONSE,
+        crate::TAG_NAME_OWNERSHIP_RESPONSE,
+    ));
+}
+
+#[cfg(feature = "envelope")]
+static TAGS_REGISTERED: Once = Once::new();
+
+/// Register this crate's tags and summarizers in the global format context.
+///
+/// Only the first call has any effect, so it is safe to call from every
+/// entry point that needs the tags.
 #[cfg(feature = "envelope")]
 pub fn register_tags() {
-    with_format_context_mut!(|context: &mut FormatContext| {
-        register_tags_in(context);
+    TAGS_REGISTERED.call_once(|| {
+        with_format_context_mut!(|context: &mut FormatContext| {
+            register_tags_in(context);
+        });
+    });
+}
+
+/// Whether [`register_tags`] has been called.
+#[cfg(feature = "envelope")]
+pub fn tags_registered() -> bool { TAGS_REGISTERED.is_completed() }
+
+#[cfg(feature = "envelope")]
+static EXPERIMENTAL_TAGS_REGISTERED: Once = Once::new();
+
+/// Register this crate's tags, as by [`register_tags`], and its experimental
+/// tags, as by [`register_experimental_tags_in`], in the global format
+/// context.
+///
+/// Only the first call has any effect.
+#[cfg(feature = "envelope")]
+pub fn register_experimental_tags() {
+    register_tags();
+    EXPERIMENTAL_TAGS_REGISTERED.call_once(|| {
+        with_format_context_mut!(|context: &mut FormatContext| {
+            register_experimental_tags_in(context);
+        });
     });
 }
 
+/// Run `f` with this crate's tags registered in the global format context.
+#[cfg(feature = "envelope")]
+pub fn with_registered_tags<R>(f: impl FnOnce() -> R) -> R {
+    register_tags();
+    f()
+}
+
+/// The UR type of a mark, as in `ur:provenance/...`.
+pub const UR_TYPE: &str = "provenance";
+
+/// The CBOR tag of a mark, [`bc_tags::TAG_PROVENANCE_MARK`].
+pub const CBOR_TAG: u64 = bc_tags::TAG_PROVENANCE_MARK;
+
+const CDDL: &str = "\
+; A provenance mark. The message is the mark's key followed by its obfuscated
+; payload, so its length depends on the resolution and the size of the info.
+provenance-mark = #6.1347571542([resolution, message])
+resolution = &(low: 0, medium: 1, quartile: 2, high: 3)
+message = bytes
+";
+
+/// A CDDL fragment describing the tagged CBOR of a mark: an array of the
+/// resolution and the [message](ProvenanceMark::message).
+pub fn cddl() -> &'static str { CDDL }
+
 impl CBORTagged for ProvenanceMark {
-    fn cbor_tags() -> Vec<Tag> {
-        tags_for_values(&[bc_tags::TAG_PROVENANCE_MARK])
-    }
+    fn cbor_tags() -> Vec<Tag> { tags_for_values(&[CBOR_TAG]) }
 }
 
 impl From<ProvenanceMark> for CBOR {
@@ -632,6 +1446,9 @@ impl TryFrom<CBOR> for ProvenanceMark {
     }
 }
 
+/// Decoding is strict, as the CBOR may come from an untrusted source: the
+/// resolution must be an unsigned integer from 0 to 3, and the message a
+/// byte string at least as long as the resolution's fixed fields.
 impl CBORTaggedDecodable for ProvenanceMark {
     fn from_untagged_cbor(cbor: CBOR) -> dcbor::Result<Self> {
         let v = CBOR::try_into_array(cbor)?;
@@ -649,10 +1466,129 @@ impl From<&ProvenanceMark> for ProvenanceMark {
     fn from(mark: &ProvenanceMark) -> Self { mark.clone() }
 }
 
+/// Parses a `ur:provenance` UR or, with the `json` feature, the base64 CBOR
+/// of [`ProvenanceMark::to_b64_cbor`]. Surrounding whitespace is ignored.
+impl std::str::FromStr for ProvenanceMark {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let s = s.trim();
+        // A colon is not in the base64 alphabet, so the forms cannot be
+        // confused
+        #[cfg(feature = "json")]
+        if !s.contains(':') {
+            return Self::from_b64_cbor(s);
+        }
+        Self::parse_ur_string(s)
+    }
+}
+
+/// The prefix of a mark's compact string, naming the version of its grammar.
+const COMPACT_PREFIX: &str = "pm1:";
+
+/// The value of a field of a compact string that starts `rest` with `name`
+/// and ends with `end`, and the rest of the string after `end`.
+fn compact_field<'a>(
+    rest: &'a str,
+    name: &str,
+    end: &str,
+) -> Result<(&'a str, &'a str)> {
+    let invalid = |expected: &str| Error::InvalidCompactString {
+        details: format!("expected {:?}", expected),
+    };
+    let rest = rest.strip_prefix(name).ok_or_else(|| invalid(name))?;
+    let len = rest.find(end).ok_or_else(|| invalid(end))?;
+    Ok((&rest[..len], &rest[len + end.len()..]))
+}
+
+impl ProvenanceMark {
+    /// A single-line form of the mark for logs, which names the mark's
+    /// resolution, sequence number, and date for grepping, followed by its
+    /// UR:
+    ///
+    /// ```text
+    /// compact    = "pm1:" resolution ":seq=" seq ":date=" date ":ur=" ur
+    /// resolution = "low" / "medium" / "quartile" / "high"
+    /// seq        = the sequence number in decimal
+    /// date       = the date as displayed by dcbor::Date
+    /// ur         = the mark's ur:provenance string
+    /// ```
+    ///
+    /// For example,
+    /// `pm1:low:seq=0:date=2023-06-20:ur=ur:provenance/lfaegd...`. The UR
+    /// carries the whole mark, so the form is lossless; see
+    /// [`from_compact_string`](Self::from_compact_string).
+    pub fn to_compact_string(&self) -> String {
+        format!(
+            "{}{}:seq={}:date={}:ur={}",
+            COMPACT_PREFIX,
+            self.res,
+            self.seq,
+            self.date,
+            self.ur_string()
+        )
+    }
+
+    /// Parses the form written by
+    /// [`to_compact_string`](Self::to_compact_string). The mark is decoded
+    /// from the UR, and the resolution, sequence number, and date must be
+    /// exactly as written for that mark. Surrounding whitespace is ignored.
+    pub fn from_compact_string(s: &str) -> Result<Self> {
+        let invalid = |details: String| Error::InvalidCompactString { details };
+        let rest = s.trim();
+        let (res, rest) = compact_field(rest, COMPACT_PREFIX, ":")?;
+        let (seq, rest) = compact_field(rest, "seq=", ":")?;
+        // A date may contain colons, but not `:ur=`
+        let (date, rest) = compact_field(rest, "date=", ":ur=")?;
+        let mark = Self::parse_ur_string(rest)?;
+
+        let fields = [
+            ("resolution", res, mark.res.to_string()),
+            ("seq", seq, mark.seq.to_string()),
+            ("date", date, mark.date.to_string()),
+        ];
+        for (name, given, actual) in fields {
+            if given != actual {
+                return Err(invalid(format!(
+                    "{} {:?} does not match the mark's {:?}",
+                    name, given, actual
+                )));
+            }
+        }
+        Ok(mark)
+    }
+
+    /// Whether `ur` has the [UR type](UR_TYPE) of a mark, for filtering a
+    /// stream of mixed URs before decoding any of them. A matching UR may
+    /// still fail to decode.
+    pub fn ur_type_matches(ur: &UR) -> bool { ur.ur_type_str() == UR_TYPE }
+
+    /// Parse a mark from its `ur:provenance` string, logging failures when
+    /// the `tracing` feature is enabled.
+    pub(crate) fn parse_ur_string(ur: &str) -> Result<Self> {
+        let result = Self::from_ur_string(ur).map_err(Error::from);
+        #[cfg(feature = "tracing")]
+        if let Err(error) = &result {
+            tracing::warn!(ur, %error, "failed to parse provenance mark UR");
+        }
+        result
+    }
+}
+
 impl ProvenanceMark {
     pub fn fingerprint(&self) -> [u8; SHA256_SIZE] {
         sha256(self.to_cbor_data())
     }
+
+    /// A stable identifier for the mark's chain, such as a registry key: the
+    /// SHA-256 of the resolution's byte followed by the chain ID.
+    ///
+    /// The chain ID is in every mark, so this needs no genesis mark. Unlike
+    /// the bare chain ID, it distinguishes chains at different resolutions
+    /// whose IDs share a prefix.
+    pub fn chain_fingerprint(&self) -> [u8; SHA256_SIZE] {
+        sha256([&[u8::from(self.res)][..], self.chain_id.as_bytes()].concat())
+    }
 }
 
 #[cfg(feature = "envelope")]
@@ -3,15 +3,12 @@ use serde::{Deserialize, Deserializer, Serialize};
 
 use crate::{
     ProvenanceMark,
-    util::{deserialize_ur, serialize_ur},
+    util::{parse_ur_lenient, serialize_ur},
 };
 
 #[derive(Serialize, Clone)]
 pub struct ProvenanceMarkInfo {
-    #[serde(
-        serialize_with = "serialize_ur",
-        deserialize_with = "deserialize_ur"
-    )]
+    #[serde(serialize_with = "serialize_ur")]
     ur: UR,
 
     bytewords: String,
@@ -31,30 +28,41 @@ impl<'de> Deserialize<'de> for ProvenanceMarkInfo {
     {
         #[derive(Deserialize)]
         struct ProvenanceMarkInfoHelper {
-            #[serde(
-                serialize_with = "serialize_ur",
-                deserialize_with = "deserialize_ur"
-            )]
-            ur: UR,
+            #[serde(default)]
+            ur: Option<String>,
             bytewords: String,
             bytemoji: String,
             #[serde(default)]
             comment: String,
-            // We deserialize mark but ignore it in favor of parsing from UR
+            // Only used if `ur` is missing, since the UR is authoritative
             #[serde(default)]
-            #[allow(dead_code)]
             mark: Option<serde_json::Value>,
         }
 
         let helper = ProvenanceMarkInfoHelper::deserialize(deserializer)?;
 
-        // Deserialize the mark from the UR to ensure date_bytes and seq_bytes
-        // match what was originally generated
-        let mark = ProvenanceMark::from_ur(&helper.ur)
-            .map_err(serde::de::Error::custom)?;
+        let (ur, mark) = match (helper.ur, helper.mark) {
+            (Some(ur), _) => {
+                let ur =
+                    parse_ur_lenient(&ur).map_err(serde::de::Error::custom)?;
+                // Deserialize the mark from the UR to ensure date_bytes and
+                // seq_bytes match what was originally generated
+                let mark = ProvenanceMark::from_ur(&ur)
+                    .map_err(serde::de::Error::custom)?;
+                (ur, mark)
+            }
+            (None, Some(mark)) => {
+                let mark: ProvenanceMark = serde_json::from_value(mark)
+                    .map_err(serde::de::Error::custom)?;
+                (mark.ur(), mark)
+            }
+            (None, None) => {
+                return Err(serde::de::Error::missing_field("ur"));
+            }
+        };
 
         Ok(ProvenanceMarkInfo {
-            ur: helper.ur,
+            ur,
             bytewords: helper.bytewords,
             bytemoji: helper.bytemoji,
             comment: helper.comment,
@@ -76,6 +84,10 @@ impl ProvenanceMarkInfo {
 
     pub fn ur(&self) -> &UR { &self.ur }
 
+    /// The length of the mark's UR string, as by
+    /// [`ProvenanceMark::ur_len`].
+    pub fn ur_len(&self) -> usize { self.mark.ur_len() }
+
     pub fn bytewords(&self) -> &str { &self.bytewords }
 
     pub fn bytemoji(&self) -> &str { &self.bytemoji }
@@ -106,13 +118,14 @@ impl ProvenanceMarkInfo {
     `https://github.com/...#urprovenancelfaohdftbstsfpiylnsfguiepthynnvwplaokpinzsctttbgskfxmtldtddtrheysgiocpgyhsetnlmwatrtvyrywmamiygstsmnkilrkinylygliantynpemssscygeoehs`
     */
 
+    #[cfg(feature = "markdown")]
     pub fn markdown_summary(&self) -> String {
         let mut lines: Vec<String> = Vec::new();
 
         lines.push("---".to_string());
 
         lines.push("".to_string());
-        lines.push(format!("{}", self.mark.date()));
+        lines.push(self.mark.date_display());
 
         lines.push("".to_string());
         lines.push(format!("#### {}", self.ur));
@@ -0,0 +1,81 @@
+use base64::Engine as _;
+use bc_ur::UREncodable;
+use serde::{Deserialize, Serialize};
+
+use crate::{Error, ProvenanceMark, ProvenanceMarkInfo, Result};
+
+/// A [`ProvenanceMarkInfo`] as a flat row of strings and integers, for
+/// storage in a database.
+///
+/// The mark is stored as its UR, and every other column except `comment` is
+/// derived from it. Converting back to a [`ProvenanceMarkInfo`] parses the UR
+/// and checks the derived columns against it, so a row edited in place is
+/// rejected rather than silently disagreeing with its mark.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MarkRecord {
+    pub ur: String,
+    pub chain_id_hex: String,
+    pub seq: u32,
+    pub date_iso: String,
+    /// The mark's [ID](ProvenanceMark::id_hex) in hex.
+    pub identifier: String,
+    pub bytewords: String,
+    pub bytemoji: String,
+    pub comment: String,
+    /// The mark's info as base64-encoded CBOR, or `None` if it has none.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub info_b64: Option<String>,
+}
+
+impl MarkRecord {
+    fn new(mark: &ProvenanceMark, comment: &str) -> Self {
+        Self {
+            ur: mark.ur_string(),
+            chain_id_hex: hex::encode(mark.chain_id()),
+            seq: mark.seq(),
+            date_iso: mark.date().to_string(),
+            identifier: mark.id_hex(),
+            bytewords: mark.id_bytewords(4, true),
+            bytemoji: mark.id_bytemoji(4, true),
+            comment: comment.to_string(),
+            info_b64: mark.info().map(|info| {
+                base64::engine::general_purpose::STANDARD
+                    .encode(info.to_cbor_data())
+            }),
+        }
+    }
+}
+
+impl From<&ProvenanceMarkInfo> for MarkRecord {
+    fn from(info: &ProvenanceMarkInfo) -> Self {
+        Self::new(info.mark(), info.comment())
+    }
+}
+
+impl TryFrom<MarkRecord> for ProvenanceMarkInfo {
+    type Error = Error;
+
+    /// Parses the record's UR, returning
+    /// [`Error::MarkRecordMismatch`] naming the first derived column that
+    /// does not match the mark.
+    fn try_from(record: MarkRecord) -> Result<Self> {
+        let mark = ProvenanceMark::parse_ur_string(&record.ur)?;
+        let expected = MarkRecord::new(&mark, &record.comment);
+        let columns = [
+            ("chain_id_hex", record.chain_id_hex == expected.chain_id_hex),
+            ("seq", record.seq == expected.seq),
+            ("date_iso", record.date_iso == expected.date_iso),
+            ("identifier", record.identifier == expected.identifier),
+            ("bytewords", record.bytewords == expected.bytewords),
+            ("bytemoji", record.bytemoji == expected.bytemoji),
+            ("info_b64", record.info_b64 == expected.info_b64),
+        ];
+        if let Some((column, _)) = columns.iter().find(|(_, matches)| !matches)
+        {
+            return Err(Error::MarkRecordMismatch {
+                column: column.to_string(),
+            });
+        }
+        Ok(ProvenanceMarkInfo::new(mark, record.comment))
+    }
+}
@@ -0,0 +1,121 @@
+use dcbor::Date;
+
+use crate::{ChainReport, ProvenanceMark, ValidationIssue};
+
+/// A date as written in prose, e.g. "June 20, 2023".
+fn prose_date(date: &Date) -> String {
+    date.datetime().format("%B %-d, %Y").to_string()
+}
+
+/// `count` followed by `singular` or `plural`, spelling out a count of one.
+fn counted(count: u64, singular: &str, plural: &str) -> String {
+    match count {
+        1 => format!("one {}", singular),
+        _ => format!("{} {}", count, plural),
+    }
+}
+
+/// The number of calendar days from `first`'s date to `last`'s.
+fn days_between(first: &ProvenanceMark, last: &ProvenanceMark) -> u64 {
+    let first = first.date().datetime().date_naive();
+    let last = last.date().datetime().date_naive();
+    (last - first).num_days().max(0) as u64
+}
+
+impl ChainReport {
+    /// A deterministic English account of the chain's history, for
+    /// provenance presentations.
+    ///
+    /// The narrative gives the date of the chain's genesis mark (or notes
+    /// that it is missing), how many marks followed and over how long, each
+    /// gap or other break between sequences, any other issues flagged on the
+    /// chain's marks, and the date of the chain's most recent mark.
+    pub fn narrative(&self) -> String {
+        let (Some(first), Some(last)) =
+            (self.marks().first(), self.marks().last())
+        else {
+            return String::new();
+        };
+        let mut sentences = Vec::new();
+
+        if first.is_genesis() {
+            sentences.push(format!(
+                "This chain began on {}.",
+                prose_date(&first.date())
+            ));
+        } else {
+            sentences.push("This chain's genesis mark is missing.".to_string());
+            sentences.push(format!(
+                "The earliest known mark, at sequence {}, is dated {}.",
+                first.seq(),
+                prose_date(&first.date())
+            ));
+        }
+
+        let more = self.marks().len() as u64 - 1;
+        if more == 0 {
+            sentences.push("No other works have been marked.".to_string());
+        } else {
+            let marked = if more == 1 {
+                "one more work was marked".to_string()
+            } else {
+                format!("{} more works were marked", more)
+            };
+            let sentence = match days_between(first, last) {
+                0 => format!("On the same day, {}.", marked),
+                1 => format!("On the following day, {}.", marked),
+                days => {
+                    format!("Over the following {} days, {}.", days, marked)
+                }
+            };
+            sentences.push(sentence);
+        }
+
+        for (previous, next, issue) in self.broken_links() {
+            let sentence = match issue {
+                ValidationIssue::SequenceGap { expected, actual } => {
+                    format!(
+                        "There is a gap of {} between {} and {}.",
+                        counted(
+                            u64::from(actual - expected),
+                            "missing mark",
+                            "missing marks"
+                        ),
+                        prose_date(&previous.date()),
+                        prose_date(&next.date())
+                    )
+                }
+                _ => format!(
+                    "The link from the mark at sequence {} to the mark at \
+                     sequence {} could not be verified: {}.",
+                    previous.seq(),
+                    next.seq(),
+                    issue
+                ),
+            };
+            sentences.push(sentence);
+        }
+
+        for flagged_mark in self.sequences().iter().flat_map(|s| s.marks()) {
+            for issue in flagged_mark.issues() {
+                if !issue.breaks_sequence() {
+                    sentences.push(format!(
+                        "The mark at sequence {} is flagged: {}.",
+                        flagged_mark.mark().seq(),
+                        issue
+                    ));
+                }
+            }
+        }
+
+        if more > 0 {
+            sentences.push(format!(
+                "The most recent mark, at sequence {}, is dated {}.",
+                last.seq(),
+                prose_date(&last.date())
+            ));
+        }
+
+        sentences.join(" ")
+    }
+}
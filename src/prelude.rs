@@ -0,0 +1,57 @@
+//! The types most programs need, for glob import.
+//!
+//! Along with the crate's own types, this re-exports [`Date`] and the UR
+//! traits that provide [`ur_string`](UREncodable::ur_string) and
+//! [`from_ur_string`](URDecodable::from_ur_string).
+//!
+//! ```
+//! use provenance_mark::prelude::*;
+//!
+//! // Generate a chain of marks and publish them as URs
+//! let mut generator = ProvenanceMarkGenerator::new_with_passphrase(
+//!     ProvenanceMarkResolution::Low,
+//!     "essays",
+//! );
+//! let date = Date::from_string("2024-01-01").unwrap();
+//! let urs: Vec<String> = (0..4)
+//!     .map(|_| generator.next(date, None::<&str>).ur_string())
+//!     .collect();
+//!
+//! // A verifier has received all but the third mark
+//! let marks: Vec<ProvenanceMark> = [&urs[0], &urs[1], &urs[3]]
+//!     .into_iter()
+//!     .map(|ur| ProvenanceMark::from_ur_string(ur.as_str()).unwrap())
+//!     .collect();
+//! let options = ValidationOptions::new()
+//!     .with_policy(
+//!         ValidationPolicy::new()
+//!             .with_reference_time(Date::from_string("2024-06-01").unwrap()),
+//!     )
+//!     .with_anchors(TrustAnchors::new().with_chain_id(marks[0].chain_id()));
+//! let report = ProvenanceMark::validate_opts(marks, &options);
+//!
+//! assert!(report.has_issues());
+//! assert!(report.chains()[0].is_anchored());
+//! assert_eq!(
+//!     report.chains()[0].sequences()[1].marks()[0].issues(),
+//!     [ValidationIssue::SequenceGap { expected: 2, actual: 3 }]
+//! );
+//!
+//! let text = report.format_with_options(
+//!     ValidationReportFormat::Text,
+//!     &FormatOptions::new().with_collapse_clean_runs(true),
+//! );
+//! assert!(text.contains("gap: 2 missing"));
+//! ```
+
+pub use bc_ur::{URDecodable, UREncodable};
+pub use dcbor::Date;
+
+#[cfg(feature = "json")]
+pub use crate::ProvenanceMarkInfo;
+pub use crate::{
+    ChainAlias, ChainId, FormatOptions, GeneratorSnapshot, GenesisPolicy,
+    LinkHash, LinkKey, ProvenanceMark, ProvenanceMarkGenerator,
+    ProvenanceMarkResolution, TrustAnchors, ValidationIssue, ValidationOptions,
+    ValidationPolicy, ValidationReport, ValidationReportFormat,
+};
@@ -0,0 +1,236 @@
+//! Proof of control of a chain, without issuing a mark.
+//!
+//! A registry sends the publisher an [`OwnershipChallenge`] naming the chain
+//! and carrying a fresh nonce. The publisher answers with
+//! [`ProvenanceMarkGenerator::respond`], which commits to the nonce with the
+//! key the chain's next mark will reveal. Only the holder of the chain's
+//! generator state knows that key before the mark is published, so once the
+//! registry sees the next mark, [`OwnershipResponse::verify`] shows that the
+//! response came from whoever controls the chain.
+//!
+//! A response proves nothing if it is received after the next mark is
+//! published, since the key is public from then on. Registries must record
+//! that a response arrived before the mark did.
+
+use dcbor::prelude::*;
+
+use crate::{
+    ChainId, ProvenanceMark, ProvenanceMarkGenerator, ProvenanceMarkResolution,
+    crypto_utils::{SHA256_SIZE, hmac_sha256},
+};
+
+/// CBOR tag for an [`OwnershipChallenge`] ("PRVQ").
+///
+/// Experimental: not allocated in `bc-tags`, and registered only by
+/// [`register_experimental_tags_in`](crate::register_experimental_tags_in).
+pub const TAG_OWNERSHIP_CHALLENGE: u64 = 1347573329;
+pub const TAG_NAME_OWNERSHIP_CHALLENGE: &str = "provenance-challenge";
+
+/// CBOR tag for an [`OwnershipResponse`] ("PRVR").
+///
+/// Experimental: not allocated in `bc-tags`, and registered only by
+/// [`register_experimental_tags_in`](crate::register_experimental_tags_in).
+pub const TAG_OWNERSHIP_RESPONSE: u64 = 1347573330;
+pub const TAG_NAME_OWNERSHIP_RESPONSE: &str = "provenance-response";
+
+/// A request to prove control of a chain, from a registry to a publisher.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OwnershipChallenge {
+    chain_id: ChainId,
+    nonce: Vec<u8>,
+}
+
+impl OwnershipChallenge {
+    /// A challenge for the chain with the given ID. The nonce should be
+    /// random and never reused, so old responses cannot be replayed.
+    pub fn new(chain_id: ChainId, nonce: impl Into<Vec<u8>>) -> Self {
+        Self { chain_id, nonce: nonce.into() }
+    }
+
+    pub fn chain_id(&self) -> &ChainId { &self.chain_id }
+
+    pub fn nonce(&self) -> &[u8] { &self.nonce }
+}
+
+/// A publisher's answer to an [`OwnershipChallenge`], from
+/// [`ProvenanceMarkGenerator::respond`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OwnershipResponse {
+    chain_id: ChainId,
+    seq: u32,
+    commitment: [u8; SHA256_SIZE],
+}
+
+/// The HMAC-SHA-256, under the key revealed by the mark at `seq`, of the
+/// chain ID, the big-endian sequence number, and the nonce.
+fn commitment(
+    key: &[u8],
+    chain_id: &ChainId,
+    seq: u32,
+    nonce: &[u8],
+) -> [u8; SHA256_SIZE] {
+    let message = [chain_id.as_bytes(), &seq.to_be_bytes()[..], nonce].concat();
+    hmac_sha256(key, message)
+}
+
+impl OwnershipResponse {
+    /// The ID of the chain of the generator that responded.
+    pub fn chain_id(&self) -> &ChainId { &self.chain_id }
+
+    /// The sequence number of the mark that will reveal the key the
+    /// response commits with.
+    pub fn seq(&self) -> u32 { self.seq }
+
+    pub fn commitment(&self) -> &[u8; SHA256_SIZE] { &self.commitment }
+
+    /// Whether the response answers `challenge` with the key revealed by
+    /// `published_next`, the mark at the response's sequence number.
+    ///
+    /// A genesis mark never verifies a response, since its key is the chain
+    /// ID. This does not check that `published_next` belongs to the chain
+    /// the registry knows, which is a matter for validation.
+    pub fn verify(
+        &self,
+        published_next: &ProvenanceMark,
+        challenge: &OwnershipChallenge,
+    ) -> bool {
+        self.chain_id == challenge.chain_id
+            && published_next.chain_id() == &challenge.chain_id
+            && published_next.seq() == self.seq
+            && !published_next.is_genesis()
+            && commitment(
+                published_next.key(),
+                &challenge.chain_id,
+                self.seq,
+                &challenge.nonce,
+            ) == self.commitment
+    }
+}
+
+impl ProvenanceMarkGenerator {
+    /// Answers an ownership challenge using the key the next mark will
+    /// reveal, without changing the generator.
+    ///
+    /// The response is made whatever chain the challenge names, and only
+    /// verifies if it is this generator's. A generator that has not issued
+    /// its genesis mark cannot prove anything, since the genesis key is the
+    /// chain ID.
+    pub fn respond(&self, challenge: &OwnershipChallenge) -> OwnershipResponse {
+        let key = self.peek_next_key();
+        OwnershipResponse {
+            chain_id: self.chain_id().clone(),
+            seq: self.next_seq(),
+            commitment: commitment(
+                &key,
+                &challenge.chain_id,
+                self.next_seq(),
+                &challenge.nonce,
+            ),
+        }
+    }
+}
+
+impl CBORTagged for OwnershipChallenge {
+    fn cbor_tags() -> Vec<Tag> {
+        vec![Tag::new(
+            TAG_OWNERSHIP_CHALLENGE,
+            TAG_NAME_OWNERSHIP_CHALLENGE,
+        )]
+    }
+}
+
+impl From<OwnershipChallenge> for CBOR {
+    fn from(value: OwnershipChallenge) -> Self { value.tagged_cbor() }
+}
+
+impl CBORTaggedEncodable for OwnershipChallenge {
+    fn untagged_cbor(&self) -> CBOR {
+        vec![
+            CBOR::to_byte_string(&self.chain_id),
+            CBOR::to_byte_string(&self.nonce),
+        ]
+        .to_cbor()
+    }
+}
+
+impl TryFrom<CBOR> for OwnershipChallenge {
+    type Error = dcbor::Error;
+
+    fn try_from(cbor: CBOR) -> dcbor::Result<Self> {
+        Self::from_tagged_cbor(cbor)
+    }
+}
+
+impl CBORTaggedDecodable for OwnershipChallenge {
+    fn from_untagged_cbor(cbor: CBOR) -> dcbor::Result<Self> {
+        let v = CBOR::try_into_array(cbor)?;
+        if v.len() != 2 {
+            return Err("Invalid ownership challenge length".into());
+        }
+        let chain_id = decode_chain_id(v[0].clone())?;
+        let nonce = CBOR::try_into_byte_string(v[1].clone())?;
+        Ok(Self::new(chain_id, nonce))
+    }
+}
+
+impl CBORTagged for OwnershipResponse {
+    fn cbor_tags() -> Vec<Tag> {
+        vec![Tag::new(
+            TAG_OWNERSHIP_RESPONSE,
+            TAG_NAME_OWNERSHIP_RESPONSE,
+        )]
+    }
+}
+
+impl From<OwnershipResponse> for CBOR {
+    fn from(value: OwnershipResponse) -> Self { value.tagged_cbor() }
+}
+
+impl CBORTaggedEncodable for OwnershipResponse {
+    fn untagged_cbor(&self) -> CBOR {
+        vec![
+            CBOR::to_byte_string(&self.chain_id),
+            self.seq.to_cbor(),
+            CBOR::to_byte_string(self.commitment),
+        ]
+        .to_cbor()
+    }
+}
+
+impl TryFrom<CBOR> for OwnershipResponse {
+    type Error = dcbor::Error;
+
+    fn try_from(cbor: CBOR) -> dcbor::Result<Self> {
+        Self::from_tagged_cbor(cbor)
+    }
+}
+
+impl CBORTaggedDecodable for OwnershipResponse {
+    fn from_untagged_cbor(cbor: CBOR) -> dcbor::Result<Self> {
+        let v = CBOR::try_into_array(cbor)?;
+        if v.len() != 3 {
+            return Err("Invalid ownership response length".into());
+        }
+        let chain_id = decode_chain_id(v[0].clone())?;
+        let seq: u32 = v[1].clone().try_into()?;
+        let commitment =
+            CBOR::try_into_byte_string(v[2].clone())?
+                .try_into()
+                .map_err(|_| dcbor::Error::from("Invalid commitment length"))?;
+        Ok(Self { chain_id, seq, commitment })
+    }
+}
+
+/// A chain ID of any resolution's link length.
+fn decode_chain_id(cbor: CBOR) -> dcbor::Result<ChainId> {
+    let bytes = CBOR::try_into_byte_string(cbor)?;
+    [
+        ProvenanceMarkResolution::Low,
+        ProvenanceMarkResolution::Medium,
+        ProvenanceMarkResolution::Quartile,
+        ProvenanceMarkResolution::High,
+    ]
+    .into_iter()
+    .find_map(|res| ChainId::new(res, bytes.clone()).ok())
+    .ok_or_else(|| "Invalid chain ID length".into())
+}
@@ -0,0 +1,73 @@
+use chrono::SecondsFormat;
+use serde_json::{Value, json};
+
+use crate::{ChainReport, ProvenanceMark};
+
+/// The namespaces in the JSON-LD context.
+const PROV_CONTEXT: &str = "http://www.w3.org/ns/prov#";
+const XSD_CONTEXT: &str = "http://www.w3.org/2001/XMLSchema#";
+
+/// The IRI of a mark: `base_iri` followed by the mark's fingerprint in hex.
+fn mark_iri(base_iri: &str, mark: &ProvenanceMark) -> String {
+    format!("{}{}", base_iri, hex::encode(mark.fingerprint()))
+}
+
+impl ChainReport {
+    /// The chain as a W3C PROV-O document in JSON-LD, for archives that
+    /// ingest provenance as PROV.
+    ///
+    /// Each mark is a `prov:Entity` whose IRI is `base_iri` followed by the
+    /// mark's fingerprint in hex, with a `prov:generatedAtTime` of the mark's
+    /// date. Each mark whose link from the mark before it was verified is a
+    /// `prov:wasRevisionOf` that mark, so there are no edges across gaps or
+    /// breaks. The chain itself is a `prov:Collection` whose IRI is
+    /// `base_iri` followed by `chain-` and the chain ID in hex, with each mark
+    /// as a `prov:hadMember`.
+    pub fn to_prov_jsonld(&self, base_iri: &str) -> Value {
+        let mut entities = Vec::new();
+        for sequence in self.sequences() {
+            let mut previous: Option<&ProvenanceMark> = None;
+            for flagged_mark in sequence.marks() {
+                let mark = flagged_mark.mark();
+                let mut entity = json!({
+                    "@id": mark_iri(base_iri, mark),
+                    "@type": "prov:Entity",
+                    "prov:generatedAtTime": {
+                        "@type": "xsd:dateTime",
+                        "@value": mark
+                            .date()
+                            .datetime()
+                            .to_rfc3339_opts(SecondsFormat::AutoSi, true),
+                    },
+                });
+                if let Some(previous) = previous {
+                    entity["prov:wasRevisionOf"] =
+                        json!({ "@id": mark_iri(base_iri, previous) });
+                }
+                entities.push(entity);
+                previous = Some(mark);
+            }
+        }
+
+        let members: Vec<Value> = self
+            .marks()
+            .iter()
+            .map(|mark| json!({ "@id": mark_iri(base_iri, mark) }))
+            .collect();
+        let collection = json!({
+            "@id": format!("{}chain-{}", base_iri, self.chain_id_hex()),
+            "@type": "prov:Collection",
+            "prov:hadMember": members,
+        });
+
+        let mut graph = vec![collection];
+        graph.extend(entities);
+        json!({
+            "@context": {
+                "prov": PROV_CONTEXT,
+                "xsd": XSD_CONTEXT,
+            },
+            "@graph": graph,
+        })
+    }
+}
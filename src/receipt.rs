@@ -0,0 +1,126 @@
+use bc_ur::UREncodable;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    ProvenanceMark, Result,
+    crypto_utils::{SHA256_SIZE, sha256},
+};
+
+/// A provenance mark bundled with metadata about the file it was issued for,
+/// suitable for shipping alongside the file as a single JSON document.
+///
+/// The mark is stored as its UR string and only decoded on use, so a receipt
+/// with a damaged mark can still be loaded and reported on by
+/// [`verify`](Self::verify).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WorkReceipt {
+    ur: String,
+    #[serde(with = "hex")]
+    sha256: [u8; SHA256_SIZE],
+    name: String,
+    size: u64,
+    #[serde(rename = "mimeType")]
+    mime_type: String,
+}
+
+/// The result of checking a [`WorkReceipt`] against a file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ReceiptVerification {
+    /// The receipt's UR decodes to a well-formed provenance mark.
+    pub mark_ok: bool,
+    /// The file's SHA-256 digest and size match the receipt.
+    pub digest_ok: bool,
+}
+
+impl ReceiptVerification {
+    pub fn is_ok(&self) -> bool { self.mark_ok && self.digest_ok }
+}
+
+impl WorkReceipt {
+    pub fn new(
+        mark: &ProvenanceMark,
+        file_bytes: &[u8],
+        name: &str,
+        mime: &str,
+    ) -> Self {
+        Self {
+            ur: mark.ur_string(),
+            sha256: sha256(file_bytes),
+            name: name.to_string(),
+            size: file_bytes.len() as u64,
+            mime_type: mime.to_string(),
+        }
+    }
+
+    pub fn ur(&self) -> &str { &self.ur }
+
+    pub fn sha256(&self) -> &[u8; SHA256_SIZE] { &self.sha256 }
+
+    pub fn name(&self) -> &str { &self.name }
+
+    pub fn size(&self) -> u64 { self.size }
+
+    pub fn mime_type(&self) -> &str { &self.mime_type }
+
+    /// Decodes the mark embedded in the receipt.
+    pub fn mark(&self) -> Result<ProvenanceMark> {
+        let mark = ProvenanceMark::parse_ur_string(&self.ur)?;
+        Ok(mark)
+    }
+
+    /// Checks the receipt's mark and whether `file_bytes` is the file it was
+    /// issued for.
+    pub fn verify(&self, file_bytes: &[u8]) -> ReceiptVerification {
+        let mark_ok =
+            self.mark().is_ok_and(|mark| mark.self_consistent().is_ok());
+        let digest_ok = file_bytes.len() as u64 == self.size
+            && sha256(file_bytes) == self.sha256;
+        ReceiptVerification { mark_ok, digest_ok }
+    }
+
+    /*
+    Example of a markdown receipt:
+
+    ```markdown
+    ---
+
+    #### `cover.png`
+
+    image/png, 1024 bytes
+
+    SHA-256: `6e340b9cffb37a989ca544e6bb780a2c78901d3fb33738768511a30617afa01d`
+
+    #### ur:provenance/lfaegdpaimkerydihsaedetiimmttpgdmocfdpbnhlasba
+
+    #### `🅟 WAVE JUDO LIAR FIGS`
+    ```
+    */
+
+    #[cfg(feature = "markdown")]
+    pub fn markdown(&self) -> String {
+        let mut lines: Vec<String> = Vec::new();
+
+        lines.push("---".to_string());
+
+        lines.push("".to_string());
+        lines.push(format!("#### `{}`", self.name));
+
+        lines.push("".to_string());
+        lines.push(format!("{}, {} bytes", self.mime_type, self.size));
+
+        lines.push("".to_string());
+        lines.push(format!("SHA-256: `{}`", hex::encode(self.sha256)));
+
+        lines.push("".to_string());
+        lines.push(format!("#### {}", self.ur));
+
+        if let Ok(mark) = self.mark() {
+            lines.push("".to_string());
+            lines.push(format!("#### `{}`", mark.id_bytewords(4, true)));
+        }
+
+        lines.push("".to_string());
+
+        lines.join("\n")
+    }
+}
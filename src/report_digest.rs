@@ -0,0 +1,127 @@
+#[cfg(feature = "json")]
+use serde::Deserialize;
+
+#[cfg(feature = "json")]
+use crate::{Error, Result, ValidationReport};
+use crate::{
+    ProvenanceMark,
+    crypto_utils::{SHA256_SIZE, Sha256Stream},
+};
+
+pub(crate) fn sequence_digest<'a>(
+    marks: impl IntoIterator<Item = &'a ProvenanceMark>,
+) -> [u8; SHA256_SIZE] {
+    let mut stream = Sha256Stream::new();
+    for mark in marks {
+        stream.update(mark.fingerprint());
+    }
+    stream.finalize()
+}
+
+pub(crate) fn chain_digest(
+    chain_id: &[u8],
+    sequence_digests: impl IntoIterator<Item = [u8; SHA256_SIZE]>,
+) -> [u8; SHA256_SIZE] {
+    let mut stream = Sha256Stream::new();
+    stream.update(chain_id);
+    for digest in sequence_digests {
+        stream.update(digest);
+    }
+    stream.finalize()
+}
+
+pub(crate) fn report_digest(
+    chain_digests: impl IntoIterator<Item = [u8; SHA256_SIZE]>,
+) -> [u8; SHA256_SIZE] {
+    let mut stream = Sha256Stream::new();
+    for digest in chain_digests {
+        stream.update(digest);
+    }
+    stream.finalize()
+}
+
+// The parts of a serialized report that its digests cover.
+
+#[cfg(feature = "json")]
+#[derive(Deserialize)]
+struct StoredReport {
+    chains: Vec<StoredChain>,
+    #[serde(with = "hex")]
+    digest: [u8; SHA256_SIZE],
+}
+
+#[cfg(feature = "json")]
+#[derive(Deserialize)]
+struct StoredChain {
+    #[serde(with = "hex")]
+    chain_id: Vec<u8>,
+    sequences: Vec<StoredSequence>,
+    #[serde(with = "hex")]
+    digest: [u8; SHA256_SIZE],
+}
+
+#[cfg(feature = "json")]
+#[derive(Deserialize)]
+struct StoredSequence {
+    marks: Vec<StoredMark>,
+    #[serde(with = "hex")]
+    digest: [u8; SHA256_SIZE],
+}
+
+#[cfg(feature = "json")]
+#[derive(Deserialize)]
+struct StoredMark {
+    mark: String,
+}
+
+#[cfg(feature = "json")]
+fn check_digest(
+    stored: [u8; SHA256_SIZE],
+    computed: [u8; SHA256_SIZE],
+    path: impl FnOnce() -> String,
+) -> Result<[u8; SHA256_SIZE]> {
+    if stored != computed {
+        return Err(Error::DigestMismatch { path: path() });
+    }
+    Ok(computed)
+}
+
+#[cfg(feature = "json")]
+impl ValidationReport {
+    /// Recompute the digests of a report stored as JSON from the marks it
+    /// lists, returning the report's [digest](Self::digest).
+    ///
+    /// Returns an error if any stored digest does not match, which shows that
+    /// the report was edited after it was produced, or if a mark cannot be
+    /// parsed.
+    pub fn recompute_digests(json: &str) -> Result<[u8; SHA256_SIZE]> {
+        let report: StoredReport = serde_json::from_str(json)?;
+        let mut chain_digests = Vec::with_capacity(report.chains.len());
+        for (i, chain) in report.chains.iter().enumerate() {
+            let mut sequence_digests =
+                Vec::with_capacity(chain.sequences.len());
+            for (j, sequence) in chain.sequences.iter().enumerate() {
+                let marks = sequence
+                    .marks
+                    .iter()
+                    .map(|flagged| {
+                        ProvenanceMark::parse_ur_string(&flagged.mark)
+                    })
+                    .collect::<Result<Vec<_>>>()?;
+                sequence_digests.push(check_digest(
+                    sequence.digest,
+                    sequence_digest(&marks),
+                    || format!("chains[{i}].sequences[{j}]"),
+                )?);
+            }
+            chain_digests.push(check_digest(
+                chain.digest,
+                chain_digest(&chain.chain_id, sequence_digests),
+                || format!("chains[{i}]"),
+            )?);
+        }
+        check_digest(report.digest, report_digest(chain_digests), || {
+            "report".to_string()
+        })
+    }
+}
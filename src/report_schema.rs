@@ -0,0 +1,467 @@
+use serde_json::{Value, json};
+
+use crate::ValidationReport;
+
+impl ValidationReport {
+    /// A JSON Schema (draft 2020-12) describing the exact shape of the JSON
+    /// produced by [`ValidationReport::format`] and
+    /// [`ValidationReport::write_json`].
+    ///
+    /// The schema is hand-written and must be updated whenever the serialized
+    /// shape of the report changes.
+    pub fn json_schema() -> Value {
+        let mut schema = object_schema(
+            &["marks", "chains", "digest"],
+            json!({
+                "marks": array_of("mark"),
+                "duplicates_removed": { "type": "integer", "minimum": 1 },
+                "duplicate_marks": array_of("mark"),
+                "chains": array_of("chain"),
+                "groups": array_of("chain_group"),
+                "missing_chains": array_of("hex"),
+                "notes": array_of("note"),
+                "suppressed": array_of("suppressed_issue"),
+                "options": reference("options"),
+                "digest": reference("digest"),
+                "statistics": reference("statistics"),
+            }),
+        );
+        schema["$schema"] =
+            json!("https://json-schema.org/draft/2020-12/schema");
+        schema["title"] = json!("ValidationReport");
+        schema["$defs"] = definitions();
+        schema
+    }
+}
+
+fn definitions() -> Value {
+    json!({
+        "mark": {
+            "oneOf": [reference("mark_ur"), reference("mark_details")]
+        },
+        "mark_details": object_schema(
+            &[
+                "ur",
+                "seq",
+                "date",
+                "chain_id",
+                "identifier",
+                "is_genesis",
+                "has_info",
+            ],
+            json!({
+                "ur": reference("mark_ur"),
+                "seq": reference("seq"),
+                "date": reference("date"),
+                "chain_id": reference("hex"),
+                "identifier": { "type": "string" },
+                "is_genesis": { "type": "boolean" },
+                "has_info": { "type": "boolean" },
+            }),
+        ),
+        "mark_ur": {
+            "type": "string",
+            "pattern": "^ur:provenance/[a-z]+$"
+        },
+        "hex": {
+            "type": "string",
+            "pattern": "^([0-9a-f]{2})*$"
+        },
+        "digest": {
+            "type": "string",
+            "pattern": "^[0-9a-f]{64}$"
+        },
+        "date": {
+            "type": "string",
+            "minLength": 10
+        },
+        "seq": {
+            "type": "integer",
+            "minimum": 0,
+            "maximum": u32::MAX
+        },
+        "resolution": {
+            "enum": ["low", "medium", "quartile", "high"]
+        },
+        "chain": object_schema(
+            &[
+                "chain_id",
+                "chain_fingerprint",
+                "has_genesis",
+                "marks",
+                "sequences",
+                "digest",
+            ],
+            json!({
+                "chain_id": reference("hex"),
+                "chain_fingerprint": reference("digest"),
+                "has_genesis": { "type": "boolean" },
+                "declares_key": { "type": "boolean" },
+                "label": { "type": "string" },
+                "anchored": { "type": "boolean" },
+                "marks": array_of("mark"),
+                "sequences": array_of("sequence"),
+                "advisories": array_of("advisory"),
+                "digest": reference("digest"),
+                "health": reference("health"),
+            }),
+        ),
+        "health": object_schema(
+            &[
+                "score",
+                "has_genesis",
+                "main_run_fraction",
+                "error_count",
+                "gap_count",
+            ],
+            json!({
+                "score": { "type": "number", "minimum": 0, "maximum": 1 },
+                "has_genesis": { "type": "boolean" },
+                "main_run_fraction": {
+                    "type": "number",
+                    "minimum": 0,
+                    "maximum": 1
+                },
+                "error_count": { "type": "integer", "minimum": 0 },
+                "gap_count": { "type": "integer", "minimum": 0 },
+                "freshness": { "type": "number", "minimum": 0, "maximum": 1 },
+            }),
+        ),
+        "sequence": object_schema(
+            &["start_seq", "end_seq", "marks", "digest"],
+            json!({
+                "start_seq": reference("seq"),
+                "end_seq": reference("seq"),
+                "preceded_by": {
+                    "type": "string",
+                    "pattern": "^[0-9a-f]{8}$"
+                },
+                "marks": array_of("flagged_mark"),
+                "digest": reference("digest"),
+            }),
+        ),
+        "flagged_mark": object_schema(
+            &["mark", "issues"],
+            json!({
+                "mark": reference("mark"),
+                "issues": array_of("issue"),
+                "evidence": array_of("evidence"),
+            }),
+        ),
+        "evidence": object_schema(
+            &[
+                "rule",
+                "previous_mark",
+                "previous_position",
+                "position",
+                "values",
+            ],
+            json!({
+                "rule": { "type": "string" },
+                "previous_mark": {
+                    "type": "string",
+                    "pattern": "^[0-9a-f]{8}$"
+                },
+                "previous_position": { "type": "integer", "minimum": 0 },
+                "position": { "type": "integer", "minimum": 0 },
+                "values": {
+                    "type": "object",
+                    "additionalProperties": { "type": "string" }
+                },
+            }),
+        ),
+        "suppressed_issue": object_schema(
+            &["mark", "issue"],
+            json!({
+                "mark": reference("mark"),
+                "issue": reference("issue"),
+            }),
+        ),
+        "suppression_rule": object_schema(
+            &["chainIdPrefix", "code"],
+            json!({
+                "chainIdPrefix": reference("hex"),
+                "code": { "type": "string" },
+                "seqRange": object_schema(
+                    &["start", "end"],
+                    json!({
+                        "start": reference("seq"),
+                        "end": reference("seq"),
+                    }),
+                ),
+            }),
+        ),
+        "options": object_schema(
+            &[],
+            json!({
+                "referenceTime": reference("date"),
+                "futureToleranceSeconds": { "type": "integer" },
+                "lowCollisionThreshold": { "type": "integer", "minimum": 0 },
+                "mediumCollisionThreshold": {
+                    "type": "integer",
+                    "minimum": 0
+                },
+                "signatureVerifier": { "type": "boolean" },
+                "selfCheck": { "type": "boolean" },
+                "dateEncodingCheck": { "type": "boolean" },
+                "includeEvidence": { "type": "boolean" },
+                "anchors": array_of("hex"),
+                "labels": {
+                    "type": "object",
+                    "additionalProperties": { "type": "string" }
+                },
+                "suppressions": array_of("suppression_rule"),
+                "aliases": {
+                    "type": "object",
+                    "additionalProperties": reference("hex")
+                },
+                "expectedChains": array_of("hex"),
+            }),
+        ),
+        "chain_group": object_schema(
+            &["canonical_chain_id", "chain_ids"],
+            json!({
+                "canonical_chain_id": reference("hex"),
+                "chain_ids": array_of("hex"),
+            }),
+        ),
+        "statistics": object_schema(
+            &[
+                "identifier_collisions",
+                "minimum_unique_identifier_len",
+                "total_bytes",
+                "chain_sizes",
+            ],
+            json!({
+                "identifier_collisions": array_of("identifier_collision"),
+                "minimum_unique_identifier_len": {
+                    "type": "integer",
+                    "minimum": 4,
+                    "maximum": 32
+                },
+                "total_bytes": { "type": "integer", "minimum": 0 },
+                "chain_sizes": array_of("chain_sizes"),
+            }),
+        ),
+        "chain_sizes": object_schema(
+            &[
+                "chain_id",
+                "mark_count",
+                "total_bytes",
+                "min_ur_len",
+                "mean_ur_len",
+                "max_ur_len",
+            ],
+            json!({
+                "chain_id": reference("hex"),
+                "mark_count": { "type": "integer", "minimum": 1 },
+                "total_bytes": { "type": "integer", "minimum": 0 },
+                "min_ur_len": { "type": "integer", "minimum": 0 },
+                "mean_ur_len": { "type": "number", "minimum": 0 },
+                "max_ur_len": { "type": "integer", "minimum": 0 },
+            }),
+        ),
+        "identifier_collision": object_schema(
+            &["identifier", "marks"],
+            json!({
+                "identifier": {
+                    "type": "string",
+                    "pattern": "^[0-9a-f]{8}$"
+                },
+                "marks": array_of("mark"),
+            }),
+        ),
+        "issue": { "oneOf": issue_schemas() },
+        "self_check_failure": { "oneOf": self_check_failure_schemas() },
+        "advisory": { "oneOf": advisory_schemas() },
+        "note": { "oneOf": note_schemas() },
+    })
+}
+
+/// One schema per `ValidationIssue` variant, matching its adjacently tagged
+/// serialization.
+fn issue_schemas() -> Vec<Value> {
+    vec![
+        variant_schema(
+            "HashMismatch",
+            Some(json!({
+                "expected": reference("hex"),
+                "actual": reference("hex"),
+            })),
+        ),
+        variant_schema("KeyMismatch", None),
+        variant_schema(
+            "SequenceGap",
+            Some(json!({
+                "expected": reference("seq"),
+                "actual": reference("seq"),
+            })),
+        ),
+        variant_schema(
+            "DuplicateSeq",
+            Some(json!({
+                "seq": reference("seq"),
+            })),
+        ),
+        variant_schema(
+            "SequenceRegression",
+            Some(json!({
+                "previous": reference("seq"),
+                "next": reference("seq"),
+            })),
+        ),
+        variant_schema(
+            "DateOrdering",
+            Some(json!({
+                "previous": reference("date"),
+                "next": reference("date"),
+            })),
+        ),
+        variant_schema("NonGenesisAtZero", None),
+        variant_schema("InvalidGenesisKey", None),
+        variant_schema(
+            "ResolutionMismatch",
+            Some(json!({
+                "previous": reference("resolution"),
+                "next": reference("resolution"),
+            })),
+        ),
+        variant_schema(
+            "ChainMismatch",
+            Some(json!({
+                "previous_chain": reference("hex"),
+                "next_chain": reference("hex"),
+            })),
+        ),
+        variant_schema(
+            "FutureDated",
+            Some(json!({
+                "date": reference("date"),
+                "reference": reference("date"),
+            })),
+        ),
+        variant_schema("MissingSignature", None),
+        variant_schema("InvalidSignature", None),
+        variant_schema(
+            "ReusedKey",
+            Some(json!({
+                "other_mark": reference("hex"),
+            })),
+        ),
+        variant_schema(
+            "MarkAfterTermination",
+            Some(json!({
+                "termination": reference("seq"),
+            })),
+        ),
+        variant_schema(
+            "SelfCheckFailed",
+            Some(json!({
+                "failures": array_of("self_check_failure"),
+            })),
+        ),
+        variant_schema(
+            "DateEncodingMismatch",
+            Some(json!({
+                "stored": reference("hex"),
+                "reencoded": reference("hex"),
+            })),
+        ),
+    ]
+}
+
+/// One schema per `SelfCheckFailure` variant.
+fn self_check_failure_schemas() -> Vec<Value> {
+    let mut schemas = vec![variant_schema(
+        "FieldLength",
+        Some(json!({
+            "field": { "enum": ["key", "hash", "chain_id"] },
+            "expected": { "type": "integer", "minimum": 0 },
+            "actual": { "type": "integer", "minimum": 0 },
+        })),
+    )];
+    schemas.extend(
+        [
+            "SeqBytes",
+            "DateBytes",
+            "DatePrecision",
+            "InfoCbor",
+            "GenesisKeyAtNonZeroSeq",
+            "NonGenesisKeyAtZeroSeq",
+            "MessageRoundTrip",
+        ]
+        .into_iter()
+        .map(|type_name| variant_schema(type_name, None)),
+    );
+    schemas
+}
+
+/// One schema per `ChainAdvisory` variant.
+fn advisory_schemas() -> Vec<Value> {
+    vec![
+        variant_schema(
+            "CollisionRiskElevated",
+            Some(json!({
+                "resolution": reference("resolution"),
+                "count": { "type": "integer", "minimum": 0 },
+                "threshold": { "type": "integer", "minimum": 0 },
+            })),
+        ),
+        variant_schema("UnexpectedChain", None),
+    ]
+}
+
+/// One schema per `ReportNote` variant.
+fn note_schemas() -> Vec<Value> {
+    vec![variant_schema(
+        "CrossChainKeyReuse",
+        Some(json!({
+            "key": reference("hex"),
+            "chain_ids": array_of("hex"),
+        })),
+    )]
+}
+
+fn variant_schema(type_name: &str, data: Option<Value>) -> Value {
+    match data {
+        Some(data) => {
+            let required = data
+                .as_object()
+                .map(|o| o.keys().map(String::as_str).collect::<Vec<_>>())
+                .unwrap_or_default();
+            object_schema(
+                &["type", "data"],
+                json!({
+                    "type": { "const": type_name },
+                    "data": object_schema(&required, data.clone()),
+                }),
+            )
+        }
+        None => object_schema(
+            &["type"],
+            json!({
+                "type": { "const": type_name },
+            }),
+        ),
+    }
+}
+
+fn object_schema(required: &[&str], properties: Value) -> Value {
+    json!({
+        "type": "object",
+        "required": required,
+        "additionalProperties": false,
+        "properties": properties,
+    })
+}
+
+fn array_of(definition: &str) -> Value {
+    json!({
+        "type": "array",
+        "items": reference(definition),
+    })
+}
+
+fn reference(definition: &str) -> Value {
+    json!({ "$ref": format!("#/$defs/{}", definition) })
+}
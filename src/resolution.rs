@@ -3,6 +3,7 @@ use std::{
     ops::{Range, RangeFrom},
 };
 
+use chrono::SecondsFormat;
 use dcbor::{Date, prelude::*};
 use serde::{Deserialize, Serialize};
 
@@ -71,9 +72,26 @@ impl From<ProvenanceMarkResolution> for CBOR {
 impl TryFrom<CBOR> for ProvenanceMarkResolution {
     type Error = dcbor::Error;
 
+    /// Accepts only an unsigned integer from 0 to 3, so values that dCBOR
+    /// would otherwise coerce to one, such as floats, are rejected with
+    /// [`Error::InvalidResolutionEncoding`].
     fn try_from(cbor: CBOR) -> dcbor::Result<Self> {
-        let value: u8 = cbor.try_into()?;
-        ProvenanceMarkResolution::try_from(value).map_err(dcbor::Error::from)
+        let found = match cbor.as_case() {
+            CBORCase::Unsigned(value @ 0..=3) => {
+                return ProvenanceMarkResolution::try_from(*value as u8)
+                    .map_err(dcbor::Error::from);
+            }
+            CBORCase::Unsigned(value) => format!("unsigned integer {}", value),
+            CBORCase::Negative(_) => "negative integer".to_string(),
+            CBORCase::ByteString(_) => "byte string".to_string(),
+            CBORCase::Text(_) => "text string".to_string(),
+            CBORCase::Array(_) => "array".to_string(),
+            CBORCase::Map(_) => "map".to_string(),
+            CBORCase::Tagged(_, _) => "tagged value".to_string(),
+            CBORCase::Simple(Simple::Float(_)) => "float".to_string(),
+            CBORCase::Simple(_) => "simple value".to_string(),
+        };
+        Err(Error::InvalidResolutionEncoding { found }.into())
     }
 }
 
@@ -110,6 +128,49 @@ impl ProvenanceMarkResolution {
             + self.date_bytes_length()
     }
 
+    /// The largest info payload, in bytes of CBOR, recommended for marks at
+    /// this resolution. Larger payloads are valid but make marks unwieldy to
+    /// publish, e.g. in URLs and QR codes.
+    pub fn recommended_info_size(&self) -> usize {
+        match self {
+            Res::Low => 32,
+            Res::Medium => 64,
+            Res::Quartile => 256,
+            Res::High => 1024,
+        }
+    }
+
+    /// The number of characters in the UR string of a mark at this
+    /// resolution whose info encodes to `info_len` bytes of CBOR (zero for a
+    /// mark with no info).
+    ///
+    /// A mark's UR is `ur:provenance/` followed by the minimal bytewords of
+    /// its untagged CBOR, a two-element array of the resolution and the
+    /// message byte string, plus a four-byte checksum. Each byte becomes two
+    /// characters.
+    pub fn estimate_ur_len(&self, info_len: usize) -> usize {
+        let message_len = self.fixed_length() + info_len;
+        let byte_string_header_len = match message_len {
+            0..=23 => 1,
+            24..=0xff => 2,
+            0x100..=0xffff => 3,
+            _ => 5,
+        };
+        // Array header, resolution, byte string header, message, checksum
+        let cbor_len = 1 + 1 + byte_string_header_len + message_len;
+        "ur:provenance/".len() + (cbor_len + 4) * 2
+    }
+
+    /// The highest resolution whose marks carrying `info_len` bytes of info
+    /// CBOR have a UR of at most `max_ur_len` characters, e.g. to fit a QR
+    /// code of a given version and error correction level. Returns `None` if
+    /// even a Low mark is too long.
+    pub fn best_fitting(max_ur_len: usize, info_len: usize) -> Option<Self> {
+        [Res::High, Res::Quartile, Res::Medium, Res::Low]
+            .into_iter()
+            .find(|res| res.estimate_ur_len(info_len) <= max_ur_len)
+    }
+
     pub fn key_range(&self) -> Range<usize> { 0..self.link_length() }
 
     pub fn chain_id_range(&self) -> Range<usize> { 0..self.link_length() }
@@ -143,6 +204,32 @@ impl ProvenanceMarkResolution {
         }
     }
 
+    /// How precisely a mark at this resolution records its date.
+    pub fn date_precision(&self) -> DatePrecision {
+        match self {
+            Res::Low => DatePrecision::Day,
+            Res::Medium => DatePrecision::Second,
+            Res::Quartile | Res::High => DatePrecision::Millisecond,
+        }
+    }
+
+    /// The earliest and latest dates a mark at this resolution can carry.
+    pub fn date_range(&self) -> (Date, Date) {
+        let decode = |min: &[u8], max: &[u8]| {
+            (
+                self.deserialize_date(min).unwrap(),
+                self.deserialize_date(max).unwrap(),
+            )
+        };
+        match self {
+            Res::Low => decode(&[0x00, 0x21], &[0xff, 0x9f]),
+            Res::Medium => decode(&[0x00; 4], &[0xff; 4]),
+            Res::Quartile | Res::High => {
+                decode(&[0x00; 6], &[0xe5, 0x94, 0x0a, 0x78, 0xa7, 0xff])
+            }
+        }
+    }
+
     /// Deserializes bytes into a Date based on the resolution.
     pub fn deserialize_date(&self, data: &[u8]) -> Result<Date> {
         match self {
@@ -166,21 +253,30 @@ impl ProvenanceMarkResolution {
         }
     }
 
+    /// The largest sequence number a mark at this resolution can carry.
+    pub fn max_seq(&self) -> u32 {
+        match self.seq_bytes_length() {
+            2 => u32::from(u16::MAX),
+            _ => u32::MAX,
+        }
+    }
+
+    /// `seq` as a sequence number at this resolution, or
+    /// [`Error::SequenceOutOfRange`] if it is larger than
+    /// [`max_seq`](Self::max_seq).
+    pub fn checked_seq(&self, seq: u64) -> Result<u32> {
+        let max = self.max_seq();
+        u32::try_from(seq)
+            .ok()
+            .filter(|seq| *seq <= max)
+            .ok_or(Error::SequenceOutOfRange { value: seq, max })
+    }
+
     /// Serializes a sequence number into bytes based on the resolution.
     pub fn serialize_seq(&self, seq: u32) -> Result<Vec<u8>> {
+        let seq = self.checked_seq(u64::from(seq))?;
         match self.seq_bytes_length() {
-            2 => {
-                if seq > (u16::MAX as u32) {
-                    return Err(Error::ResolutionError {
-                        details: format!(
-                            "sequence number {} out of range for 2-byte format (max {})",
-                            seq,
-                            u16::MAX
-                        ),
-                    });
-                }
-                Ok((seq as u16).to_be_bytes().to_vec())
-            }
+            2 => Ok((seq as u16).to_be_bytes().to_vec()),
             4 => Ok(seq.to_be_bytes().to_vec()),
             _ => unreachable!(),
         }
@@ -215,3 +311,45 @@ impl std::fmt::Display for ProvenanceMarkResolution {
         }
     }
 }
+
+/// How precisely a mark records its date, from
+/// [`ProvenanceMarkResolution::date_precision`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum DatePrecision {
+    /// The date only: low resolution marks carry no time of day.
+    Day,
+    /// The date and time to the second, for medium resolution marks.
+    Second,
+    /// The date and time to the millisecond, for quartile and high
+    /// resolution marks.
+    Millisecond,
+}
+
+impl DatePrecision {
+    /// Renders `date` to this precision: `YYYY-MM-DD` for
+    /// [`Day`](Self::Day), and RFC 3339 in UTC otherwise, such as
+    /// `2023-06-20T12:34:56Z` or `2023-06-20T12:34:56.789Z`. Finer parts of
+    /// `date` are dropped, not rounded.
+    pub fn format(&self, date: &Date) -> String {
+        let datetime = date.datetime();
+        match self {
+            DatePrecision::Day => datetime.format("%Y-%m-%d").to_string(),
+            DatePrecision::Second => {
+                datetime.to_rfc3339_opts(SecondsFormat::Secs, true)
+            }
+            DatePrecision::Millisecond => {
+                datetime.to_rfc3339_opts(SecondsFormat::Millis, true)
+            }
+        }
+    }
+}
+
+impl std::fmt::Display for DatePrecision {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DatePrecision::Day => write!(f, "day"),
+            DatePrecision::Second => write!(f, "second"),
+            DatePrecision::Millisecond => write!(f, "millisecond"),
+        }
+    }
+}
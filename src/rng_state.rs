@@ -1,15 +1,21 @@
 use dcbor::prelude::*;
+#[cfg(feature = "json")]
 use serde::{Deserialize, Serialize};
 
+#[cfg(feature = "json")]
 use crate::util::{deserialize_block, serialize_block};
 
 pub const RNG_STATE_LENGTH: usize = 32;
 
-#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "json", derive(Serialize, Deserialize))]
 pub struct RngState(
-    #[serde(
-        serialize_with = "serialize_block",
-        deserialize_with = "deserialize_block"
+    #[cfg_attr(
+        feature = "json",
+        serde(
+            serialize_with = "serialize_block",
+            deserialize_with = "deserialize_block"
+        )
     )]
     [u8; RNG_STATE_LENGTH],
 );
@@ -0,0 +1,104 @@
+//! Discovery of provenance marks in free text, such as pasted emails or web
+//! pages.
+
+use std::ops::Range;
+
+use crate::{ProvenanceMark, Result};
+
+const UR_PREFIX: &[u8] = b"ur:provenance/";
+
+/// The length of the shortest minimal ByteWords encoding of a mark, that of a
+/// low resolution mark without info.
+const MIN_BYTEWORDS_LENGTH: usize = 32;
+
+/// Find every provenance mark in `text`, returning each with the byte offset
+/// at which it starts, in order of appearance.
+///
+/// Two forms are recognized:
+///
+/// - `ur:provenance/` URs, matched case-insensitively. A UR ends at the first
+///   character that cannot be part of it, so trailing punctuation is ignored,
+///   and a UR wrapped onto following lines is rejoined. Each UR yields one
+///   entry, which is an error if it cannot be decoded.
+/// - Standalone runs of minimal ByteWords, such as the `provenance` parameter
+///   of a mark's URL. These yield an entry only if they decode as a mark.
+///
+/// The marks found can be passed straight to
+/// [`ProvenanceMark::validate`] after discarding the offsets and errors.
+pub fn extract_marks(text: &str) -> Vec<(usize, Result<ProvenanceMark>)> {
+    let lower = text.to_ascii_lowercase();
+    let bytes = lower.as_bytes();
+    let mut results = Vec::new();
+    let mut pos = 0;
+    while pos < bytes.len() {
+        if bytes[pos..].starts_with(UR_PREFIX) {
+            let (result, end) = parse_ur(&lower, pos);
+            results.push((pos, result));
+            pos = end;
+        } else if bytes[pos].is_ascii_alphabetic() {
+            let end = letters_end(bytes, pos);
+            let mark = Some(&lower[pos..end])
+                .filter(|run| run.len() >= MIN_BYTEWORDS_LENGTH)
+                .and_then(|run| ProvenanceMark::from_url_encoding(run).ok());
+            if let Some(mark) = mark {
+                results.push((pos, Ok(mark)));
+            }
+            pos = end;
+        } else {
+            pos += 1;
+        }
+    }
+    results
+}
+
+/// Parse the UR starting at `start`, returning the result and the offset just
+/// past the UR.
+fn parse_ur(lower: &str, start: usize) -> (Result<ProvenanceMark>, usize) {
+    let body_start = start + UR_PREFIX.len();
+    let lines = ur_lines(lower.as_bytes(), body_start);
+    // Try joining the most lines first, so that a wrapped UR is decoded whole,
+    // backing off in case the lines that follow are ordinary text.
+    for count in (2..=lines.len()).rev() {
+        let body: String = lines[..count]
+            .iter()
+            .map(|line| &lower[line.clone()])
+            .collect();
+        if let Ok(mark) = ProvenanceMark::from_url_encoding(&body) {
+            return (Ok(mark), lines[count - 1].end);
+        }
+    }
+    let end = lines.first().map_or(body_start, |line| line.end);
+    (ProvenanceMark::parse_ur_string(&lower[start..end]), end)
+}
+
+/// The runs of letters that may make up a UR body starting at `start`: the
+/// first run, followed by each run that begins a new line directly after the
+/// previous one.
+fn ur_lines(bytes: &[u8], start: usize) -> Vec<Range<usize>> {
+    let mut lines = Vec::new();
+    let mut pos = start;
+    loop {
+        let end = letters_end(bytes, pos);
+        if end == pos {
+            break;
+        }
+        lines.push(pos..end);
+        let gap = bytes[end..]
+            .iter()
+            .take_while(|b| b.is_ascii_whitespace())
+            .count();
+        if !bytes[end..end + gap].contains(&b'\n') {
+            break;
+        }
+        pos = end + gap;
+    }
+    lines
+}
+
+fn letters_end(bytes: &[u8], start: usize) -> usize {
+    start
+        + bytes[start..]
+            .iter()
+            .take_while(|b| b.is_ascii_alphabetic())
+            .count()
+}
@@ -2,21 +2,27 @@ use bc_rand::{
     RandomNumberGenerator, SecureRandomNumberGenerator, rng_random_data,
 };
 use dcbor::prelude::*;
+#[cfg(feature = "json")]
 use serde::{Deserialize, Serialize};
 
+#[cfg(feature = "json")]
+use crate::util::{deserialize_block, serialize_block};
 use crate::{
     Error, Result,
-    crypto_utils::extend_key,
-    util::{deserialize_block, serialize_block},
+    crypto_utils::{extend_key, hkdf_hmac_sha256_info},
 };
 
 pub const PROVENANCE_SEED_LENGTH: usize = 32;
 
-#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "json", derive(Serialize, Deserialize))]
 pub struct ProvenanceSeed(
-    #[serde(
-        serialize_with = "serialize_block",
-        deserialize_with = "deserialize_block"
+    #[cfg_attr(
+        feature = "json",
+        serde(
+            serialize_with = "serialize_block",
+            deserialize_with = "deserialize_block"
+        )
     )]
     [u8; PROVENANCE_SEED_LENGTH],
 );
@@ -57,6 +63,49 @@ impl ProvenanceSeed {
     }
 
     pub fn hex(&self) -> String { hex::encode(self.0) }
+
+    /// Derives the seed of the child at `index`, for creating many
+    /// independent chains from one master seed.
+    ///
+    /// The derivation is HKDF-SHA-256 over this seed with the info
+    /// `provenance-child` followed by the big-endian index, so it is stable
+    /// across releases and children cannot be linked to each other or to the
+    /// master without it.
+    pub fn derive_child(&self, index: u32) -> Self {
+        let info =
+            [b"provenance-child".as_slice(), &index.to_be_bytes()].concat();
+        let child =
+            hkdf_hmac_sha256_info(self.0, [], info, PROVENANCE_SEED_LENGTH);
+        let mut seed_data = [0; PROVENANCE_SEED_LENGTH];
+        seed_data.copy_from_slice(&child);
+        Self::from_bytes(seed_data)
+    }
+}
+
+#[cfg(feature = "components")]
+impl ProvenanceSeed {
+    /// This seed as a [`bc_components::Seed`] with optional name and note,
+    /// e.g. for storing as a `ur:seed` alongside other Blockchain Commons
+    /// seeds.
+    pub fn to_bc_seed(
+        &self,
+        name: Option<&str>,
+        note: Option<&str>,
+    ) -> bc_components::Seed {
+        bc_components::Seed::new_opt(
+            self.0.to_vec(),
+            name.map(str::to_string),
+            note.map(str::to_string),
+            None,
+        )
+        .unwrap()
+    }
+
+    /// The seed held in a [`bc_components::Seed`], which must be exactly
+    /// [`PROVENANCE_SEED_LENGTH`] bytes. Its metadata is ignored.
+    pub fn try_from_bc_seed(seed: &bc_components::Seed) -> Result<Self> {
+        Self::from_slice(seed.as_bytes())
+    }
 }
 
 impl Default for ProvenanceSeed {
@@ -0,0 +1,182 @@
+use std::{
+    collections::HashSet,
+    sync::{Arc, PoisonError, RwLock, RwLockReadGuard, RwLockWriteGuard},
+};
+
+use crate::{
+    ChainAdvisory, ChainReport, ProvenanceMark, ValidationOptions,
+    ValidationReport,
+};
+
+/// The result of [`SharedProvenanceService::submit`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InsertOutcome {
+    /// The mark was new and has been added.
+    Inserted,
+    /// The mark had already been submitted and was ignored.
+    Duplicate,
+}
+
+/// The overall state of the marks submitted to a
+/// [`SharedProvenanceService`], from
+/// [`verdict`](SharedProvenanceService::verdict).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Verdict {
+    /// No marks have been submitted, and no chains are
+    /// [expected](ValidationOptions::with_expected_chains).
+    Empty,
+    /// Every chain starts with its genesis mark and is a single sequence with
+    /// no flagged marks.
+    Valid,
+    /// Some chain is missing its genesis mark, is broken into several
+    /// sequences, has a flagged mark, or is not one of the [expected
+    /// chains](ValidationOptions::with_expected_chains), or an expected
+    /// chain has no marks.
+    Issues,
+}
+
+#[derive(Default)]
+struct ServiceState {
+    marks: Vec<ProvenanceMark>,
+    seen: HashSet<ProvenanceMark>,
+    /// The report of `marks`, until another mark is submitted.
+    report: Option<Arc<ValidationReport>>,
+}
+
+/// A store of marks that can be shared between threads, such as the
+/// handlers of a web service, and validated on demand.
+///
+/// Cloning the service is cheap and shares the store. Submissions are
+/// serialized, while reports are computed without holding the lock and
+/// cached until the next submission, so concurrent reads do not block each
+/// other or writers.
+///
+/// ```
+/// use dcbor::Date;
+/// use provenance_mark::{
+///     InsertOutcome, ProvenanceMarkGenerator, ProvenanceMarkResolution,
+///     SharedProvenanceService, Verdict,
+/// };
+///
+/// let service = SharedProvenanceService::new();
+/// let mut generator = ProvenanceMarkGenerator::new_with_passphrase(
+///     ProvenanceMarkResolution::Low,
+///     "service",
+/// );
+/// let date = Date::from_string("2023-06-20").unwrap();
+/// let genesis = generator.next(date, None::<&str>);
+///
+/// let handler = service.clone();
+/// std::thread::spawn(move || handler.submit(genesis))
+///     .join()
+///     .unwrap();
+/// assert_eq!(service.verdict(), Verdict::Valid);
+/// assert_eq!(service.snapshot_report().marks().len(), 1);
+/// ```
+#[derive(Clone, Default)]
+pub struct SharedProvenanceService {
+    state: Arc<RwLock<ServiceState>>,
+    options: Arc<ValidationOptions>,
+}
+
+impl SharedProvenanceService {
+    pub fn new() -> Self { Self::default() }
+
+    /// Validate the submitted marks with `options` rather than the defaults.
+    pub fn with_options(mut self, options: ValidationOptions) -> Self {
+        self.options = Arc::new(options);
+        self
+    }
+
+    /// Adds `mark` to the store, unless an identical mark was already
+    /// submitted.
+    pub fn submit(&self, mark: ProvenanceMark) -> InsertOutcome {
+        let mut state = self.write();
+        if !state.seen.insert(mark.clone()) {
+            return InsertOutcome::Duplicate;
+        }
+        state.marks.push(mark);
+        state.report = None;
+        InsertOutcome::Inserted
+    }
+
+    /// The number of distinct marks submitted.
+    pub fn len(&self) -> usize { self.read().marks.len() }
+
+    pub fn is_empty(&self) -> bool { self.len() == 0 }
+
+    /// The validation report of every mark submitted so far.
+    ///
+    /// The report is [canonicalized](ValidationReport::canonicalize), so it
+    /// does not depend on the order in which concurrent submissions were
+    /// applied.
+    pub fn snapshot_report(&self) -> Arc<ValidationReport> {
+        let marks = {
+            let state = self.read();
+            if let Some(report) = &state.report {
+                return report.clone();
+            }
+            state.marks.clone()
+        };
+        let count = marks.len();
+        let report = Arc::new(
+            ValidationReport::validate_opts(marks, &self.options)
+                .canonicalize(),
+        );
+
+        // Marks are only ever added, so an unchanged count means the report
+        // is still current
+        let mut state = self.write();
+        if state.marks.len() == count {
+            state.report = Some(report.clone());
+        }
+        report
+    }
+
+    /// The report of the chain with the given ID, as it appears in the
+    /// [snapshot report](Self::snapshot_report), or `None` if no submitted
+    /// mark has that chain ID.
+    pub fn report_for_chain(&self, chain_id: &[u8]) -> Option<ChainReport> {
+        self.snapshot_report()
+            .chains()
+            .iter()
+            .find(|chain| chain.chain_id() == chain_id)
+            .cloned()
+    }
+
+    /// Whether the submitted marks form valid chains.
+    ///
+    /// Unlike [`ValidationReport::has_issues`], marks from several chains are
+    /// not an issue in themselves, since a service typically accepts marks
+    /// from many publishers.
+    pub fn verdict(&self) -> Verdict {
+        let report = self.snapshot_report();
+        if !report.missing_chains().is_empty() {
+            return Verdict::Issues;
+        }
+        if report.chains().is_empty() {
+            return Verdict::Empty;
+        }
+        let valid = report.chains().iter().all(|chain| {
+            chain.has_genesis()
+                && chain.sequences().len() == 1
+                && chain.sequences()[0].is_clean()
+                && !chain.advisories().contains(&ChainAdvisory::UnexpectedChain)
+        });
+        if valid {
+            Verdict::Valid
+        } else {
+            Verdict::Issues
+        }
+    }
+
+    // The state is updated only after every fallible step, so it is
+    // consistent even if a thread panicked while holding the lock.
+    fn read(&self) -> RwLockReadGuard<'_, ServiceState> {
+        self.state.read().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn write(&self) -> RwLockWriteGuard<'_, ServiceState> {
+        self.state.write().unwrap_or_else(PoisonError::into_inner)
+    }
+}
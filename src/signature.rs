@@ -0,0 +1,145 @@
+use dcbor::{Date, prelude::*};
+
+use crate::{Error, ProvenanceMark, ProvenanceMarkGenerator, Result};
+
+/// Label of the verifying key in a genesis mark's info map.
+pub const VERIFYING_KEY_LABEL: &str = "verifying-key";
+/// Label of the publisher's own info in an info map.
+pub const CONTENT_LABEL: &str = "content";
+/// Label of the signature over the content in a signed info map.
+pub const SIGNATURE_LABEL: &str = "signature";
+
+/// Declarations placed in a chain's genesis mark.
+///
+/// A chain may declare a verifying key in its genesis info. The info of later
+/// marks on the chain is then expected to be [`SignedInfo`], whose signature
+/// can be checked during validation with
+/// [`ValidationPolicy::with_signature_verifier`](crate::ValidationPolicy::with_signature_verifier).
+/// This lets a verifier distinguish info tampered with by a third party from
+/// info legitimately issued by the publisher. The crate does not interpret
+/// the key or signatures itself, so any signature scheme may be used.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct GenesisPolicy {
+    verifying_key: Option<Vec<u8>>,
+}
+
+impl GenesisPolicy {
+    pub fn new() -> Self { Self::default() }
+
+    pub fn with_verifying_key(mut self, key: impl AsRef<[u8]>) -> Self {
+        self.verifying_key = Some(key.as_ref().to_vec());
+        self
+    }
+
+    pub fn verifying_key(&self) -> Option<&[u8]> {
+        self.verifying_key.as_deref()
+    }
+
+    /// The genesis info map: `{"verifying-key": bytes, "content": info}`,
+    /// with each entry present only when set.
+    fn genesis_info(&self, info: Option<CBOR>) -> Option<CBOR> {
+        if self.verifying_key.is_none() {
+            return info;
+        }
+        let mut map = Map::new();
+        if let Some(key) = &self.verifying_key {
+            map.insert(VERIFYING_KEY_LABEL, CBOR::to_byte_string(key));
+        }
+        if let Some(info) = info {
+            map.insert(CONTENT_LABEL, info);
+        }
+        Some(map.into())
+    }
+}
+
+/// Info signed by the holder of a chain's declared verifying key.
+///
+/// Encoded as the map `{"content": content, "signature": bytes}`, where the
+/// signature is over the deterministic CBOR encoding of `content`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SignedInfo {
+    content: CBOR,
+    signature: Vec<u8>,
+}
+
+impl SignedInfo {
+    pub fn new(
+        content: impl CBOREncodable,
+        signature: impl AsRef<[u8]>,
+    ) -> Self {
+        Self {
+            content: content.to_cbor(),
+            signature: signature.as_ref().to_vec(),
+        }
+    }
+
+    pub fn content(&self) -> &CBOR { &self.content }
+
+    pub fn signature(&self) -> &[u8] { &self.signature }
+
+    /// The bytes covered by the signature.
+    pub fn signed_data(&self) -> Vec<u8> { self.content.to_cbor_data() }
+}
+
+impl From<SignedInfo> for CBOR {
+    fn from(value: SignedInfo) -> Self {
+        let mut map = Map::new();
+        map.insert(CONTENT_LABEL, value.content);
+        map.insert(SIGNATURE_LABEL, CBOR::to_byte_string(value.signature));
+        map.into()
+    }
+}
+
+fn map_value(info: &CBOR, label: &str) -> Option<CBOR> {
+    let CBORCase::Map(map) = info.as_case() else {
+        return None;
+    };
+    let label = CBOR::from(label);
+    map.iter()
+        .find(|(key, _)| **key == label)
+        .map(|(_, value)| value.clone())
+}
+
+impl ProvenanceMarkGenerator {
+    /// Issues the genesis mark of the chain, embedding the declarations in
+    /// `policy` in its info.
+    ///
+    /// Returns an error, leaving the generator unchanged, if it has already
+    /// issued its genesis mark or if the mark can't be issued as by
+    /// [`try_next`](Self::try_next).
+    pub fn genesis_with_policy(
+        &mut self,
+        date: Date,
+        policy: &GenesisPolicy,
+        info: Option<impl CBOREncodable>,
+    ) -> Result<ProvenanceMark> {
+        if self.next_seq() != 0 {
+            return Err(Error::NotAtGenesis { next_seq: self.next_seq() });
+        }
+        let info = policy.genesis_info(info.map(|info| info.to_cbor()));
+        self.try_next(date, info)
+    }
+}
+
+impl ProvenanceMark {
+    /// The verifying key declared by a genesis mark created with
+    /// [`ProvenanceMarkGenerator::genesis_with_policy`], if any.
+    ///
+    /// Always `None` for non-genesis marks.
+    pub fn declared_verifying_key(&self) -> Option<Vec<u8>> {
+        if !self.is_genesis() {
+            return None;
+        }
+        map_value(&self.info()?, VERIFYING_KEY_LABEL)
+            .and_then(|key| CBOR::try_into_byte_string(key).ok())
+    }
+
+    /// The mark's info decoded as [`SignedInfo`], if it has that shape.
+    pub fn signed_info(&self) -> Option<SignedInfo> {
+        let info = self.info()?;
+        let content = map_value(&info, CONTENT_LABEL)?;
+        let signature = map_value(&info, SIGNATURE_LABEL)
+            .and_then(|signature| CBOR::try_into_byte_string(signature).ok())?;
+        Some(SignedInfo { content, signature })
+    }
+}
@@ -0,0 +1,78 @@
+use std::sync::Arc;
+
+use serde::Serialize;
+
+use crate::{ChainReport, ProvenanceMark, ValidationReport};
+
+/// The sizes of a set of marks, for capacity planning, from
+/// [`ChainReport::size_statistics`] or
+/// [`ValidationReport::size_statistics`].
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct SizeStatistics {
+    mark_count: usize,
+    total_bytes: usize,
+    min_ur_len: usize,
+    mean_ur_len: f64,
+    max_ur_len: usize,
+}
+
+impl SizeStatistics {
+    fn new(marks: &[Arc<ProvenanceMark>]) -> Self {
+        let ur_lens = marks.iter().map(|mark| mark.ur_len());
+        let total_ur_len: usize = ur_lens.clone().sum();
+        Self {
+            mark_count: marks.len(),
+            total_bytes: marks.iter().map(|mark| mark.message_len()).sum(),
+            min_ur_len: ur_lens.clone().min().unwrap_or(0),
+            mean_ur_len: if marks.is_empty() {
+                0.0
+            } else {
+                total_ur_len as f64 / marks.len() as f64
+            },
+            max_ur_len: ur_lens.max().unwrap_or(0),
+        }
+    }
+
+    pub fn mark_count(&self) -> usize { self.mark_count }
+
+    /// The total length in bytes of the marks'
+    /// [messages](ProvenanceMark::message_len).
+    pub fn total_bytes(&self) -> usize { self.total_bytes }
+
+    /// The length of the shortest [UR](ProvenanceMark::ur_len), or zero if
+    /// there are no marks.
+    pub fn min_ur_len(&self) -> usize { self.min_ur_len }
+
+    /// The mean length of the marks' URs, or zero if there are no marks.
+    pub fn mean_ur_len(&self) -> f64 { self.mean_ur_len }
+
+    /// The length of the longest UR, or zero if there are no marks.
+    pub fn max_ur_len(&self) -> usize { self.max_ur_len }
+}
+
+impl std::fmt::Display for SizeStatistics {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} bytes, UR length min {} / mean {:.1} / max {}",
+            self.total_bytes,
+            self.min_ur_len,
+            self.mean_ur_len,
+            self.max_ur_len
+        )
+    }
+}
+
+impl ChainReport {
+    /// The sizes of the chain's marks.
+    pub fn size_statistics(&self) -> SizeStatistics {
+        SizeStatistics::new(self.marks())
+    }
+}
+
+impl ValidationReport {
+    /// The sizes of every mark in the report, across all chains.
+    pub fn size_statistics(&self) -> SizeStatistics {
+        SizeStatistics::new(self.marks())
+    }
+}
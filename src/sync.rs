@@ -0,0 +1,121 @@
+use std::sync::Arc;
+
+use crate::{
+    ChainReport, FlaggedMark, ProvenanceMark, SyncError, ValidationReport,
+};
+
+/// What a subscriber needs to fetch for one chain, from
+/// [`ValidationReport::sync_plan`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SyncItem {
+    chain_id: Vec<u8>,
+    known_seq: Option<u32>,
+    marks: Vec<Arc<ProvenanceMark>>,
+    error: Option<SyncError>,
+}
+
+impl SyncItem {
+    pub fn chain_id(&self) -> &[u8] { &self.chain_id }
+
+    /// The sequence number of the subscriber's last known mark of the chain,
+    /// or `None` if the subscriber has none.
+    pub fn known_seq(&self) -> Option<u32> { self.known_seq }
+
+    /// The marks to fetch, in sequence order. Empty if there is an
+    /// [`error`](Self::error).
+    pub fn marks(&self) -> &[Arc<ProvenanceMark>] { &self.marks }
+
+    /// Why the subscriber cannot be caught up, including
+    /// [`AlreadyUpToDate`](SyncError::AlreadyUpToDate) if there is nothing
+    /// to fetch.
+    pub fn error(&self) -> Option<SyncError> { self.error }
+}
+
+impl ChainReport {
+    /// The marks a subscriber who already has `known` needs to catch up on
+    /// the chain, in sequence order.
+    ///
+    /// The verified path is the chain's first sequence, whose marks each
+    /// link from the one before them. `known` must be on it, and only the
+    /// marks after `known` on it are returned: marks after a break in the
+    /// chain cannot be verified from `known`.
+    pub fn marks_after(
+        &self,
+        known: &ProvenanceMark,
+    ) -> Result<Vec<&ProvenanceMark>, SyncError> {
+        let after = self.flagged_marks_after(|mark| mark == known)?;
+        Ok(after.iter().map(FlaggedMark::mark).collect())
+    }
+
+    /// The marks after the first mark on the verified path for which
+    /// `is_known` holds.
+    fn flagged_marks_after(
+        &self,
+        is_known: impl Fn(&ProvenanceMark) -> bool,
+    ) -> Result<&[FlaggedMark], SyncError> {
+        let (verified, rest) = self
+            .sequences()
+            .split_first()
+            .ok_or(SyncError::UnknownMark)?;
+        let Some(index) =
+            verified.marks().iter().position(|m| is_known(m.mark()))
+        else {
+            let on_broken_branch = rest
+                .iter()
+                .flat_map(|sequence| sequence.marks())
+                .any(|m| is_known(m.mark()));
+            return Err(if on_broken_branch {
+                SyncError::MarkOnBrokenBranch
+            } else {
+                SyncError::UnknownMark
+            });
+        };
+        match &verified.marks()[index + 1..] {
+            [] => Err(SyncError::AlreadyUpToDate),
+            after => Ok(after),
+        }
+    }
+}
+
+impl ValidationReport {
+    /// What a subscriber needs to fetch for each chain in the report, given
+    /// the chain ID and sequence number of the last mark they have of each
+    /// chain they follow, as by [`ChainReport::marks_after`].
+    ///
+    /// There is one item per chain in the report, in report order. For a
+    /// chain with no known head, the item holds the chain's whole verified
+    /// path. Known heads of chains not in the report are ignored.
+    pub fn sync_plan(&self, known_heads: &[(Vec<u8>, u32)]) -> Vec<SyncItem> {
+        self.chains()
+            .iter()
+            .map(|chain| {
+                let known_seq = known_heads
+                    .iter()
+                    .find(|(chain_id, _)| chain_id == chain.chain_id())
+                    .map(|&(_, seq)| seq);
+                let result = match known_seq {
+                    Some(seq) => {
+                        chain.flagged_marks_after(|mark| mark.seq() == seq)
+                    }
+                    None => Ok(chain
+                        .sequences()
+                        .first()
+                        .map_or(&[][..], |verified| verified.marks())),
+                };
+                let (marks, error) = match result {
+                    Ok(after) => (
+                        after.iter().map(|m| m.shared_mark().clone()).collect(),
+                        None,
+                    ),
+                    Err(error) => (Vec::new(), Some(error)),
+                };
+                SyncItem {
+                    chain_id: chain.chain_id().to_vec(),
+                    known_seq,
+                    marks,
+                    error,
+                }
+            })
+            .collect()
+    }
+}
@@ -1,15 +1,20 @@
+#[cfg(feature = "json")]
 use base64::Engine as _;
 use bc_ur::UR;
 use dcbor::prelude::*;
+use serde::Deserialize;
+#[cfg(feature = "json")]
 use serde::{
-    Deserialize,
     de::{Deserializer, Error as DeError},
     ser::Serializer,
 };
+#[cfg(feature = "json")]
 use serde_json::json;
 
+#[cfg(feature = "json")]
 use crate::{PROVENANCE_SEED_LENGTH, ProvenanceSeed};
 
+#[cfg(feature = "json")]
 pub fn serialize_base64<S>(
     bytes: &[u8],
     serializer: S,
@@ -21,6 +26,7 @@ where
     serializer.serialize_str(&encoded)
 }
 
+#[cfg(feature = "json")]
 pub fn deserialize_base64<'de, D>(deserializer: D) -> Result<Vec<u8>, D::Error>
 where
     D: Deserializer<'de>,
@@ -31,6 +37,7 @@ where
         .map_err(DeError::custom)
 }
 
+#[cfg(feature = "json")]
 pub fn parse_seed(s: &str) -> Result<ProvenanceSeed, String> {
     let seed: ProvenanceSeed =
         serde_json::from_value(json!(s)).map_err(|e| e.to_string())?;
@@ -41,6 +48,125 @@ pub fn parse_date(s: &str) -> Result<Date, String> {
     Date::from_string(s).map_err(|e| e.to_string())
 }
 
+/// The forms accepted by [`parse_date_flexible`], for display to users.
+pub const ACCEPTED_DATE_FORMATS: &[&str] = &[
+    "YYYY-MM-DD",
+    "YYYY-MM-DD HH:MM[:SS[.fff]][Z|±HH:MM]",
+    "YYYY-MM-DDTHH:MM[:SS[.fff]][Z|±HH:MM]",
+    "Month D YYYY",
+    "Month D, YYYY",
+    "D Month YYYY",
+    "today",
+    "yesterday",
+];
+
+/// A date that [`parse_date_flexible`] could not understand
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error(
+    "unrecognized date {input:?}; accepted formats are {}",
+    ACCEPTED_DATE_FORMATS.join(", ")
+)]
+pub struct DateParseError {
+    input: String,
+}
+
+impl DateParseError {
+    /// The text that could not be parsed.
+    pub fn input(&self) -> &str { &self.input }
+
+    /// The forms that would have been accepted.
+    pub fn accepted_formats(&self) -> &'static [&'static str] {
+        ACCEPTED_DATE_FORMATS
+    }
+}
+
+/// Parse a date typed by a person, in any of the
+/// [accepted formats](ACCEPTED_DATE_FORMATS).
+///
+/// Unlike [`parse_date`], numbers need not be zero-padded, month names and
+/// their abbreviations are understood in any case, and a date may be given
+/// as `today` or `yesterday`: midnight UTC of that day relative to
+/// `reference_now`, or to the current time if it is `None`. A time with a
+/// trailing offset is converted to UTC; a time without one is taken as UTC.
+pub fn parse_date_flexible(
+    s: &str,
+    reference_now: Option<Date>,
+) -> Result<Date, DateParseError> {
+    let input = s.trim();
+    let datetime = match input.to_ascii_lowercase().as_str() {
+        "today" => relative_day(reference_now, 0),
+        "yesterday" => relative_day(reference_now, 1),
+        _ => parse_month_name_date(input)
+            .or_else(|| parse_numeric_date_time(input)),
+    };
+    datetime
+        .map(Date::from_datetime)
+        .ok_or_else(|| DateParseError { input: s.to_string() })
+}
+
+/// Midnight UTC of the day `days_ago` days before `reference_now`.
+fn relative_day(
+    reference_now: Option<Date>,
+    days_ago: i64,
+) -> Option<chrono::DateTime<chrono::Utc>> {
+    let now = reference_now.unwrap_or_else(Date::now);
+    let day = now.datetime().date_naive() - chrono::Duration::days(days_ago);
+    Some(day.and_time(chrono::NaiveTime::MIN).and_utc())
+}
+
+/// `March 5 2024`, `Mar 5, 2024`, or `5 March 2024`.
+fn parse_month_name_date(s: &str) -> Option<chrono::DateTime<chrono::Utc>> {
+    ["%B %d %Y", "%B %d, %Y", "%d %B %Y"]
+        .iter()
+        .find_map(|format| chrono::NaiveDate::parse_from_str(s, format).ok())
+        .map(|day| day.and_time(chrono::NaiveTime::MIN).and_utc())
+}
+
+/// `2024-3-5`, optionally followed by a time and a UTC offset.
+fn parse_numeric_date_time(s: &str) -> Option<chrono::DateTime<chrono::Utc>> {
+    let (date, time) = match s.split_once(['T', 't', ' ']) {
+        Some((date, time)) => (date, Some(time.trim())),
+        None => (s, None),
+    };
+    let day = chrono::NaiveDate::parse_from_str(date, "%Y-%m-%d").ok()?;
+    let Some(time) = time else {
+        return Some(day.and_time(chrono::NaiveTime::MIN).and_utc());
+    };
+
+    let (time, offset_seconds) = split_utc_offset(time)?;
+    let time = ["%H:%M:%S%.f", "%H:%M"].iter().find_map(|format| {
+        chrono::NaiveTime::parse_from_str(time.trim(), format).ok()
+    })?;
+    let local = day.and_time(time);
+    Some((local - chrono::Duration::seconds(offset_seconds)).and_utc())
+}
+
+/// Split a trailing `Z`, `±HH`, `±HHMM`, or `±HH:MM` from a time, returning
+/// the time and the offset east of UTC in seconds.
+fn split_utc_offset(time: &str) -> Option<(&str, i64)> {
+    if let Some(time) = time.strip_suffix(['Z', 'z']) {
+        return Some((time, 0));
+    }
+    let Some(index) = time.rfind(['+', '-']) else {
+        return Some((time, 0));
+    };
+    let (time, offset) = time.split_at(index);
+    let sign = if offset.starts_with('-') { -1 } else { 1 };
+    let digits: String = offset[1..].chars().filter(|c| *c != ':').collect();
+    if !matches!(digits.len(), 2 | 4)
+        || !digits.chars().all(|c| c.is_ascii_digit())
+    {
+        return None;
+    }
+    let hours: i64 = digits[..2].parse().ok()?;
+    let minutes: i64 = digits[2..].parse().unwrap_or(0);
+    if hours > 23 || minutes > 59 {
+        return None;
+    }
+    Some((time, sign * (hours * 3600 + minutes * 60)))
+}
+
+#[cfg(feature = "json")]
 pub fn serialize_cbor<S>(bytes: &[u8], serializer: S) -> Result<S::Ok, S::Error>
 where
     S: Serializer,
@@ -48,6 +174,7 @@ where
     serialize_base64(bytes, serializer)
 }
 
+#[cfg(feature = "json")]
 pub fn deserialize_cbor<'de, D>(deserializer: D) -> Result<Vec<u8>, D::Error>
 where
     D: Deserializer<'de>,
@@ -57,6 +184,7 @@ where
     Ok(data)
 }
 
+#[cfg(feature = "json")]
 pub fn serialize_block<S>(
     seed: &[u8; PROVENANCE_SEED_LENGTH],
     serializer: S,
@@ -67,6 +195,7 @@ where
     serialize_base64(seed, serializer)
 }
 
+#[cfg(feature = "json")]
 pub fn deserialize_block<'de, D>(
     deserializer: D,
 ) -> Result<[u8; PROVENANCE_SEED_LENGTH], D::Error>
@@ -111,10 +240,80 @@ where
     serializer.serialize_str(&ur.to_string())
 }
 
+/// Deserializes a UR leniently, as described for [`parse_ur_lenient`].
 pub fn deserialize_ur<'de, D>(deserializer: D) -> Result<UR, D::Error>
 where
     D: serde::Deserializer<'de>,
 {
     let s = String::deserialize(deserializer)?;
-    UR::from_ur_string(s).map_err(serde::de::Error::custom)
+    parse_ur_lenient(&s).map_err(serde::de::Error::custom)
+}
+
+/// Parses a UR as found in the wild, where it may be uppercase as in a QR
+/// code, padded with whitespace, or missing its `ur:` scheme.
+///
+/// The string is tried as given, then trimmed, then lowercased, then with the
+/// `ur:` scheme added, skipping any attempt that would not change it. If none
+/// parses, the error names every attempt made and the last parse error.
+pub fn parse_ur_lenient(s: &str) -> Result<UR, String> {
+    let trimmed = s.trim();
+    let lowercased = trimmed.to_lowercase();
+    let with_scheme = if lowercased.starts_with("ur:") {
+        lowercased.clone()
+    } else {
+        format!("ur:{}", lowercased)
+    };
+    let mut attempts = vec![("as given", s.to_string())];
+    for (name, candidate) in [
+        ("trimmed", trimmed.to_string()),
+        ("lowercased", lowercased),
+        ("with ur: scheme", with_scheme),
+    ] {
+        if attempts.iter().all(|(_, tried)| *tried != candidate) {
+            attempts.push((name, candidate));
+        }
+    }
+
+    let mut last_error = None;
+    for (_, candidate) in &attempts {
+        match UR::from_ur_string(candidate) {
+            Ok(ur) => return Ok(ur),
+            Err(err) => last_error = Some(err),
+        }
+    }
+    let tried = attempts
+        .iter()
+        .map(|(name, _)| *name)
+        .collect::<Vec<_>>()
+        .join(", ");
+    Err(format!(
+        "invalid UR {:?} (tried {}): {}",
+        s,
+        tried,
+        last_error.unwrap()
+    ))
+}
+
+pub fn serialize_optional_iso8601<S>(
+    date: &Option<Date>,
+    serializer: S,
+) -> Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+{
+    match date {
+        Some(date) => serialize_iso8601(date, serializer),
+        None => serializer.serialize_none(),
+    }
+}
+
+pub fn deserialize_optional_iso8601<'de, D>(
+    deserializer: D,
+) -> Result<Option<Date>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    Option::<String>::deserialize(deserializer)?
+        .map(|s| Date::from_string(s).map_err(serde::de::Error::custom))
+        .transpose()
 }
@@ -1,77 +1,926 @@
-use std::collections::{HashMap, HashSet};
-
+use std::{
+    collections::{HashMap, HashSet},
+    io::Write,
+    sync::{Arc, OnceLock},
+};
+
+use bc_ur::UREncodable;
+use chrono::Duration;
+use dcbor::Date;
 use serde::Serialize;
 
-use crate::ProvenanceMark;
+#[cfg(feature = "json")]
+use crate::SizeStatistics;
+use crate::{
+    AppliedOptions, ChainGroup, Error, FingerprintIndex, HealthDetails,
+    IssueEvidence, ProvenanceMark, ProvenanceMarkResolution, Result,
+    SelfCheckFailure, ValidationOptions,
+    report_digest::{chain_digest, report_digest, sequence_digest},
+};
+
+/// The decoded fields of a mark, for consumers of the JSON report that cannot
+/// decode UR strings themselves. See
+/// [`FormatOptions::with_inline_mark_details`].
+#[derive(Serialize)]
+struct MarkDetails {
+    ur: String,
+    seq: u32,
+    /// The date to the mark's [precision](ProvenanceMark::date_display).
+    date: String,
+    #[serde(with = "hex")]
+    chain_id: Vec<u8>,
+    identifier: String,
+    is_genesis: bool,
+    has_info: bool,
+}
 
-// Helper module for serializing ProvenanceMark as UR string
-mod provenance_mark_as_ur {
-    use bc_ur::UREncodable;
-    use serde::Serializer;
+impl MarkDetails {
+    fn new(mark: &ProvenanceMark) -> Self {
+        Self {
+            ur: mark.ur_string(),
+            seq: mark.seq(),
+            date: mark.date_display(),
+            chain_id: mark.chain_id().to_vec(),
+            identifier: mark.id_bytewords(4, false),
+            is_genesis: mark.is_genesis(),
+            has_info: mark.info().is_some(),
+        }
+    }
+}
 
-    use crate::ProvenanceMark;
+/// A mark as referred to in a JSON report: its UR string, or its
+/// [`MarkDetails`] with [`FormatOptions::with_inline_mark_details`].
+struct MarkRef<'a> {
+    mark: &'a ProvenanceMark,
+    inline: bool,
+}
+
+impl<'a> MarkRef<'a> {
+    fn new(mark: &'a ProvenanceMark, options: &FormatOptions) -> Self {
+        Self { mark, inline: options.inline_mark_details() }
+    }
+
+    fn list(
+        marks: &'a [Arc<ProvenanceMark>],
+        options: &FormatOptions,
+    ) -> Vec<Self> {
+        marks.iter().map(|mark| Self::new(mark, options)).collect()
+    }
+}
+
+impl Serialize for MarkRef<'_> {
+    fn serialize<S>(
+        &self,
+        serializer: S,
+    ) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        if self.inline {
+            MarkDetails::new(self.mark).serialize(serializer)
+        } else {
+            serializer.serialize_str(&self.mark.ur_string())
+        }
+    }
+}
+
+/// A [`ValidationReport`] as written to JSON with the given
+/// [`FormatOptions`], with its optional statistics after the report's own
+/// fields.
+#[derive(Serialize)]
+struct ReportJson<'a> {
+    marks: Vec<MarkRef<'a>>,
+    #[serde(skip_serializing_if = "is_zero")]
+    duplicates_removed: usize,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    duplicate_marks: Vec<MarkRef<'a>>,
+    chains: Vec<ChainJson<'a>>,
+    #[serde(skip_serializing_if = "<[_]>::is_empty")]
+    groups: &'a [ChainGroup],
+    #[serde(
+        skip_serializing_if = "<[_]>::is_empty",
+        serialize_with = "hex_list::serialize"
+    )]
+    missing_chains: &'a [Vec<u8>],
+    #[serde(skip_serializing_if = "<[_]>::is_empty")]
+    notes: &'a [ReportNote],
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    suppressed: Vec<SuppressedJson<'a>>,
+    #[serde(skip_serializing_if = "AppliedOptions::is_default")]
+    options: &'a AppliedOptions,
+    #[serde(with = "hex")]
+    digest: [u8; 32],
+    #[cfg(feature = "json")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    statistics: Option<ReportStatistics<'a>>,
+}
+
+impl<'a> ReportJson<'a> {
+    fn new(report: &'a ValidationReport, options: &FormatOptions) -> Self {
+        Self {
+            marks: MarkRef::list(&report.marks, options),
+            duplicates_removed: report.duplicates_removed,
+            duplicate_marks: MarkRef::list(&report.duplicate_marks, options),
+            chains: report
+                .chains
+                .iter()
+                .map(|chain| ChainJson::new(chain, options))
+                .collect(),
+            groups: &report.groups,
+            missing_chains: &report.missing_chains,
+            notes: &report.notes,
+            suppressed: report
+                .suppressed
+                .iter()
+                .map(|suppressed| SuppressedJson::new(suppressed, options))
+                .collect(),
+            options: &report.options,
+            digest: report.digest,
+            #[cfg(feature = "json")]
+            statistics: options
+                .statistics()
+                .then(|| ReportStatistics::new(report, options)),
+        }
+    }
+}
+
+/// A [`ChainReport`] as written to JSON, with its health details after the
+/// chain's own fields if [`FormatOptions::with_health`] is set.
+#[derive(Serialize)]
+struct ChainJson<'a> {
+    #[serde(with = "hex")]
+    chain_id: &'a [u8],
+    #[serde(with = "hex")]
+    chain_fingerprint: [u8; 32],
+    has_genesis: bool,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    declares_key: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    label: Option<&'a str>,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    anchored: bool,
+    marks: Vec<MarkRef<'a>>,
+    sequences: Vec<SequenceJson<'a>>,
+    #[serde(skip_serializing_if = "<[_]>::is_empty")]
+    advisories: &'a [ChainAdvisory],
+    #[serde(with = "hex")]
+    digest: [u8; 32],
+    #[serde(skip_serializing_if = "Option::is_none")]
+    health: Option<HealthDetails>,
+}
+
+impl<'a> ChainJson<'a> {
+    fn new(chain: &'a ChainReport, options: &FormatOptions) -> Self {
+        Self {
+            chain_id: &chain.chain_id,
+            chain_fingerprint: chain.chain_fingerprint,
+            has_genesis: chain.has_genesis,
+            declares_key: chain.declares_key,
+            label: chain.label.as_deref(),
+            anchored: chain.anchored,
+            marks: MarkRef::list(&chain.marks, options),
+            sequences: chain
+                .sequences
+                .iter()
+                .map(|sequence| SequenceJson::new(sequence, options))
+                .collect(),
+            advisories: &chain.advisories,
+            digest: chain.digest,
+            health: options.health().then(|| match options.as_of() {
+                Some(as_of) => {
+                    chain.health_details_as_of(&as_of, options.max_age())
+                }
+                None => chain.health_details(),
+            }),
+        }
+    }
+}
+
+/// A [`SequenceReport`] as written to JSON.
+#[derive(Serialize)]
+struct SequenceJson<'a> {
+    start_seq: u32,
+    end_seq: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    preceded_by: Option<&'a str>,
+    marks: Vec<FlaggedMarkJson<'a>>,
+    #[serde(with = "hex")]
+    digest: [u8; 32],
+}
+
+impl<'a> SequenceJson<'a> {
+    fn new(sequence: &'a SequenceReport, options: &FormatOptions) -> Self {
+        Self {
+            start_seq: sequence.start_seq,
+            end_seq: sequence.end_seq,
+            preceded_by: sequence.preceded_by.as_deref(),
+            marks: sequence
+                .marks
+                .iter()
+                .map(|flagged| FlaggedMarkJson::new(flagged, options))
+                .collect(),
+            digest: sequence.digest,
+        }
+    }
+}
+
+/// A [`FlaggedMark`] as written to JSON.
+#[derive(Serialize)]
+struct FlaggedMarkJson<'a> {
+    mark: MarkRef<'a>,
+    issues: &'a [ValidationIssue],
+    #[serde(skip_serializing_if = "<[_]>::is_empty")]
+    evidence: &'a [IssueEvidence],
+}
+
+impl<'a> FlaggedMarkJson<'a> {
+    fn new(flagged: &'a FlaggedMark, options: &FormatOptions) -> Self {
+        Self {
+            mark: MarkRef::new(&flagged.mark, options),
+            issues: &flagged.issues,
+            evidence: &flagged.evidence,
+        }
+    }
+}
+
+/// A [`SuppressedIssue`] as written to JSON.
+#[derive(Serialize)]
+struct SuppressedJson<'a> {
+    mark: MarkRef<'a>,
+    issue: &'a ValidationIssue,
+}
+
+impl<'a> SuppressedJson<'a> {
+    fn new(suppressed: &'a SuppressedIssue, options: &FormatOptions) -> Self {
+        Self {
+            mark: MarkRef::new(&suppressed.mark, options),
+            issue: &suppressed.issue,
+        }
+    }
+}
+
+/// Serializes a report type through its JSON form with the default
+/// [`FormatOptions`].
+macro_rules! impl_serialize_as_json {
+    ($type:ty, $json:expr) => {
+        impl Serialize for $type {
+            fn serialize<S>(
+                &self,
+                serializer: S,
+            ) -> std::result::Result<S::Ok, S::Error>
+            where
+                S: serde::Serializer,
+            {
+                $json(self, &FormatOptions::default()).serialize(serializer)
+            }
+        }
+    };
+}
+
+impl_serialize_as_json!(ValidationReport, ReportJson::new);
+impl_serialize_as_json!(ChainReport, ChainJson::new);
+impl_serialize_as_json!(SequenceReport, SequenceJson::new);
+impl_serialize_as_json!(FlaggedMark, FlaggedMarkJson::new);
+impl_serialize_as_json!(SuppressedIssue, SuppressedJson::new);
+
+/// See [`FormatOptions::with_statistics`].
+#[cfg(feature = "json")]
+#[derive(Serialize)]
+struct ReportStatistics<'a> {
+    identifier_collisions: Vec<IdentifierCollision<'a>>,
+    minimum_unique_identifier_len: usize,
+    total_bytes: usize,
+    chain_sizes: Vec<ChainSizes>,
+}
+
+#[cfg(feature = "json")]
+#[derive(Serialize)]
+struct ChainSizes {
+    chain_id: String,
+    #[serde(flatten)]
+    sizes: SizeStatistics,
+}
+
+#[cfg(feature = "json")]
+#[derive(Serialize)]
+struct IdentifierCollision<'a> {
+    identifier: String,
+    marks: Vec<MarkRef<'a>>,
+}
+
+#[cfg(feature = "json")]
+impl<'a> ReportStatistics<'a> {
+    fn new(report: &'a ValidationReport, options: &FormatOptions) -> Self {
+        Self {
+            identifier_collisions: report
+                .identifier_groups()
+                .into_iter()
+                .filter(|(_, marks)| marks.len() > 1)
+                .map(|(identifier, marks)| IdentifierCollision {
+                    identifier,
+                    marks: marks
+                        .into_iter()
+                        .map(|mark| MarkRef::new(mark, options))
+                        .collect(),
+                })
+                .collect(),
+            minimum_unique_identifier_len: report
+                .minimum_unique_identifier_len(),
+            total_bytes: report.size_statistics().total_bytes(),
+            chain_sizes: report
+                .chains()
+                .iter()
+                .map(|chain| ChainSizes {
+                    chain_id: chain.chain_id_hex(),
+                    sizes: chain.size_statistics(),
+                })
+                .collect(),
+        }
+    }
+}
+
+#[cfg(feature = "json")]
+fn write_json_document(
+    w: impl Write,
+    pretty: bool,
+    document: &impl Serialize,
+) -> serde_json::Result<()> {
+    if pretty {
+        serde_json::to_writer_pretty(w, document)
+    } else {
+        serde_json::to_writer(w, document)
+    }
+}
+
+// Helper module for serializing dcbor::Date as ISO8601 string
+mod date_as_iso8601 {
+    use serde::Serializer;
 
     pub fn serialize<S>(
-        mark: &ProvenanceMark,
+        date: &dcbor::Date,
         serializer: S,
     ) -> Result<S::Ok, S::Error>
     where
         S: Serializer,
     {
-        serializer.serialize_str(&mark.ur_string())
+        serializer.serialize_str(&date.to_string())
     }
 }
 
-// Helper module for serializing Vec<ProvenanceMark> as Vec<UR string>
-mod provenance_marks_as_ur {
-    use bc_ur::UREncodable;
+// Helper module for serializing ProvenanceMarkResolution by name
+mod resolution_as_name {
     use serde::Serializer;
 
-    use crate::ProvenanceMark;
+    use crate::ProvenanceMarkResolution;
 
     pub fn serialize<S>(
-        marks: &[ProvenanceMark],
+        res: &ProvenanceMarkResolution,
         serializer: S,
     ) -> Result<S::Ok, S::Error>
     where
         S: Serializer,
     {
-        use serde::ser::SerializeSeq;
-        let mut seq = serializer.serialize_seq(Some(marks.len()))?;
-        for mark in marks {
-            seq.serialize_element(&mark.ur_string())?;
-        }
-        seq.end()
+        serializer.serialize_str(&res.to_string())
     }
 }
 
-// Helper module for serializing dcbor::Date as ISO8601 string
-mod date_as_iso8601 {
+// Helper module for serializing a list of byte strings as hex strings
+pub(crate) mod hex_list {
     use serde::Serializer;
 
     pub fn serialize<S>(
-        date: &dcbor::Date,
+        values: &[Vec<u8>],
         serializer: S,
     ) -> Result<S::Ok, S::Error>
     where
         S: Serializer,
     {
-        serializer.serialize_str(&date.to_string())
+        serializer.collect_seq(values.iter().map(hex::encode))
     }
 }
 
+fn is_zero(n: &usize) -> bool { *n == 0 }
+
 /// Format for validation report output
+///
+/// Formats are named `text`, `json-compact`, and `json-pretty`, as accepted
+/// by [`FromStr`](std::str::FromStr) and written by
+/// [`Display`](std::fmt::Display). Parsing ignores case and also accepts
+/// `json` for `json-pretty`. The JSON formats require the `json` feature.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "clap", derive(clap::ValueEnum))]
 pub enum ValidationReportFormat {
     /// Human-readable text format
     #[default]
     Text,
     /// Compact JSON format (no whitespace)
+    #[cfg(feature = "json")]
     JsonCompact,
     /// Pretty-printed JSON format (with indentation)
+    #[cfg(feature = "json")]
+    #[cfg_attr(feature = "clap", value(alias = "json"))]
     JsonPretty,
 }
 
+#[cfg(feature = "json")]
+const REPORT_FORMATS: &[ValidationReportFormat] = &[
+    ValidationReportFormat::Text,
+    ValidationReportFormat::JsonCompact,
+    ValidationReportFormat::JsonPretty,
+];
+#[cfg(not(feature = "json"))]
+const REPORT_FORMATS: &[ValidationReportFormat] =
+    &[ValidationReportFormat::Text];
+
+impl ValidationReportFormat {
+    /// Every format, in the order they should be listed in help text.
+    pub fn all() -> &'static [Self] { REPORT_FORMATS }
+
+    /// The format's name, as written by `Display`.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Text => "text",
+            #[cfg(feature = "json")]
+            Self::JsonCompact => "json-compact",
+            #[cfg(feature = "json")]
+            Self::JsonPretty => "json-pretty",
+        }
+    }
+}
+
+impl std::fmt::Display for ValidationReportFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl std::str::FromStr for ValidationReportFormat {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        // `json` is an alias of `json-pretty`
+        let name = if s.eq_ignore_ascii_case("json") {
+            "json-pretty"
+        } else {
+            s
+        };
+        Self::all()
+            .iter()
+            .find(|format| name.eq_ignore_ascii_case(format.name()))
+            .copied()
+            .ok_or_else(|| Error::UnknownReportFormat {
+                format: s.to_string(),
+                valid: Self::all().iter().map(Self::name).collect(),
+            })
+    }
+}
+
+/// Options controlling how a validation report is formatted as text
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FormatOptions {
+    as_of: Option<Date>,
+    max_age: Duration,
+    cadence: bool,
+    max_marks_per_chain: Option<usize>,
+    collapse_clean_runs: bool,
+    inline_mark_details: bool,
+    always_emit: bool,
+    show_info: bool,
+    redact_info: bool,
+    statistics: bool,
+    health: bool,
+    aligned: bool,
+    color: bool,
+}
+
+impl Default for FormatOptions {
+    fn default() -> Self {
+        Self {
+            as_of: None,
+            max_age: Duration::days(1),
+            cadence: false,
+            max_marks_per_chain: None,
+            collapse_clean_runs: false,
+            inline_mark_details: false,
+            always_emit: false,
+            show_info: false,
+            redact_info: false,
+            statistics: false,
+            health: false,
+            aligned: false,
+            color: false,
+        }
+    }
+}
+
+impl FormatOptions {
+    pub fn new() -> Self { Self::default() }
+
+    /// Annotate chains whose head mark is older than `max_age` as of the given
+    /// date.
+    pub fn with_as_of(mut self, as_of: Date) -> Self {
+        self.as_of = Some(as_of);
+        self
+    }
+
+    /// The age beyond which a chain is annotated as stale. Defaults to one
+    /// day.
+    pub fn with_max_age(mut self, max_age: Duration) -> Self {
+        self.max_age = max_age;
+        self
+    }
+
+    /// Show each chain's [`CadenceSummary`] beneath its heading.
+    pub fn with_cadence(mut self, cadence: bool) -> Self {
+        self.cadence = cadence;
+        self
+    }
+
+    /// Show at most this many mark lines per chain, summarizing the rest in a
+    /// final line. A collapsed run counts as one line.
+    pub fn with_max_marks_per_chain(mut self, max: usize) -> Self {
+        self.max_marks_per_chain = Some(max);
+        self
+    }
+
+    /// Render each run of consecutive issue-free marks as a single line, such
+    /// as `2–147: 146 verified marks`. The first and last mark of each
+    /// sequence and any mark with issues are always shown.
+    pub fn with_collapse_clean_runs(mut self, collapse: bool) -> Self {
+        self.collapse_clean_runs = collapse;
+        self
+    }
+
+    /// In JSON output, serialize each mark as an object giving its UR along
+    /// with its decoded sequence number, date, chain ID, identifier, and
+    /// whether it is a genesis mark or has info, rather than as a bare UR
+    /// string.
+    pub fn with_inline_mark_details(mut self, inline: bool) -> Self {
+        self.inline_mark_details = inline;
+        self
+    }
+
+    /// Write the text report even for a single perfect chain, which is
+    /// otherwise written as nothing, and include a `Status:` line in the
+    /// summary.
+    pub fn with_always_emit(mut self, always_emit: bool) -> Self {
+        self.always_emit = always_emit;
+        self
+    }
+
+    /// Show each mark's info on its line in text reports, truncated as by
+    /// [`ProvenanceMark::info_summary`], or only its size if
+    /// [`with_redact_info`](Self::with_redact_info) is set.
+    pub fn with_show_info(mut self, show_info: bool) -> Self {
+        self.show_info = show_info;
+        self
+    }
+
+    /// With [`with_show_info`](Self::with_show_info), show only the size of
+    /// each mark's info, as `<info: N bytes>`, for deployments where info may
+    /// be private.
+    pub fn with_redact_info(mut self, redact_info: bool) -> Self {
+        self.redact_info = redact_info;
+        self
+    }
+
+    /// In JSON output, add a `statistics` object giving the report's
+    /// [identifier collisions](ValidationReport::identifier_collisions),
+    /// [minimum unique identifier
+    /// length](ValidationReport::minimum_unique_identifier_len), and [mark
+    /// sizes](ValidationReport::size_statistics) overall and per chain. In
+    /// text output, add the total size of the marks and a line giving each
+    /// chain's sizes.
+    pub fn with_statistics(mut self, statistics: bool) -> Self {
+        self.statistics = statistics;
+        self
+    }
+
+    /// In JSON output, add a `health` object to each chain giving its
+    /// [health details](ChainReport::health_details), including staleness
+    /// if [`with_as_of`](Self::with_as_of) is set.
+    pub fn with_health(mut self, health: bool) -> Self {
+        self.health = health;
+        self
+    }
+
+    /// In text output, right-align the sequence numbers of each chain's mark
+    /// lines, and the ranges of its collapsed runs, to the widest of them, so
+    /// that identifiers and annotations line up.
+    pub fn with_aligned(mut self, aligned: bool) -> Self {
+        self.aligned = aligned;
+        self
+    }
+
+    /// In text output, color issue annotations red, genesis annotations
+    /// green, and advisories yellow with ANSI escape codes. The text is
+    /// otherwise unchanged, so it is identical to uncolored text with the
+    /// escape codes removed.
+    pub fn with_color(mut self, color: bool) -> Self {
+        self.color = color;
+        self
+    }
+
+    pub fn as_of(&self) -> Option<Date> { self.as_of }
+    pub fn max_age(&self) -> Duration { self.max_age }
+    pub fn cadence(&self) -> bool { self.cadence }
+    pub fn max_marks_per_chain(&self) -> Option<usize> {
+        self.max_marks_per_chain
+    }
+    pub fn collapse_clean_runs(&self) -> bool { self.collapse_clean_runs }
+    pub fn inline_mark_details(&self) -> bool { self.inline_mark_details }
+    pub fn always_emit(&self) -> bool { self.always_emit }
+    pub fn show_info(&self) -> bool { self.show_info }
+    pub fn redact_info(&self) -> bool { self.redact_info }
+    pub fn statistics(&self) -> bool { self.statistics }
+    pub fn health(&self) -> bool { self.health }
+    pub fn aligned(&self) -> bool { self.aligned }
+    pub fn color(&self) -> bool { self.color }
+}
+
+const ANSI_RED: &str = "\x1b[31m";
+const ANSI_GREEN: &str = "\x1b[32m";
+const ANSI_YELLOW: &str = "\x1b[33m";
+const ANSI_RESET: &str = "\x1b[0m";
+
+/// `text` wrapped in the given ANSI color if `color` is on.
+fn paint(text: String, ansi: &str, color: bool) -> String {
+    if color {
+        format!("{}{}{}", ansi, text, ANSI_RESET)
+    } else {
+        text
+    }
+}
+
+/// A line listing marks in a chain's text report. A mark that starts a
+/// sequence after a break carries the short ID of the mark before the break.
+enum MarkLine<'a> {
+    Mark(&'a FlaggedMark, Option<&'a str>),
+    Run { first: u32, last: u32, count: usize },
+}
+
+impl MarkLine<'_> {
+    fn mark_count(&self) -> usize {
+        match self {
+            MarkLine::Mark(..) => 1,
+            MarkLine::Run { count, .. } => *count,
+        }
+    }
+
+    /// The text before the line's colon: a sequence number or a range.
+    fn label(&self) -> String {
+        match self {
+            MarkLine::Mark(flagged_mark, _) => {
+                flagged_mark.mark().seq().to_string()
+            }
+            MarkLine::Run { first, last, .. } => format!("{}–{}", first, last),
+        }
+    }
+}
+
+/// A signature verification callback, called with the verifying key, the
+/// signed data, and the signature.
+pub type SignatureVerifier = dyn Fn(&[u8], &[u8], &[u8]) -> bool + Send + Sync;
+
+/// Policy controlling optional checks performed during validation
+#[derive(Clone)]
+pub struct ValidationPolicy {
+    reference_time: Option<Date>,
+    future_tolerance: Duration,
+    low_collision_threshold: usize,
+    medium_collision_threshold: usize,
+    signature_verifier: Option<Arc<SignatureVerifier>>,
+    self_check: bool,
+    date_encoding_check: bool,
+    include_evidence: bool,
+}
+
+impl std::fmt::Debug for ValidationPolicy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ValidationPolicy")
+            .field("reference_time", &self.reference_time)
+            .field("future_tolerance", &self.future_tolerance)
+            .field("low_collision_threshold", &self.low_collision_threshold)
+            .field(
+                "medium_collision_threshold",
+                &self.medium_collision_threshold,
+            )
+            .field("signature_verifier", &self.signature_verifier.is_some())
+            .field("self_check", &self.self_check)
+            .field("date_encoding_check", &self.date_encoding_check)
+            .field("include_evidence", &self.include_evidence)
+            .finish()
+    }
+}
+
+impl Default for ValidationPolicy {
+    fn default() -> Self {
+        Self {
+            reference_time: None,
+            future_tolerance: Duration::minutes(5),
+            low_collision_threshold: Self::DEFAULT_LOW_COLLISION_THRESHOLD,
+            medium_collision_threshold:
+                Self::DEFAULT_MEDIUM_COLLISION_THRESHOLD,
+            signature_verifier: None,
+            self_check: false,
+            date_encoding_check: false,
+            include_evidence: false,
+        }
+    }
+}
+
+impl ValidationPolicy {
+    /// The number of 4-byte link hashes at which the chance of any two
+    /// colliding reaches about 1%.
+    pub const DEFAULT_LOW_COLLISION_THRESHOLD: usize = 9_300;
+
+    /// The number of 8-byte link hashes at which the chance of any two
+    /// colliding reaches about 1%.
+    pub const DEFAULT_MEDIUM_COLLISION_THRESHOLD: usize = 607_000_000;
+
+    pub fn new() -> Self { Self::default() }
+
+    /// Flag marks dated later than the given time (usually "now") as
+    /// [`ValidationIssue::FutureDated`].
+    pub fn with_reference_time(mut self, reference_time: Date) -> Self {
+        self.reference_time = Some(reference_time);
+        self
+    }
+
+    /// How far past the reference time a mark may be dated before it is
+    /// flagged, allowing for clock skew. Defaults to five minutes.
+    pub fn with_future_tolerance(mut self, future_tolerance: Duration) -> Self {
+        self.future_tolerance = future_tolerance;
+        self
+    }
+
+    /// The number of marks a Low resolution chain may contain before it is
+    /// given a [`ChainAdvisory::CollisionRiskElevated`] advisory.
+    pub fn with_low_collision_threshold(mut self, threshold: usize) -> Self {
+        self.low_collision_threshold = threshold;
+        self
+    }
+
+    /// The number of marks a Medium resolution chain may contain before it is
+    /// given a [`ChainAdvisory::CollisionRiskElevated`] advisory.
+    pub fn with_medium_collision_threshold(mut self, threshold: usize) -> Self {
+        self.medium_collision_threshold = threshold;
+        self
+    }
+
+    /// Check signed info on chains whose genesis mark declares a verifying
+    /// key (see [`GenesisPolicy`](crate::GenesisPolicy)).
+    ///
+    /// The verifier is called with the verifying key, the signed data, and the
+    /// signature, and returns whether the signature is valid. Marks after
+    /// genesis whose info is unsigned are flagged with
+    /// [`ValidationIssue::MissingSignature`], and those whose signature fails
+    /// with [`ValidationIssue::InvalidSignature`]. Marks without info are not
+    /// checked.
+    pub fn with_signature_verifier(
+        mut self,
+        verifier: impl Fn(&[u8], &[u8], &[u8]) -> bool + Send + Sync + 'static,
+    ) -> Self {
+        self.signature_verifier = Some(Arc::new(verifier));
+        self
+    }
+
+    /// Run [`ProvenanceMark::self_check`] on every mark, flagging those that
+    /// fail with [`ValidationIssue::SelfCheckFailed`]. Off by default, since
+    /// marks built by this crate always pass.
+    pub fn with_self_check(mut self, self_check: bool) -> Self {
+        self.self_check = self_check;
+        self
+    }
+
+    /// Decode each mark's date bytes and encode the date again at the mark's
+    /// resolution, flagging marks whose stored bytes differ with
+    /// [`ValidationIssue::DateEncodingMismatch`]. Such bytes come from a
+    /// nonconforming producer, and the link hash cannot catch them since it
+    /// commits to whatever bytes were used. Off by default.
+    pub fn with_date_encoding_check(mut self, check: bool) -> Self {
+        self.date_encoding_check = check;
+        self
+    }
+
+    /// Record what each linkage check compared when it flagged a mark, as
+    /// [`FlaggedMark::evidence`], for audits. The evidence is written to JSON
+    /// reports and as `why:` lines in text reports. Off by default.
+    pub fn with_include_evidence(mut self, include: bool) -> Self {
+        self.include_evidence = include;
+        self
+    }
+
+    pub fn reference_time(&self) -> Option<Date> { self.reference_time }
+    pub fn self_check(&self) -> bool { self.self_check }
+    pub fn date_encoding_check(&self) -> bool { self.date_encoding_check }
+    pub fn include_evidence(&self) -> bool { self.include_evidence }
+    pub fn future_tolerance(&self) -> Duration { self.future_tolerance }
+    pub fn has_signature_verifier(&self) -> bool {
+        self.signature_verifier.is_some()
+    }
+
+    /// The collision advisory threshold for the given resolution, or `None`
+    /// for resolutions whose link hashes are long enough not to need one.
+    pub fn collision_threshold(
+        &self,
+        res: ProvenanceMarkResolution,
+    ) -> Option<usize> {
+        match res {
+            ProvenanceMarkResolution::Low => Some(self.low_collision_threshold),
+            ProvenanceMarkResolution::Medium => {
+                Some(self.medium_collision_threshold)
+            }
+            ProvenanceMarkResolution::Quartile
+            | ProvenanceMarkResolution::High => None,
+        }
+    }
+
+    fn signature_issue(
+        &self,
+        verifying_key: &[u8],
+        mark: &ProvenanceMark,
+    ) -> Option<ValidationIssue> {
+        let verifier = self.signature_verifier.as_ref()?;
+        if mark.is_genesis() || mark.info().is_none() {
+            return None;
+        }
+        match mark.signed_info() {
+            None => Some(ValidationIssue::MissingSignature),
+            Some(signed) => (!verifier(
+                verifying_key,
+                &signed.signed_data(),
+                signed.signature(),
+            ))
+            .then_some(ValidationIssue::InvalidSignature),
+        }
+    }
+
+    fn chain_advisories(
+        &self,
+        chain_marks: &[Arc<ProvenanceMark>],
+    ) -> Vec<ChainAdvisory> {
+        let count = chain_marks.len();
+        chain_marks
+            .first()
+            .map(|mark| mark.res())
+            .and_then(|res| {
+                self.collision_threshold(res)
+                    .filter(|threshold| count > *threshold)
+                    .map(|threshold| ChainAdvisory::CollisionRiskElevated {
+                        resolution: res,
+                        count,
+                        threshold,
+                    })
+            })
+            .into_iter()
+            .collect()
+    }
+
+    fn self_check_issue(
+        &self,
+        mark: &ProvenanceMark,
+    ) -> Option<ValidationIssue> {
+        if !self.self_check {
+            return None;
+        }
+        mark.self_check()
+            .err()
+            .map(|failures| ValidationIssue::SelfCheckFailed { failures })
+    }
+
+    fn date_encoding_issue(
+        &self,
+        mark: &ProvenanceMark,
+    ) -> Option<ValidationIssue> {
+        if !self.date_encoding_check {
+            return None;
+        }
+        let res = mark.res();
+        let reencoded = res
+            .deserialize_date(mark.date_bytes())
+            .and_then(|date| res.serialize_date(date))
+            .unwrap_or_default();
+        (reencoded != mark.date_bytes()).then(|| {
+            ValidationIssue::DateEncodingMismatch {
+                stored: mark.date_bytes().to_vec(),
+                reencoded,
+            }
+        })
+    }
+
+    fn future_dated_issue(
+        &self,
+        mark: &ProvenanceMark,
+    ) -> Option<ValidationIssue> {
+        let reference = self.reference_time?;
+        let ahead = mark.date().datetime() - reference.datetime();
+        (ahead > self.future_tolerance).then_some(
+            ValidationIssue::FutureDated { date: mark.date(), reference },
+        )
+    }
+}
+
 /// Issue flagged during validation
 #[derive(Debug, Clone, PartialEq, Eq, Serialize)]
 #[serde(tag = "type", content = "data")]
@@ -87,6 +936,10 @@ pub enum ValidationIssue {
     KeyMismatch,
     /// Sequence number gap
     SequenceGap { expected: u32, actual: u32 },
+    /// Two different marks with the same sequence number, as in a fork
+    DuplicateSeq { seq: u32 },
+    /// Sequence number lower than that of the preceding mark
+    SequenceRegression { previous: u32, next: u32 },
     /// Date ordering violation
     DateOrdering {
         #[serde(serialize_with = "date_as_iso8601::serialize")]
@@ -98,6 +951,100 @@ pub enum ValidationIssue {
     NonGenesisAtZero,
     /// Invalid genesis key
     InvalidGenesisKey,
+    /// Consecutive marks at different resolutions
+    ResolutionMismatch {
+        #[serde(serialize_with = "resolution_as_name::serialize")]
+        previous: ProvenanceMarkResolution,
+        #[serde(serialize_with = "resolution_as_name::serialize")]
+        next: ProvenanceMarkResolution,
+    },
+    /// Consecutive marks from different chains, identified by the first 8 hex
+    /// digits of their chain IDs. Validation bins marks by chain first, so
+    /// this only comes from calling [`ProvenanceMark::precedes_opt`]
+    /// directly.
+    ChainMismatch {
+        previous_chain: String,
+        next_chain: String,
+    },
+    /// Mark dated later than the validation reference time. This is a warning
+    /// only and does not break sequence continuity.
+    FutureDated {
+        #[serde(serialize_with = "date_as_iso8601::serialize")]
+        date: dcbor::Date,
+        #[serde(serialize_with = "date_as_iso8601::serialize")]
+        reference: dcbor::Date,
+    },
+    /// Unsigned info on a chain that declares a verifying key
+    MissingSignature,
+    /// Info signature rejected by the signature verifier
+    InvalidSignature,
+    /// The mark's key also appears in another mark, identified by its
+    /// [ID](ProvenanceMark::id_hex). This is a warning only and does not break
+    /// sequence continuity.
+    ReusedKey { other_mark: String },
+    /// The mark is sequenced after its chain's
+    /// [termination mark](ProvenanceMark::is_termination), at `termination`,
+    /// so it cannot have been issued legitimately. This does not break
+    /// sequence continuity.
+    MarkAfterTermination { termination: u32 },
+    /// The mark fails [`ProvenanceMark::self_check`], reported only when
+    /// [`ValidationPolicy::with_self_check`] is on. This does not break
+    /// sequence continuity.
+    SelfCheckFailed { failures: Vec<SelfCheckFailure> },
+    /// The mark's stored date bytes differ from its date encoded again at its
+    /// resolution, which is empty if they do not decode. Reported only when
+    /// [`ValidationPolicy::with_date_encoding_check`] is on. This does not
+    /// break sequence continuity.
+    DateEncodingMismatch {
+        #[serde(with = "hex")]
+        stored: Vec<u8>,
+        #[serde(with = "hex")]
+        reencoded: Vec<u8>,
+    },
+}
+
+impl ValidationIssue {
+    /// The name of the issue's variant, as used for its `type` in JSON.
+    pub fn code(&self) -> &'static str {
+        match self {
+            ValidationIssue::HashMismatch { .. } => "HashMismatch",
+            ValidationIssue::KeyMismatch => "KeyMismatch",
+            ValidationIssue::SequenceGap { .. } => "SequenceGap",
+            ValidationIssue::DuplicateSeq { .. } => "DuplicateSeq",
+            ValidationIssue::SequenceRegression { .. } => "SequenceRegression",
+            ValidationIssue::DateOrdering { .. } => "DateOrdering",
+            ValidationIssue::NonGenesisAtZero => "NonGenesisAtZero",
+            ValidationIssue::InvalidGenesisKey => "InvalidGenesisKey",
+            ValidationIssue::ResolutionMismatch { .. } => "ResolutionMismatch",
+            ValidationIssue::ChainMismatch { .. } => "ChainMismatch",
+            ValidationIssue::FutureDated { .. } => "FutureDated",
+            ValidationIssue::MissingSignature => "MissingSignature",
+            ValidationIssue::InvalidSignature => "InvalidSignature",
+            ValidationIssue::ReusedKey { .. } => "ReusedKey",
+            ValidationIssue::MarkAfterTermination { .. } => {
+                "MarkAfterTermination"
+            }
+            ValidationIssue::SelfCheckFailed { .. } => "SelfCheckFailed",
+            ValidationIssue::DateEncodingMismatch { .. } => {
+                "DateEncodingMismatch"
+            }
+        }
+    }
+
+    /// Whether the issue is one [`ProvenanceMark::precedes_opt`] reports,
+    /// which starts a new sequence, rather than a warning.
+    pub(crate) fn breaks_sequence(&self) -> bool {
+        !matches!(
+            self,
+            ValidationIssue::FutureDated { .. }
+                | ValidationIssue::MissingSignature
+                | ValidationIssue::InvalidSignature
+                | ValidationIssue::ReusedKey { .. }
+                | ValidationIssue::MarkAfterTermination { .. }
+                | ValidationIssue::SelfCheckFailed { .. }
+                | ValidationIssue::DateEncodingMismatch { .. }
+        )
+    }
 }
 
 impl std::fmt::Display for ValidationIssue {
@@ -120,220 +1067,1036 @@ impl std::fmt::Display for ValidationIssue {
             ValidationIssue::SequenceGap { expected, actual } => {
                 write!(
                     f,
-                    "sequence number gap: expected {}, got {}",
-                    expected, actual
+                    "sequence number gap: expected {}, got {}",
+                    expected, actual
+                )
+            }
+            ValidationIssue::DuplicateSeq { seq } => {
+                write!(f, "duplicate sequence number: {}", seq)
+            }
+            ValidationIssue::SequenceRegression { previous, next } => {
+                write!(
+                    f,
+                    "sequence number must increase: previous is {}, next is {}",
+                    previous, next
+                )
+            }
+            ValidationIssue::DateOrdering { previous, next } => {
+                write!(
+                    f,
+                    "date must be equal or later: previous is {}, next is {}",
+                    previous, next
+                )
+            }
+            ValidationIssue::NonGenesisAtZero => {
+                write!(f, "non-genesis mark at sequence 0")
+            }
+            ValidationIssue::InvalidGenesisKey => {
+                write!(f, "genesis mark must have key equal to chain_id")
+            }
+            ValidationIssue::ResolutionMismatch { previous, next } => {
+                write!(
+                    f,
+                    "resolution must match: previous is {}, next is {}",
+                    previous, next
+                )
+            }
+            ValidationIssue::ChainMismatch { previous_chain, next_chain } => {
+                write!(
+                    f,
+                    "chain must match: previous is {}, next is {}",
+                    previous_chain, next_chain
+                )
+            }
+            ValidationIssue::FutureDated { date, reference } => {
+                write!(
+                    f,
+                    "mark is dated in the future: {} is after reference time {}",
+                    date, reference
+                )
+            }
+            ValidationIssue::MissingSignature => {
+                write!(f, "info is not signed but the chain declares a key")
+            }
+            ValidationIssue::InvalidSignature => {
+                write!(f, "info signature is invalid")
+            }
+            ValidationIssue::ReusedKey { other_mark } => {
+                write!(f, "key is reused by mark {}", other_mark)
+            }
+            ValidationIssue::MarkAfterTermination { termination } => {
+                write!(
+                    f,
+                    "mark follows the chain's termination mark at sequence {}",
+                    termination
+                )
+            }
+            ValidationIssue::SelfCheckFailed { failures } => {
+                let failures = failures
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect::<Vec<_>>()
+                    .join("; ");
+                write!(f, "mark fails self-check: {}", failures)
+            }
+            ValidationIssue::DateEncodingMismatch { stored, reencoded } => {
+                write!(
+                    f,
+                    "date bytes {} do not match the date's encoding {}",
+                    hex::encode(stored),
+                    hex::encode(reencoded)
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for ValidationIssue {}
+
+/// Advisory about a chain that is valid but warrants attention
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "type", content = "data")]
+pub enum ChainAdvisory {
+    /// The chain is long enough that an accidental collision between its short
+    /// link hashes is no longer negligible
+    CollisionRiskElevated {
+        #[serde(serialize_with = "resolution_as_name::serialize")]
+        resolution: ProvenanceMarkResolution,
+        count: usize,
+        threshold: usize,
+    },
+    /// The chain is not one of the
+    /// [expected chains](ValidationOptions::with_expected_chains)
+    UnexpectedChain,
+}
+
+impl std::fmt::Display for ChainAdvisory {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ChainAdvisory::CollisionRiskElevated {
+                resolution,
+                count,
+                threshold,
+            } => {
+                write!(
+                    f,
+                    "{} marks at {} resolution exceeds the collision-safety threshold of {}",
+                    count, resolution, threshold
                 )
             }
-            ValidationIssue::DateOrdering { previous, next } => {
+            ChainAdvisory::UnexpectedChain => {
+                write!(f, "chain is not one of the expected chains")
+            }
+        }
+    }
+}
+
+/// Note about the report as a whole, rather than any one chain
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "type", content = "data")]
+pub enum ReportNote {
+    /// Marks in different chains share a key, which suggests a generator was
+    /// restored from the wrong state
+    CrossChainKeyReuse {
+        #[serde(with = "hex")]
+        key: Vec<u8>,
+        #[serde(serialize_with = "hex_list::serialize")]
+        chain_ids: Vec<Vec<u8>>,
+    },
+}
+
+impl std::fmt::Display for ReportNote {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ReportNote::CrossChainKeyReuse { key, chain_ids } => {
+                let chain_ids = chain_ids
+                    .iter()
+                    .map(hex::encode)
+                    .collect::<Vec<_>>()
+                    .join(", ");
                 write!(
                     f,
-                    "date must be equal or later: previous is {}, next is {}",
-                    previous, next
+                    "key {} is reused across chains {}",
+                    hex::encode(key),
+                    chain_ids
                 )
             }
-            ValidationIssue::NonGenesisAtZero => {
-                write!(f, "non-genesis mark at sequence 0")
-            }
-            ValidationIssue::InvalidGenesisKey => {
-                write!(f, "genesis mark must have key equal to chain_id")
-            }
         }
     }
 }
 
-impl std::error::Error for ValidationIssue {}
-
 /// A mark with any issues flagged during validation
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone)]
 pub struct FlaggedMark {
-    #[serde(serialize_with = "provenance_mark_as_ur::serialize")]
-    mark: ProvenanceMark,
+    mark: Arc<ProvenanceMark>,
     issues: Vec<ValidationIssue>,
+    evidence: Vec<IssueEvidence>,
 }
 
 impl FlaggedMark {
-    fn new(mark: ProvenanceMark) -> Self { Self { mark, issues: Vec::new() } }
+    fn new(mark: Arc<ProvenanceMark>) -> Self {
+        Self { mark, issues: Vec::new(), evidence: Vec::new() }
+    }
 
-    fn with_issue(mark: ProvenanceMark, issue: ValidationIssue) -> Self {
-        Self { mark, issues: vec![issue] }
+    fn with_issues(
+        mark: Arc<ProvenanceMark>,
+        issues: Vec<ValidationIssue>,
+        evidence: Vec<IssueEvidence>,
+    ) -> Self {
+        Self { mark, issues, evidence }
     }
 
     pub fn mark(&self) -> &ProvenanceMark { &self.mark }
+
+    /// The mark as shared with the [chain](ChainReport::marks) and
+    /// [report](ValidationReport::marks) it belongs to.
+    pub fn shared_mark(&self) -> &Arc<ProvenanceMark> { &self.mark }
+
     pub fn issues(&self) -> &[ValidationIssue] { &self.issues }
+
+    /// What the linkage checks compared for each of the mark's linkage
+    /// issues, or nothing unless
+    /// [`ValidationPolicy::with_include_evidence`] is on.
+    pub fn evidence(&self) -> &[IssueEvidence] { &self.evidence }
+}
+
+/// An issue that matched a [`SuppressionRule`](crate::SuppressionRule), with
+/// the mark it would have been flagged on
+#[derive(Debug, Clone)]
+pub struct SuppressedIssue {
+    mark: Arc<ProvenanceMark>,
+    issue: ValidationIssue,
+}
+
+impl SuppressedIssue {
+    pub fn mark(&self) -> &ProvenanceMark { &self.mark }
+    pub fn issue(&self) -> &ValidationIssue { &self.issue }
 }
 
 /// Report for a contiguous sequence of marks within a chain
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone)]
 pub struct SequenceReport {
     start_seq: u32,
     end_seq: u32,
+    preceded_by: Option<String>,
     marks: Vec<FlaggedMark>,
+    digest: [u8; 32],
 }
 
 impl SequenceReport {
     pub fn start_seq(&self) -> u32 { self.start_seq }
     pub fn end_seq(&self) -> u32 { self.end_seq }
     pub fn marks(&self) -> &[FlaggedMark] { &self.marks }
+
+    /// The short ID of the last mark of the previous sequence in the same
+    /// chain, as shown in text reports, or `None` for the chain's first
+    /// sequence.
+    pub fn preceded_by(&self) -> Option<&str> { self.preceded_by.as_deref() }
+
+    /// The number of marks in the sequence.
+    pub fn len(&self) -> usize { self.marks.len() }
+
+    /// Whether the sequence has no marks, which is never the case for a
+    /// sequence in a report.
+    pub fn is_empty(&self) -> bool { self.marks.is_empty() }
+
+    /// Whether none of the sequence's marks have issues.
+    pub fn is_clean(&self) -> bool {
+        self.marks.iter().all(|mark| mark.issues.is_empty())
+    }
+
+    /// SHA-256 over the fingerprints of the sequence's marks, in order.
+    pub fn digest(&self) -> [u8; 32] { self.digest }
 }
 
 /// Report for a chain of marks with the same chain ID
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone)]
 pub struct ChainReport {
-    #[serde(with = "hex")]
     chain_id: Vec<u8>,
+    chain_fingerprint: [u8; 32],
     has_genesis: bool,
-    #[serde(serialize_with = "provenance_marks_as_ur::serialize")]
-    marks: Vec<ProvenanceMark>,
+    declares_key: bool,
+    label: Option<String>,
+    anchored: bool,
+    marks: Vec<Arc<ProvenanceMark>>,
     sequences: Vec<SequenceReport>,
+    advisories: Vec<ChainAdvisory>,
+    digest: [u8; 32],
 }
 
 impl ChainReport {
     pub fn chain_id(&self) -> &[u8] { &self.chain_id }
+    /// The [chain fingerprint](ProvenanceMark::chain_fingerprint) of the
+    /// chain's marks.
+    pub fn chain_fingerprint(&self) -> [u8; 32] { self.chain_fingerprint }
     pub fn has_genesis(&self) -> bool { self.has_genesis }
-    pub fn marks(&self) -> &[ProvenanceMark] { &self.marks }
+    /// Whether the chain's genesis mark declares a verifying key.
+    pub fn declares_key(&self) -> bool { self.declares_key }
+    /// The label attached via [`ValidationOptions::with_labels`], if any.
+    pub fn label(&self) -> Option<&str> { self.label.as_deref() }
+    /// Whether the chain ID is one of the configured trust anchors.
+    pub fn is_anchored(&self) -> bool { self.anchored }
+    pub fn marks(&self) -> &[Arc<ProvenanceMark>] { &self.marks }
     pub fn sequences(&self) -> &[SequenceReport] { &self.sequences }
+    pub fn advisories(&self) -> &[ChainAdvisory] { &self.advisories }
+    /// SHA-256 over the chain ID followed by the digests of the chain's
+    /// sequences.
+    pub fn digest(&self) -> [u8; 32] { self.digest }
 
     /// Get the chain ID as a hex string for display
     pub fn chain_id_hex(&self) -> String { hex::encode(&self.chain_id) }
+
+    /// The shortest prefix of the chain ID, in hex, that distinguishes this
+    /// chain from every other chain in `report`, as shown in text reports.
+    ///
+    /// The prefix is at least 4 bytes, and is extended a byte at a time, like
+    /// a git short hash, until no other chain in the report shares it. A
+    /// chain whose whole ID is a prefix of another's is shown in full.
+    pub fn display_id(&self, report: &ValidationReport) -> String {
+        let len = report
+            .chains()
+            .iter()
+            .filter(|other| other.chain_id != self.chain_id)
+            .map(|other| {
+                let shared = self
+                    .chain_id
+                    .iter()
+                    .zip(&other.chain_id)
+                    .take_while(|(a, b)| a == b)
+                    .count();
+                shared + 1
+            })
+            .fold(4, usize::max)
+            .min(self.chain_id.len());
+        hex::encode(&self.chain_id[..len])
+    }
+
+    /// How long before `as_of` the chain's head mark was dated.
+    ///
+    /// Returns `None` if the chain has no marks. A head dated after `as_of`
+    /// has a staleness of zero. Date-only marks are treated as midnight UTC.
+    pub fn staleness(&self, as_of: &Date) -> Option<Duration> {
+        self.marks.last().map(|head| {
+            let age = as_of.datetime() - head.date().datetime();
+            age.max(Duration::zero())
+        })
+    }
+
+    /// The chain's marks dated between `start` and `end` inclusive, in
+    /// sequence order.
+    ///
+    /// Low-resolution marks carry only a date, so they are treated as
+    /// covering the whole day: a mark dated 2023-06-20 matches an `end` of
+    /// 2023-06-20T10:00:00Z, and a `start` later that same day.
+    pub fn marks_between(
+        &self,
+        start: &Date,
+        end: &Date,
+    ) -> Vec<&ProvenanceMark> {
+        self.marks
+            .iter()
+            .map(Arc::as_ref)
+            .filter(|mark| is_dated_between(mark, start, end))
+            .collect()
+    }
+
+    /// The time between the dates of each pair of consecutive marks within
+    /// the chain's sequences.
+    ///
+    /// Pairs that straddle a break between sequences are not included, since
+    /// the marks between them are missing or unverified.
+    pub fn intervals(&self) -> Vec<Duration> {
+        self.sequences
+            .iter()
+            .flat_map(|seq| {
+                seq.marks.windows(2).map(|pair| {
+                    pair[1].mark.date().datetime()
+                        - pair[0].mark.date().datetime()
+                })
+            })
+            .collect()
+    }
+
+    /// Each pair of consecutive marks within the chain's sequences, in order.
+    ///
+    /// These are exactly the pairs for which
+    /// [`precedes`](ProvenanceMark::precedes) holds.
+    pub fn verified_links(
+        &self,
+    ) -> impl Iterator<Item = (&ProvenanceMark, &ProvenanceMark)> {
+        self.sequences.iter().flat_map(|seq| {
+            seq.marks
+                .windows(2)
+                .map(|pair| (pair[0].mark(), pair[1].mark()))
+        })
+    }
+
+    /// Each pair of marks on either side of a break between sequences, with
+    /// the issue that broke the link, in order.
+    ///
+    /// Breaks whose issue was suppressed are not included.
+    pub fn broken_links(
+        &self,
+    ) -> impl Iterator<Item = (&ProvenanceMark, &ProvenanceMark, &ValidationIssue)>
+    {
+        self.sequences.windows(2).filter_map(|pair| {
+            let previous = pair[0].marks.last()?;
+            let next = pair[1].marks.first()?;
+            let issue = next.issues.iter().find(|i| i.breaks_sequence())?;
+            Some((previous.mark(), next.mark(), issue))
+        })
+    }
+
+    /// Summary statistics over [`intervals`](Self::intervals), or `None` if
+    /// the chain has no consecutive pairs.
+    pub fn cadence_summary(&self) -> Option<CadenceSummary> {
+        let mut intervals = self.intervals();
+        if intervals.is_empty() {
+            return None;
+        }
+        intervals.sort();
+        let count = intervals.len();
+        let mid = count / 2;
+        let median = if count.is_multiple_of(2) {
+            (intervals[mid - 1] + intervals[mid]) / 2
+        } else {
+            intervals[mid]
+        };
+        let total = intervals.iter().fold(Duration::zero(), |a, b| a + *b);
+        Some(CadenceSummary {
+            min: intervals[0],
+            median,
+            max: intervals[count - 1],
+            mean: total / i32::try_from(count).ok()?,
+        })
+    }
+}
+
+/// Statistics describing how often a chain's marks were issued
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CadenceSummary {
+    pub min: Duration,
+    pub median: Duration,
+    pub max: Duration,
+    pub mean: Duration,
+}
+
+impl std::fmt::Display for CadenceSummary {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "min {}, median {}, max {}, mean {}",
+            format_interval(self.min),
+            format_interval(self.median),
+            format_interval(self.max),
+            format_interval(self.mean)
+        )
+    }
+}
+
+/// Format a duration compactly to the second, e.g. `2d 3h 4m 5s`.
+fn format_interval(interval: Duration) -> String {
+    let seconds = interval.num_seconds();
+    let parts = [
+        (seconds / 86_400, "d"),
+        (seconds % 86_400 / 3_600, "h"),
+        (seconds % 3_600 / 60, "m"),
+        (seconds % 60, "s"),
+    ];
+    let formatted = parts
+        .iter()
+        .filter(|(value, _)| *value != 0)
+        .map(|(value, unit)| format!("{}{}", value, unit))
+        .collect::<Vec<_>>();
+    if formatted.is_empty() {
+        "0s".to_string()
+    } else {
+        formatted.join(" ")
+    }
+}
+
+/// Whether the mark's date falls between `start` and `end` inclusive, with a
+/// date-only mark covering its whole day.
+fn is_dated_between(mark: &ProvenanceMark, start: &Date, end: &Date) -> bool {
+    let date = mark.date().datetime();
+    match mark.res() {
+        ProvenanceMarkResolution::Low => {
+            date <= end.datetime()
+                && date + Duration::days(1) > start.datetime()
+        }
+        ProvenanceMarkResolution::Medium
+        | ProvenanceMarkResolution::Quartile
+        | ProvenanceMarkResolution::High => {
+            start.datetime() <= date && date <= end.datetime()
+        }
+    }
 }
 
 /// Complete validation report
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone)]
 pub struct ValidationReport {
-    #[serde(serialize_with = "provenance_marks_as_ur::serialize")]
-    marks: Vec<ProvenanceMark>,
+    marks: Vec<Arc<ProvenanceMark>>,
+    duplicates_removed: usize,
+    duplicate_marks: Vec<Arc<ProvenanceMark>>,
     chains: Vec<ChainReport>,
+    groups: Vec<ChainGroup>,
+    missing_chains: Vec<Vec<u8>>,
+    notes: Vec<ReportNote>,
+    suppressed: Vec<SuppressedIssue>,
+    options: AppliedOptions,
+    digest: [u8; 32],
+    fingerprint_index: OnceLock<FingerprintIndex>,
+    validation_options: ValidationOptions,
 }
 
 impl ValidationReport {
-    pub fn marks(&self) -> &[ProvenanceMark] { &self.marks }
+    /// The distinct marks that were validated, in order of first appearance.
+    ///
+    /// Each mark is stored once and shared with the chain and sequence
+    /// reports that contain it.
+    pub fn marks(&self) -> &[Arc<ProvenanceMark>] { &self.marks }
     pub fn chains(&self) -> &[ChainReport] { &self.chains }
 
-    /// Format the validation report as human-readable text.
+    /// The chains grouped with the canonical chains they are
+    /// [aliases](ValidationOptions::with_aliases) of, ordered by canonical
+    /// chain ID. Empty if no chain in the report is an alias.
+    pub fn groups(&self) -> &[ChainGroup] { &self.groups }
+
+    /// The IDs of the [expected
+    /// chains](ValidationOptions::with_expected_chains) that have no marks in
+    /// the report, in order.
+    pub fn missing_chains(&self) -> &[Vec<u8>] { &self.missing_chains }
+
+    /// The number of exact duplicates dropped from the input.
+    pub fn duplicates_removed(&self) -> usize { self.duplicates_removed }
+
+    /// The distinct marks that appeared more than once in the input, in order
+    /// of first appearance.
+    pub fn duplicate_marks(&self) -> &[Arc<ProvenanceMark>] {
+        &self.duplicate_marks
+    }
+
+    /// Notes about the report as a whole, such as keys reused across chains.
+    pub fn notes(&self) -> &[ReportNote] { &self.notes }
+
+    /// The issues that matched a [suppression
+    /// rule](ValidationOptions::suppress), ordered by chain ID and sequence
+    /// number. These are not included in the issues of their marks.
+    pub fn suppressed(&self) -> &[SuppressedIssue] { &self.suppressed }
+
+    /// The non-default options the report was produced with.
+    pub fn options(&self) -> &AppliedOptions { &self.options }
+
+    /// SHA-256 over the digests of the report's chains. See
+    /// [`recompute_digests`](Self::recompute_digests) for checking a stored
+    /// report against its marks.
+    pub fn digest(&self) -> [u8; 32] { self.digest }
+
+    /// An index of the report's marks by fingerprint, built on first use.
+    pub fn fingerprint_index(&self) -> &FingerprintIndex {
+        self.fingerprint_index
+            .get_or_init(|| FingerprintIndex::from_shared(&self.marks))
+    }
+
+    /// Whether the [canonicalized](Self::canonicalize) report is independent
+    /// of the order of the marks that were validated.
+    ///
+    /// Chains are always sorted by chain ID and their marks by sequence
+    /// number, so this holds unless a chain has two marks with the same
+    /// sequence number: which of them is flagged as the duplicate depends on
+    /// the order in which they were given.
+    pub fn is_order_invariant(&self) -> bool {
+        self.chains.iter().all(|chain| {
+            chain
+                .marks
+                .windows(2)
+                .all(|pair| pair[0].seq() != pair[1].seq())
+        })
+    }
+
+    /// Sorts the top-level marks and duplicate marks, which otherwise follow
+    /// the order of the input, by chain ID, sequence number, and mark ID.
+    ///
+    /// Reports of the same marks given in different orders then serialize
+    /// identically, provided they are [order
+    /// invariant](Self::is_order_invariant).
+    pub fn canonicalize(mut self) -> Self {
+        let key = |mark: &Arc<ProvenanceMark>| {
+            (mark.chain_id().to_vec(), mark.seq(), mark.id())
+        };
+        self.marks.sort_by_key(key);
+        self.duplicate_marks.sort_by_key(key);
+        self.fingerprint_index = OnceLock::new();
+        self
+    }
+
+    /// Format the validation report as a string.
     ///
-    /// Returns a formatted string if the report contains interesting
-    /// information (issues, multiple chains, or multiple sequences).
-    /// Returns an empty string if the report represents a single perfect chain
-    /// with no issues.
+    /// For [`ValidationReportFormat::Text`], returns a formatted string if the
+    /// report contains interesting information (issues, multiple chains, or
+    /// multiple sequences). Returns an empty string if the report represents a
+    /// single perfect chain with no issues, unless
+    /// [`FormatOptions::with_always_emit`] is set.
     pub fn format(&self, format: ValidationReportFormat) -> String {
-        match format {
-            ValidationReportFormat::Text => self.format_text(),
+        self.format_with_options(format, &FormatOptions::default())
+    }
+
+    /// Format the validation report as a string using the given options.
+    pub fn format_with_options(
+        &self,
+        format: ValidationReportFormat,
+        options: &FormatOptions,
+    ) -> String {
+        let mut buf = Vec::new();
+        let result = match format {
+            ValidationReportFormat::Text => {
+                self.write_text_with_options(&mut buf, options)
+            }
+            #[cfg(feature = "json")]
             ValidationReportFormat::JsonCompact => {
-                serde_json::to_string(self).unwrap_or_default()
+                self.write_json_with_options(&mut buf, false, options)
             }
+            #[cfg(feature = "json")]
             ValidationReportFormat::JsonPretty => {
-                serde_json::to_string_pretty(self).unwrap_or_default()
+                self.write_json_with_options(&mut buf, true, options)
             }
+        };
+        if result.is_err() {
+            return String::new();
         }
+        String::from_utf8(buf).unwrap_or_default()
     }
 
-    fn format_text(&self) -> String {
-        if !self.is_interesting() {
-            return String::new();
-        }
+    /// Write the validation report as JSON directly to a writer, without
+    /// building the whole document in memory first.
+    #[cfg(feature = "json")]
+    pub fn write_json(&self, w: impl Write, pretty: bool) -> Result<()> {
+        self.write_json_with_options(w, pretty, &FormatOptions::default())
+    }
 
-        let mut lines = Vec::new();
+    /// Write the validation report as JSON using the given options. Only
+    /// [`FormatOptions::inline_mark_details`], [`FormatOptions::statistics`],
+    /// and [`FormatOptions::health`] affect JSON output.
+    #[cfg(feature = "json")]
+    pub fn write_json_with_options(
+        &self,
+        w: impl Write,
+        pretty: bool,
+        options: &FormatOptions,
+    ) -> Result<()> {
+        let document = ReportJson::new(self, options);
+        write_json_document(w, pretty, &document).map_err(|e| {
+            if e.is_io() {
+                Error::Io(e.into())
+            } else {
+                Error::Json(e)
+            }
+        })
+    }
+
+    /// Write the validation report as human-readable text directly to a
+    /// writer.
+    ///
+    /// Writes nothing if the report represents a single perfect chain with no
+    /// issues.
+    pub fn write_text(&self, w: impl Write) -> Result<()> {
+        self.write_text_with_options(w, &FormatOptions::default())
+    }
+
+    /// Write the validation report as human-readable text directly to a
+    /// writer using the given options.
+    pub fn write_text_with_options(
+        &self,
+        mut w: impl Write,
+        options: &FormatOptions,
+    ) -> Result<()> {
+        if !self.is_interesting() && !options.always_emit() {
+            return Ok(());
+        }
 
         // Report summary
-        lines.push(format!("Total marks: {}", self.marks.len()));
-        lines.push(format!("Chains: {}", self.chains.len()));
-        lines.push(String::new());
-
-        // Report each chain
-        for (chain_idx, chain) in self.chains.iter().enumerate() {
-            // Show short chain ID (first 4 bytes)
-            let chain_id_hex = chain.chain_id_hex();
-            let short_chain_id = if chain_id_hex.len() > 8 {
-                &chain_id_hex[..8]
+        write!(w, "Total marks: {}", self.marks.len())?;
+        if self.duplicates_removed > 0 {
+            write!(w, "\nDuplicates removed: {}", self.duplicates_removed)?;
+        }
+        write!(w, "\nChains: {}", self.chains.len())?;
+        for chain_id in &self.missing_chains {
+            write!(w, "\nMissing chain: {}", hex::encode(chain_id))?;
+        }
+        if options.statistics() {
+            write!(
+                w,
+                "\nTotal bytes: {}",
+                self.size_statistics().total_bytes()
+            )?;
+        }
+        if options.always_emit() {
+            let status = if self.has_issues() {
+                "issues found"
             } else {
-                &chain_id_hex
+                "valid"
             };
+            write!(w, "\nStatus: {}", status)?;
+        }
+        for note in &self.notes {
+            write!(w, "\nNote: {}", note)?;
+        }
+        writeln!(w)?;
+
+        // Report each chain, separated by blank lines, with aliases nested
+        // under their canonical chains
+        for (chain_idx, (chain, canonical)) in
+            self.chains_in_text_order().into_iter().enumerate()
+        {
+            if chain_idx > 0 {
+                writeln!(w)?;
+            }
+            let display_id = chain.display_id(self);
+            let alias_of = canonical
+                .map(|canonical| canonical.display_id(self))
+                .or_else(|| {
+                    self.validation_options
+                        .aliases()
+                        .canonical(chain.chain_id())
+                        .map(hex::encode)
+                });
+            if canonical.is_none() {
+                Self::write_chain_text(
+                    &mut w,
+                    chain_idx,
+                    chain,
+                    &display_id,
+                    alias_of.as_deref(),
+                    options,
+                )?;
+                continue;
+            }
+            let mut nested = Vec::new();
+            Self::write_chain_text(
+                &mut nested,
+                chain_idx,
+                chain,
+                &display_id,
+                alias_of.as_deref(),
+                options,
+            )?;
+            let nested = String::from_utf8_lossy(&nested);
+            write!(w, "{}", nested.replace('\n', "\n  "))?;
+        }
 
-            lines.push(format!("Chain {}: {}", chain_idx + 1, short_chain_id));
+        match self.suppressed.len() {
+            0 => {}
+            1 => write!(w, "\n\n1 finding suppressed")?,
+            count => write!(w, "\n\n{} findings suppressed", count)?,
+        }
 
-            if !chain.has_genesis() {
-                lines.push("  Warning: No genesis mark found".to_string());
+        Ok(())
+    }
+
+    /// The chains in the order text reports list them, each with the
+    /// canonical chain it is nested under, if any. An alias follows its
+    /// canonical chain if that chain is in the report and is not nested
+    /// itself, and otherwise keeps its place.
+    fn chains_in_text_order(
+        &self,
+    ) -> Vec<(&ChainReport, Option<&ChainReport>)> {
+        let aliases = self.validation_options.aliases();
+        let present =
+            |id: &[u8]| self.chains.iter().find(|chain| chain.chain_id() == id);
+        let canonical_of = |chain: &ChainReport| {
+            let canonical = present(aliases.canonical(chain.chain_id())?)?;
+            aliases
+                .canonical(canonical.chain_id())
+                .and_then(present)
+                .is_none()
+                .then_some(canonical)
+        };
+        let mut ordered = Vec::new();
+        for chain in self.chains.iter().filter(|c| canonical_of(c).is_none()) {
+            ordered.push((chain, None));
+            for alias in &self.chains {
+                if canonical_of(alias)
+                    .is_some_and(|canonical| std::ptr::eq(canonical, chain))
+                {
+                    ordered.push((alias, Some(chain)));
+                }
             }
+        }
+        ordered
+    }
 
-            // Report each sequence
-            for seq in chain.sequences() {
-                // Report each mark in the sequence
-                for flagged_mark in seq.marks() {
-                    let mark = flagged_mark.mark();
-                    let short_id = &mark.id_hex()[..8];
-                    let seq_num = mark.seq();
-
-                    // Build the mark line with annotations
-                    let mut annotations = Vec::new();
-
-                    // Check if it's genesis
-                    if mark.is_genesis() {
-                        annotations.push("genesis mark".to_string());
-                    }
+    /// Write the lines for a single chain, each preceded by a newline.
+    fn write_chain_text(
+        w: &mut impl Write,
+        chain_idx: usize,
+        chain: &ChainReport,
+        display_id: &str,
+        alias_of: Option<&str>,
+        options: &FormatOptions,
+    ) -> Result<()> {
+        write!(w, "\nChain {}: {}", chain_idx + 1, display_id)?;
+
+        if let Some(label) = chain.label() {
+            write!(w, " \"{}\"", label)?;
+        }
 
-                    // Add issue annotations
-                    for issue in flagged_mark.issues() {
-                        let issue_str = match issue {
-                            ValidationIssue::SequenceGap {
-                                expected,
-                                actual: _,
-                            } => {
-                                format!("gap: {} missing", expected)
-                            }
-                            ValidationIssue::DateOrdering {
-                                previous,
-                                next,
-                            } => {
-                                format!("date {} < {}", previous, next)
-                            }
-                            ValidationIssue::HashMismatch { .. } => {
-                                "hash mismatch".to_string()
-                            }
-                            ValidationIssue::KeyMismatch => {
-                                "key mismatch".to_string()
-                            }
-                            ValidationIssue::NonGenesisAtZero => {
-                                "non-genesis at seq 0".to_string()
-                            }
-                            ValidationIssue::InvalidGenesisKey => {
-                                "invalid genesis key".to_string()
-                            }
-                        };
-                        annotations.push(issue_str);
-                    }
+        if chain.is_anchored() {
+            write!(w, " (anchored)")?;
+        }
 
-                    // Format the line
-                    if annotations.is_empty() {
-                        lines.push(format!("  {}: {}", seq_num, short_id));
-                    } else {
-                        lines.push(format!(
-                            "  {}: {} ({})",
-                            seq_num,
-                            short_id,
-                            annotations.join(", ")
-                        ));
-                    }
+        if let Some(alias_of) = alias_of {
+            write!(w, " (alias of {})", alias_of)?;
+        }
+
+        let stale = options
+            .as_of()
+            .and_then(|as_of| chain.staleness(&as_of))
+            .filter(|age| *age > options.max_age());
+        if let Some(staleness) = stale {
+            write!(w, " (stale: {} days)", staleness.num_days())?;
+        }
+
+        if !chain.has_genesis() {
+            write!(w, "\n  Warning: No genesis mark found")?;
+        }
+
+        for advisory in chain.advisories() {
+            let advisory =
+                paint(advisory.to_string(), ANSI_YELLOW, options.color());
+            write!(w, "\n  Advisory: {}", advisory)?;
+        }
+
+        if let Some(cadence) =
+            chain.cadence_summary().filter(|_| options.cadence())
+        {
+            write!(w, "\n  Cadence: {}", cadence)?;
+        }
+
+        if options.statistics() {
+            write!(w, "\n  Size: {}", chain.size_statistics())?;
+        }
+
+        // Report each sequence
+        let lines = Self::mark_lines(chain, options.collapse_clean_runs());
+        let shown = options.max_marks_per_chain().unwrap_or(lines.len());
+        let width = if options.aligned() {
+            lines
+                .iter()
+                .take(shown)
+                .map(|line| line.label().chars().count())
+                .max()
+                .unwrap_or(0)
+        } else {
+            0
+        };
+        for line in lines.iter().take(shown) {
+            // Pad by characters, since a range's dash is multibyte
+            let label = line.label();
+            let padding = width.saturating_sub(label.chars().count());
+            let label = format!("{}{}", " ".repeat(padding), label);
+            match line {
+                MarkLine::Mark(flagged_mark, preceded_by) => {
+                    Self::write_mark_text(
+                        w,
+                        flagged_mark,
+                        &label,
+                        *preceded_by,
+                        options,
+                    )?;
+                }
+                MarkLine::Run { count, .. } => {
+                    write!(w, "\n  {}: {} verified marks", label, count)?;
+                }
+            }
+        }
+        let hidden: usize =
+            lines.iter().skip(shown).map(MarkLine::mark_count).sum();
+        if hidden > 0 {
+            write!(w, "\n  ... {} more marks", hidden)?;
+        }
+
+        Ok(())
+    }
+
+    /// The lines listing a chain's marks, optionally collapsing runs of
+    /// issue-free marks within each sequence.
+    fn mark_lines(chain: &ChainReport, collapse: bool) -> Vec<MarkLine<'_>> {
+        let mut lines = Vec::new();
+        for seq in chain.sequences() {
+            let marks = seq.marks();
+            let mut run: Vec<&FlaggedMark> = Vec::new();
+            for (i, flagged_mark) in marks.iter().enumerate() {
+                let collapsible = collapse
+                    && i > 0
+                    && i < marks.len() - 1
+                    && flagged_mark.issues().is_empty();
+                if collapsible {
+                    run.push(flagged_mark);
+                } else {
+                    Self::push_run(&mut lines, &mut run);
+                    let preceded_by = seq.preceded_by().filter(|_| i == 0);
+                    lines.push(MarkLine::Mark(flagged_mark, preceded_by));
                 }
             }
+            Self::push_run(&mut lines, &mut run);
+        }
+        lines
+    }
+
+    /// Push a run of collapsible marks, as a single line if it has more than
+    /// one mark, and clear it.
+    fn push_run<'a>(
+        lines: &mut Vec<MarkLine<'a>>,
+        run: &mut Vec<&'a FlaggedMark>,
+    ) {
+        match run.as_slice() {
+            [] => {}
+            [flagged_mark] => lines.push(MarkLine::Mark(*flagged_mark, None)),
+            [first, .., last] => lines.push(MarkLine::Run {
+                first: first.mark().seq(),
+                last: last.mark().seq(),
+                count: run.len(),
+            }),
+        }
+        run.clear();
+    }
+
+    fn write_mark_text(
+        w: &mut impl Write,
+        flagged_mark: &FlaggedMark,
+        label: &str,
+        preceded_by: Option<&str>,
+        options: &FormatOptions,
+    ) -> Result<()> {
+        let mark = flagged_mark.mark();
+        let short_id = &mark.id_hex()[..8];
+
+        // Build the mark line with annotations
+        let mut annotations = Vec::new();
+
+        // Check if it's genesis
+        if mark.is_genesis() {
+            annotations.push(paint(
+                "genesis mark".to_string(),
+                ANSI_GREEN,
+                options.color(),
+            ));
+        }
+
+        // Add issue annotations
+        for issue in flagged_mark.issues() {
+            let issue_str = match issue {
+                ValidationIssue::SequenceGap { expected, actual: _ } => {
+                    format!("gap: {} missing", expected)
+                }
+                ValidationIssue::DuplicateSeq { .. } => {
+                    "duplicate seq".to_string()
+                }
+                ValidationIssue::SequenceRegression { .. } => {
+                    "seq regression".to_string()
+                }
+                ValidationIssue::DateOrdering { previous, next } => {
+                    let precision = mark.date_precision();
+                    format!(
+                        "date {} < {}",
+                        precision.format(previous),
+                        precision.format(next)
+                    )
+                }
+                ValidationIssue::HashMismatch { .. } => {
+                    "hash mismatch".to_string()
+                }
+                ValidationIssue::KeyMismatch => "key mismatch".to_string(),
+                ValidationIssue::NonGenesisAtZero => {
+                    "non-genesis at seq 0".to_string()
+                }
+                ValidationIssue::InvalidGenesisKey => {
+                    "invalid genesis key".to_string()
+                }
+                ValidationIssue::ResolutionMismatch { previous, next } => {
+                    format!("resolution {} != {}", previous, next)
+                }
+                ValidationIssue::ChainMismatch { next_chain, .. } => {
+                    format!("chain {}", next_chain)
+                }
+                ValidationIssue::FutureDated { .. } => {
+                    "future-dated".to_string()
+                }
+                ValidationIssue::MissingSignature => {
+                    "missing signature".to_string()
+                }
+                ValidationIssue::InvalidSignature => {
+                    "invalid signature".to_string()
+                }
+                ValidationIssue::ReusedKey { .. } => "reused key".to_string(),
+                ValidationIssue::MarkAfterTermination { .. } => {
+                    "after termination".to_string()
+                }
+                ValidationIssue::SelfCheckFailed { .. } => {
+                    "self-check failed".to_string()
+                }
+                ValidationIssue::DateEncodingMismatch { .. } => {
+                    "date encoding mismatch".to_string()
+                }
+            };
+            annotations.push(paint(issue_str, ANSI_RED, options.color()));
+        }
+
+        // Name the mark before a sequence break
+        if let Some(preceded_by) = preceded_by {
+            annotations.push(format!("after {}", preceded_by));
+        }
 
-            lines.push(String::new());
+        if let Some(info) = options
+            .show_info()
+            .then(|| mark.report_info(options.redact_info()))
+            .flatten()
+        {
+            annotations.push(format!("info: {}", info));
         }
 
-        lines.join("\n").trim_end().to_string()
+        // Format the line
+        if annotations.is_empty() {
+            write!(w, "\n  {}: {}", label, short_id)?;
+        } else {
+            write!(
+                w,
+                "\n  {}: {} ({})",
+                label,
+                short_id,
+                annotations.join(", ")
+            )?;
+        }
+        for evidence in flagged_mark.evidence() {
+            write!(w, "\n    why: {}", evidence)?;
+        }
+        Ok(())
     }
 
     /// Check if the validation report contains interesting information.
     ///
-    /// Returns false for a single perfect chain with no issues, true otherwise.
-    fn is_interesting(&self) -> bool {
+    /// Returns false for a single perfect chain with no issues and no dropped
+    /// duplicates, true otherwise. Text reports that are not interesting are
+    /// written as nothing unless [`FormatOptions::with_always_emit`] is set.
+    pub fn is_interesting(&self) -> bool {
+        // Expected chains with no marks are always worth mentioning
+        if !self.missing_chains.is_empty() {
+            return true;
+        }
+
         // Not interesting if empty
         if self.chains.is_empty() {
             return false;
         }
 
-        // Check if any chain is missing genesis
+        // Dropped duplicates and report notes are worth mentioning
+        if self.duplicates_removed > 0 || !self.notes.is_empty() {
+            return true;
+        }
+
+        // Check if any chain is missing genesis or has advisories
         for chain in &self.chains {
-            if !chain.has_genesis() {
+            if !chain.has_genesis() || !chain.advisories().is_empty() {
                 return true;
             }
         }
@@ -344,7 +2107,7 @@ impl ValidationReport {
             if chain.sequences().len() == 1 {
                 let seq = &chain.sequences()[0];
                 // Check if the sequence has no issues
-                if seq.marks().iter().all(|m| m.issues().is_empty()) {
+                if seq.is_clean() {
                     return false;
                 }
             }
@@ -356,7 +2119,9 @@ impl ValidationReport {
     /// Check if the validation report has any issues.
     ///
     /// Returns true if there are validation issues, missing genesis,
-    /// multiple chains, or multiple sequences.
+    /// multiple chains, or multiple sequences. Suppressed issues are not
+    /// counted, nor are breaks between sequences whose issues were all
+    /// suppressed.
     pub fn has_issues(&self) -> bool {
         // Missing genesis is considered an issue
         for chain in &self.chains {
@@ -381,27 +2146,121 @@ impl ValidationReport {
             return true;
         }
 
-        if self.chains.len() == 1 && self.chains[0].sequences().len() > 1 {
+        if self.chains.len() == 1
+            && self.chains[0].sequences()[1..]
+                .iter()
+                .any(|seq| !self.is_suppressed_break(seq))
+        {
             return true;
         }
 
         false
     }
 
-    /// Validate a collection of provenance marks
+    /// Whether the sequence starts after a break whose issues were all
+    /// suppressed.
+    fn is_suppressed_break(&self, sequence: &SequenceReport) -> bool {
+        let first = &sequence.marks[0];
+        first.issues.is_empty()
+            && self
+                .suppressed
+                .iter()
+                .any(|suppressed| Arc::ptr_eq(&suppressed.mark, &first.mark))
+    }
+
+    /// The [verified links](ChainReport::verified_links) of every chain, in
+    /// chain order.
+    pub fn verified_links(
+        &self,
+    ) -> impl Iterator<Item = (&ProvenanceMark, &ProvenanceMark)> {
+        self.chains.iter().flat_map(ChainReport::verified_links)
+    }
+
+    /// Chains whose head mark is older than `max_age` as of the given date.
+    pub fn stale_chains(
+        &self,
+        as_of: &Date,
+        max_age: Duration,
+    ) -> Vec<&ChainReport> {
+        self.chains
+            .iter()
+            .filter(|chain| {
+                chain.staleness(as_of).is_some_and(|age| age > max_age)
+            })
+            .collect()
+    }
+
+    /// The marks of every chain dated between `start` and `end` inclusive,
+    /// ordered by chain ID and then sequence number. See
+    /// [`ChainReport::marks_between`] for how date-only marks are matched.
+    pub fn marks_between(
+        &self,
+        start: &Date,
+        end: &Date,
+    ) -> Vec<&ProvenanceMark> {
+        self.chains
+            .iter()
+            .flat_map(|chain| chain.marks_between(start, end))
+            .collect()
+    }
+
     /// Validate a collection of provenance marks
     pub fn validate(marks: Vec<ProvenanceMark>) -> Self {
-        // Deduplicate exact duplicates
-        let mut seen = HashSet::new();
+        Self::validate_with_policy(marks, &ValidationPolicy::default())
+    }
+
+    /// Validate a collection of provenance marks, applying the optional checks
+    /// enabled by `policy`.
+    pub fn validate_with_policy(
+        marks: Vec<ProvenanceMark>,
+        policy: &ValidationPolicy,
+    ) -> Self {
+        let options = ValidationOptions::default().with_policy(policy.clone());
+        Self::validate_opts(marks, &options)
+    }
+
+    /// Validate a collection of provenance marks using the given options.
+    pub fn validate_opts(
+        marks: Vec<ProvenanceMark>,
+        options: &ValidationOptions,
+    ) -> Self {
+        let policy = options.policy();
+
+        #[cfg(feature = "tracing")]
+        let _span =
+            tracing::info_span!("validate", marks = marks.len()).entered();
+
+        // Deduplicate exact duplicates, remembering what was dropped and the
+        // input position of each distinct mark. Each distinct mark is stored
+        // once and shared by every part of the report.
+        let mut seen: HashMap<Arc<ProvenanceMark>, usize> = HashMap::new();
         let mut deduplicated_marks = Vec::new();
-        for mark in marks {
-            if seen.insert(mark.clone()) {
-                deduplicated_marks.push(mark);
+        let mut duplicates_removed = 0;
+        let mut duplicate_marks: Vec<Arc<ProvenanceMark>> = Vec::new();
+        // Input positions of the distinct marks already in `duplicate_marks`
+        let mut reported = HashSet::new();
+        for (position, mark) in marks.into_iter().enumerate() {
+            match seen.get_key_value(&mark) {
+                Some((existing, &first)) => {
+                    duplicates_removed += 1;
+                    if reported.insert(first) {
+                        duplicate_marks.push(existing.clone());
+                    }
+                }
+                None => {
+                    let mark = Arc::new(mark);
+                    seen.insert(mark.clone(), position);
+                    deduplicated_marks.push(mark);
+                }
             }
         }
 
+        // Find reused keys across all marks, before they are split by chain
+        let (mut reused_keys, notes) =
+            Self::find_reused_keys(&deduplicated_marks);
+
         // Bin marks by chain ID
-        let mut chain_bins: HashMap<Vec<u8>, Vec<ProvenanceMark>> =
+        let mut chain_bins: HashMap<Vec<u8>, Vec<Arc<ProvenanceMark>>> =
             HashMap::new();
         for mark in &deduplicated_marks {
             chain_bins
@@ -422,23 +2281,294 @@ impl ValidationReport {
                 .is_some_and(|m| m.seq() == 0 && m.is_genesis());
 
             // Build sequence bins
-            let sequences = Self::build_sequence_bins(&chain_marks);
+            let mut sequences = Self::build_sequence_bins(
+                &chain_marks,
+                policy.include_evidence().then_some(&seen),
+            );
+
+            let verifying_key = chain_marks
+                .first()
+                .and_then(|mark| mark.declared_verifying_key());
+
+            let termination = chain_marks
+                .iter()
+                .find(|mark| mark.is_termination())
+                .map(|mark| mark.seq());
+
+            // Flag future-dated marks, signature problems, reused keys, and
+            // marks after the chain's termination without affecting
+            // continuity
+            for flagged_mark in
+                sequences.iter_mut().flat_map(|seq| seq.marks.iter_mut())
+            {
+                let mark = &flagged_mark.mark;
+                let signature_issue = verifying_key
+                    .as_ref()
+                    .and_then(|key| policy.signature_issue(key, mark));
+                let issues = [
+                    policy.future_dated_issue(mark),
+                    signature_issue,
+                    policy.self_check_issue(mark),
+                    policy.date_encoding_issue(mark),
+                ];
+                let reused = reused_keys.remove(mark).unwrap_or_default();
+                flagged_mark.issues.extend(issues.into_iter().flatten());
+                flagged_mark.issues.extend(reused);
+                if let Some(termination) =
+                    termination.filter(|seq| mark.seq() > *seq)
+                {
+                    flagged_mark.issues.push(
+                        ValidationIssue::MarkAfterTermination { termination },
+                    );
+                }
+            }
+
+            let mut advisories = policy.chain_advisories(&chain_marks);
+            if !options.is_expected(&chain_id_bytes) {
+                advisories.push(ChainAdvisory::UnexpectedChain);
+            }
+            let digest = chain_digest(
+                &chain_id_bytes,
+                sequences.iter().map(|sequence| sequence.digest),
+            );
 
             chains.push(ChainReport {
                 chain_id: chain_id_bytes,
+                // Every chain has at least one mark
+                chain_fingerprint: chain_marks[0].chain_fingerprint(),
                 has_genesis,
+                declares_key: verifying_key.is_some(),
+                label: options.label(&chain_id_bytes).map(str::to_string),
+                anchored: options.anchors().contains(&chain_id_bytes),
                 marks: chain_marks,
                 sequences,
+                advisories,
+                digest,
             });
         }
 
         // Sort chains by chain ID for consistent output
         chains.sort_by(|a, b| a.chain_id.cmp(&b.chain_id));
 
-        ValidationReport { marks: deduplicated_marks, chains }
+        let suppressed = Self::suppress_issues(&mut chains, options);
+        let groups = ChainGroup::from_chains(&chains, options.aliases());
+        let missing_chains = options
+            .expected_chains()
+            .into_iter()
+            .flatten()
+            .filter(|id| !chains.iter().any(|chain| chain.chain_id == **id))
+            .cloned()
+            .collect();
+
+        #[cfg(feature = "tracing")]
+        Self::trace_chains(&chains);
+
+        let digest = report_digest(chains.iter().map(|chain| chain.digest));
+
+        ValidationReport {
+            marks: deduplicated_marks,
+            duplicates_removed,
+            duplicate_marks,
+            chains,
+            groups,
+            missing_chains,
+            notes,
+            suppressed,
+            options: options.applied(),
+            digest,
+            fingerprint_index: OnceLock::new(),
+            validation_options: options.clone(),
+        }
+    }
+
+    /// Validate the report's marks again together with `marks`, using the
+    /// options the report was produced with.
+    ///
+    /// This is the full recompute after obtaining marks that were missing,
+    /// such as one checked with [`ChainReport::fills_gap`]. Additional marks
+    /// that are already in the report count as duplicates; duplicates dropped
+    /// from the original input are not counted again. The result is not
+    /// [canonicalized](Self::canonicalize).
+    pub fn with_additional_marks(
+        self,
+        marks: impl IntoIterator<Item = ProvenanceMark>,
+    ) -> ValidationReport {
+        let Self {
+            marks: existing,
+            duplicate_marks,
+            chains,
+            fingerprint_index,
+            validation_options,
+            ..
+        } = self;
+        // Release the other references to the marks first, so they are moved
+        // rather than cloned
+        drop((duplicate_marks, chains, fingerprint_index));
+        let all_marks = existing
+            .into_iter()
+            .map(Arc::unwrap_or_clone)
+            .chain(marks)
+            .collect();
+        Self::validate_opts(all_marks, &validation_options)
+    }
+
+    /// The report restricted to the chains for which `predicate` holds, as if
+    /// only their marks had been validated.
+    ///
+    /// The marks of the matching chains are validated again with the options
+    /// the report was produced with, so the top-level marks, digests, and
+    /// notes such as cross-chain key reuse describe only those chains. Each
+    /// of their [duplicate marks](Self::duplicate_marks) is counted once,
+    /// even if it appeared more than twice in the original input. The result
+    /// is not [canonicalized](Self::canonicalize).
+    pub fn filter_chains(
+        &self,
+        predicate: impl Fn(&ChainReport) -> bool,
+    ) -> ValidationReport {
+        let chain_ids: HashSet<&[u8]> = self
+            .chains
+            .iter()
+            .filter(|chain| predicate(chain))
+            .map(ChainReport::chain_id)
+            .collect();
+        let marks = self
+            .marks
+            .iter()
+            .chain(&self.duplicate_marks)
+            .filter(|mark| chain_ids.contains(mark.chain_id().as_bytes()))
+            .map(|mark| mark.as_ref().clone())
+            .collect();
+        Self::validate_opts(marks, &self.validation_options)
+    }
+
+    /// The report restricted to the chain with the given ID, as described
+    /// for [`filter_chains`](Self::filter_chains). The report has no chains
+    /// if none has that ID.
+    pub fn filter_chain_id(&self, chain_id: &[u8]) -> ValidationReport {
+        self.filter_chains(|chain| chain.chain_id() == chain_id)
+    }
+
+    /// Move the issues matching the options' suppression rules out of the
+    /// chains' marks.
+    fn suppress_issues(
+        chains: &mut [ChainReport],
+        options: &ValidationOptions,
+    ) -> Vec<SuppressedIssue> {
+        let mut suppressed = Vec::new();
+        if options.suppressions().is_empty() {
+            return suppressed;
+        }
+        for flagged_mark in chains
+            .iter_mut()
+            .flat_map(|chain| chain.sequences.iter_mut())
+            .flat_map(|seq| seq.marks.iter_mut())
+        {
+            let (matching, kept): (Vec<_>, Vec<_>) =
+                std::mem::take(&mut flagged_mark.issues)
+                    .into_iter()
+                    .partition(|issue| {
+                        options.is_suppressed(&flagged_mark.mark, issue)
+                    });
+            flagged_mark.evidence.retain(|evidence| {
+                kept.iter().any(|issue| issue.code() == evidence.rule())
+            });
+            flagged_mark.issues = kept;
+            suppressed.extend(matching.into_iter().map(|issue| {
+                SuppressedIssue { mark: flagged_mark.mark.clone(), issue }
+            }));
+        }
+        suppressed
+    }
+
+    /// Flag every mark whose key also appears in another mark, and note each
+    /// key shared by marks in different chains.
+    ///
+    /// A genesis mark's key is its chain ID, so it only counts as reused if
+    /// another mark has the same key.
+    fn find_reused_keys(
+        marks: &[Arc<ProvenanceMark>],
+    ) -> (
+        HashMap<Arc<ProvenanceMark>, Vec<ValidationIssue>>,
+        Vec<ReportNote>,
+    ) {
+        let mut marks_by_key: HashMap<&[u8], Vec<&Arc<ProvenanceMark>>> =
+            HashMap::new();
+        for mark in marks {
+            marks_by_key
+                .entry(mark.key().as_bytes())
+                .or_default()
+                .push(mark);
+        }
+
+        let mut shared: Vec<_> = marks_by_key
+            .into_iter()
+            .filter(|(_, sharing)| sharing.len() > 1)
+            .collect();
+        shared.sort_by_key(|(key, _)| *key);
+
+        let mut issues: HashMap<Arc<ProvenanceMark>, Vec<ValidationIssue>> =
+            HashMap::new();
+        let mut notes = Vec::new();
+        for (key, mut sharing) in shared {
+            sharing.sort_by_key(|mark| {
+                (mark.chain_id().to_vec(), mark.seq(), mark.id())
+            });
+            for mark in &sharing {
+                let others = sharing.iter().filter(|other| *other != mark).map(
+                    |other| ValidationIssue::ReusedKey {
+                        other_mark: other.id_hex(),
+                    },
+                );
+                issues.entry((*mark).clone()).or_default().extend(others);
+            }
+
+            let mut chain_ids: Vec<Vec<u8>> = sharing
+                .iter()
+                .map(|mark| mark.chain_id().to_vec())
+                .collect();
+            chain_ids.dedup();
+            if chain_ids.len() > 1 {
+                notes.push(ReportNote::CrossChainKeyReuse {
+                    key: key.to_vec(),
+                    chain_ids,
+                });
+            }
+        }
+        (issues, notes)
+    }
+
+    #[cfg(feature = "tracing")]
+    fn trace_chains(chains: &[ChainReport]) {
+        for chain in chains {
+            let chain_id = chain.chain_id_hex();
+            tracing::info!(
+                chain_id = %chain_id,
+                marks = chain.marks.len(),
+                sequences = chain.sequences.len(),
+                has_genesis = chain.has_genesis,
+                "validated chain"
+            );
+            for flagged in chain.sequences.iter().flat_map(|seq| &seq.marks) {
+                for issue in &flagged.issues {
+                    tracing::warn!(
+                        chain_id = %chain_id,
+                        seq = flagged.mark.seq(),
+                        issue = issue.code(),
+                        "{}",
+                        issue
+                    );
+                }
+            }
+        }
     }
 
-    fn build_sequence_bins(marks: &[ProvenanceMark]) -> Vec<SequenceReport> {
+    /// Split a chain's sorted marks into sequences at each mark that does not
+    /// follow the one before it, recording evidence for the issues found if
+    /// given the input positions of the marks.
+    fn build_sequence_bins(
+        marks: &[Arc<ProvenanceMark>],
+        positions: Option<&HashMap<Arc<ProvenanceMark>, usize>>,
+    ) -> Vec<SequenceReport> {
         let mut sequences = Vec::new();
         let mut current_sequence: Vec<FlaggedMark> = Vec::new();
 
@@ -448,6 +2578,11 @@ impl ValidationReport {
                 current_sequence.push(FlaggedMark::new(mark.clone()));
             } else {
                 let prev = &marks[i - 1];
+                debug_assert_eq!(
+                    prev.chain_id(),
+                    mark.chain_id(),
+                    "marks are binned by chain before sequencing"
+                );
 
                 // Check if this mark follows the previous one
                 match prev.precedes_opt(mark) {
@@ -458,19 +2593,35 @@ impl ValidationReport {
                     Err(e) => {
                         // Breaks the sequence - save current and start new
                         if !current_sequence.is_empty() {
-                            sequences.push(Self::create_sequence_report(
+                            let sequence = Self::create_sequence_report(
                                 current_sequence,
-                            ));
+                                sequences.last(),
+                            );
+                            sequences.push(sequence);
                         }
 
                         // Start new sequence with this mark, flagged with the
                         // issue
-                        let issue = match e {
-                            crate::Error::Validation(v) => v,
-                            _ => ValidationIssue::KeyMismatch, // Fallback
-                        };
-                        current_sequence =
-                            vec![FlaggedMark::with_issue(mark.clone(), issue)];
+                        let issues = Self::issues_from_error(e);
+                        let evidence = positions
+                            .map(|positions| {
+                                issues
+                                    .iter()
+                                    .map(|issue| {
+                                        IssueEvidence::new(
+                                            issue,
+                                            (prev.as_ref(), positions[prev]),
+                                            (mark.as_ref(), positions[mark]),
+                                        )
+                                    })
+                                    .collect()
+                            })
+                            .unwrap_or_default();
+                        current_sequence = vec![FlaggedMark::with_issues(
+                            mark.clone(),
+                            issues,
+                            evidence,
+                        )];
                     }
                 }
             }
@@ -478,17 +2629,40 @@ impl ValidationReport {
 
         // Add the final sequence
         if !current_sequence.is_empty() {
-            sequences.push(Self::create_sequence_report(current_sequence));
+            let sequence = Self::create_sequence_report(
+                current_sequence,
+                sequences.last(),
+            );
+            sequences.push(sequence);
         }
 
         sequences
     }
 
-    fn create_sequence_report(marks: Vec<FlaggedMark>) -> SequenceReport {
+    fn issues_from_error(error: Error) -> Vec<ValidationIssue> {
+        match error.flatten() {
+            Error::Validation(issue) => vec![issue],
+            Error::Multiple(errors) => errors
+                .into_iter()
+                .flat_map(Self::issues_from_error)
+                .collect(),
+            _ => vec![ValidationIssue::KeyMismatch], // Fallback
+        }
+    }
+
+    fn create_sequence_report(
+        marks: Vec<FlaggedMark>,
+        previous: Option<&SequenceReport>,
+    ) -> SequenceReport {
         let start_seq = marks.first().map(|m| m.mark.seq()).unwrap_or(0);
         let end_seq = marks.last().map(|m| m.mark.seq()).unwrap_or(0);
+        let preceded_by = previous
+            .and_then(|sequence| sequence.marks.last())
+            .map(|m| m.mark.id_hex()[..8].to_string());
+
+        let digest = sequence_digest(marks.iter().map(|m| m.mark.as_ref()));
 
-        SequenceReport { start_seq, end_seq, marks }
+        SequenceReport { start_seq, end_seq, preceded_by, marks, digest }
     }
 }
 
@@ -505,4 +2679,21 @@ impl ProvenanceMark {
     pub fn validate(marks: Vec<ProvenanceMark>) -> ValidationReport {
         ValidationReport::validate(marks)
     }
+
+    /// Validate a collection of provenance marks, applying the optional checks
+    /// enabled by `policy`.
+    pub fn validate_with_policy(
+        marks: Vec<ProvenanceMark>,
+        policy: &ValidationPolicy,
+    ) -> ValidationReport {
+        ValidationReport::validate_with_policy(marks, policy)
+    }
+
+    /// Validate a collection of provenance marks using the given options.
+    pub fn validate_opts(
+        marks: Vec<ProvenanceMark>,
+        options: &ValidationOptions,
+    ) -> ValidationReport {
+        ValidationReport::validate_opts(marks, options)
+    }
 }
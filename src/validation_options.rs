@@ -0,0 +1,387 @@
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    ops::RangeInclusive,
+};
+
+use dcbor::Date;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    ProvenanceMark, ProvenanceMarkResolution, ValidationIssue, ValidationPolicy,
+};
+
+/// A set of chain IDs the caller already trusts
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TrustAnchors {
+    chain_ids: BTreeSet<Vec<u8>>,
+}
+
+impl TrustAnchors {
+    pub fn new() -> Self { Self::default() }
+
+    /// Trust the chain with the given chain ID.
+    pub fn with_chain_id(mut self, chain_id: impl AsRef<[u8]>) -> Self {
+        self.chain_ids.insert(chain_id.as_ref().to_vec());
+        self
+    }
+
+    pub fn contains(&self, chain_id: &[u8]) -> bool {
+        self.chain_ids.contains(chain_id)
+    }
+
+    pub fn chain_ids(&self) -> impl Iterator<Item = &[u8]> {
+        self.chain_ids.iter().map(Vec::as_slice)
+    }
+
+    pub fn len(&self) -> usize { self.chain_ids.len() }
+    pub fn is_empty(&self) -> bool { self.chain_ids.is_empty() }
+}
+
+impl<T: AsRef<[u8]>> FromIterator<T> for TrustAnchors {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        iter.into_iter()
+            .fold(Self::new(), |anchors, id| anchors.with_chain_id(id))
+    }
+}
+
+/// A mapping of chain IDs to the canonical chain IDs they are aliases of, such
+/// as a chain that re-issued a work at a higher resolution
+///
+/// Aliases only change how a report presents chains: each chain is still
+/// validated on its own, and its aliased chains are listed with it in a
+/// [`ChainGroup`](crate::ChainGroup). Aliases are not followed transitively.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ChainAlias {
+    aliases: BTreeMap<Vec<u8>, Vec<u8>>,
+}
+
+impl ChainAlias {
+    pub fn new() -> Self { Self::default() }
+
+    /// Group the chain with ID `alias` under the chain with ID `canonical`,
+    /// replacing any canonical chain given for it before. A chain cannot be
+    /// an alias of itself.
+    pub fn with_alias(
+        mut self,
+        alias: impl AsRef<[u8]>,
+        canonical: impl AsRef<[u8]>,
+    ) -> Self {
+        let (alias, canonical) = (alias.as_ref(), canonical.as_ref());
+        if alias != canonical {
+            self.aliases.insert(alias.to_vec(), canonical.to_vec());
+        }
+        self
+    }
+
+    /// The ID of the canonical chain the given chain is an alias of, if any.
+    pub fn canonical(&self, chain_id: &[u8]) -> Option<&[u8]> {
+        self.aliases.get(chain_id).map(Vec::as_slice)
+    }
+
+    /// Each alias chain ID with its canonical chain ID, ordered by alias.
+    pub fn iter(&self) -> impl Iterator<Item = (&[u8], &[u8])> {
+        self.aliases
+            .iter()
+            .map(|(alias, canonical)| (alias.as_slice(), canonical.as_slice()))
+    }
+
+    pub fn len(&self) -> usize { self.aliases.len() }
+    pub fn is_empty(&self) -> bool { self.aliases.is_empty() }
+}
+
+impl<A: AsRef<[u8]>, C: AsRef<[u8]>> FromIterator<(A, C)> for ChainAlias {
+    fn from_iter<I: IntoIterator<Item = (A, C)>>(iter: I) -> Self {
+        iter.into_iter()
+            .fold(Self::new(), |aliases, (alias, canonical)| {
+                aliases.with_alias(alias, canonical)
+            })
+    }
+}
+
+/// A known, accepted finding to leave out of a report's issues, such as a
+/// documented gap
+///
+/// A rule matches an issue by the [code](ValidationIssue::code) of the issue,
+/// a prefix of the chain ID of the mark it is flagged on, and optionally a
+/// range of sequence numbers the mark falls in. A gap is flagged on the first
+/// mark after it. Rules can be loaded from JSON such as
+/// `{"chainIdPrefix": "b16a", "code": "SequenceGap", "seqRange": {"start": 3,
+/// "end": 3}}`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SuppressionRule {
+    #[serde(with = "hex")]
+    chain_id_prefix: Vec<u8>,
+    code: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    seq_range: Option<RangeInclusive<u32>>,
+}
+
+impl SuppressionRule {
+    /// A rule matching issues with the given code on any mark of chains whose
+    /// IDs start with `chain_id_prefix`.
+    pub fn new(
+        chain_id_prefix: impl AsRef<[u8]>,
+        code: impl Into<String>,
+    ) -> Self {
+        Self {
+            chain_id_prefix: chain_id_prefix.as_ref().to_vec(),
+            code: code.into(),
+            seq_range: None,
+        }
+    }
+
+    /// Only match issues flagged on marks with sequence numbers in `seqs`.
+    pub fn with_seq_range(mut self, seqs: RangeInclusive<u32>) -> Self {
+        self.seq_range = Some(seqs);
+        self
+    }
+
+    pub fn chain_id_prefix(&self) -> &[u8] { &self.chain_id_prefix }
+    pub fn code(&self) -> &str { &self.code }
+    pub fn seq_range(&self) -> Option<&RangeInclusive<u32>> {
+        self.seq_range.as_ref()
+    }
+
+    /// Whether the rule matches `issue` flagged on `mark`.
+    pub fn matches(
+        &self,
+        mark: &ProvenanceMark,
+        issue: &ValidationIssue,
+    ) -> bool {
+        issue.code() == self.code
+            && mark.chain_id().starts_with(&self.chain_id_prefix)
+            && self
+                .seq_range
+                .as_ref()
+                .is_none_or(|seqs| seqs.contains(&mark.seq()))
+    }
+}
+
+/// Options controlling a call to
+/// [`ProvenanceMark::validate_opts`](crate::ProvenanceMark::validate_opts)
+#[derive(Debug, Clone, Default)]
+pub struct ValidationOptions {
+    policy: ValidationPolicy,
+    anchors: TrustAnchors,
+    labels: BTreeMap<Vec<u8>, String>,
+    suppressions: Vec<SuppressionRule>,
+    aliases: ChainAlias,
+    expected_chains: Option<BTreeSet<Vec<u8>>>,
+}
+
+impl ValidationOptions {
+    pub fn new() -> Self { Self::default() }
+
+    /// Replace the validation policy. A reference time set with
+    /// [`with_reference_time`](Self::with_reference_time) is overwritten, so
+    /// set the policy first.
+    pub fn with_policy(mut self, policy: ValidationPolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    /// Mark chains whose IDs appear in `anchors` as anchored in the report.
+    pub fn with_anchors(mut self, anchors: TrustAnchors) -> Self {
+        self.anchors = anchors;
+        self
+    }
+
+    /// Attach human-readable labels to chains, keyed by chain ID.
+    pub fn with_labels<K, V>(
+        mut self,
+        labels: impl IntoIterator<Item = (K, V)>,
+    ) -> Self
+    where
+        K: AsRef<[u8]>,
+        V: Into<String>,
+    {
+        self.labels.extend(
+            labels
+                .into_iter()
+                .map(|(id, label)| (id.as_ref().to_vec(), label.into())),
+        );
+        self
+    }
+
+    /// Group chains with the canonical chains they are aliases of, in the
+    /// report's [`groups`](crate::ValidationReport::groups) and by nesting
+    /// them under their canonical chains in text reports.
+    pub fn with_aliases(mut self, aliases: ChainAlias) -> Self {
+        self.aliases = aliases;
+        self
+    }
+
+    /// Only expect chains with the given IDs, such as the chains a platform
+    /// has onboarded. Other chains are given a
+    /// [`ChainAdvisory::UnexpectedChain`](crate::ChainAdvisory::UnexpectedChain)
+    /// advisory, and expected chains with no marks are listed in the report's
+    /// [`missing_chains`](crate::ValidationReport::missing_chains).
+    pub fn with_expected_chains<T: AsRef<[u8]>>(
+        mut self,
+        chain_ids: impl IntoIterator<Item = T>,
+    ) -> Self {
+        self.expected_chains = Some(
+            chain_ids
+                .into_iter()
+                .map(|id| id.as_ref().to_vec())
+                .collect(),
+        );
+        self
+    }
+
+    /// Report issues matching `rule` as suppressed rather than as issues of
+    /// their marks. Suppressed issues are listed in
+    /// [`ValidationReport::suppressed`](crate::ValidationReport::suppressed)
+    /// and do not count toward
+    /// [`has_issues`](crate::ValidationReport::has_issues).
+    pub fn suppress(mut self, rule: SuppressionRule) -> Self {
+        self.suppressions.push(rule);
+        self
+    }
+
+    /// Flag marks dated later than the given time; shorthand for
+    /// [`ValidationPolicy::with_reference_time`].
+    pub fn with_reference_time(mut self, reference_time: Date) -> Self {
+        self.policy = self.policy.with_reference_time(reference_time);
+        self
+    }
+
+    pub fn policy(&self) -> &ValidationPolicy { &self.policy }
+    pub fn anchors(&self) -> &TrustAnchors { &self.anchors }
+    pub fn suppressions(&self) -> &[SuppressionRule] { &self.suppressions }
+    pub fn aliases(&self) -> &ChainAlias { &self.aliases }
+
+    /// The IDs of the expected chains, or `None` if any chain is expected.
+    pub fn expected_chains(&self) -> Option<&BTreeSet<Vec<u8>>> {
+        self.expected_chains.as_ref()
+    }
+
+    /// Whether the chain with the given ID is expected.
+    pub(crate) fn is_expected(&self, chain_id: &[u8]) -> bool {
+        self.expected_chains
+            .as_ref()
+            .is_none_or(|expected| expected.contains(chain_id))
+    }
+
+    /// The label attached to the given chain, if any.
+    pub fn label(&self, chain_id: &[u8]) -> Option<&str> {
+        self.labels.get(chain_id).map(String::as_str)
+    }
+
+    /// Whether any suppression rule matches `issue` flagged on `mark`.
+    pub(crate) fn is_suppressed(
+        &self,
+        mark: &ProvenanceMark,
+        issue: &ValidationIssue,
+    ) -> bool {
+        self.suppressions
+            .iter()
+            .any(|rule| rule.matches(mark, issue))
+    }
+
+    pub(crate) fn applied(&self) -> AppliedOptions {
+        let default_policy = ValidationPolicy::default();
+        let differs = |res| {
+            let threshold = self.policy.collision_threshold(res);
+            (threshold != default_policy.collision_threshold(res))
+                .then_some(threshold)
+                .flatten()
+        };
+        AppliedOptions {
+            reference_time: self.policy.reference_time(),
+            future_tolerance_seconds: (self.policy.future_tolerance()
+                != default_policy.future_tolerance())
+            .then(|| self.policy.future_tolerance().num_seconds()),
+            low_collision_threshold: differs(ProvenanceMarkResolution::Low),
+            medium_collision_threshold: differs(
+                ProvenanceMarkResolution::Medium,
+            ),
+            signature_verifier: self.policy.has_signature_verifier(),
+            self_check: self.policy.self_check(),
+            date_encoding_check: self.policy.date_encoding_check(),
+            include_evidence: self.policy.include_evidence(),
+            anchors: self.anchors.chain_ids().map(hex::encode).collect(),
+            labels: self
+                .labels
+                .iter()
+                .map(|(id, label)| (hex::encode(id), label.clone()))
+                .collect(),
+            suppressions: self.suppressions.clone(),
+            aliases: self
+                .aliases
+                .iter()
+                .map(|(alias, canonical)| {
+                    (hex::encode(alias), hex::encode(canonical))
+                })
+                .collect(),
+            expected_chains: self
+                .expected_chains
+                .as_ref()
+                .map(|expected| expected.iter().map(hex::encode).collect()),
+        }
+    }
+}
+
+/// The non-default options that were in effect when a report was produced
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AppliedOptions {
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        serialize_with = "crate::util::serialize_optional_iso8601"
+    )]
+    reference_time: Option<Date>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    future_tolerance_seconds: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    low_collision_threshold: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    medium_collision_threshold: Option<usize>,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    signature_verifier: bool,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    self_check: bool,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    date_encoding_check: bool,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    include_evidence: bool,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    anchors: Vec<String>,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    labels: BTreeMap<String, String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    suppressions: Vec<SuppressionRule>,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    aliases: BTreeMap<String, String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    expected_chains: Option<Vec<String>>,
+}
+
+impl AppliedOptions {
+    pub fn reference_time(&self) -> Option<Date> { self.reference_time }
+    pub fn has_signature_verifier(&self) -> bool { self.signature_verifier }
+    pub fn self_check(&self) -> bool { self.self_check }
+    pub fn date_encoding_check(&self) -> bool { self.date_encoding_check }
+    pub fn include_evidence(&self) -> bool { self.include_evidence }
+
+    /// Hex-encoded IDs of the trusted chains.
+    pub fn anchors(&self) -> &[String] { &self.anchors }
+
+    /// Chain labels keyed by hex-encoded chain ID.
+    pub fn labels(&self) -> &BTreeMap<String, String> { &self.labels }
+
+    /// The rules issues were suppressed by.
+    pub fn suppressions(&self) -> &[SuppressionRule] { &self.suppressions }
+
+    /// Hex-encoded canonical chain IDs keyed by hex-encoded alias chain ID.
+    pub fn aliases(&self) -> &BTreeMap<String, String> { &self.aliases }
+
+    /// Hex-encoded IDs of the expected chains, if only some were expected.
+    pub fn expected_chains(&self) -> Option<&[String]> {
+        self.expected_chains.as_deref()
+    }
+
+    /// Whether the report was produced with the default options.
+    pub fn is_default(&self) -> bool { *self == Self::default() }
+}
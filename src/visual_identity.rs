@@ -0,0 +1,58 @@
+use bc_ur::bytewords;
+use serde::Serialize;
+
+use crate::{ChainReport, ProvenanceMark, crypto_utils::sha256};
+
+/// A deterministic visual identity for a chain, suitable for distinguishing
+/// chains in user interfaces.
+///
+/// All values are derived from `SHA-256(chain_id)`:
+///
+/// - `color_rgb` is the first three bytes of the digest.
+/// - `emoji` is the Bytemoji for the first byte of the digest.
+/// - `bytewords` is the first four bytes of the digest as upper-case ByteWords.
+///
+/// This derivation is part of the stable API: the same chain ID always
+/// produces the same visual identity across releases.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize)]
+pub struct VisualIdentity {
+    color_rgb: [u8; 3],
+    emoji: String,
+    bytewords: String,
+}
+
+impl VisualIdentity {
+    /// Derives the visual identity for the given chain ID.
+    pub fn from_chain_id(chain_id: impl AsRef<[u8]>) -> Self {
+        let digest = sha256(chain_id);
+        let color_rgb = [digest[0], digest[1], digest[2]];
+        let emoji = bytewords::encode_to_bytemojis(&digest[..1]);
+        let bytewords = bytewords::encode_to_words(&digest[..4]).to_uppercase();
+        Self { color_rgb, emoji, bytewords }
+    }
+
+    pub fn color_rgb(&self) -> [u8; 3] { self.color_rgb }
+
+    pub fn emoji(&self) -> &str { &self.emoji }
+
+    pub fn bytewords(&self) -> &str { &self.bytewords }
+
+    /// The color as a CSS-style hex string, e.g. `#f3fe7c`.
+    pub fn color_hex(&self) -> String {
+        format!("#{}", hex::encode(self.color_rgb))
+    }
+}
+
+impl ProvenanceMark {
+    /// The visual identity of the chain this mark belongs to.
+    pub fn visual_identity(&self) -> VisualIdentity {
+        VisualIdentity::from_chain_id(self.chain_id())
+    }
+}
+
+impl ChainReport {
+    /// The visual identity of this chain.
+    pub fn visual_identity(&self) -> VisualIdentity {
+        VisualIdentity::from_chain_id(self.chain_id())
+    }
+}
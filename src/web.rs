@@ -0,0 +1,201 @@
+//! Discovery of provenance marks published in web pages.
+//!
+//! A page may carry its mark in either of two forms:
+//!
+//! ```html
+//! <meta name="provenance" content="ur:provenance/...">
+//! <link rel="provenance" href="https://example.com/?provenance=...">
+//! ```
+
+use url::Url;
+
+use crate::{ProvenanceMark, Result};
+
+/// Extract every provenance mark declared in `html` by a `<meta
+/// name="provenance">` or `<link rel="provenance">` element, in document
+/// order.
+///
+/// The scan is deliberately tolerant rather than a full HTML parse: tag and
+/// attribute names are matched case-insensitively, attribute values may be
+/// double-quoted, single-quoted, or bare, and character references in values
+/// are decoded. Each declared mark yields one entry, which is an error if the
+/// declared value cannot be parsed.
+pub fn extract_marks_from_html(html: &str) -> Vec<Result<ProvenanceMark>> {
+    Tags::new(html)
+        .filter_map(|tag| tag.provenance_value())
+        .map(|value| parse_value(&value))
+        .collect()
+}
+
+/// Parse a declared value as either a `ur:provenance` string or a URL with a
+/// `provenance` query parameter. Relative URLs are accepted.
+fn parse_value(value: &str) -> Result<ProvenanceMark> {
+    let value = value.trim();
+    if value
+        .get(..3)
+        .is_some_and(|s| s.eq_ignore_ascii_case("ur:"))
+    {
+        return ProvenanceMark::parse_ur_string(&value.to_ascii_lowercase());
+    }
+    let base = Url::parse("https://localhost/")?;
+    let url = Url::options().base_url(Some(&base)).parse(value)?;
+    ProvenanceMark::from_url(&url)
+}
+
+struct Tag {
+    name: String,
+    attributes: Vec<(String, String)>,
+}
+
+impl Tag {
+    fn attribute(&self, name: &str) -> Option<&str> {
+        self.attributes
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    fn provenance_value(&self) -> Option<String> {
+        let is_provenance =
+            |value: &str| value.eq_ignore_ascii_case("provenance");
+        match self.name.as_str() {
+            "meta" => self
+                .attribute("name")
+                .filter(|name| is_provenance(name.trim()))
+                .and(self.attribute("content")),
+            "link" => self
+                .attribute("rel")
+                .filter(|rel| rel.split_ascii_whitespace().any(is_provenance))
+                .and(self.attribute("href")),
+            _ => None,
+        }
+        .map(decode_entities)
+    }
+}
+
+/// Iterates over the start tags in an HTML document, skipping comments.
+struct Tags<'a> {
+    rest: &'a str,
+}
+
+impl<'a> Tags<'a> {
+    fn new(html: &'a str) -> Self { Self { rest: html } }
+
+    /// Parse the attributes of a tag body, returning them along with the text
+    /// following the tag's closing `>`.
+    fn parse_attributes(mut s: &str) -> (Vec<(String, String)>, &str) {
+        let mut attributes = Vec::new();
+        loop {
+            s = s.trim_start_matches(|c: char| c.is_whitespace() || c == '/');
+            if s.is_empty() {
+                return (attributes, s);
+            }
+            if let Some(rest) = s.strip_prefix('>') {
+                return (attributes, rest);
+            }
+            let name_len = s
+                .find(|c: char| {
+                    c.is_whitespace() || matches!(c, '=' | '>' | '/')
+                })
+                .unwrap_or(s.len());
+            let name = s[..name_len].to_ascii_lowercase();
+            s = s[name_len..].trim_start();
+            let value = match s.strip_prefix('=') {
+                Some(after) => {
+                    let after = after.trim_start();
+                    let (value, rest) = match after.chars().next() {
+                        Some(quote @ ('"' | '\'')) => {
+                            let body = &after[1..];
+                            let end = body.find(quote).unwrap_or(body.len());
+                            (&body[..end], body.get(end + 1..).unwrap_or(""))
+                        }
+                        _ => {
+                            let end = after
+                                .find(|c: char| c.is_whitespace() || c == '>')
+                                .unwrap_or(after.len());
+                            (&after[..end], &after[end..])
+                        }
+                    };
+                    s = rest;
+                    value.to_string()
+                }
+                None => String::new(),
+            };
+            attributes.push((name, value));
+        }
+    }
+}
+
+impl Iterator for Tags<'_> {
+    type Item = Tag;
+
+    fn next(&mut self) -> Option<Tag> {
+        loop {
+            let start = self.rest.find('<')?;
+            let s = &self.rest[start + 1..];
+            if let Some(comment) = s.strip_prefix("!--") {
+                self.rest =
+                    comment.find("-->").map_or("", |end| &comment[end + 3..]);
+                continue;
+            }
+            let name_len = s
+                .find(|c: char| !c.is_ascii_alphanumeric())
+                .unwrap_or(s.len());
+            if name_len == 0 {
+                self.rest = s;
+                continue;
+            }
+            let name = s[..name_len].to_ascii_lowercase();
+            let (attributes, rest) = Self::parse_attributes(&s[name_len..]);
+            self.rest = rest;
+            return Some(Tag { name, attributes });
+        }
+    }
+}
+
+/// Decode the character references that commonly appear in attribute values.
+/// Unrecognized references are left as written.
+fn decode_entities(value: &str) -> String {
+    let mut decoded = String::with_capacity(value.len());
+    let mut rest = value;
+    while let Some(start) = rest.find('&') {
+        decoded.push_str(&rest[..start]);
+        rest = &rest[start..];
+        let entity = rest
+            .find(';')
+            .filter(|end| *end <= 10)
+            .and_then(|end| decode_entity(&rest[1..end]).map(|c| (c, end)));
+        match entity {
+            Some((c, end)) => {
+                decoded.push(c);
+                rest = &rest[end + 1..];
+            }
+            None => {
+                decoded.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    decoded.push_str(rest);
+    decoded
+}
+
+fn decode_entity(name: &str) -> Option<char> {
+    let numeric = name.strip_prefix('#').and_then(|number| {
+        match number.strip_prefix(['x', 'X']) {
+            Some(hex) => u32::from_str_radix(hex, 16).ok(),
+            None => number.parse().ok(),
+        }
+    });
+    if numeric.is_some() {
+        return numeric.and_then(char::from_u32);
+    }
+    match name.to_ascii_lowercase().as_str() {
+        "amp" => Some('&'),
+        "quot" => Some('"'),
+        "apos" => Some('\''),
+        "lt" => Some('<'),
+        "gt" => Some('>'),
+        _ => None,
+    }
+}
@@ -0,0 +1,88 @@
+use dcbor::Date;
+use provenance_mark::*;
+
+mod common;
+use common::daily_marks;
+
+fn chain(marks: Vec<ProvenanceMark>) -> ChainReport {
+    ProvenanceMark::validate(marks).chains()[0].clone()
+}
+
+fn short_id(marks: &[ProvenanceMark]) -> String {
+    hex::encode(&marks[0].chain_id()[..4])
+}
+
+#[test]
+fn test_badge_clean_chain() {
+    let marks = daily_marks(ProvenanceMarkResolution::Low, "test", 5);
+    let id = short_id(&marks);
+    let chain = chain(marks.clone());
+    assert_eq!(
+        chain.badge(),
+        format!(
+            "Provenance: chain {} · 5 marks · latest 2023-06-24 · ✓ verified",
+            id
+        )
+    );
+    assert_eq!(
+        chain.badge_html(),
+        format!(
+            "<span class=\"provenance-badge\">Provenance: chain <code>{}</code> \
+             · 5 marks · latest <time datetime=\"2023-06-24\">2023-06-24</time> \
+             · <span class=\"provenance-verified\">✓ verified</span></span>",
+            id
+        )
+    );
+
+    let single = self::chain(marks[..1].to_vec());
+    assert_eq!(
+        single.badge(),
+        format!(
+            "Provenance: chain {} · 1 mark · latest 2023-06-20 · ✓ verified",
+            id
+        )
+    );
+
+    // Warnings do not affect the badge
+    let options = ValidationOptions::default()
+        .with_reference_time(Date::from_string("2023-06-22").unwrap());
+    let report = ValidationReport::validate_opts(marks, &options);
+    assert!(report.has_issues());
+    assert!(report.chains()[0].badge().ends_with("✓ verified"));
+}
+
+#[test]
+fn test_badge_without_genesis() {
+    let marks = daily_marks(ProvenanceMarkResolution::Low, "test", 5);
+    let id = short_id(&marks);
+    let chain = chain(marks[1..].to_vec());
+    assert_eq!(
+        chain.badge(),
+        format!(
+            "Provenance: chain {} · 4 marks · latest 2023-06-24 · ✗ unverified",
+            id
+        )
+    );
+    assert!(
+        chain.badge_html().contains(
+            "<span class=\"provenance-unverified\">✗ unverified</span>"
+        )
+    );
+}
+
+#[test]
+fn test_badge_with_issues() {
+    let marks = daily_marks(ProvenanceMarkResolution::Low, "test", 5);
+    let id = short_id(&marks);
+    let gapped = vec![marks[0].clone(), marks[1].clone(), marks[3].clone()];
+    let chain = chain(gapped);
+    assert_eq!(
+        chain.badge(),
+        format!(
+            "Provenance: chain {} · 3 marks · latest 2023-06-23 · ✗ unverified",
+            id
+        )
+    );
+    // The same report always produces the same badge
+    assert_eq!(chain.badge(), chain.clone().badge());
+}
@@ -0,0 +1,69 @@
+#![cfg(feature = "mmap")]
+
+use std::fs;
+
+use dcbor::Date;
+use provenance_mark::{chain_archive::*, *};
+use tempfile::TempDir;
+
+fn marks(count: usize) -> Vec<ProvenanceMark> {
+    let mut generator = ProvenanceMarkGenerator::new_with_passphrase(
+        ProvenanceMarkResolution::Medium,
+        "Wolf",
+    );
+    let date = Date::from_string("2023-06-20").unwrap();
+    (0..count)
+        .map(|i| generator.next(date, Some(format!("Mark {i}"))))
+        .collect()
+}
+
+#[test]
+#[cfg(feature = "json")]
+fn test_mmap_archive_matches_in_memory_validation() {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("marks.pma");
+
+    let mut marks = marks(3000);
+    // Leave a gap so the report has something to say
+    marks.remove(1500);
+    write_archive(fs::File::create(&path).unwrap(), &marks).unwrap();
+
+    let decoded: Vec<ProvenanceMark> =
+        open_mmap(&path).unwrap().collect::<Result<_>>().unwrap();
+    assert_eq!(decoded, marks);
+
+    let from_archive = ProvenanceMark::validate(decoded);
+    let in_memory = ProvenanceMark::validate(marks);
+    assert_eq!(
+        from_archive.format(ValidationReportFormat::JsonCompact),
+        in_memory.format(ValidationReportFormat::JsonCompact)
+    );
+    assert_eq!(from_archive.chains()[0].sequences().len(), 2);
+}
+
+#[test]
+fn test_mmap_archive_errors() {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("marks.pma");
+
+    fs::write(&path, b"not an archive").unwrap();
+    assert!(matches!(
+        open_mmap(&path),
+        Err(Error::InvalidArchive { offset: 0, .. })
+    ));
+
+    // A truncated final frame yields the complete marks, then one error
+    let marks = marks(3);
+    let mut bytes = Vec::new();
+    write_archive(&mut bytes, &marks).unwrap();
+    bytes.truncate(bytes.len() - 1);
+    fs::write(&path, &bytes).unwrap();
+
+    let results: Vec<Result<ProvenanceMark>> =
+        open_mmap(&path).unwrap().collect();
+    assert_eq!(results.len(), 3);
+    assert_eq!(results[0].as_ref().unwrap(), &marks[0]);
+    assert_eq!(results[1].as_ref().unwrap(), &marks[1]);
+    let error = results[2].as_ref().unwrap_err();
+    assert!(error.to_string().contains("truncated message"), "{error}");
+}
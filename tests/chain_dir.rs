@@ -0,0 +1,141 @@
+#![cfg(feature = "fs")]
+
+use chrono::TimeZone;
+use dcbor::Date;
+use provenance_mark::{chain_dir, *};
+
+fn date_for_day(day: u32) -> Date {
+    Date::from_datetime(
+        chrono::Utc
+            .with_ymd_and_hms(2023, 6, day, 12, 0, 0)
+            .single()
+            .unwrap(),
+    )
+}
+
+fn next_info(
+    generator: &mut ProvenanceMarkGenerator,
+    comment: &str,
+) -> ProvenanceMarkInfo {
+    let day = 20 + generator.next_seq();
+    let mark = generator.next(date_for_day(day), None::<&str>);
+    ProvenanceMarkInfo::new(mark, comment)
+}
+
+#[test]
+fn test_chain_dir_save_load_append() {
+    let dir = tempfile::tempdir().unwrap();
+    let mut generator = ProvenanceMarkGenerator::new_with_passphrase(
+        ProvenanceMarkResolution::Low,
+        "Wolf",
+    );
+    let mut infos: Vec<_> = (0..4)
+        .map(|i| next_info(&mut generator, &format!("Mark {i}")))
+        .collect();
+
+    chain_dir::save(dir.path(), &generator, &infos, false).unwrap();
+    assert!(
+        chain_dir::mark_file_path(dir.path(), 3).ends_with("marks/0003.json")
+    );
+    assert!(chain_dir::mark_file_path(dir.path(), 3).exists());
+
+    let (loaded_generator, loaded_infos) = chain_dir::load(dir.path()).unwrap();
+    assert_eq!(loaded_generator, generator);
+    assert_eq!(loaded_infos.len(), 4);
+    for (loaded, original) in loaded_infos.iter().zip(&infos) {
+        assert_eq!(loaded.mark(), original.mark());
+        assert_eq!(loaded.comment(), original.comment());
+    }
+
+    // Append one mark by saving the whole chain again
+    let mut generator = loaded_generator;
+    infos.push(next_info(&mut generator, ""));
+    chain_dir::save(dir.path(), &generator, &infos, false).unwrap();
+
+    let (reloaded_generator, reloaded_infos) =
+        chain_dir::load(dir.path()).unwrap();
+    assert_eq!(reloaded_generator.next_seq(), 5);
+    assert_eq!(reloaded_infos.len(), 5);
+    assert_eq!(reloaded_infos[4].mark(), infos[4].mark());
+    assert_eq!(reloaded_infos[4].comment(), "");
+    assert!(ProvenanceMark::is_sequence_valid(
+        &reloaded_infos
+            .iter()
+            .map(|i| i.mark().clone())
+            .collect::<Vec<_>>()
+    ));
+}
+
+#[test]
+fn test_chain_dir_refuses_overwrite() {
+    let dir = tempfile::tempdir().unwrap();
+    let mut generator = ProvenanceMarkGenerator::new_with_passphrase(
+        ProvenanceMarkResolution::Low,
+        "Wolf",
+    );
+    let info = next_info(&mut generator, "Original");
+    chain_dir::save(dir.path(), &generator, &[info.clone()], false).unwrap();
+
+    let changed = ProvenanceMarkInfo::new(info.mark().clone(), "Changed");
+    let err =
+        chain_dir::save(dir.path(), &generator, &[changed.clone()], false)
+            .unwrap_err();
+    assert!(matches!(err, Error::FileExists { .. }), "unexpected: {err}");
+
+    chain_dir::save(dir.path(), &generator, &[changed], true).unwrap();
+    let (_, infos) = chain_dir::load(dir.path()).unwrap();
+    assert_eq!(infos[0].comment(), "Changed");
+}
+
+#[test]
+fn test_chain_dir_tolerates_missing_comment() {
+    let dir = tempfile::tempdir().unwrap();
+    let mut generator = ProvenanceMarkGenerator::new_with_passphrase(
+        ProvenanceMarkResolution::Low,
+        "Wolf",
+    );
+    let info = next_info(&mut generator, "");
+    chain_dir::save(dir.path(), &generator, &[info], false).unwrap();
+
+    let json =
+        std::fs::read_to_string(chain_dir::mark_file_path(dir.path(), 0))
+            .unwrap();
+    assert!(!json.contains("comment"));
+    let (_, infos) = chain_dir::load(dir.path()).unwrap();
+    assert_eq!(infos[0].comment(), "");
+}
+
+#[test]
+fn test_chain_dir_reports_bad_files() {
+    let dir = tempfile::tempdir().unwrap();
+    let mut generator = ProvenanceMarkGenerator::new_with_passphrase(
+        ProvenanceMarkResolution::Low,
+        "Wolf",
+    );
+    let infos: Vec<_> = (0..3).map(|_| next_info(&mut generator, "")).collect();
+    chain_dir::save(dir.path(), &generator, &infos, false).unwrap();
+
+    // A corrupted mark file is reported with its path
+    let corrupted = chain_dir::mark_file_path(dir.path(), 1);
+    std::fs::write(&corrupted, "{ not json").unwrap();
+    let err = chain_dir::load(dir.path()).unwrap_err();
+    assert!(
+        matches!(err, Error::InvalidFile { .. }),
+        "unexpected: {err}"
+    );
+    assert!(err.to_string().contains(&corrupted.display().to_string()));
+
+    // A mark stored under the wrong number is rejected
+    chain_dir::save(dir.path(), &generator, &infos, true).unwrap();
+    std::fs::rename(
+        chain_dir::mark_file_path(dir.path(), 2),
+        chain_dir::mark_file_path(dir.path(), 7),
+    )
+    .unwrap();
+    let err = chain_dir::load(dir.path()).unwrap_err();
+    assert!(
+        err.to_string()
+            .contains("file index 7 does not match mark seq 2"),
+        "unexpected: {err}"
+    );
+}
@@ -0,0 +1,137 @@
+use chrono::TimeZone;
+use dcbor::Date;
+use provenance_mark::{chain_merge::*, *};
+
+mod common;
+use common::daily_marks;
+
+fn date(day: usize, hour: u32) -> Date {
+    Date::from_datetime(
+        chrono::Utc
+            .with_ymd_and_hms(2023, 6, 20, hour, 0, 0)
+            .single()
+            .unwrap()
+            .checked_add_signed(chrono::Duration::days(day as i64))
+            .unwrap(),
+    )
+}
+
+fn generator() -> ProvenanceMarkGenerator {
+    ProvenanceMarkGenerator::new_with_passphrase(
+        ProvenanceMarkResolution::Low,
+        "test",
+    )
+}
+
+/// `marks[..seq]` followed by `count` different marks, issued in the morning
+/// of the days the originals were issued.
+fn fork_at(
+    marks: &[ProvenanceMark],
+    seq: usize,
+    count: usize,
+) -> Vec<ProvenanceMark> {
+    let mut generator = generator();
+    for mark in &marks[..seq] {
+        generator.commit_speculation(mark).unwrap();
+    }
+    let mut forked = marks[..seq].to_vec();
+    for day in seq..seq + count {
+        forked.push(generator.next(date(day, 6), Some("fork")));
+    }
+    forked
+}
+
+#[test]
+fn test_resolve_fork() {
+    let a = daily_marks(ProvenanceMarkResolution::Low, "test", 50);
+    let b = fork_at(&a, 40, 5);
+
+    let outcome = resolve(&a, &b, MergeStrategy::PreferLongerVerifiedRun);
+    assert_eq!(outcome.common_prefix(), &a[..40]);
+    assert_eq!(outcome.branch(MergeBranch::A), &a[40..]);
+    assert_eq!(outcome.branch(MergeBranch::B), &b[40..]);
+    assert_eq!(outcome.verified_run(MergeBranch::A), 10);
+    assert_eq!(outcome.verified_run(MergeBranch::B), 5);
+    assert_eq!(outcome.diverged_at_seq(), Some(40));
+    assert_eq!(outcome.chosen(), Some(MergeBranch::A));
+    assert_eq!(outcome.canonical(), a);
+    assert_eq!(outcome.head(), a.last());
+
+    let outcome = resolve(&a, &b, MergeStrategy::PreferEarlierDates);
+    assert_eq!(outcome.common_prefix(), &a[..40]);
+    assert_eq!(outcome.chosen(), Some(MergeBranch::B));
+    assert_eq!(outcome.canonical(), b);
+    assert_eq!(outcome.head(), b.last());
+
+    let outcome = resolve(&a, &b, MergeStrategy::KeepBoth);
+    assert_eq!(outcome.chosen(), None);
+    assert_eq!(outcome.branch(MergeBranch::A), &a[40..]);
+    assert_eq!(outcome.branch(MergeBranch::B), &b[40..]);
+    assert_eq!(outcome.canonical(), &a[..40]);
+    assert_eq!(outcome.head(), Some(&a[39]));
+    assert!(ProvenanceMark::is_sequence_valid(&outcome.canonical()));
+
+    // The copies may be given in any order
+    let mut shuffled = b.clone();
+    shuffled.reverse();
+    assert_eq!(
+        resolve(&a, &shuffled, MergeStrategy::KeepBoth),
+        resolve(&a, &b, MergeStrategy::KeepBoth)
+    );
+}
+
+#[test]
+fn test_resolve_prefers_verified_marks() {
+    let full = daily_marks(ProvenanceMarkResolution::Low, "test", 50);
+    let b = fork_at(&full, 40, 5);
+
+    // A gap after seq 42 leaves only three verified marks in branch A
+    let mut a = full.clone();
+    a.remove(43);
+
+    let outcome = resolve(&a, &b, MergeStrategy::PreferLongerVerifiedRun);
+    assert_eq!(outcome.branch(MergeBranch::A).len(), 9);
+    assert_eq!(outcome.verified_run(MergeBranch::A), 3);
+    assert_eq!(outcome.chosen(), Some(MergeBranch::B));
+    assert_eq!(outcome.canonical(), b);
+
+    // The canonical sequence stops at the gap when its branch is chosen
+    let outcome = resolve(&a, &b[..42], MergeStrategy::PreferLongerVerifiedRun);
+    assert_eq!(outcome.chosen(), Some(MergeBranch::A));
+    assert_eq!(outcome.canonical(), &full[..43]);
+    assert_eq!(outcome.head(), Some(&full[42]));
+
+    // A gap before the fork ends the common prefix
+    let mut a = full.clone();
+    a.remove(20);
+    let outcome = resolve(&a, &b, MergeStrategy::KeepBoth);
+    assert_eq!(outcome.common_prefix(), &full[..20]);
+    assert_eq!(outcome.diverged_at_seq(), Some(20));
+}
+
+#[test]
+fn test_resolve_without_divergence() {
+    let a = daily_marks(ProvenanceMarkResolution::Low, "test", 30);
+    let b = daily_marks(ProvenanceMarkResolution::Low, "test", 50);
+
+    for strategy in [
+        MergeStrategy::PreferLongerVerifiedRun,
+        MergeStrategy::PreferEarlierDates,
+    ] {
+        let outcome = resolve(&a, &b, strategy);
+        assert_eq!(outcome.common_prefix(), a);
+        assert!(outcome.branch(MergeBranch::A).is_empty());
+        assert_eq!(outcome.branch(MergeBranch::B), &b[30..]);
+        assert_eq!(outcome.diverged_at_seq(), None);
+        assert_eq!(outcome.chosen(), Some(MergeBranch::B), "{strategy:?}");
+        assert_eq!(outcome.canonical(), b);
+    }
+
+    let outcome = resolve(&b, &b, MergeStrategy::PreferEarlierDates);
+    assert_eq!(outcome.common_prefix(), b);
+    assert_eq!(outcome.canonical(), b);
+
+    let outcome = resolve(&[], &[], MergeStrategy::KeepBoth);
+    assert!(outcome.canonical().is_empty());
+    assert_eq!(outcome.head(), None);
+}
@@ -0,0 +1,96 @@
+use provenance_mark::*;
+
+mod common;
+use common::daily_marks;
+
+#[test]
+fn test_chain_origin_info() {
+    let low = ProvenanceMarkResolution::Low;
+    let alice = daily_marks(low, "alice", 4);
+    let bob = daily_marks(low, "bob", 5);
+
+    // Alice's chain with its genesis, Bob's without his first two marks
+    let mut all = alice.clone();
+    all.extend(bob[2..].iter().cloned());
+    let report = ProvenanceMark::validate(all);
+
+    let origin = report.chain_origin_info(alice[2].chain_id()).unwrap();
+    assert_eq!(origin.genesis(), Some(&alice[0]));
+    assert_eq!(origin.earliest_known_seq(), 0);
+    assert_eq!(origin.earliest_known_date(), alice[0].date());
+    assert_eq!(origin.to_string(), "genesis present, dated 2023-06-20");
+
+    let origin = report.chain_origin_info(bob[4].chain_id()).unwrap();
+    assert_eq!(origin.genesis(), None);
+    assert_eq!(origin.earliest_known_seq(), 2);
+    assert_eq!(origin.earliest_known_date(), bob[2].date());
+    assert_eq!(
+        origin.to_string(),
+        "genesis absent; earliest known mark is seq 2, dated 2023-06-22"
+    );
+
+    let other = daily_marks(low, "carol", 1);
+    assert!(report.chain_origin_info(other[0].chain_id()).is_none());
+}
+
+#[test]
+fn test_chain_fingerprint() {
+    let known = [
+        (
+            ProvenanceMarkResolution::Low,
+            "test",
+            "262b5561638371c84e0ea776ffdb69b6f6fa5627325187f8607f6197aa114ec9",
+        ),
+        (
+            ProvenanceMarkResolution::Medium,
+            "test",
+            "af664dca8a182a9084477cb7172d4b42d0ecf467073f105e38711d70431a46c5",
+        ),
+        (
+            ProvenanceMarkResolution::Quartile,
+            "test",
+            "74fb3fb3b71a78bd2710c122286d13111957b62b8490214f8e07defc16787c2e",
+        ),
+        (
+            ProvenanceMarkResolution::High,
+            "test",
+            "ab19a459d328870636b9a2cbd6787af9ea85b57bfa397cbcce717415f7a12785",
+        ),
+        (
+            ProvenanceMarkResolution::Low,
+            "Wolf",
+            "6f507ae8e4faa265c14d53ed8407bca291bc1abd60540e7cfab80c533ec7b6d4",
+        ),
+    ];
+    for (res, passphrase, expected) in known {
+        let marks = daily_marks(res, passphrase, 3);
+        // Every mark of a chain has the same fingerprint, genesis or not
+        for mark in &marks {
+            assert_eq!(hex::encode(mark.chain_fingerprint()), expected);
+        }
+        let report = ProvenanceMark::validate(marks[1..].to_vec());
+        assert_eq!(
+            hex::encode(report.chains()[0].chain_fingerprint()),
+            expected
+        );
+    }
+}
+
+#[test]
+#[cfg(feature = "json")]
+fn test_chain_fingerprint_json() {
+    let marks = daily_marks(ProvenanceMarkResolution::Low, "test", 2);
+    let report = ProvenanceMark::validate(marks.clone());
+    let json: serde_json::Value = serde_json::from_str(
+        &report.format(ValidationReportFormat::JsonCompact),
+    )
+    .unwrap();
+    assert_eq!(
+        json["chains"][0]["chain_fingerprint"],
+        hex::encode(marks[0].chain_fingerprint())
+    );
+
+    let validator =
+        jsonschema::validator_for(&ValidationReport::json_schema()).unwrap();
+    assert!(validator.is_valid(&json));
+}
@@ -0,0 +1,138 @@
+use provenance_mark::*;
+
+mod common;
+use common::daily_marks;
+
+/// `marks` with a different mark issued at `seq` and after.
+fn fork_at(marks: &[ProvenanceMark], seq: usize) -> Vec<ProvenanceMark> {
+    let mut generator = ProvenanceMarkGenerator::new_with_passphrase(
+        ProvenanceMarkResolution::Low,
+        "test",
+    );
+    for mark in &marks[..seq] {
+        generator.commit_speculation(mark).unwrap();
+    }
+    let mut forked = marks[..seq].to_vec();
+    for mark in &marks[seq..] {
+        forked.push(generator.next(mark.date(), Some("fork")));
+    }
+    forked
+}
+
+#[test]
+fn test_compare_equal_and_mirror() {
+    let marks = daily_marks(ProvenanceMarkResolution::Low, "test", 6);
+    let local = ProvenanceMark::validate(marks[..4].to_vec());
+    let mirror = ProvenanceMark::validate(marks.clone());
+
+    let chain = &local.chains()[0];
+    assert_eq!(chain.compare(chain), ChainRelation::Equal);
+    assert_eq!(
+        chain.compare(&mirror.chains()[0]),
+        ChainRelation::PrefixOfOther
+    );
+    assert_eq!(
+        mirror.chains()[0].compare(chain),
+        ChainRelation::ExtensionOfOther
+    );
+
+    // A copy with a gap is still contained in the full chain
+    let gappy = ProvenanceMark::validate(vec![
+        marks[0].clone(),
+        marks[1].clone(),
+        marks[3].clone(),
+    ]);
+    assert_eq!(
+        gappy.chains()[0].compare(chain),
+        ChainRelation::PrefixOfOther
+    );
+}
+
+#[test]
+fn test_compare_diverged() {
+    let marks = daily_marks(ProvenanceMarkResolution::Low, "test", 5);
+    for seq in 1..5 {
+        let forked = fork_at(&marks, seq);
+        let ours = ProvenanceMark::validate(marks.clone());
+        let theirs = ProvenanceMark::validate(forked);
+        assert_eq!(
+            ours.chains()[0].compare(&theirs.chains()[0]),
+            ChainRelation::Diverged { at_seq: seq as u32 }
+        );
+        assert_eq!(
+            theirs.chains()[0].compare(&ours.chains()[0]),
+            ChainRelation::Diverged { at_seq: seq as u32 }
+        );
+    }
+
+    // A shorter copy that forked still diverges
+    let ours = ProvenanceMark::validate(marks[..3].to_vec());
+    let theirs = ProvenanceMark::validate(fork_at(&marks, 2));
+    assert_eq!(
+        ours.chains()[0].compare(&theirs.chains()[0]),
+        ChainRelation::Diverged { at_seq: 2 }
+    );
+}
+
+#[test]
+fn test_compare_disjoint() {
+    let test = ProvenanceMark::validate(daily_marks(
+        ProvenanceMarkResolution::Low,
+        "test",
+        3,
+    ));
+    let alice = ProvenanceMark::validate(daily_marks(
+        ProvenanceMarkResolution::Low,
+        "alice",
+        3,
+    ));
+    assert_eq!(
+        test.chains()[0].compare(&alice.chains()[0]),
+        ChainRelation::Disjoint
+    );
+
+    // Same chain, but no marks in common
+    let marks = daily_marks(ProvenanceMarkResolution::Low, "test", 6);
+    let early = ProvenanceMark::validate(marks[..2].to_vec());
+    let late = ProvenanceMark::validate(marks[3..].to_vec());
+    assert_eq!(
+        early.chains()[0].compare(&late.chains()[0]),
+        ChainRelation::Disjoint
+    );
+}
+
+#[test]
+fn test_compare_chains() {
+    let test = daily_marks(ProvenanceMarkResolution::Low, "test", 4);
+    let alice = daily_marks(ProvenanceMarkResolution::Low, "alice", 2);
+    let bob = daily_marks(ProvenanceMarkResolution::Low, "bob", 2);
+
+    let mut ours = test[..2].to_vec();
+    ours.extend(alice.iter().cloned());
+    let mut theirs = test.clone();
+    theirs.extend(bob.iter().cloned());
+
+    let ours = ProvenanceMark::validate(ours);
+    let theirs = ProvenanceMark::validate(theirs);
+    let relations = ours.compare_chains(&theirs);
+    assert_eq!(relations.len(), 3);
+    assert_eq!(
+        relations[test[0].chain_id().as_bytes()],
+        ChainRelation::PrefixOfOther
+    );
+    assert_eq!(
+        relations[alice[0].chain_id().as_bytes()],
+        ChainRelation::ExtensionOfOther
+    );
+    assert_eq!(
+        relations[bob[0].chain_id().as_bytes()],
+        ChainRelation::PrefixOfOther
+    );
+
+    assert!(
+        theirs
+            .compare_chains(&theirs)
+            .values()
+            .all(|relation| *relation == ChainRelation::Equal)
+    );
+}
@@ -0,0 +1,101 @@
+#![cfg(feature = "arrow")]
+
+use std::sync::Arc;
+
+use bc_ur::UREncodable;
+use dcbor::Date;
+use provenance_mark::{
+    columnar::{
+        arrow::{
+            array::{Array, ArrayRef, BinaryArray, StringArray},
+            record_batch::RecordBatch,
+        },
+        *,
+    },
+    *,
+};
+
+fn marks() -> Vec<ProvenanceMark> {
+    let mut marks = Vec::new();
+    for (res, passphrase) in [
+        (ProvenanceMarkResolution::Low, "test"),
+        (ProvenanceMarkResolution::Medium, "Wolf"),
+        (ProvenanceMarkResolution::Quartile, "zebra"),
+        (ProvenanceMarkResolution::High, "apple"),
+    ] {
+        let mut generator =
+            ProvenanceMarkGenerator::new_with_passphrase(res, passphrase);
+        for day in 0..3 {
+            let date =
+                Date::from_string(&format!("2023-06-{:02}", 20 + day)).unwrap();
+            let info = (day == 1).then_some("Lorem ipsum sit dolor amet.");
+            marks.push(generator.next(date, info));
+        }
+    }
+    marks
+}
+
+#[test]
+fn test_record_batch_round_trip() {
+    let marks = marks();
+    let batch = marks_to_record_batch(&marks).unwrap();
+    assert_eq!(batch.schema(), mark_schema());
+    assert_eq!(batch.num_rows(), marks.len());
+
+    let info = batch
+        .column_by_name("info")
+        .unwrap()
+        .as_any()
+        .downcast_ref::<BinaryArray>()
+        .unwrap();
+    assert_eq!(info.null_count(), 8);
+    let ur = batch
+        .column_by_name("ur")
+        .unwrap()
+        .as_any()
+        .downcast_ref::<StringArray>()
+        .unwrap();
+    assert_eq!(ur.value(5), marks[5].ur_string());
+
+    assert_eq!(record_batch_to_marks(&batch).unwrap(), marks);
+
+    let empty = marks_to_record_batch(&[]).unwrap();
+    assert!(record_batch_to_marks(&empty).unwrap().is_empty());
+}
+
+#[test]
+fn test_record_batch_wrong_key_length() {
+    let marks = marks();
+    let batch = marks_to_record_batch(&marks).unwrap();
+
+    // Give the second low-resolution mark a medium-resolution key
+    let keys: Vec<&[u8]> = marks
+        .iter()
+        .enumerate()
+        .map(|(row, mark)| {
+            if row == 1 {
+                marks[3].key().as_bytes()
+            } else {
+                mark.key().as_bytes()
+            }
+        })
+        .collect();
+    let mut columns = batch.columns().to_vec();
+    let key_index = batch.schema().index_of("key").unwrap();
+    columns[key_index] =
+        Arc::new(BinaryArray::from_iter_values(keys)) as ArrayRef;
+    let batch = RecordBatch::try_new(batch.schema(), columns).unwrap();
+
+    let error = record_batch_to_marks(&batch).unwrap_err();
+    assert!(
+        matches!(&error, Error::InvalidRow { row: 1, details }
+            if details.contains("invalid key length")),
+        "{error}"
+    );
+
+    let batch = batch.project(&[0, 1, 2, 3, 5, 6, 7]).unwrap();
+    assert!(matches!(
+        record_batch_to_marks(&batch),
+        Err(Error::InvalidColumn { column, .. }) if column == "key"
+    ));
+}
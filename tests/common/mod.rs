@@ -27,3 +27,48 @@ macro_rules! assert_actual_expected {
         }
     };
 }
+
+use chrono::TimeZone;
+use dcbor::Date;
+use provenance_mark::{
+    ProvenanceMark, ProvenanceMarkGenerator, ProvenanceMarkResolution,
+};
+
+/// The first `count` marks of the chain generated from `passphrase`, one a
+/// day at noon UTC starting on 2023-06-20.
+pub fn daily_marks(
+    res: ProvenanceMarkResolution,
+    passphrase: &str,
+    count: usize,
+) -> Vec<ProvenanceMark> {
+    let mut generator =
+        ProvenanceMarkGenerator::new_with_passphrase(res, passphrase);
+    (0..count)
+        .map(|i| {
+            let date = Date::from_datetime(
+                chrono::Utc
+                    .with_ymd_and_hms(2023, 6, 20, 12, 0, 0)
+                    .single()
+                    .unwrap()
+                    .checked_add_signed(chrono::Duration::days(i as i64))
+                    .unwrap(),
+            );
+            generator.next(date, None::<&str>)
+        })
+        .collect()
+}
+
+/// The first `count` marks of the chain generated from `passphrase`, all
+/// dated 2023-06-20.
+pub fn same_day_marks(
+    res: ProvenanceMarkResolution,
+    passphrase: &str,
+    count: usize,
+) -> Vec<ProvenanceMark> {
+    let mut generator =
+        ProvenanceMarkGenerator::new_with_passphrase(res, passphrase);
+    let date = Date::from_string("2023-06-20").unwrap();
+    (0..count)
+        .map(|_| generator.next(date, None::<&str>))
+        .collect()
+}
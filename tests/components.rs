@@ -0,0 +1,63 @@
+#![cfg(feature = "components")]
+
+use bc_components::Seed;
+use bc_ur::prelude::*;
+use hex_literal::hex;
+use provenance_mark::{crypto_utils::sha256, *};
+
+const SEED_BYTES: [u8; 32] =
+    hex!("59f2293a5bce7d4de59e71b4207ac5d2e1c84a5a2c6d4b3e9f8a7b6c5d4e3f20");
+
+#[test]
+fn test_bc_seed_roundtrip() {
+    let seed = ProvenanceSeed::from_bytes(SEED_BYTES);
+    let bc_seed = seed.to_bc_seed(Some("Essays"), Some("Marks my essays"));
+    assert_eq!(bc_seed.as_bytes(), &SEED_BYTES);
+    assert_eq!(bc_seed.name(), "Essays");
+    assert_eq!(bc_seed.note(), "Marks my essays");
+    assert_eq!(ProvenanceSeed::try_from_bc_seed(&bc_seed).unwrap(), seed);
+
+    let bc_seed = seed.to_bc_seed(None, None);
+    assert_eq!(bc_seed.name(), "");
+    assert_eq!(ProvenanceSeed::try_from_bc_seed(&bc_seed).unwrap(), seed);
+}
+
+#[test]
+fn test_bc_seed_wrong_length() {
+    let bc_seed = Seed::new_opt(vec![0u8; 16], None, None, None).unwrap();
+    assert!(matches!(
+        ProvenanceSeed::try_from_bc_seed(&bc_seed),
+        Err(Error::InvalidSeedLength { actual: 16 })
+    ));
+    assert!(
+        ProvenanceMarkGenerator::new_with_bc_seed(
+            ProvenanceMarkResolution::Low,
+            &bc_seed
+        )
+        .is_err()
+    );
+}
+
+#[test]
+fn test_generator_from_ur_seed() {
+    let ur = ProvenanceSeed::from_bytes(SEED_BYTES)
+        .to_bc_seed(Some("Essays"), None)
+        .ur_string();
+    assert!(ur.starts_with("ur:seed/"));
+
+    let bc_seed = Seed::from_ur_string(&ur).unwrap();
+    let res = ProvenanceMarkResolution::Medium;
+    let generator =
+        ProvenanceMarkGenerator::new_with_bc_seed(res, &bc_seed).unwrap();
+    assert_eq!(
+        generator.chain_id(),
+        &sha256(SEED_BYTES)[..res.link_length()]
+    );
+    assert_eq!(
+        generator,
+        ProvenanceMarkGenerator::new_with_seed(
+            res,
+            ProvenanceSeed::from_bytes(SEED_BYTES)
+        )
+    );
+}
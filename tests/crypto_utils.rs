@@ -12,6 +12,27 @@ fn test_sha256() {
     );
 }
 
+#[test]
+fn test_sha256_stream() {
+    let mut stream = Sha256Stream::new();
+    stream.update(b"Hello");
+    stream.update(b" ");
+    stream.update(b"World");
+    assert_eq!(stream.finalize(), sha256(b"Hello World"));
+}
+
+#[test]
+fn test_sha256_reader() {
+    let data = vec![b'a'; 100_000];
+    assert_eq!(
+        sha256_reader(data.as_slice()).unwrap(),
+        hex!(
+            "6d1cf22d7cc09b085dfc25ee1a1f3ae0265804c607bc2074ad253bcc82fd81ee"
+        )
+    );
+    assert_eq!(sha256_reader(data.as_slice()).unwrap(), sha256(&data));
+}
+
 #[test]
 fn test_extend_key() {
     let data = b"Hello World";
@@ -33,3 +54,33 @@ fn test_obfuscate() {
     let deobfuscated = obfuscate(key, obfuscated);
     assert_eq!(deobfuscated, message);
 }
+
+#[test]
+fn test_hkdf_hmac_sha256_info() {
+    // RFC 5869, test case 1
+    let okm = hkdf_hmac_sha256_info(
+        [0x0b; 22],
+        hex!("000102030405060708090a0b0c"),
+        hex!("f0f1f2f3f4f5f6f7f8f9"),
+        42,
+    );
+    assert_eq!(
+        okm,
+        hex!(
+            "3cb25f25faacd57a90434f64d0362f2a2d2d0a90cf1a5a4c5db02d56ecc4c5bf34007208d5b887185865"
+        )
+    );
+
+    // An empty info matches the info-less derivation
+    let data = b"Hello World";
+    assert_eq!(
+        hkdf_hmac_sha256_info(data, [], [], 32),
+        hkdf_hmac_sha256(data, [], 32)
+    );
+    assert_eq!(
+        hkdf_hmac_sha256_info(data, [], b"provenance-mark", 32),
+        hex!(
+            "0edd0d087c22f50382a616c7347a542be4e5685872c8516fccd819c94e86d289"
+        )
+    );
+}
@@ -97,3 +97,111 @@ fn test_6_byte_dates() {
     let invalid_serialized = hex!("e5940a78a800");
     assert!(Date::deserialize_6_bytes(&invalid_serialized).is_err());
 }
+
+fn out_of_range_details(result: provenance_mark::Result<impl Sized>) -> String {
+    match result {
+        Err(provenance_mark::Error::DateOutOfRange { details }) => details,
+        Err(other) => panic!("unexpected error: {other}"),
+        Ok(_) => panic!("expected an error"),
+    }
+}
+
+#[test]
+fn test_dates_out_of_range() {
+    use provenance_mark::ProvenanceMarkResolution as Res;
+
+    let before = Date::from_datetime(
+        Utc.with_ymd_and_hms(1999, 12, 31, 0, 0, 0).unwrap(),
+    );
+    let min_message = "before minimum representable date (2001-01-01)";
+    assert_eq!(
+        out_of_range_details(before.serialize_4_bytes()),
+        min_message
+    );
+    assert_eq!(
+        out_of_range_details(before.serialize_6_bytes()),
+        min_message
+    );
+
+    // Just before the minimum, within the first second
+    let just_before = Date::from_datetime(
+        Utc.with_ymd_and_hms(2000, 12, 31, 23, 59, 59)
+            .unwrap()
+            .with_nanosecond(500_000_000)
+            .unwrap(),
+    );
+    assert_eq!(
+        out_of_range_details(just_before.serialize_4_bytes()),
+        min_message
+    );
+
+    let after_4 =
+        Date::from_datetime(Utc.with_ymd_and_hms(2138, 1, 1, 0, 0, 0).unwrap());
+    assert_eq!(
+        out_of_range_details(after_4.serialize_4_bytes()),
+        format!(
+            "after maximum representable date ({})",
+            Res::Medium.date_range().1
+        )
+    );
+    assert!(after_4.serialize_6_bytes().is_ok());
+
+    let after_6 = Date::from_datetime(
+        Utc.with_ymd_and_hms(10000, 1, 1, 0, 0, 0).unwrap(),
+    );
+    assert_eq!(
+        out_of_range_details(after_6.serialize_6_bytes()),
+        format!(
+            "after maximum representable date ({})",
+            Res::Quartile.date_range().1
+        )
+    );
+
+    // The 2-byte encoding reports its narrower range by year
+    assert!(matches!(
+        before.serialize_2_bytes(),
+        Err(provenance_mark::Error::YearOutOfRange { year: 1999 })
+    ));
+    assert!(after_4.serialize_2_bytes().is_ok());
+    let after_2 =
+        Date::from_datetime(Utc.with_ymd_and_hms(2151, 1, 1, 0, 0, 0).unwrap());
+    assert!(matches!(
+        after_2.serialize_2_bytes(),
+        Err(provenance_mark::Error::YearOutOfRange { year: 2151 })
+    ));
+}
+
+#[test]
+fn test_resolution_date_range() {
+    use provenance_mark::ProvenanceMarkResolution as Res;
+
+    let date = |y, mo, d, h, mi, s| {
+        Date::from_datetime(Utc.with_ymd_and_hms(y, mo, d, h, mi, s).unwrap())
+    };
+    assert_eq!(
+        Res::Low.date_range(),
+        (date(2023, 1, 1, 0, 0, 0), date(2150, 12, 31, 0, 0, 0))
+    );
+    assert_eq!(
+        Res::Medium.date_range(),
+        (date(2001, 1, 1, 0, 0, 0), date(2137, 2, 7, 6, 28, 15))
+    );
+    let max_6 = Date::from_datetime(
+        Utc.with_ymd_and_hms(9999, 12, 31, 23, 59, 59)
+            .unwrap()
+            .with_nanosecond(999_000_000)
+            .unwrap(),
+    );
+    for res in [Res::Quartile, Res::High] {
+        assert_eq!(res.date_range(), (date(2001, 1, 1, 0, 0, 0), max_6));
+    }
+
+    // Both endpoints round-trip at every resolution
+    for res in [Res::Low, Res::Medium, Res::Quartile, Res::High] {
+        let (min, max) = res.date_range();
+        for endpoint in [min, max] {
+            let bytes = res.serialize_date(endpoint).unwrap();
+            assert_eq!(res.deserialize_date(&bytes).unwrap(), endpoint);
+        }
+    }
+}
@@ -0,0 +1,154 @@
+use dcbor::{Date, prelude::*};
+use provenance_mark::*;
+
+fn date(day: u32) -> Date {
+    Date::from_string(&format!("2023-06-{day:02}")).unwrap()
+}
+
+fn generator() -> ProvenanceMarkGenerator {
+    ProvenanceMarkGenerator::new_with_passphrase(
+        ProvenanceMarkResolution::Quartile,
+        "Wolf",
+    )
+}
+
+#[test]
+fn test_encrypted_info_roundtrip() {
+    let mut generator = generator();
+    let genesis = generator.next(date(20), None::<&str>);
+    let first = generator
+        .next_with_encrypted_info(date(21), b"draft of chapter one")
+        .unwrap();
+    let second = generator
+        .next_with_encrypted_info(date(22), b"draft of chapter one")
+        .unwrap();
+    assert!(ProvenanceMark::is_sequence_valid(&[
+        genesis,
+        first.clone(),
+        second.clone()
+    ]));
+
+    // Each mark has its own nonce, so equal plaintexts encrypt differently.
+    assert_ne!(first.info(), second.info());
+    let info = first.info().unwrap();
+    assert!(
+        info.try_into_expected_tagged_value(TAG_ENCRYPTED_INFO)
+            .is_ok()
+    );
+
+    let seed = generator.seed().unwrap();
+    assert_eq!(seed.decrypt_info(&first).unwrap(), b"draft of chapter one");
+    assert_eq!(seed.decrypt_info(&second).unwrap(), b"draft of chapter one");
+
+    // The mark round-trips through its UR with the payload intact.
+    let decoded = ProvenanceMark::from_ur_string(&first.ur_string()).unwrap();
+    assert_eq!(
+        seed.decrypt_info(&decoded).unwrap(),
+        b"draft of chapter one"
+    );
+}
+
+#[test]
+fn test_encrypted_info_wrong_seed() {
+    let mut generator = generator();
+    generator.next(date(20), None::<&str>);
+    let mark = generator
+        .next_with_encrypted_info(date(21), b"secret")
+        .unwrap();
+
+    let other = ProvenanceSeed::new_with_passphrase("Fox");
+    assert!(matches!(
+        other.decrypt_info(&mark),
+        Err(Error::InfoAuthenticationFailed)
+    ));
+
+    // A generator restored from a continuation has no seed to encrypt with.
+    let mut continued = ProvenanceMarkGenerator::from_continuation(
+        generator.export_continuation(),
+    );
+    let next_seq = continued.next_seq();
+    assert!(matches!(
+        continued.next_with_encrypted_info(date(22), b"secret"),
+        Err(Error::MissingSeed)
+    ));
+    assert_eq!(continued.next_seq(), next_seq);
+}
+
+#[test]
+fn test_encrypted_info_missing() {
+    let mut generator = generator();
+    let genesis = generator.next(date(20), None::<&str>);
+    let plain = generator.next(date(21), Some("not encrypted"));
+    let seed = generator.seed().unwrap();
+    for mark in [&genesis, &plain] {
+        assert!(matches!(
+            seed.decrypt_info(mark),
+            Err(Error::NoEncryptedInfo)
+        ));
+    }
+}
+
+/// The byte string payload of a mark's encrypted info.
+fn payload(mark: &ProvenanceMark) -> Vec<u8> {
+    mark.info()
+        .unwrap()
+        .try_into_expected_tagged_value(TAG_ENCRYPTED_INFO)
+        .unwrap()
+        .try_into_byte_string()
+        .unwrap()
+}
+
+#[test]
+fn test_encrypted_info_nonce_not_reused() {
+    // A forked generator issues the same sequence number twice
+    let mut generator = generator();
+    generator.next(date(20), None::<&str>);
+    let mut fork = generator.dangerous_clone_for_fork();
+    let plaintext = b"the same plaintext at the same seq";
+    let first = generator
+        .next_with_encrypted_info(date(21), plaintext)
+        .unwrap();
+    let second = fork.next_with_encrypted_info(date(21), plaintext).unwrap();
+    assert_eq!(first.seq(), second.seq());
+
+    // Each encryption draws its own nonce, so the ciphertexts differ
+    let (first_payload, second_payload) = (payload(&first), payload(&second));
+    assert_eq!(first_payload.len(), 12 + plaintext.len() + 32);
+    assert_ne!(first_payload[..12], second_payload[..12]);
+    assert_ne!(first_payload[12..], second_payload[12..]);
+
+    let seed = generator.seed().unwrap();
+    assert_eq!(seed.decrypt_info(&first).unwrap(), plaintext);
+    assert_eq!(seed.decrypt_info(&second).unwrap(), plaintext);
+}
+
+#[test]
+fn test_encrypted_info_nonce_authenticated() {
+    let mut generator = generator();
+    generator.next(date(20), None::<&str>);
+    let mark = generator
+        .next_with_encrypted_info(date(21), b"secret")
+        .unwrap();
+
+    // The same mark with the first byte of its nonce flipped
+    let mut altered = payload(&mark);
+    altered[0] ^= 1;
+    let tampered = ProvenanceMark::from_fields(
+        mark.res(),
+        mark.key().clone(),
+        mark.hash().clone(),
+        mark.chain_id().clone(),
+        mark.seq().into(),
+        mark.date(),
+        CBOR::to_tagged_value(
+            TAG_ENCRYPTED_INFO,
+            CBOR::to_byte_string(altered),
+        )
+        .to_cbor_data(),
+    )
+    .unwrap();
+    assert!(matches!(
+        generator.seed().unwrap().decrypt_info(&tampered),
+        Err(Error::InfoAuthenticationFailed)
+    ));
+}
@@ -0,0 +1,73 @@
+use provenance_mark::*;
+
+fn three_errors() -> Error {
+    Error::Multiple(vec![
+        ValidationIssue::KeyMismatch.into(),
+        Error::InvalidSeedLength { actual: 16 },
+        ValidationIssue::SequenceGap { expected: 3, actual: 5 }.into(),
+    ])
+}
+
+#[test]
+fn test_multiple_display() {
+    assert_eq!(
+        three_errors().to_string(),
+        "3 errors: \
+         (1) validation error: key mismatch: current hash was not generated from next key; \
+         (2) invalid seed length: expected 32 bytes, got 16 bytes; \
+         (3) validation error: sequence number gap: expected 3, got 5"
+    );
+}
+
+#[test]
+fn test_multiple_flatten() {
+    let nested = Error::Multiple(vec![
+        three_errors(),
+        Error::Multiple(vec![Error::Multiple(vec![
+            ValidationIssue::NonGenesisAtZero.into(),
+        ])]),
+    ]);
+    match nested.flatten() {
+        Error::Multiple(errors) => {
+            assert_eq!(errors.len(), 4);
+            assert!(errors.iter().all(|e| !matches!(e, Error::Multiple(_))));
+            assert!(matches!(
+                errors[3],
+                Error::Validation(ValidationIssue::NonGenesisAtZero)
+            ));
+        }
+        other => panic!("unexpected error: {other}"),
+    }
+
+    // A single wrapped error is unwrapped
+    let single =
+        Error::Multiple(vec![Error::Multiple(vec![Error::InvalidInfoCbor])]);
+    assert!(matches!(single.flatten(), Error::InvalidInfoCbor));
+}
+
+#[test]
+fn test_multiple_from_issues() {
+    let error: Error = vec![ValidationIssue::KeyMismatch].into();
+    assert!(matches!(
+        error,
+        Error::Validation(ValidationIssue::KeyMismatch)
+    ));
+
+    let error: Error = vec![
+        ValidationIssue::KeyMismatch,
+        ValidationIssue::InvalidGenesisKey,
+    ]
+    .into();
+    assert!(matches!(error, Error::Multiple(ref errors) if errors.len() == 2));
+
+    // No issues give an empty `Multiple`
+    let error: Error = Vec::<ValidationIssue>::new().into();
+    assert!(matches!(error, Error::Multiple(ref errors) if errors.is_empty()));
+    assert_eq!(error.to_string(), "0 errors: ");
+}
+
+#[test]
+fn test_multiple_to_dcbor() {
+    let error: dcbor::Error = three_errors().into();
+    assert!(error.to_string().contains(&three_errors().to_string()));
+}
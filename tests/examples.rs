@@ -0,0 +1,67 @@
+#![cfg(feature = "examples")]
+
+use bc_ur::prelude::*;
+use provenance_mark::{examples::*, *};
+
+#[test]
+fn test_demo_chain_first_urs() {
+    // Pinned so that accidental changes to determinism are caught.
+    let cases = [
+        (
+            ProvenanceMarkResolution::Low,
+            "ur:provenance/lfaegdasbdwzyarhjnbyjzytwlmkftuecaemahwmfgaxcl",
+        ),
+        (
+            ProvenanceMarkResolution::Medium,
+            "ur:provenance/lfadhdcxasbdwzyarehpvehpsrmyltuobzpauehkotmojoayvdlfmenbstbkbkvljnpydsonurlefxhf",
+        ),
+        (
+            ProvenanceMarkResolution::Quartile,
+            "ur:provenance/lfaohdftasbdwzyarehpvehpfghsprgrkbnseebniyjpmdwmwmpeoyryvyhlyksgfgiomewkemvofyinqzeeahcpbegyzstksahfmkjtmupkfnfzimctrhmdnlotcywzfrzo",
+        ),
+        (
+            ProvenanceMarkResolution::High,
+            "ur:provenance/lfaxhdimasbdwzyarehpvehpfghsprgrkbnseebnytfggshewlhhlryklamdgepkrnayhykeonfzdkdalabwgoclgwesbyrpectyhhimjnnysogaayvwadrlrnonksbydesbfrihkkwywyttimcetlwzwektgdtnlpcnlneycfktasrfwketnlyktkvylygroeoejkoyhgbdmudwclnnjtgelsvdlgcxdlso",
+        ),
+    ];
+    for (res, expected) in cases {
+        let chain = demo_chain(res, 3);
+        assert_eq!(chain.len(), 3);
+        assert_eq!(chain[0].ur_string(), expected);
+        assert!(ProvenanceMark::is_sequence_valid(&chain));
+        assert_eq!(demo_chain(res, 3), chain);
+    }
+}
+
+#[test]
+fn test_demo_reports() {
+    let res = ProvenanceMarkResolution::Low;
+
+    let clean = demo_report(res, DemoScenario::Clean);
+    assert!(!clean.has_issues());
+
+    let gap = demo_report(res, DemoScenario::Gap);
+    let sequences = gap.chains()[0].sequences();
+    assert_eq!(sequences.len(), 2);
+    assert_eq!(
+        sequences[1].marks()[0].issues(),
+        &[ValidationIssue::SequenceGap { expected: 2, actual: 3 }]
+    );
+
+    let missing = demo_report(res, DemoScenario::MissingGenesis);
+    assert!(!missing.chains()[0].has_genesis());
+
+    let mismatch = demo_report(res, DemoScenario::HashMismatch);
+    let sequences = mismatch.chains()[0].sequences();
+    assert_eq!(sequences.len(), 2);
+    assert_eq!(sequences[1].start_seq(), 2);
+    assert_eq!(sequences[1].end_seq(), 4);
+    assert!(matches!(
+        sequences[1].marks()[0].issues(),
+        [ValidationIssue::HashMismatch { .. }]
+    ));
+
+    let two_chains = demo_report(res, DemoScenario::TwoChains);
+    assert_eq!(two_chains.chains().len(), 2);
+    assert!(two_chains.chains().iter().all(|c| c.has_genesis()));
+}
@@ -0,0 +1,133 @@
+#![cfg(feature = "ffi")]
+
+use std::{
+    ffi::{CString, c_char},
+    ptr,
+};
+
+use bc_ur::UREncodable;
+use provenance_mark::{ffi::*, *};
+
+mod common;
+use common::same_day_marks;
+
+fn parse(mark: &ProvenanceMark) -> *mut PmMark {
+    let ur = CString::new(mark.ur_string()).unwrap();
+    let handle = unsafe { pm_mark_parse_ur(ur.as_ptr()) };
+    assert!(!handle.is_null());
+    handle
+}
+
+fn last_error() -> String {
+    let mut buf = [0 as c_char; 256];
+    let len = unsafe { pm_last_error_message(buf.as_mut_ptr(), buf.len()) };
+    assert!(len < buf.len());
+    let bytes: Vec<u8> = buf[..len].iter().map(|c| *c as u8).collect();
+    String::from_utf8(bytes).unwrap()
+}
+
+#[test]
+fn test_ffi_round_trip() {
+    let marks = same_day_marks(ProvenanceMarkResolution::Low, "test", 3);
+    let handles: Vec<_> = marks.iter().map(parse).collect();
+
+    unsafe {
+        assert_eq!(pm_mark_seq(handles[1]), 1);
+        assert_eq!(
+            pm_mark_date(handles[1]),
+            marks[1].date().datetime().timestamp()
+        );
+
+        let mut chain_id = [0u8; 32];
+        let len =
+            pm_mark_chain_id(handles[1], chain_id.as_mut_ptr(), chain_id.len());
+        assert_eq!(&chain_id[..len], marks[1].chain_id());
+
+        let mut identifier = [0 as c_char; 32];
+        let len = pm_mark_identifier(
+            handles[0],
+            identifier.as_mut_ptr(),
+            identifier.len(),
+        );
+        assert_eq!(len, "WHAT HANG SOAP SETS".len());
+        assert_eq!(identifier[len], 0);
+        let bytes: Vec<u8> =
+            identifier[..len].iter().map(|c| *c as u8).collect();
+        assert_eq!(bytes, b"WHAT HANG SOAP SETS");
+
+        assert_eq!(pm_mark_precedes(handles[0], handles[1]), 1);
+        assert_eq!(pm_mark_precedes(handles[1], handles[2]), 1);
+        assert_eq!(pm_mark_precedes(handles[0], handles[2]), 0);
+        assert!(last_error().contains("sequence number gap"));
+
+        for handle in handles {
+            pm_mark_free(handle);
+        }
+    }
+}
+
+#[test]
+fn test_ffi_truncated_output() {
+    let handle =
+        parse(&same_day_marks(ProvenanceMarkResolution::Low, "test", 3)[0]);
+    unsafe {
+        let mut buf = [0x7f as c_char; 8];
+        let len = pm_mark_identifier(handle, buf.as_mut_ptr(), buf.len());
+        assert_eq!(len, 19);
+        let bytes: Vec<u8> = buf.iter().map(|c| *c as u8).collect();
+        assert_eq!(bytes, b"WHAT HA\0");
+
+        // A null buffer only reports the length
+        assert_eq!(pm_mark_identifier(handle, ptr::null_mut(), 0), 19);
+        assert_eq!(pm_mark_chain_id(handle, ptr::null_mut(), 0), 4);
+
+        let mut chain_id = [0u8; 2];
+        assert_eq!(pm_mark_chain_id(handle, chain_id.as_mut_ptr(), 2), 4);
+        assert_eq!(chain_id, [0xb1, 0x6a]);
+
+        pm_mark_free(handle);
+    }
+}
+
+#[test]
+fn test_ffi_errors() {
+    unsafe {
+        assert!(pm_mark_parse_ur(ptr::null()).is_null());
+        assert_eq!(last_error(), "null UR string");
+
+        let invalid = c"ur:provenance/lfaegdtokebz";
+        assert!(pm_mark_parse_ur(invalid.as_ptr()).is_null());
+        assert!(!last_error().is_empty());
+
+        let not_utf8 = [0xff as c_char, 0];
+        assert!(pm_mark_parse_ur(not_utf8.as_ptr()).is_null());
+        assert_eq!(last_error(), "UR string is not valid UTF-8");
+
+        assert_eq!(pm_mark_seq(ptr::null()), 0);
+        assert_eq!(last_error(), "null mark handle");
+        assert_eq!(pm_mark_precedes(ptr::null(), ptr::null()), -1);
+
+        // Freeing null is allowed
+        pm_mark_free(ptr::null_mut());
+    }
+}
+
+#[test]
+fn test_ffi_header_matches_cbindgen() {
+    let dir = env!("CARGO_MANIFEST_DIR");
+    let config =
+        cbindgen::Config::from_file(format!("{dir}/cbindgen.toml")).unwrap();
+    let mut generated = Vec::new();
+    cbindgen::Builder::new()
+        .with_config(config)
+        .with_src(format!("{dir}/src/ffi.rs"))
+        .generate()
+        .unwrap()
+        .write(&mut generated);
+    assert_eq!(
+        String::from_utf8(generated).unwrap(),
+        include_str!("../include/provenance_mark.h"),
+        "regenerate the header with \
+         `cbindgen --config cbindgen.toml src/ffi.rs -o include/provenance_mark.h`"
+    );
+}
@@ -0,0 +1,88 @@
+use provenance_mark::*;
+
+mod common;
+use common::same_day_marks;
+
+#[test]
+fn test_get_by_fingerprint() {
+    let marks = same_day_marks(ProvenanceMarkResolution::Low, "Wolf", 5);
+    let index = FingerprintIndex::build(&marks);
+    assert_eq!(index.len(), 5);
+    for mark in &marks {
+        assert_eq!(index.get(&mark.fingerprint()), Some(mark));
+    }
+    assert_eq!(index.get(&[0u8; 32]), None);
+}
+
+#[test]
+fn test_resolve_unique_prefix() {
+    let marks = same_day_marks(ProvenanceMarkResolution::Low, "Wolf", 5);
+    let report = ProvenanceMark::validate(marks.clone());
+    let index = report.fingerprint_index();
+
+    let fingerprint = hex::encode(marks[3].fingerprint());
+    assert_eq!(
+        index.resolve_prefix(&fingerprint[..16]).unwrap(),
+        PrefixResolution::Unique(&marks[3])
+    );
+    assert_eq!(
+        index.resolve_prefix(&fingerprint.to_uppercase()).unwrap(),
+        PrefixResolution::Unique(&marks[3])
+    );
+}
+
+#[test]
+fn test_resolve_ambiguous_prefix() {
+    // With 257 marks, at least two must share a first fingerprint byte.
+    let marks = same_day_marks(ProvenanceMarkResolution::Low, "Wolf", 257);
+    let index = FingerprintIndex::build(&marks);
+
+    let mut by_first_byte: Vec<Vec<&ProvenanceMark>> = vec![Vec::new(); 256];
+    for mark in &marks {
+        by_first_byte[mark.fingerprint()[0] as usize].push(mark);
+    }
+    let (byte, expected) = by_first_byte
+        .iter()
+        .enumerate()
+        .find(|(_, matching)| matching.len() >= 2)
+        .unwrap();
+
+    let PrefixResolution::Ambiguous(found) =
+        index.resolve_prefix(&format!("{byte:02x}")).unwrap()
+    else {
+        panic!("expected an ambiguous resolution");
+    };
+    assert_eq!(found.len(), expected.len());
+    assert!(expected.iter().all(|mark| found.contains(mark)));
+}
+
+#[test]
+fn test_resolve_prefix_not_found() {
+    let marks = same_day_marks(ProvenanceMarkResolution::Low, "Wolf", 1);
+    let index = FingerprintIndex::build(&marks);
+    let mut fingerprint = marks[0].fingerprint();
+    fingerprint[0] ^= 0xff;
+    assert_eq!(
+        index
+            .resolve_prefix(&hex::encode(&fingerprint[..4]))
+            .unwrap(),
+        PrefixResolution::NotFound
+    );
+}
+
+#[test]
+fn test_resolve_invalid_prefix() {
+    let index = FingerprintIndex::build(&same_day_marks(
+        ProvenanceMarkResolution::Low,
+        "Wolf",
+        1,
+    ));
+
+    let error = index.resolve_prefix("3fa").unwrap_err();
+    assert!(matches!(error, Error::InvalidFingerprintPrefix { .. }));
+    assert!(error.to_string().contains("odd number of hex digits"));
+
+    assert!(index.resolve_prefix("").is_err());
+    assert!(index.resolve_prefix("zz").is_err());
+    assert!(index.resolve_prefix(&"00".repeat(33)).is_err());
+}
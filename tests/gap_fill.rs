@@ -0,0 +1,123 @@
+use provenance_mark::*;
+
+mod common;
+use common::daily_marks;
+
+/// Validate `marks` with the marks at `missing` left out.
+fn report_without(
+    marks: &[ProvenanceMark],
+    missing: &[usize],
+) -> ValidationReport {
+    let present = marks
+        .iter()
+        .enumerate()
+        .filter(|(i, _)| !missing.contains(i))
+        .map(|(_, mark)| mark.clone())
+        .collect();
+    ProvenanceMark::validate(present)
+}
+
+#[test]
+fn test_fills_gap_with_missing_mark() {
+    let marks = daily_marks(ProvenanceMarkResolution::Low, "test", 5);
+    let report = report_without(&marks, &[2]);
+    let chain = &report.chains()[0];
+    assert_eq!(chain.sequences().len(), 2);
+
+    let result = chain.fills_gap(&marks[2]);
+    assert!(result.chain_id_matches());
+    assert!(result.in_gap());
+    assert_eq!(result.follows_previous(), Some(true));
+    assert_eq!(result.precedes_next(), Some(true));
+    assert!(result.is_fill());
+
+    // The full recompute closes the gap
+    let report = report.with_additional_marks([marks[2].clone()]);
+    assert!(!report.has_issues());
+    assert_eq!(report.chains()[0].sequences().len(), 1);
+    assert_eq!(report.marks().len(), 5);
+    assert_eq!(report.duplicates_removed(), 0);
+}
+
+#[test]
+fn test_fills_gap_wrong_key() {
+    let marks = daily_marks(ProvenanceMarkResolution::Low, "test", 5);
+    let report = report_without(&marks, &[2]);
+    let chain = &report.chains()[0];
+
+    // Right chain, sequence number, and date, but a key that does not link
+    let impostor = ProvenanceMark::new(
+        marks[2].res(),
+        LinkKey::new(marks[2].res(), vec![0x11; 4]).unwrap(),
+        LinkKey::new(marks[2].res(), vec![0x22; 4]).unwrap(),
+        marks[2].chain_id().clone(),
+        2,
+        marks[2].date(),
+        None::<String>,
+    )
+    .unwrap();
+
+    let result = chain.fills_gap(&impostor);
+    assert!(result.chain_id_matches());
+    assert!(result.in_gap());
+    assert_eq!(result.follows_previous(), Some(false));
+    assert_eq!(result.precedes_next(), Some(false));
+    assert!(!result.is_fill());
+}
+
+#[test]
+fn test_fills_gap_not_in_gap() {
+    let marks = daily_marks(ProvenanceMarkResolution::Low, "test", 5);
+    let report = report_without(&marks, &[2]);
+    let chain = &report.chains()[0];
+
+    // Already present
+    let result = chain.fills_gap(&marks[1]);
+    assert!(!result.in_gap());
+    assert_eq!(result.follows_previous(), None);
+    assert_eq!(result.precedes_next(), None);
+    assert!(!result.is_fill());
+
+    // Beyond the head of the chain
+    let result = report_without(&marks, &[4]).chains()[0].fills_gap(&marks[4]);
+    assert!(!result.in_gap());
+
+    // From another chain
+    let other = ProvenanceMarkGenerator::new_with_passphrase(
+        ProvenanceMarkResolution::Low,
+        "alice",
+    )
+    .next(marks[2].date(), None::<String>);
+    assert!(!chain.fills_gap(&other).chain_id_matches());
+}
+
+#[test]
+fn test_fills_gap_in_middle_of_longer_gap() {
+    let marks = daily_marks(ProvenanceMarkResolution::Low, "test", 6);
+    let report = report_without(&marks, &[2, 3]);
+    let chain = &report.chains()[0];
+
+    let result = chain.fills_gap(&marks[2]);
+    assert_eq!(result.follows_previous(), Some(true));
+    assert_eq!(result.precedes_next(), None);
+    assert!(result.is_fill());
+
+    let report = report.with_additional_marks([marks[2].clone()]);
+    let chain = &report.chains()[0];
+    assert_eq!(chain.sequences().len(), 2);
+    let result = chain.fills_gap(&marks[3]);
+    assert_eq!(result.follows_previous(), Some(true));
+    assert_eq!(result.precedes_next(), Some(true));
+}
+
+#[test]
+fn test_with_additional_marks_keeps_options() {
+    let marks = daily_marks(ProvenanceMarkResolution::Low, "test", 3);
+    let options = ValidationOptions::new()
+        .with_labels([(marks[0].chain_id().to_vec(), "Essays")]);
+    let report = ProvenanceMark::validate_opts(marks[..2].to_vec(), &options)
+        .with_additional_marks(marks[1..].to_vec());
+    assert_eq!(report.chains()[0].label(), Some("Essays"));
+    assert_eq!(report.marks().len(), 3);
+    assert_eq!(report.duplicates_removed(), 1);
+}
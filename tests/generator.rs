@@ -0,0 +1,702 @@
+#[cfg(feature = "envelope")]
+use bc_envelope::prelude::*;
+use bc_ur::prelude::*;
+use dcbor::Date;
+use provenance_mark::*;
+
+fn dates() -> Vec<Date> {
+    ["2023-06-20", "2023-06-21", "2023-06-22"]
+        .iter()
+        .map(|s| Date::from_string(*s).unwrap())
+        .collect()
+}
+
+#[test]
+fn test_speculate_then_commit_matches_next() {
+    let mut speculative = ProvenanceMarkGenerator::new_with_passphrase(
+        ProvenanceMarkResolution::Medium,
+        "Wolf",
+    );
+    let mut plain = speculative.dangerous_clone_for_fork();
+
+    for (i, date) in dates().into_iter().enumerate() {
+        let info = format!("Mark {i}");
+        let mark = speculative.speculate(date, Some(info.as_str())).unwrap();
+        // Speculation does not advance the generator
+        assert_eq!(speculative.next_seq(), i as u32);
+
+        speculative.commit_speculation(&mark).unwrap();
+        let expected = plain.next(date, Some(info.as_str()));
+        assert_eq!(mark, expected);
+        assert_eq!(speculative, plain);
+    }
+}
+
+#[test]
+fn test_commit_speculation_after_next_fails() {
+    let mut generator = ProvenanceMarkGenerator::new_with_passphrase(
+        ProvenanceMarkResolution::Low,
+        "Wolf",
+    );
+    let dates = dates();
+    generator.next(dates[0], None::<&str>);
+
+    let speculation = generator.speculate(dates[1], Some("Draft")).unwrap();
+    generator.next(dates[1], Some("Published"));
+    let before = generator.snapshot();
+
+    let err = generator.commit_speculation(&speculation).unwrap_err();
+    assert!(
+        matches!(
+            err,
+            Error::SpeculationMismatch { expected_seq: 2, actual_seq: 1 }
+        ),
+        "unexpected error: {err}"
+    );
+    assert_eq!(generator.snapshot(), before);
+}
+
+#[test]
+fn test_continuation_handoff() {
+    let date = |day: u32| {
+        Date::from_string(&format!("2023-06-{:02}", 20 + day)).unwrap()
+    };
+
+    let mut original = ProvenanceMarkGenerator::new_with_passphrase(
+        ProvenanceMarkResolution::Medium,
+        "Wolf",
+    );
+    let mut marks: Vec<ProvenanceMark> = (0..3)
+        .map(|i| original.next(date(i), None::<&str>))
+        .collect();
+
+    // Hand the chain off to a new custodian via UR
+    let continuation = original.export_continuation();
+    assert_eq!(continuation.next_seq(), 3);
+    assert_eq!(continuation.chain_id(), original.chain_id());
+    let ur = continuation.ur_string();
+    assert!(ur.starts_with("ur:provenance-continuation/"));
+    let received = ChainContinuation::from_ur_string(&ur).unwrap();
+    assert_eq!(received, continuation);
+
+    let mut custodian = ProvenanceMarkGenerator::from_continuation(received);
+    assert!(custodian.seed().is_none());
+    #[cfg(feature = "json")]
+    {
+        // The missing seed is explicit in JSON
+        let json = serde_json::to_value(&custodian).unwrap();
+        assert_eq!(json["seed"], serde_json::Value::Null);
+        let restored: ProvenanceMarkGenerator =
+            serde_json::from_value(json).unwrap();
+        assert_eq!(restored, custodian);
+    }
+    marks.extend((3..5).map(|i| custodian.next(date(i), None::<&str>)));
+
+    let report = ProvenanceMark::validate(marks.clone());
+    assert!(!report.has_issues());
+    assert_eq!(report.chains().len(), 1);
+    assert_eq!(report.chains()[0].sequences().len(), 1);
+
+    // The previous custodian can still issue marks, forking the chain
+    marks.push(original.next(date(4), Some("fork")));
+    let report = ProvenanceMark::validate(marks);
+    assert!(report.has_issues());
+    assert!(report.chains()[0].sequences().len() > 1);
+}
+
+#[test]
+#[cfg(feature = "envelope")]
+fn test_continuation_envelope_roundtrip() {
+    let mut original = ProvenanceMarkGenerator::new_with_passphrase(
+        ProvenanceMarkResolution::Low,
+        "Wolf",
+    );
+    original.next(dates()[0], None::<&str>);
+    let custodian = ProvenanceMarkGenerator::from_continuation(
+        original.export_continuation(),
+    );
+
+    let envelope = custodian.clone().into_envelope();
+    let decoded = ProvenanceMarkGenerator::try_from(envelope).unwrap();
+    assert_eq!(decoded, custodian);
+    assert!(decoded.seed().is_none());
+    // Statistics are not part of the envelope
+    assert_eq!(decoded.created_at(), None);
+    assert_eq!(decoded.marks_issued(), 0);
+}
+
+#[test]
+#[cfg(all(feature = "markdown", feature = "json"))]
+fn test_generator_stats() {
+    let before = Date::now();
+    let mut generator = ProvenanceMarkGenerator::new_with_passphrase(
+        ProvenanceMarkResolution::Low,
+        "Wolf",
+    );
+    assert!(generator.created_at().unwrap().datetime() >= before.datetime());
+    assert_eq!(generator.marks_issued(), 0);
+    assert_eq!(generator.last_issued_at(), None);
+    assert!(
+        generator
+            .summary_markdown()
+            .contains("- Last issued: never")
+    );
+
+    let dates = dates();
+    for date in &dates[..2] {
+        generator.next(*date, None::<&str>);
+    }
+    let mark = generator.speculate(dates[2], None::<&str>).unwrap();
+    generator.commit_speculation(&mark).unwrap();
+
+    let stats = generator.stats();
+    assert_eq!(stats.created_at(), generator.created_at());
+    assert_eq!(stats.marks_issued(), 3);
+    assert_eq!(stats.last_issued_at(), Some(dates[2]));
+
+    let summary = generator.summary_markdown();
+    assert!(summary.contains("#### Chain `090bf2f8`"));
+    assert!(summary.contains("- Marks issued: 3"));
+    assert!(summary.contains("- Last issued: 2023-06-22"));
+
+    // Statistics survive a JSON round trip
+    let json = serde_json::to_string(&generator).unwrap();
+    let restored: ProvenanceMarkGenerator =
+        serde_json::from_str(&json).unwrap();
+    assert_eq!(restored.marks_issued(), 3);
+    assert_eq!(restored.last_issued_at(), Some(dates[2]));
+}
+
+#[test]
+#[cfg(feature = "json")]
+fn test_generator_legacy_json() {
+    // Stored before generators recorded statistics
+    let legacy = r#"{"res":0,"seed":"znwVmbBQb1+QkeD8p5ak890F+UMrzoC5Ke2E1lh0zhA=","chainID":"CQvy+A==","nextSeq":0,"rngState":"NP1R6mPh5Gsz5KjGVReUxRnM4u3NohBbR1jomiWZCCw="}"#;
+    let generator: ProvenanceMarkGenerator =
+        serde_json::from_str(legacy).unwrap();
+    assert_eq!(
+        generator,
+        ProvenanceMarkGenerator::new_with_passphrase(
+            ProvenanceMarkResolution::Low,
+            "Wolf",
+        )
+    );
+    assert_eq!(generator.marks_issued(), 0);
+    assert_eq!(generator.last_issued_at(), None);
+    // A seeded generator whose seed was dropped does not load as a seedless
+    // one
+    let seedless = legacy.replace(
+        r#""seed":"znwVmbBQb1+QkeD8p5ak890F+UMrzoC5Ke2E1lh0zhA=","#,
+        "",
+    );
+    assert_ne!(seedless, legacy);
+    let err =
+        serde_json::from_str::<ProvenanceMarkGenerator>(&seedless).unwrap_err();
+    assert!(err.to_string().contains("missing field `seed`"), "{err}");
+
+    // Not the time it was loaded
+    assert_eq!(generator.created_at(), None);
+    assert!(
+        !serde_json::to_string(&generator)
+            .unwrap()
+            .contains("createdAt")
+    );
+
+    // Every mark before the next sequence number was issued by it
+    let legacy = legacy.replace(r#""nextSeq":0"#, r#""nextSeq":3"#);
+    let mut generator: ProvenanceMarkGenerator =
+        serde_json::from_str(&legacy).unwrap();
+    assert_eq!(generator.next_seq(), 3);
+    assert_eq!(generator.marks_issued(), 3);
+    assert_eq!(generator.stats().marks_issued(), 3);
+    generator.next(Date::from_string("2023-06-20").unwrap(), None::<&str>);
+    assert_eq!(generator.marks_issued(), 4);
+}
+
+#[test]
+fn test_check_info_too_large() {
+    let mut generator = ProvenanceMarkGenerator::new_with_passphrase(
+        ProvenanceMarkResolution::Low,
+        "Wolf",
+    )
+    .with_info_checks(true);
+    let mut map = Map::new();
+    for i in 0..10 {
+        map.insert(i, "lorem ipsum");
+    }
+    let info = CBOR::from(map);
+
+    let check = generator.check_info(&info).unwrap();
+    assert_eq!(check.encoded_size(), 131);
+    assert_eq!(check.limit(), 32);
+    assert!(check.exceeds_limit());
+    assert!(check.is_canonical());
+    assert!(!check.is_ok());
+
+    let error = generator
+        .try_next(dates()[0], Some(info.clone()))
+        .unwrap_err();
+    assert!(matches!(
+        error,
+        Error::InfoTooLarge { size: 131, limit: 32 }
+    ));
+    assert_eq!(
+        error.to_string(),
+        "info payload too large: 131 bytes exceeds the recommended 32 bytes"
+    );
+    assert_eq!(generator.next_seq(), 0);
+
+    // Without enforcement the payload is still accepted.
+    generator.set_info_checks(false);
+    let mark = generator.try_next(dates()[0], Some(info)).unwrap();
+    assert_eq!(mark.seq(), 0);
+}
+
+#[test]
+fn test_check_info_ur_length() {
+    for (res, info) in [
+        (ProvenanceMarkResolution::Low, "Hello".to_string()),
+        (ProvenanceMarkResolution::Medium, "Hello".to_string()),
+        (ProvenanceMarkResolution::Quartile, "x".repeat(200)),
+        (ProvenanceMarkResolution::High, "x".repeat(300)),
+    ] {
+        let mut generator =
+            ProvenanceMarkGenerator::new_with_passphrase(res, "Wolf")
+                .with_info_checks(true);
+        let check = generator.check_info(&info.to_cbor()).unwrap();
+        assert!(check.is_ok(), "{res}");
+
+        let mark = generator.try_next(dates()[0], Some(info)).unwrap();
+        assert_eq!(check.ur_length(), mark.ur_string().len(), "{res}");
+    }
+}
+
+#[test]
+fn test_bootstrap() {
+    let mut generator = ProvenanceMarkGenerator::new_with_passphrase(
+        ProvenanceMarkResolution::Medium,
+        "Wolf",
+    );
+    let mut plain = generator.dangerous_clone_for_fork();
+    let date = dates()[0];
+    let (genesis, first_work) = generator
+        .bootstrap(
+            date,
+            Some(CBOR::from("Announcing the chain")),
+            date,
+            Some(CBOR::from("First work")),
+        )
+        .unwrap();
+    assert!(genesis.is_genesis());
+    assert_eq!(first_work.seq(), 1);
+    assert!(ProvenanceMark::is_sequence_valid(&[
+        genesis.clone(),
+        first_work.clone()
+    ]));
+
+    // The pair matches issuing the marks one at a time.
+    assert_eq!(genesis, plain.next(date, Some("Announcing the chain")));
+    assert_eq!(first_work, plain.next(date, Some("First work")));
+    assert_eq!(generator, plain);
+    assert_eq!(generator.marks_issued(), 2);
+
+    assert!(matches!(
+        generator.bootstrap(date, None, date, None),
+        Err(Error::NotAtGenesis { next_seq: 2 })
+    ));
+}
+
+#[test]
+fn test_bootstrap_earlier_first_work_fails() {
+    let mut generator = ProvenanceMarkGenerator::new_with_passphrase(
+        ProvenanceMarkResolution::Low,
+        "Wolf",
+    );
+    let original = generator.snapshot();
+    let result = generator.bootstrap(dates()[1], None, dates()[0], None);
+    assert!(matches!(
+        result,
+        Err(Error::Validation(ValidationIssue::DateOrdering { .. }))
+    ));
+    assert_eq!(generator.snapshot(), original);
+    assert_eq!(generator.marks_issued(), 0);
+    assert_eq!(generator.last_issued_at(), None);
+}
+
+#[test]
+fn test_bulk_new_derived() {
+    let master = ProvenanceSeed::new_with_passphrase("Institution");
+    let dates = dates();
+    let chains = ProvenanceMarkGenerator::bulk_new_derived(
+        &master,
+        ProvenanceMarkResolution::Low,
+        50,
+        dates[0],
+    )
+    .unwrap();
+    assert_eq!(chains.len(), 50);
+
+    let chain_ids: std::collections::HashSet<Vec<u8>> = chains
+        .iter()
+        .map(|(generator, _)| generator.chain_id().to_vec())
+        .collect();
+    assert_eq!(chain_ids.len(), 50);
+
+    for (i, (generator, genesis)) in chains.iter().enumerate() {
+        assert_eq!(generator.seed(), Some(&master.derive_child(i as u32)));
+        assert!(genesis.is_genesis());
+        assert_eq!(genesis.chain_id(), generator.chain_id());
+
+        // Each generator continues its own chain
+        let mut generator = generator.dangerous_clone_for_fork();
+        let second = generator.next(dates[1], None::<&str>);
+        let report = ProvenanceMark::validate(vec![genesis.clone(), second]);
+        assert!(!report.has_issues(), "chain {i}");
+    }
+
+    // The same master yields the same chains in the same order
+    let again = ProvenanceMarkGenerator::bulk_new_derived(
+        &master,
+        ProvenanceMarkResolution::Low,
+        50,
+        dates[0],
+    )
+    .unwrap();
+    assert_eq!(again, chains);
+
+    // Children differ from each other and from the master
+    assert_ne!(master.derive_child(0), master.derive_child(1));
+    assert_ne!(master.derive_child(0), master);
+}
+
+#[test]
+fn test_bulk_new_derived_date_out_of_range() {
+    let master = ProvenanceSeed::new_with_passphrase("Institution");
+    let date = Date::from_string("2200-01-01").unwrap();
+
+    // Every failing chain is reported
+    let error = ProvenanceMarkGenerator::bulk_new_derived(
+        &master,
+        ProvenanceMarkResolution::Low,
+        3,
+        date,
+    )
+    .unwrap_err();
+    assert!(matches!(error, Error::Multiple(ref errors) if errors.len() == 3));
+
+    // A single failure is reported on its own
+    let error = ProvenanceMarkGenerator::bulk_new_derived(
+        &master,
+        ProvenanceMarkResolution::Low,
+        1,
+        date,
+    )
+    .unwrap_err();
+    assert!(!matches!(error, Error::Multiple(_)));
+}
+
+/// A chain of `count` marks from the seed for "Wolf", with the generator that
+/// issued them.
+fn wolf_chain(count: u32) -> (ProvenanceMarkGenerator, Vec<ProvenanceMark>) {
+    let mut generator = ProvenanceMarkGenerator::new_with_seed(
+        ProvenanceMarkResolution::Medium,
+        ProvenanceSeed::new_with_passphrase("Wolf"),
+    );
+    let marks = (0..count)
+        .map(|i| {
+            let date =
+                Date::from_string(&format!("2023-06-{:02}", 20 + i)).unwrap();
+            generator.next(date, Some(format!("Mark {i}")))
+        })
+        .collect();
+    (generator, marks)
+}
+
+#[test]
+fn test_recover() {
+    let seed = ProvenanceSeed::new_with_passphrase("Wolf");
+    let (lost, marks) = wolf_chain(5);
+
+    let mut recovered = ProvenanceMarkGenerator::recover(
+        &seed,
+        ProvenanceMarkResolution::Medium,
+        &marks,
+    )
+    .unwrap();
+    assert_eq!(recovered, lost);
+    assert_eq!(recovered.next_seq(), 5);
+    assert_eq!(recovered.marks_issued(), 5);
+    assert_eq!(recovered.last_issued_at(), Some(marks[4].date()));
+
+    let next =
+        recovered.next(Date::from_string("2023-06-25").unwrap(), None::<&str>);
+    assert_eq!(next.seq(), 5);
+    let mut chain = marks.clone();
+    chain.push(next);
+    let report = ProvenanceMark::validate(chain);
+    assert!(!report.has_issues());
+    assert_eq!(report.chains()[0].marks().len(), 6);
+
+    // Missing marks are skipped, in any order
+    let published = [
+        marks[4].clone(),
+        marks[0].clone(),
+        marks[2].clone(),
+        marks[2].clone(),
+    ];
+    let recovered = ProvenanceMarkGenerator::recover(
+        &seed,
+        ProvenanceMarkResolution::Medium,
+        &published,
+    )
+    .unwrap();
+    assert_eq!(recovered, lost);
+    assert_eq!(recovered.marks_issued(), 3);
+
+    // With nothing published, the chain starts over
+    let recovered = ProvenanceMarkGenerator::recover(
+        &seed,
+        ProvenanceMarkResolution::Medium,
+        &[],
+    )
+    .unwrap();
+    assert_eq!(recovered.next_seq(), 0);
+}
+
+#[test]
+fn test_recover_mismatch() {
+    let seed = ProvenanceSeed::new_with_passphrase("Wolf");
+    let (_, mut marks) = wolf_chain(5);
+    let recover = |seed: &ProvenanceSeed,
+                   res: ProvenanceMarkResolution,
+                   published: &[ProvenanceMark]| {
+        match ProvenanceMarkGenerator::recover(seed, res, published) {
+            Err(Error::RecoveryMismatch { seq }) => seq,
+            Err(error) => panic!("unexpected error: {error}"),
+            Ok(_) => panic!("recovery succeeded"),
+        }
+    };
+
+    // A published mark whose info was altered
+    let mut generator = ProvenanceMarkGenerator::recover(
+        &seed,
+        ProvenanceMarkResolution::Medium,
+        &marks[..2],
+    )
+    .unwrap();
+    let original = marks[2].clone();
+    marks[2] = generator.next(original.date(), Some("Tampered"));
+    assert_eq!(recover(&seed, ProvenanceMarkResolution::Medium, &marks), 2);
+
+    // Two different marks with the same sequence number
+    let published = [marks[0].clone(), marks[2].clone(), original];
+    assert_eq!(
+        recover(&seed, ProvenanceMarkResolution::Medium, &published),
+        2
+    );
+
+    // The wrong seed or resolution
+    let other = ProvenanceSeed::new_with_passphrase("Fox");
+    assert_eq!(recover(&other, ProvenanceMarkResolution::Medium, &marks), 0);
+    assert_eq!(recover(&seed, ProvenanceMarkResolution::Low, &marks), 0);
+}
+
+#[test]
+fn test_next_with_hook_veto() {
+    let mut generator = ProvenanceMarkGenerator::new_with_passphrase(
+        ProvenanceMarkResolution::Medium,
+        "Wolf",
+    );
+    let date = dates()[0];
+    generator.next(date, Some("Genesis"));
+    let before = generator.snapshot();
+
+    let mut seen = None;
+    let result = generator.next_with_hook(date, Some("Essay"), |candidate| {
+        seen = Some(candidate.clone());
+        Err("policy check failed")
+    });
+    assert!(matches!(
+        result,
+        Err(IssueError::Rejected("policy check failed"))
+    ));
+    assert_eq!(generator.snapshot(), before);
+
+    // The next call produces the identical candidate
+    let vetoed = seen.unwrap();
+    let mark = generator
+        .next_with_hook(date, Some("Essay"), |candidate| {
+            assert_eq!(candidate, &vetoed);
+            Ok::<_, String>(None)
+        })
+        .unwrap();
+    assert_eq!(mark, vetoed);
+    assert_eq!(generator.next_seq(), 2);
+}
+
+#[test]
+fn test_next_with_hook_annotation() {
+    let mut generator = ProvenanceMarkGenerator::new_with_passphrase(
+        ProvenanceMarkResolution::Quartile,
+        "Wolf",
+    );
+    let dates = dates();
+    let genesis = generator.next(dates[0], None::<String>);
+
+    let mut info = Map::new();
+    info.insert("title", "On provenance");
+    let mut approval = Map::new();
+    approval.insert("approved-by", "policy-engine");
+    let mark = generator
+        .next_with_hook(dates[1], Some(CBOR::from(info)), |_| {
+            Ok::<_, String>(Some(CBOR::from(approval.clone())))
+        })
+        .unwrap();
+
+    let mut expected = Map::new();
+    expected.insert("title", "On provenance");
+    expected.insert("approved-by", "policy-engine");
+    assert_eq!(mark.info(), Some(CBOR::from(expected)));
+    assert!(genesis.precedes(&mark));
+
+    // Round-trips through the mark's UR
+    let restored = ProvenanceMark::from_ur_string(&mark.ur_string()).unwrap();
+    assert_eq!(restored.info(), mark.info());
+
+    // Info that is not a map is paired with the hook's annotation
+    let mark = generator
+        .next_with_hook(dates[2], Some("Essay"), |_| {
+            Ok::<_, String>(Some(CBOR::from("approved")))
+        })
+        .unwrap();
+    assert_eq!(
+        mark.info(),
+        Some(CBOR::from(vec![
+            CBOR::from("Essay"),
+            CBOR::from("approved")
+        ]))
+    );
+    assert_eq!(generator.next_seq(), 3);
+}
+
+#[test]
+fn test_terminate() {
+    let mut generator = ProvenanceMarkGenerator::new_with_passphrase(
+        ProvenanceMarkResolution::Medium,
+        "Wolf",
+    );
+    let dates = dates();
+    let genesis = generator.next(dates[0], Some("Genesis"));
+    let last = generator
+        .terminate(dates[1], Some("Project closed"))
+        .unwrap();
+    assert!(!genesis.is_termination());
+    assert!(last.is_termination());
+    assert!(genesis.precedes(&last));
+    assert!(generator.is_terminated());
+    assert_eq!(generator.next_seq(), 2);
+
+    // The info is kept inside the termination tag
+    let info = last.info().unwrap();
+    let info = info
+        .try_into_expected_tagged_value(TAG_CHAIN_TERMINATION)
+        .unwrap();
+    assert_eq!(info, CBOR::from("Project closed"));
+
+    let before = generator.snapshot();
+    assert!(matches!(
+        generator.try_next(dates[2], None::<String>),
+        Err(Error::ChainTerminated)
+    ));
+    assert!(matches!(
+        generator.terminate(dates[2], None::<String>),
+        Err(Error::ChainTerminated)
+    ));
+    assert_eq!(generator.snapshot(), before);
+
+    #[cfg(feature = "json")]
+    {
+        let json = serde_json::to_string(&generator).unwrap();
+        assert!(json.contains(r#""terminated":true"#));
+        let restored: ProvenanceMarkGenerator =
+            serde_json::from_str(&json).unwrap();
+        assert!(restored.is_terminated());
+        assert_eq!(restored, generator);
+    }
+
+    // A generator recovered from the published chain is terminated too
+    let recovered = ProvenanceMarkGenerator::recover(
+        generator.seed().unwrap(),
+        ProvenanceMarkResolution::Medium,
+        &[genesis, last],
+    )
+    .unwrap();
+    assert!(recovered.is_terminated());
+}
+
+#[test]
+fn test_snapshot() {
+    let mut generator = ProvenanceMarkGenerator::new_with_passphrase(
+        ProvenanceMarkResolution::Quartile,
+        "Wolf",
+    );
+    let dates = dates();
+    generator.next(dates[0], None::<&str>);
+    let snapshot = generator.snapshot();
+
+    assert_eq!(snapshot.res(), generator.res());
+    assert_eq!(snapshot.chain_id(), generator.chain_id());
+    assert_eq!(snapshot.next_seq(), 1);
+    assert!(!snapshot.is_terminated());
+    assert_eq!(snapshot.stats(), generator.stats());
+    assert_eq!(snapshot.clone(), snapshot);
+
+    let expected = snapshot.expected_next(dates[1], Some("Next")).unwrap();
+    let mark = generator.next(dates[1], Some("Next"));
+    assert_eq!(expected, mark);
+    assert!(snapshot.is_expected_next(&mark));
+    assert!(!generator.snapshot().is_expected_next(&mark));
+
+    // The snapshot does not follow the generator
+    assert_eq!(snapshot.next_seq(), 1);
+    assert_ne!(generator.snapshot(), snapshot);
+}
+
+#[test]
+#[cfg(feature = "json")]
+fn test_snapshot_json() {
+    let mut generator = ProvenanceMarkGenerator::new_with_passphrase(
+        ProvenanceMarkResolution::Low,
+        "Wolf",
+    );
+    generator.next(dates()[0], None::<&str>);
+    let snapshot = generator.snapshot();
+
+    let json = serde_json::to_string(&snapshot).unwrap();
+    assert_eq!(json, serde_json::to_string(&generator).unwrap());
+    let decoded: GeneratorSnapshot = serde_json::from_str(&json).unwrap();
+    assert_eq!(decoded, snapshot);
+}
+
+#[test]
+fn test_dangerous_clone_for_fork() {
+    let mut generator = ProvenanceMarkGenerator::new_with_passphrase(
+        ProvenanceMarkResolution::Medium,
+        "Wolf",
+    );
+    let dates = dates();
+    generator.next(dates[0], None::<&str>);
+
+    let mut fork = generator.dangerous_clone_for_fork();
+    assert_eq!(fork, generator);
+    assert_eq!(fork.stats(), generator.stats());
+
+    // Both copies issue the same mark, forking the chain once either issues
+    // a different one
+    let mark = generator.next(dates[1], Some("Original"));
+    assert_eq!(fork.next(dates[1], Some("Original")), mark);
+    let forked = fork.next(dates[2], Some("Fork"));
+    let next = generator.next(dates[2], Some("Continued"));
+    assert_eq!(forked.seq(), next.seq());
+    assert_ne!(forked, next);
+}
@@ -0,0 +1,125 @@
+use dcbor::Date;
+use provenance_mark::{hash::link_hash, *};
+
+struct Vector {
+    res: ProvenanceMarkResolution,
+    seq_bytes: &'static str,
+    date_bytes: &'static str,
+    info: Option<&'static str>,
+    hash: &'static str,
+}
+
+/// The test vectors published in the `hash` module documentation.
+const VECTORS: [Vector; 4] = [
+    Vector {
+        res: ProvenanceMarkResolution::Low,
+        seq_bytes: "0001",
+        date_bytes: "00d4",
+        info: None,
+        hash: "c971b97b",
+    },
+    Vector {
+        res: ProvenanceMarkResolution::Medium,
+        seq_bytes: "00000001",
+        date_bytes: "2a41cc40",
+        info: None,
+        hash: "7ae9e9500417f171",
+    },
+    Vector {
+        res: ProvenanceMarkResolution::Quartile,
+        seq_bytes: "00000001",
+        date_bytes: "00a51105da00",
+        info: Some("Hello"),
+        hash: "e5b76e52f88835b95d21689c0cd703b0",
+    },
+    Vector {
+        res: ProvenanceMarkResolution::High,
+        seq_bytes: "00000001",
+        date_bytes: "00a51105da00",
+        info: Some("Hello"),
+        hash: "95596a46dbeaa9c2243f30e8d25c80c10fcd797b42eb5ccc7a0039dc8caf1cc7",
+    },
+];
+
+/// `link_length` bytes counting up from `start`.
+fn counting(res: ProvenanceMarkResolution, start: u8) -> Vec<u8> {
+    (0..res.link_length() as u8).map(|i| start + i).collect()
+}
+
+fn info_bytes(info: Option<&str>) -> Vec<u8> {
+    info.map(|info| dcbor::CBOR::from(info).to_cbor_data())
+        .unwrap_or_default()
+}
+
+#[test]
+fn test_link_hash_vectors() {
+    for vector in VECTORS {
+        let hash = link_hash(
+            vector.res,
+            counting(vector.res, 0x00),
+            counting(vector.res, 0x40),
+            counting(vector.res, 0x80),
+            hex::decode(vector.seq_bytes).unwrap(),
+            hex::decode(vector.date_bytes).unwrap(),
+            info_bytes(vector.info),
+        )
+        .unwrap();
+        assert_eq!(hex::encode(hash), vector.hash, "{:?}", vector.res);
+    }
+}
+
+#[test]
+fn test_link_hash_matches_marks() {
+    let date = Date::from_string("2023-06-20T12:00:00Z").unwrap();
+    for vector in VECTORS {
+        let mark = ProvenanceMark::new(
+            vector.res,
+            LinkKey::new(vector.res, counting(vector.res, 0x00)).unwrap(),
+            LinkKey::new(vector.res, counting(vector.res, 0x40)).unwrap(),
+            ChainId::new(vector.res, counting(vector.res, 0x80)).unwrap(),
+            1,
+            date,
+            vector.info,
+        )
+        .unwrap();
+        assert_eq!(hex::encode(mark.seq_bytes()), vector.seq_bytes);
+        assert_eq!(hex::encode(mark.date_bytes()), vector.date_bytes);
+        assert_eq!(hex::encode(mark.hash()), vector.hash, "{:?}", vector.res);
+    }
+}
+
+#[test]
+fn test_link_hash_rejects_wrong_lengths() {
+    let res = ProvenanceMarkResolution::Medium;
+    let key = counting(res, 0x00);
+    let short = &key[..4];
+    let hash = |key: &[u8],
+                next_key: &[u8],
+                chain_id: &[u8],
+                seq_bytes: &[u8],
+                date_bytes: &[u8]| {
+        link_hash(res, key, next_key, chain_id, seq_bytes, date_bytes, b"")
+    };
+
+    assert!(matches!(
+        hash(short, &key, &key, &[0; 4], &[0; 4]),
+        Err(Error::InvalidKeyLength { expected: 8, actual: 4 })
+    ));
+    assert!(matches!(
+        hash(&key, short, &key, &[0; 4], &[0; 4]),
+        Err(Error::InvalidNextKeyLength { expected: 8, actual: 4 })
+    ));
+    assert!(matches!(
+        hash(&key, &key, short, &[0; 4], &[0; 4]),
+        Err(Error::InvalidChainIdLength { expected: 8, actual: 4 })
+    ));
+    assert!(matches!(
+        hash(&key, &key, &key, &[0; 2], &[0; 4]),
+        Err(Error::InvalidSeqBytesLength { expected: 4, actual: 2 })
+    ));
+    assert!(matches!(
+        hash(&key, &key, &key, &[0; 4], &[0; 6]),
+        Err(Error::InvalidDateBytesLength { expected: 4, actual: 6 })
+    ));
+    assert!(hash(&key, &key, &key, &[0; 4], &[0; 4]).is_ok());
+}
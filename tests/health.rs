@@ -0,0 +1,181 @@
+use dcbor::Date;
+use provenance_mark::*;
+
+mod common;
+use common::daily_marks;
+
+fn health(marks: Vec<ProvenanceMark>) -> HealthDetails {
+    ProvenanceMark::validate(marks).chains()[0].health_details()
+}
+
+/// Scores are pinned so that changes to the formula are deliberate.
+fn assert_score(details: &HealthDetails, expected: f32) {
+    assert!(
+        (details.score() - expected).abs() < 1e-6,
+        "expected {expected}, got {details:?}"
+    );
+}
+
+#[test]
+fn test_health_clean_chain() {
+    let details = health(daily_marks(ProvenanceMarkResolution::Low, "test", 5));
+    assert!(details.has_genesis());
+    assert_eq!(details.main_run_fraction(), 1.0);
+    assert_eq!((details.error_count(), details.gap_count()), (0, 0));
+    assert_eq!(details.freshness(), None);
+    assert_score(&details, 1.0);
+    assert_eq!(
+        ProvenanceMark::validate(daily_marks(
+            ProvenanceMarkResolution::Low,
+            "test",
+            5
+        ))
+        .chains()[0]
+            .health_score(),
+        details.score()
+    );
+}
+
+#[test]
+fn test_health_gap() {
+    let marks = daily_marks(ProvenanceMarkResolution::Low, "test", 5);
+    let details = health(vec![
+        marks[0].clone(),
+        marks[1].clone(),
+        marks[3].clone(),
+        marks[4].clone(),
+    ]);
+    assert_eq!(details.main_run_fraction(), 0.5);
+    assert_eq!((details.error_count(), details.gap_count()), (0, 1));
+    // (1 + 0.5 + 1 + 0.5) / 4
+    assert_score(&details, 0.75);
+}
+
+#[test]
+fn test_health_missing_genesis() {
+    let details = health(
+        daily_marks(ProvenanceMarkResolution::Low, "test", 5)
+            .into_iter()
+            .skip(1)
+            .collect(),
+    );
+    assert!(!details.has_genesis());
+    assert_eq!(details.main_run_fraction(), 1.0);
+    // (0 + 1 + 1 + 1) / 4
+    assert_score(&details, 0.75);
+}
+
+#[test]
+fn test_health_hash_mismatch() {
+    let marks = daily_marks(ProvenanceMarkResolution::Low, "test", 3);
+    let date = Date::from_string("2023-06-22").unwrap();
+    let bad_mark = ProvenanceMark::new(
+        marks[1].res(),
+        marks[1].key().clone(),
+        LinkKey::new(marks[1].res(), marks[0].hash().as_bytes()).unwrap(),
+        marks[1].chain_id().clone(),
+        2,
+        date,
+        None::<String>,
+    )
+    .unwrap();
+    let details = health(vec![marks[0].clone(), marks[1].clone(), bad_mark]);
+    // The reused key warnings are not counted
+    assert_eq!((details.error_count(), details.gap_count()), (1, 0));
+    // (1 + 2/3 + 1/2 + 1) / 4
+    assert_score(&details, 0.791_666_7);
+}
+
+#[test]
+fn test_health_staleness() {
+    let chain = ProvenanceMark::validate(daily_marks(
+        ProvenanceMarkResolution::Low,
+        "test",
+        5,
+    ))
+    .chains()[0]
+        .clone();
+    let max_age = chrono::Duration::days(2);
+
+    // The head is dated 2023-06-24
+    let fresh = Date::from_string("2023-06-25").unwrap();
+    let details = chain.health_details_as_of(&fresh, max_age);
+    assert_eq!(details.freshness(), Some(1.0));
+    assert_score(&details, 1.0);
+
+    let stale = Date::from_string("2023-06-28").unwrap();
+    let details = chain.health_details_as_of(&stale, max_age);
+    assert_eq!(details.freshness(), Some(0.5));
+    assert_score(&details, 0.5);
+}
+
+#[test]
+#[cfg(feature = "json")]
+fn test_health_json() {
+    let report = ProvenanceMark::validate(daily_marks(
+        ProvenanceMarkResolution::Low,
+        "test",
+        5,
+    ));
+    let json: serde_json::Value = serde_json::from_str(
+        &report.format(ValidationReportFormat::JsonCompact),
+    )
+    .unwrap();
+    assert!(json["chains"][0].get("health").is_none());
+
+    let options = FormatOptions::default()
+        .with_health(true)
+        .with_as_of(Date::from_string("2023-06-25").unwrap());
+    let text = report
+        .format_with_options(ValidationReportFormat::JsonPretty, &options);
+    let json: serde_json::Value = serde_json::from_str(&text).unwrap();
+    assert_eq!(
+        json["chains"][0]["health"],
+        serde_json::json!({
+            "score": 1.0,
+            "has_genesis": true,
+            "main_run_fraction": 1.0,
+            "error_count": 0,
+            "gap_count": 0,
+            "freshness": 1.0,
+        })
+    );
+
+    let schema = ValidationReport::json_schema();
+    let validator = jsonschema::validator_for(&schema).unwrap();
+    assert!(validator.is_valid(&json));
+}
+
+#[test]
+#[cfg(feature = "json")]
+fn test_health_json_per_chain() {
+    // A clean chain and a chain missing its genesis mark
+    let mut marks = daily_marks(ProvenanceMarkResolution::Low, "alice", 3);
+    marks.extend(
+        daily_marks(ProvenanceMarkResolution::Low, "bob", 3)[1..].to_vec(),
+    );
+    let report = ProvenanceMark::validate(marks);
+
+    let options = FormatOptions::default().with_health(true);
+    let json: serde_json::Value = serde_json::from_str(
+        &report
+            .format_with_options(ValidationReportFormat::JsonCompact, &options),
+    )
+    .unwrap();
+    for (chain, chain_json) in report
+        .chains()
+        .iter()
+        .zip(json["chains"].as_array().unwrap())
+    {
+        assert_eq!(chain_json["chain_id"], chain.chain_id_hex());
+        assert_eq!(
+            chain_json["health"],
+            serde_json::to_value(chain.health_details()).unwrap()
+        );
+    }
+    assert_ne!(json["chains"][0]["health"], json["chains"][1]["health"]);
+
+    // Serializing a chain on its own leaves out its health
+    let chain = serde_json::to_value(&report.chains()[0]).unwrap();
+    assert!(chain.get("health").is_none());
+}
@@ -1,59 +1,17 @@
-use chrono::TimeZone;
-use dcbor::Date;
-use provenance_mark::*;
+use provenance_mark::{identifier::*, *};
+
+mod common;
+use common::{daily_marks, same_day_marks};
 
 fn make_test_marks(count: usize) -> Vec<ProvenanceMark> {
-    let generator = ProvenanceMarkGenerator::new_with_passphrase(
-        ProvenanceMarkResolution::Low,
-        "Wolf",
-    );
-    let calendar = chrono::Utc;
-    let mut encoded = serde_json::to_string(&generator).unwrap();
-
-    (0..count)
-        .map(|i| {
-            let mut g: ProvenanceMarkGenerator =
-                serde_json::from_str(&encoded).unwrap();
-            let date = Date::from_datetime(
-                calendar
-                    .with_ymd_and_hms(2023, 6, 20, 12, 0, 0)
-                    .single()
-                    .unwrap()
-                    .checked_add_signed(chrono::Duration::days(i as i64))
-                    .unwrap(),
-            );
-            let mark = g.next(date, None::<&str>);
-            encoded = serde_json::to_string(&g).unwrap();
-            mark
-        })
-        .collect()
+    make_marks_for_resolution(ProvenanceMarkResolution::Low, count)
 }
 
 fn make_marks_for_resolution(
     res: ProvenanceMarkResolution,
     count: usize,
 ) -> Vec<ProvenanceMark> {
-    let generator = ProvenanceMarkGenerator::new_with_passphrase(res, "Wolf");
-    let calendar = chrono::Utc;
-    let mut encoded = serde_json::to_string(&generator).unwrap();
-
-    (0..count)
-        .map(|i| {
-            let mut g: ProvenanceMarkGenerator =
-                serde_json::from_str(&encoded).unwrap();
-            let date = Date::from_datetime(
-                calendar
-                    .with_ymd_and_hms(2023, 6, 20, 12, 0, 0)
-                    .single()
-                    .unwrap()
-                    .checked_add_signed(chrono::Duration::days(i as i64))
-                    .unwrap(),
-            );
-            let mark = g.next(date, None::<&str>);
-            encoded = serde_json::to_string(&g).unwrap();
-            mark
-        })
-        .collect()
+    daily_marks(res, "Wolf", count)
 }
 
 // --- id ---
@@ -348,3 +306,183 @@ fn test_disambiguated_with_prefix() {
         assert_eq!(&pfx[5..], no_pfx.as_str());
     }
 }
+
+// --- parsing ---
+
+#[test]
+fn test_parse_bytewords_identifier() {
+    for mark in same_day_marks(ProvenanceMarkResolution::Quartile, "Wolf", 5) {
+        let expected = mark.id()[..4].to_vec();
+        let plain = mark.id_bytewords(4, false);
+        assert_eq!(parse_bytewords_identifier(&plain).unwrap(), expected);
+        assert_eq!(
+            parse_bytewords_identifier(&mark.id_bytewords(4, true)).unwrap(),
+            expected
+        );
+        assert_eq!(
+            parse_bytewords_identifier(&plain.to_lowercase()).unwrap(),
+            expected
+        );
+        assert_eq!(
+            parse_bytewords_identifier(&plain.replace(' ', "-")).unwrap(),
+            expected
+        );
+        assert_eq!(
+            parse_bytewords_identifier(&mark.id_bytewords(8, true)).unwrap(),
+            mark.id()[..8].to_vec()
+        );
+    }
+}
+
+#[test]
+fn test_parse_bytemoji_identifier() {
+    for mark in same_day_marks(ProvenanceMarkResolution::Quartile, "Wolf", 5) {
+        let expected = mark.id()[..4].to_vec();
+        assert_eq!(
+            parse_bytemoji_identifier(&mark.id_bytemoji(4, false)).unwrap(),
+            expected
+        );
+        assert_eq!(
+            parse_bytemoji_identifier(&mark.id_bytemoji(4, true)).unwrap(),
+            expected
+        );
+    }
+}
+
+#[test]
+fn test_parse_identifier_errors() {
+    let mark =
+        &same_day_marks(ProvenanceMarkResolution::Quartile, "Wolf", 5)[0];
+    let mut words: Vec<String> = mark
+        .id_bytewords(4, false)
+        .split(' ')
+        .map(str::to_string)
+        .collect();
+    words[2] = "WAEV".to_string();
+    let error = parse_bytewords_identifier(&words.join(" ")).unwrap_err();
+    assert!(matches!(
+        &error,
+        Error::UnknownIdentifierWords { words } if words == &["WAEV"]
+    ));
+    assert_eq!(error.to_string(), "unknown words in identifier: WAEV");
+
+    assert!(matches!(
+        parse_bytewords_identifier(&words[..1].join(" ")),
+        Err(Error::InvalidIdentifierLength { actual: 1 })
+    ));
+}
+
+#[test]
+fn test_find_by_spoken_identifier() {
+    let marks = same_day_marks(ProvenanceMarkResolution::Quartile, "Wolf", 5);
+    let report = ProvenanceMark::validate(marks.clone());
+    for mark in &marks {
+        let found = report
+            .find_by_spoken_identifier(&mark.id_bytewords(4, true))
+            .unwrap();
+        assert_eq!(found, vec![mark]);
+        let found = report
+            .find_by_spoken_identifier(&mark.id_bytemoji(4, true))
+            .unwrap();
+        assert_eq!(found, vec![mark]);
+    }
+    assert!(
+        report
+            .find_by_spoken_identifier("ABLE ABLE ABLE ABLE")
+            .unwrap()
+            .iter()
+            .all(|mark| mark.id()[..4] == [0, 0, 0, 0])
+    );
+    assert!(report.find_by_spoken_identifier("WAEV JUDO").is_err());
+}
+
+/// `source` reassembled with a hash sharing its first `shared` bytes with
+/// `target`'s hash, so that their Mark IDs share that prefix.
+fn colliding(
+    source: &ProvenanceMark,
+    target: &ProvenanceMark,
+    shared: usize,
+) -> ProvenanceMark {
+    let mut hash = target.hash().to_vec();
+    hash[shared] = !hash[shared];
+    ProvenanceMark::from_fields(
+        source.res(),
+        source.key().clone(),
+        LinkHash::new(source.res(), hash).unwrap(),
+        source.chain_id().clone(),
+        source.seq().into(),
+        source.date(),
+        Vec::new(),
+    )
+    .unwrap()
+}
+
+#[test]
+fn test_identifier_collisions() {
+    let marks = same_day_marks(ProvenanceMarkResolution::Quartile, "Wolf", 5);
+    let report = ProvenanceMark::validate(marks.clone());
+    assert!(report.identifier_collisions().is_empty());
+    assert_eq!(report.minimum_unique_identifier_len(), 4);
+
+    let forged = colliding(&marks[2], &marks[1], 6);
+    let report = ProvenanceMark::validate(vec![
+        marks[0].clone(),
+        marks[1].clone(),
+        forged.clone(),
+        marks[3].clone(),
+    ]);
+    let collisions = report.identifier_collisions();
+    assert_eq!(collisions.len(), 1);
+    let (identifier, colliding_marks) = &collisions[0];
+    assert_eq!(*identifier, marks[1].id_hex()[..8]);
+    assert_eq!(*colliding_marks, vec![&marks[1], &forged]);
+    assert_eq!(report.minimum_unique_identifier_len(), 7);
+
+    // A second, shorter collision does not lower the minimum
+    let other = colliding(&marks[4], &marks[3], 4);
+    let report = ProvenanceMark::validate(vec![
+        marks[1].clone(),
+        forged,
+        marks[3].clone(),
+        other,
+    ]);
+    assert_eq!(report.identifier_collisions().len(), 2);
+    assert_eq!(report.minimum_unique_identifier_len(), 7);
+}
+
+#[test]
+#[cfg(feature = "json")]
+fn test_identifier_statistics_json() {
+    let marks = same_day_marks(ProvenanceMarkResolution::Quartile, "Wolf", 5);
+    let forged = colliding(&marks[2], &marks[1], 4);
+    let report = ProvenanceMark::validate(vec![
+        marks[0].clone(),
+        marks[1].clone(),
+        forged.clone(),
+    ]);
+
+    let json: serde_json::Value = serde_json::from_str(
+        &report.format(ValidationReportFormat::JsonCompact),
+    )
+    .unwrap();
+    assert!(json.get("statistics").is_none());
+
+    let options = FormatOptions::default().with_statistics(true);
+    let json: serde_json::Value = serde_json::from_str(
+        &report
+            .format_with_options(ValidationReportFormat::JsonCompact, &options),
+    )
+    .unwrap();
+    assert_eq!(
+        json["statistics"]["identifier_collisions"],
+        serde_json::json!([{
+            "identifier": marks[1].id_hex()[..8],
+            "marks": [
+                bc_ur::UREncodable::ur_string(&marks[1]),
+                bc_ur::UREncodable::ur_string(&forged),
+            ],
+        }])
+    );
+    assert_eq!(json["statistics"]["minimum_unique_identifier_len"], 5);
+    assert_eq!(json["digest"], hex::encode(report.digest()));
+}
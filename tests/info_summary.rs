@@ -0,0 +1,160 @@
+use dcbor::{Date, prelude::*};
+use provenance_mark::*;
+
+fn mark_with_info(info: Option<String>) -> ProvenanceMark {
+    ProvenanceMarkGenerator::new_with_passphrase(
+        ProvenanceMarkResolution::Low,
+        "test",
+    )
+    .next(Date::from_string("2023-06-20").unwrap(), info)
+}
+
+#[test]
+fn test_info_summary() {
+    assert_eq!(mark_with_info(None).info_summary(64), None);
+
+    let mark = mark_with_info(Some("Hello".to_string()));
+    assert_eq!(mark.info_summary(64).as_deref(), Some(r#""Hello""#));
+    assert_eq!(mark.info_summary(7).as_deref(), Some(r#""Hello""#));
+    assert_eq!(mark.info_summary(4).as_deref(), Some(r#""Hel… (6 bytes)"#));
+
+    let mark = mark_with_info(Some("x".repeat(10_000)));
+    assert_eq!(
+        mark.info_summary(10).as_deref(),
+        Some(r#""xxxxxxxxx… (10003 bytes)"#)
+    );
+}
+
+#[test]
+fn test_large_info_in_debug_and_reports() {
+    let mark = mark_with_info(Some("x".repeat(10_000)));
+
+    let debug = format!("{:?}", mark);
+    assert!(debug.len() < 250, "{debug}");
+    assert!(debug.contains("… (10003 bytes)"), "{debug}");
+
+    let options = FormatOptions::new()
+        .with_always_emit(true)
+        .with_show_info(true);
+    let report = ProvenanceMark::validate(vec![mark.clone()]);
+    let text =
+        report.format_with_options(ValidationReportFormat::Text, &options);
+    assert!(text.len() < 250, "{text}");
+    assert!(text.contains(r#"(genesis mark, info: "xxx"#), "{text}");
+    assert!(text.contains("… (10003 bytes))"), "{text}");
+
+    // Info is not shown unless asked for
+    let text = report.format_with_options(
+        ValidationReportFormat::Text,
+        &FormatOptions::new().with_always_emit(true),
+    );
+    assert!(!text.contains("info:"), "{text}");
+
+    let text = report.format_with_options(
+        ValidationReportFormat::Text,
+        &options.clone().with_redact_info(true),
+    );
+    assert!(
+        text.contains("(genesis mark, info: <info: 10003 bytes>)"),
+        "{text}"
+    );
+    assert!(!text.contains("xxx"), "{text}");
+    // Redacting a report leaves Debug output, which is bounded by length,
+    // unchanged
+    assert_eq!(format!("{:?}", mark), debug);
+
+    // Marks without info are unaffected
+    let debug = format!("{:?}", mark_with_info(None));
+    assert!(!debug.contains("info"), "{debug}");
+}
+
+/// Info wrapped in tags no consumer registers, nested several deep and
+/// inside a map, as a newer tool might produce.
+fn exotic_info() -> CBOR {
+    let mut map = Map::new();
+    map.insert(
+        "ext",
+        CBOR::to_tagged_value(
+            900_001,
+            CBOR::to_tagged_value(900_002, vec![CBOR::from(1), 2.into()]),
+        ),
+    );
+    map.insert("plain", "text");
+    map.insert(CBOR::to_tagged_value(900_003, "key"), "value");
+    CBOR::to_tagged_value(
+        u64::MAX,
+        CBOR::to_tagged_value(900_000, CBOR::from(map)),
+    )
+}
+
+#[test]
+fn test_exotic_info_tags_render() {
+    let mark = ProvenanceMarkGenerator::new_with_passphrase(
+        ProvenanceMarkResolution::Low,
+        "test",
+    )
+    .next(
+        Date::from_string("2023-06-20").unwrap(),
+        Some(exotic_info()),
+    );
+
+    assert_eq!(
+        mark.info_tags(),
+        vec![u64::MAX, 900_000, 900_001, 900_002, 900_003]
+    );
+
+    let summary = mark.info_summary(1_000).unwrap();
+    assert!(summary.contains("900001("), "{summary}");
+    assert!(!summary.contains("unrenderable"), "{summary}");
+    assert!(format!("{:?}", mark).contains("info: "));
+
+    let options = FormatOptions::new()
+        .with_always_emit(true)
+        .with_show_info(true);
+    let text = ProvenanceMark::validate(vec![mark])
+        .format_with_options(ValidationReportFormat::Text, &options);
+    assert!(text.contains("info: "), "{text}");
+}
+
+#[test]
+fn test_info_tags() {
+    assert!(mark_with_info(None).info_tags().is_empty());
+    assert!(
+        mark_with_info(Some("Hello".to_string()))
+            .info_tags()
+            .is_empty()
+    );
+
+    // Tags below the top level of an array are not listed
+    let info = vec![
+        CBOR::to_tagged_value(42, "a"),
+        CBOR::from(vec![CBOR::to_tagged_value(43, "b")]),
+        CBOR::to_tagged_value(42, "c"),
+    ];
+    let mark = ProvenanceMarkGenerator::new_with_passphrase(
+        ProvenanceMarkResolution::Low,
+        "test",
+    )
+    .next(
+        Date::from_string("2023-06-20").unwrap(),
+        Some(CBOR::from(info)),
+    );
+    assert_eq!(mark.info_tags(), vec![42]);
+}
+
+#[test]
+#[cfg(feature = "envelope")]
+fn test_info_with_rejected_summarizer_tag() {
+    // The summarizer registered for marks fails on anything but a mark
+    register_tags();
+    let info = CBOR::to_tagged_value(CBOR_TAG, "not a mark");
+    let mark = ProvenanceMarkGenerator::new_with_passphrase(
+        ProvenanceMarkResolution::Low,
+        "test",
+    )
+    .next(Date::from_string("2023-06-20").unwrap(), Some(info));
+
+    let summary = mark.info_summary(1_000).unwrap();
+    assert_eq!(summary, format!(r#"{CBOR_TAG}("not a mark")"#));
+    assert!(format!("{:?}", mark).contains(&summary));
+}
@@ -0,0 +1,150 @@
+//! Cross-checks against fixtures exported from the reference Swift
+//! implementation.
+//!
+//! Each file in `tests/fixtures/interop` is a JSON list of chains, giving the
+//! passphrase, resolution, dates, and info used to generate each chain and
+//! the UR, hash, and ByteWords identifier expected for each of its marks.
+//! [`export_fixture`] writes this crate's output in the same format, so the
+//! check can be run in the other direction; set
+//! `PROVENANCE_INTEROP_EXPORT_DIR` to write the fixtures to that directory.
+
+use bc_ur::UREncodable;
+use dcbor::Date;
+use provenance_mark::*;
+use serde_json::{Value, json};
+
+const FIXTURES: [(&str, &str); 2] = [
+    ("low", include_str!("fixtures/interop/low.json")),
+    ("high", include_str!("fixtures/interop/high.json")),
+];
+
+fn resolution(name: &str) -> ProvenanceMarkResolution {
+    [
+        ProvenanceMarkResolution::Low,
+        ProvenanceMarkResolution::Medium,
+        ProvenanceMarkResolution::Quartile,
+        ProvenanceMarkResolution::High,
+    ]
+    .into_iter()
+    .find(|res| res.to_string() == name)
+    .unwrap_or_else(|| panic!("unknown resolution {name:?}"))
+}
+
+fn string(value: &Value) -> &str {
+    value
+        .as_str()
+        .unwrap_or_else(|| panic!("expected a string, got {value}"))
+}
+
+/// Generate the chain a fixture describes.
+fn generate(chain: &Value) -> Vec<ProvenanceMark> {
+    let mut generator = ProvenanceMarkGenerator::new_with_passphrase(
+        resolution(string(&chain["resolution"])),
+        string(&chain["passphrase"]),
+    );
+    let info = chain["info"].as_str();
+    chain["dates"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|date| {
+            let date = Date::from_string(string(date)).unwrap();
+            generator.next(date, info)
+        })
+        .collect()
+}
+
+/// This crate's output for a chain, in the fixture format.
+fn export_fixture(
+    res: ProvenanceMarkResolution,
+    passphrase: &str,
+    dates: &[&str],
+    info: Option<&str>,
+) -> Value {
+    let chain = json!({
+        "passphrase": passphrase,
+        "resolution": res.to_string(),
+        "dates": dates,
+        "info": info,
+    });
+    let marks: Vec<Value> = generate(&chain)
+        .iter()
+        .map(|mark| {
+            json!({
+                "seq": mark.seq(),
+                "ur": mark.ur_string(),
+                "hash": hex::encode(mark.hash()),
+                "identifier": mark.id_bytewords(4, false),
+            })
+        })
+        .collect();
+    let mut chain = chain;
+    chain["marks"] = json!(marks);
+    chain
+}
+
+#[test]
+fn test_interop_fixtures() {
+    let mut mismatches = Vec::new();
+    for (name, fixture) in FIXTURES {
+        let chains: Vec<Value> = serde_json::from_str(fixture).unwrap();
+        assert!(!chains.is_empty(), "{name} has no chains");
+        for chain in &chains {
+            let res = string(&chain["resolution"]);
+            let marks = generate(chain);
+            let expected = chain["marks"].as_array().unwrap();
+            assert_eq!(marks.len(), expected.len(), "{name}: mark count");
+            for (mark, expected) in marks.iter().zip(expected) {
+                let actual = [
+                    ("ur", mark.ur_string()),
+                    ("hash", hex::encode(mark.hash())),
+                    ("identifier", mark.id_bytewords(4, false)),
+                ];
+                for (field, actual) in actual {
+                    let expected = string(&expected[field]);
+                    if actual != expected {
+                        mismatches.push(format!(
+                            "({res}, seq {}, {field}): expected {expected}, \
+                             got {actual}",
+                            mark.seq()
+                        ));
+                    }
+                }
+            }
+        }
+    }
+    assert!(mismatches.is_empty(), "{}", mismatches.join("\n"));
+}
+
+#[test]
+fn test_interop_export() {
+    let export_dir = std::env::var_os("PROVENANCE_INTEROP_EXPORT_DIR");
+    for (name, fixture) in FIXTURES {
+        let chains: Vec<Value> = serde_json::from_str(fixture).unwrap();
+        let exported: Vec<Value> = chains
+            .iter()
+            .map(|chain| {
+                let dates: Vec<&str> = chain["dates"]
+                    .as_array()
+                    .unwrap()
+                    .iter()
+                    .map(string)
+                    .collect();
+                export_fixture(
+                    resolution(string(&chain["resolution"])),
+                    string(&chain["passphrase"]),
+                    &dates,
+                    chain["info"].as_str(),
+                )
+            })
+            .collect();
+        // The export of a fixture's inputs is the fixture itself
+        assert_eq!(exported, chains, "{name}");
+
+        if let Some(dir) = &export_dir {
+            let path = std::path::Path::new(dir).join(format!("{name}.json"));
+            let json = serde_json::to_string_pretty(&exported).unwrap();
+            std::fs::write(path, json + "\n").unwrap();
+        }
+    }
+}
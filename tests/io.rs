@@ -0,0 +1,75 @@
+#![cfg(feature = "json")]
+
+use dcbor::Date;
+use provenance_mark::{io::*, *};
+
+fn marks() -> Vec<ProvenanceMark> {
+    let mut generator = ProvenanceMarkGenerator::new_with_passphrase(
+        ProvenanceMarkResolution::Medium,
+        "Wolf",
+    );
+    let date = Date::from_string("2023-06-20").unwrap();
+    vec![
+        generator.next(date, None::<&str>),
+        generator.next(date, Some("Lorem ipsum sit dolor amet.")),
+        generator.next(date, None::<&str>),
+    ]
+}
+
+#[test]
+fn test_b64_cbor_round_trip() {
+    for mark in marks() {
+        let b64 = mark.to_b64_cbor();
+        assert_eq!(ProvenanceMark::from_b64_cbor(&b64).unwrap(), mark);
+
+        // Surrounding and embedded whitespace is ignored
+        let (head, tail) = b64.split_at(b64.len() / 2);
+        let wrapped = format!("  {}\n  {}\t\n", head, tail);
+        assert_eq!(ProvenanceMark::from_b64_cbor(&wrapped).unwrap(), mark);
+
+        // `FromStr` accepts either form
+        assert_eq!(wrapped.parse::<ProvenanceMark>().unwrap(), mark);
+        let ur = bc_ur::UREncodable::ur_string(&mark);
+        assert_eq!(format!(" {ur}\n").parse::<ProvenanceMark>().unwrap(), mark);
+    }
+}
+
+#[test]
+fn test_b64_cbor_invalid_padding() {
+    let b64 = marks()[0].to_b64_cbor();
+    let unpadded = b64.trim_end_matches('=');
+    let broken = if unpadded.len() == b64.len() {
+        format!("{}=", b64)
+    } else {
+        unpadded.to_string()
+    };
+    assert!(matches!(
+        ProvenanceMark::from_b64_cbor(&broken),
+        Err(Error::Base64(_))
+    ));
+    assert!(broken.parse::<ProvenanceMark>().is_err());
+}
+
+#[test]
+fn test_b64_lines() {
+    let marks = marks();
+    let mut buf = Vec::new();
+    write_b64_lines(&mut buf, &marks).unwrap();
+    let text = String::from_utf8(buf).unwrap();
+    assert_eq!(text.lines().count(), 3);
+    assert_eq!(read_b64_lines(text.as_bytes()).unwrap(), marks);
+
+    // Blank lines and surrounding whitespace are tolerated
+    let spaced = text.replace('\n', " \n\n");
+    assert_eq!(read_b64_lines(spaced.as_bytes()).unwrap(), marks);
+
+    // Errors give the line number, counting blank lines
+    let lines: Vec<&str> = text.lines().collect();
+    let corrupt = format!("{}\n\n{}\nnot base64!\n", lines[0], lines[1]);
+    let err = read_b64_lines(corrupt.as_bytes()).unwrap_err();
+    assert!(matches!(err, Error::InvalidLine { line: 4, .. }), "{err}");
+    assert!(
+        err.to_string().starts_with("invalid mark on line 4: "),
+        "{err}"
+    );
+}
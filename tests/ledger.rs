@@ -0,0 +1,70 @@
+#![cfg(all(feature = "markdown", feature = "json"))]
+
+use dcbor::Date;
+use indoc::indoc;
+use provenance_mark::*;
+
+#[macro_use]
+mod common;
+use common::daily_marks;
+
+#[test]
+fn test_markdown_ledgers() {
+    let alice = daily_marks(ProvenanceMarkResolution::Low, "alice", 2);
+    let bob = daily_marks(ProvenanceMarkResolution::Low, "bob", 2);
+    let infos = vec![
+        ProvenanceMarkInfo::new(bob[0].clone(), "Genesis | <first> *draft*"),
+        ProvenanceMarkInfo::new(alice[1].clone(), ""),
+    ];
+
+    // Input order does not affect the ledger
+    let report = ProvenanceMark::validate(vec![
+        alice[1].clone(),
+        bob[1].clone(),
+        alice[0].clone(),
+        bob[0].clone(),
+    ]);
+
+    // expected-text-output-rubric:
+    #[rustfmt::skip]
+    assert_actual_expected!(report.markdown_ledgers(Some(&infos)), indoc! {r#"
+        - [Chain `7a9c3f5e`](#chain-7a9c3f5e)
+        - [Chain `a33e10de`](#chain-a33e10de)
+
+        <a id="chain-7a9c3f5e"></a>
+
+        ### Chain `7a9c3f5e`
+
+        | Seq | Date | ID | Bytewords | Comment | UR |
+        | ---: | --- | --- | --- | --- | --- |
+        | 0 | 2023-06-20 | `0d6e0afd` | BELT JOLT BACK ZINC | Genesis \| &lt;first&gt; \*draft\* | <details><summary>UR</summary><code>ur:provenance/lfaegdknnsfhhylrgytdhtsnheskzepmctgrwnlyjeyngh</code></details> |
+        | 1 | 2023-06-21 | `6cd504e7` | JAZZ TOIL AQUA VOID |  | <details><summary>UR</summary><code>ur:provenance/lfaegdrtckinuywdosecpedtbnismdcllyvsbbplkpspyl</code></details> |
+
+        <a id="chain-a33e10de"></a>
+
+        ### Chain `a33e10de`
+
+        | Seq | Date | ID | Bytewords | Comment | UR |
+        | ---: | --- | --- | --- | --- | --- |
+        | 0 | 2023-06-20 | `c2a985ff` | SAGA PART LIMP ZOOM |  | <details><summary>UR</summary><code>ur:provenance/lfaegdotfmbeuerniolpveenmowliegyfrfrwnfzntnbwe</code></details> |
+        | 1 | 2023-06-21 | `5567cd24` | GYRO INTO SWAN DARK |  | <details><summary>UR</summary><code>ur:provenance/lfaegdztfetoehnyjswzsopecewkqdiskshfnyndiemkld</code></details> |
+    "#}.trim());
+}
+
+#[test]
+fn test_markdown_ledger_without_infos() {
+    let bob = daily_marks(ProvenanceMarkResolution::Low, "bob", 1);
+    let report = ProvenanceMark::validate(bob);
+
+    // expected-text-output-rubric:
+    #[rustfmt::skip]
+    assert_actual_expected!(report.chains()[0].markdown_ledger(None), indoc! {r#"
+        <a id="chain-7a9c3f5e"></a>
+
+        ### Chain `7a9c3f5e`
+
+        | Seq | Date | ID | Bytewords | UR |
+        | ---: | --- | --- | --- | --- |
+        | 0 | 2023-06-20 | `0d6e0afd` | BELT JOLT BACK ZINC | <details><summary>UR</summary><code>ur:provenance/lfaegdknnsfhhylrgytdhtsnheskzepmctgrwnlyjeyngh</code></details> |
+    "#}.trim());
+}
@@ -0,0 +1,192 @@
+use provenance_mark::*;
+
+mod common;
+use common::daily_marks;
+
+#[test]
+fn test_link_bytes_lengths() {
+    let low = ProvenanceMarkResolution::Low;
+    let high = ProvenanceMarkResolution::High;
+
+    let key = LinkKey::new(low, vec![1, 2, 3, 4]).unwrap();
+    assert_eq!(key.as_bytes(), &[1, 2, 3, 4]);
+    assert_eq!(key.to_string(), "01020304");
+    assert_eq!(key.as_ref(), &[1, 2, 3, 4]);
+    assert_eq!(key.clone().into_bytes(), vec![1, 2, 3, 4]);
+
+    assert!(matches!(
+        LinkKey::new(high, vec![0; 4]),
+        Err(Error::InvalidKeyLength { expected: 32, actual: 4 })
+    ));
+    assert!(matches!(
+        LinkHash::new(low, vec![0; 5]),
+        Err(Error::InvalidHashLength { expected: 4, actual: 5 })
+    ));
+    assert!(matches!(
+        ChainId::new(low, [0u8; 3].as_slice()),
+        Err(Error::InvalidChainIdLength { expected: 4, actual: 3 })
+    ));
+
+    let chain_id = ChainId::new(low, vec![1, 2, 3, 4]).unwrap();
+    assert_eq!(chain_id.genesis_key(), key);
+}
+
+#[test]
+fn test_typed_accessors() {
+    let marks = daily_marks(ProvenanceMarkResolution::Low, "Wolf", 3);
+    let genesis = &marks[0];
+    assert_eq!(genesis.chain_id().genesis_key(), *genesis.key());
+    assert_eq!(marks[1].chain_id(), genesis.chain_id());
+    assert_eq!(genesis.key().to_string(), hex::encode(genesis.key()));
+
+    // The typed fields still compare and slice like the bytes they wrap
+    assert_eq!(genesis.hash().len(), 4);
+    assert_eq!(&genesis.message()[..4], genesis.key());
+    assert!(genesis.chain_id() == genesis.key().as_bytes());
+
+    let rebuilt = ProvenanceMark::from_fields(
+        marks[1].res(),
+        marks[1].key().clone(),
+        marks[1].hash().clone(),
+        marks[1].chain_id().clone(),
+        marks[1].seq().into(),
+        marks[1].date(),
+        Vec::new(),
+    )
+    .unwrap();
+    assert_eq!(rebuilt, marks[1]);
+}
+
+#[test]
+fn test_mismatched_resolution() {
+    let mark = &daily_marks(ProvenanceMarkResolution::Low, "Wolf", 1)[0];
+    let high = ProvenanceMarkResolution::High;
+    assert!(matches!(
+        ProvenanceMark::from_fields(
+            high,
+            mark.key().clone(),
+            mark.hash().clone(),
+            mark.chain_id().clone(),
+            mark.seq().into(),
+            mark.date(),
+            Vec::new(),
+        ),
+        Err(Error::InvalidKeyLength { expected: 32, actual: 4 })
+    ));
+}
+
+#[test]
+#[allow(deprecated)]
+fn test_byte_constructors() {
+    let marks = daily_marks(ProvenanceMarkResolution::Low, "Wolf", 2);
+    let mark = &marks[1];
+    let next_key = vec![0x55; 4];
+
+    let typed = ProvenanceMark::new(
+        mark.res(),
+        mark.key().clone(),
+        LinkKey::new(mark.res(), next_key.clone()).unwrap(),
+        mark.chain_id().clone(),
+        mark.seq().into(),
+        mark.date(),
+        None::<&str>,
+    )
+    .unwrap();
+    let untyped = ProvenanceMark::new_from_bytes(
+        mark.res(),
+        mark.key().to_vec(),
+        next_key,
+        mark.chain_id().to_vec(),
+        mark.seq().into(),
+        mark.date(),
+        None::<&str>,
+    )
+    .unwrap();
+    assert_eq!(untyped, typed);
+
+    let rebuilt = ProvenanceMark::from_byte_fields(
+        mark.res(),
+        mark.key().to_vec(),
+        mark.hash().to_vec(),
+        mark.chain_id().to_vec(),
+        mark.seq().into(),
+        mark.date(),
+        Vec::new(),
+    )
+    .unwrap();
+    assert_eq!(rebuilt, *mark);
+
+    // The typed constructors also take the untyped fields
+    let untyped = ProvenanceMark::new(
+        mark.res(),
+        mark.key().to_vec(),
+        vec![0x55; 4],
+        mark.chain_id().to_vec(),
+        mark.seq().into(),
+        mark.date(),
+        None::<&str>,
+    )
+    .unwrap();
+    assert_eq!(untyped, typed);
+    let rebuilt = ProvenanceMark::from_fields(
+        mark.res(),
+        mark.key().to_vec(),
+        mark.hash().to_vec(),
+        mark.chain_id().to_vec(),
+        mark.seq().into(),
+        mark.date(),
+        Vec::new(),
+    )
+    .unwrap();
+    assert_eq!(rebuilt, *mark);
+    assert!(matches!(
+        ProvenanceMark::from_fields(
+            mark.res(),
+            mark.key().to_vec(),
+            vec![0; 3],
+            mark.chain_id().to_vec(),
+            mark.seq().into(),
+            mark.date(),
+            Vec::new(),
+        ),
+        Err(Error::InvalidHashLength { expected: 4, actual: 3 })
+    ));
+}
+
+#[test]
+#[cfg(feature = "json")]
+fn test_link_bytes_json() {
+    let mark = &daily_marks(ProvenanceMarkResolution::Low, "Wolf", 1)[0];
+    let json = serde_json::to_value(mark).unwrap();
+    assert_eq!(json["key"], serde_json::to_value(mark.key()).unwrap());
+    assert_eq!(json["hash"], serde_json::to_value(mark.hash()).unwrap());
+    // Base64, as before the fields were typed
+    assert_eq!(json["key"], json["chainID"]);
+    assert_eq!(json["key"].as_str().unwrap().len(), 8);
+
+    let chain_id: ChainId =
+        serde_json::from_value(json["chainID"].clone()).unwrap();
+    assert_eq!(&chain_id, mark.chain_id());
+    assert!(serde_json::from_str::<LinkKey>("\"not base64\"").is_err());
+
+    // Only the link lengths of the resolutions are accepted
+    for res in [
+        ProvenanceMarkResolution::Low,
+        ProvenanceMarkResolution::Medium,
+        ProvenanceMarkResolution::Quartile,
+        ProvenanceMarkResolution::High,
+    ] {
+        let hash = LinkHash::new(res, vec![7; res.link_length()]).unwrap();
+        let json = serde_json::to_string(&hash).unwrap();
+        assert_eq!(serde_json::from_str::<LinkHash>(&json).unwrap(), hash);
+    }
+    // 3 and 6 bytes
+    for json in ["\"AAAA\"", "\"AAAAAAAA\""] {
+        let err = serde_json::from_str::<ChainId>(json).unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("is not the link length of any resolution"),
+            "unexpected error: {err}"
+        );
+    }
+}
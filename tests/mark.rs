@@ -35,24 +35,19 @@ fn run_test(
         })
         .collect();
 
-    let mut encoded_generator = serde_json::to_string(&provenance_gen).unwrap();
+    let mut generator = provenance_gen;
 
     let marks = dates
         .into_iter()
         .map(|date| {
-            let mut generator: ProvenanceMarkGenerator =
-                serde_json::from_str(&encoded_generator).unwrap();
+            generator = reload(&generator);
 
             let title = if include_info {
                 Some("Lorem ipsum sit dolor amet.")
             } else {
                 None
             };
-            let result = generator.next(date, title);
-
-            encoded_generator = serde_json::to_string(&generator).unwrap();
-
-            result
+            generator.next(date, title)
         })
         .collect::<Vec<_>>();
 
@@ -137,6 +132,42 @@ fn run_test(
         .collect::<Vec<_>>();
     assert_eq!(marks, ur_marks);
 
+    for mark in &marks {
+        let segment = mark.to_path_segment();
+        assert_eq!(&ProvenanceMark::from_path_segment(&segment).unwrap(), mark);
+    }
+
+    #[cfg(feature = "url")]
+    check_urls(&marks, expected_urls);
+    #[cfg(not(feature = "url"))]
+    let _ = expected_urls;
+
+    #[cfg(feature = "json")]
+    for mark in marks.clone() {
+        let data = serde_json::to_string(&mark).unwrap();
+        let mark2: ProvenanceMark = serde_json::from_str(&data).unwrap();
+        assert_eq!(mark, mark2);
+
+        let display = mark.to_display_json();
+        assert_eq!(ProvenanceMark::from_display_json(&display).unwrap(), mark);
+    }
+}
+
+/// The generator as restored from its JSON encoding, so that every mark is
+/// issued by a generator that has been through storage.
+#[cfg(feature = "json")]
+fn reload(generator: &ProvenanceMarkGenerator) -> ProvenanceMarkGenerator {
+    let encoded = serde_json::to_string(generator).unwrap();
+    serde_json::from_str(&encoded).unwrap()
+}
+
+#[cfg(not(feature = "json"))]
+fn reload(generator: &ProvenanceMarkGenerator) -> ProvenanceMarkGenerator {
+    generator.dangerous_clone_for_fork()
+}
+
+#[cfg(feature = "url")]
+fn check_urls(marks: &[ProvenanceMark], expected_urls: &[&str]) {
     let base_url = "https://example.com/validate";
     let urls = marks
         .iter()
@@ -156,10 +187,10 @@ fn run_test(
         .collect::<Vec<_>>();
     assert_eq!(marks, url_marks);
 
-    for mark in marks.clone() {
-        let data = serde_json::to_string(&mark).unwrap();
-        let mark2: ProvenanceMark = serde_json::from_str(&data).unwrap();
-        assert_eq!(mark, mark2);
+    let base = url::Url::parse(base_url).unwrap();
+    for mark in marks {
+        let link = mark.to_deep_link(&base).unwrap();
+        assert_eq!(&ProvenanceMark::from_deep_link(&link).unwrap(), mark);
     }
 }
 
@@ -578,16 +609,16 @@ fn test_quartile() {
         "ProvenanceMark(a308d3f2)",
     ];
     let expected_debug = [
-        r#"ProvenanceMark(key: 090bf2f8b55be45b4661b24b7e9c340c, hash: 4a0738a31a3e9073f1c01999cd01ff0a, chainID: 090bf2f8b55be45b4661b24b7e9c340c, seq: 0, date: 2023-06-20T12:00:00Z)"#,
-        r#"ProvenanceMark(key: 558dbfc6536b296875bb47d085cf746e, hash: d95dc3573bee6e1f4504ce6e971de31a, chainID: 090bf2f8b55be45b4661b24b7e9c340c, seq: 1, date: 2023-06-21T12:00:00Z)"#,
-        r#"ProvenanceMark(key: ca274110de5cbde40f34e1b651372ca2, hash: a427f3d2f56c956e715d05089a9c1125, chainID: 090bf2f8b55be45b4661b24b7e9c340c, seq: 2, date: 2023-06-22T12:00:00Z)"#,
-        r#"ProvenanceMark(key: abc6aa642861a61a42c751c2012df374, hash: 706fada07359cfc46e9f227405e5a50a, chainID: 090bf2f8b55be45b4661b24b7e9c340c, seq: 3, date: 2023-06-23T12:00:00Z)"#,
-        r#"ProvenanceMark(key: e6528cad9d939b515e78dd288b3915f9, hash: 2ad78680ddfa4a7789cfc974b99edab3, chainID: 090bf2f8b55be45b4661b24b7e9c340c, seq: 4, date: 2023-06-24T12:00:00Z)"#,
-        r#"ProvenanceMark(key: 7fc7b276b810e4fc14a72ac53b5cd9b5, hash: 94169f216caa0853c1108435cf51856f, chainID: 090bf2f8b55be45b4661b24b7e9c340c, seq: 5, date: 2023-06-25T12:00:00Z)"#,
-        r#"ProvenanceMark(key: 445dbfc1264fd6de95b5ca8c060c24ba, hash: 479cac604e8b022e0f63688f165a4334, chainID: 090bf2f8b55be45b4661b24b7e9c340c, seq: 6, date: 2023-06-26T12:00:00Z)"#,
-        r#"ProvenanceMark(key: 1dadf8aa6a2b2fa657fc7a0225d1880e, hash: 575cbdd132391fc3a0d5f1f2038d1923, chainID: 090bf2f8b55be45b4661b24b7e9c340c, seq: 7, date: 2023-06-27T12:00:00Z)"#,
-        r#"ProvenanceMark(key: 5946b5fd32e588c593923750478d74d4, hash: cfc526bf20716acb922a0eb52c4e88f7, chainID: 090bf2f8b55be45b4661b24b7e9c340c, seq: 8, date: 2023-06-28T12:00:00Z)"#,
-        r#"ProvenanceMark(key: 469cbdca128f2d85d803e81b3e0e3a7d, hash: a308d3f2b0cb42330b971ceef18a168e, chainID: 090bf2f8b55be45b4661b24b7e9c340c, seq: 9, date: 2023-06-29T12:00:00Z)"#,
+        r#"ProvenanceMark(key: 090bf2f8b55be45b4661b24b7e9c340c, hash: 4a0738a31a3e9073f1c01999cd01ff0a, chainID: 090bf2f8b55be45b4661b24b7e9c340c, seq: 0, date: 2023-06-20T12:00:00.000Z)"#,
+        r#"ProvenanceMark(key: 558dbfc6536b296875bb47d085cf746e, hash: d95dc3573bee6e1f4504ce6e971de31a, chainID: 090bf2f8b55be45b4661b24b7e9c340c, seq: 1, date: 2023-06-21T12:00:00.000Z)"#,
+        r#"ProvenanceMark(key: ca274110de5cbde40f34e1b651372ca2, hash: a427f3d2f56c956e715d05089a9c1125, chainID: 090bf2f8b55be45b4661b24b7e9c340c, seq: 2, date: 2023-06-22T12:00:00.000Z)"#,
+        r#"ProvenanceMark(key: abc6aa642861a61a42c751c2012df374, hash: 706fada07359cfc46e9f227405e5a50a, chainID: 090bf2f8b55be45b4661b24b7e9c340c, seq: 3, date: 2023-06-23T12:00:00.000Z)"#,
+        r#"ProvenanceMark(key: e6528cad9d939b515e78dd288b3915f9, hash: 2ad78680ddfa4a7789cfc974b99edab3, chainID: 090bf2f8b55be45b4661b24b7e9c340c, seq: 4, date: 2023-06-24T12:00:00.000Z)"#,
+        r#"ProvenanceMark(key: 7fc7b276b810e4fc14a72ac53b5cd9b5, hash: 94169f216caa0853c1108435cf51856f, chainID: 090bf2f8b55be45b4661b24b7e9c340c, seq: 5, date: 2023-06-25T12:00:00.000Z)"#,
+        r#"ProvenanceMark(key: 445dbfc1264fd6de95b5ca8c060c24ba, hash: 479cac604e8b022e0f63688f165a4334, chainID: 090bf2f8b55be45b4661b24b7e9c340c, seq: 6, date: 2023-06-26T12:00:00.000Z)"#,
+        r#"ProvenanceMark(key: 1dadf8aa6a2b2fa657fc7a0225d1880e, hash: 575cbdd132391fc3a0d5f1f2038d1923, chainID: 090bf2f8b55be45b4661b24b7e9c340c, seq: 7, date: 2023-06-27T12:00:00.000Z)"#,
+        r#"ProvenanceMark(key: 5946b5fd32e588c593923750478d74d4, hash: cfc526bf20716acb922a0eb52c4e88f7, chainID: 090bf2f8b55be45b4661b24b7e9c340c, seq: 8, date: 2023-06-28T12:00:00.000Z)"#,
+        r#"ProvenanceMark(key: 469cbdca128f2d85d803e81b3e0e3a7d, hash: a308d3f2b0cb42330b971ceef18a168e, chainID: 090bf2f8b55be45b4661b24b7e9c340c, seq: 9, date: 2023-06-29T12:00:00.000Z)"#,
     ];
     let expected_bytewords = [
         "axis bald whiz yoga race help vibe help frog huts purr gear knob news edge barn inky jump mild warm warm pose obey ruby very hill yank song frog into maze work exam veto foxy iron quiz edge arch cusp blue gray zaps task saga half monk jolt menu peck fern fizz item cost rich mild nail omit lazy song meow claw",
@@ -678,16 +709,16 @@ fn test_quartile_with_info() {
         "ProvenanceMark(1c0b6f5e)",
     ];
     let expected_debug = [
-        r#"ProvenanceMark(key: 090bf2f8b55be45b4661b24b7e9c340c, hash: 5bbcccab578f10c794a2a66fd42abff1, chainID: 090bf2f8b55be45b4661b24b7e9c340c, seq: 0, date: 2023-06-20T12:00:00Z, info: "Lorem ipsum sit dolor amet.")"#,
-        r#"ProvenanceMark(key: 558dbfc6536b296875bb47d085cf746e, hash: 9b1f7fe61f3f9a2fbfc5f896b5e1bb5c, chainID: 090bf2f8b55be45b4661b24b7e9c340c, seq: 1, date: 2023-06-21T12:00:00Z, info: "Lorem ipsum sit dolor amet.")"#,
-        r#"ProvenanceMark(key: ca274110de5cbde40f34e1b651372ca2, hash: 89272fb4945aa03ce0efe8bb7d454773, chainID: 090bf2f8b55be45b4661b24b7e9c340c, seq: 2, date: 2023-06-22T12:00:00Z, info: "Lorem ipsum sit dolor amet.")"#,
-        r#"ProvenanceMark(key: abc6aa642861a61a42c751c2012df374, hash: 363c21c2ca486cd34922912670a6c525, chainID: 090bf2f8b55be45b4661b24b7e9c340c, seq: 3, date: 2023-06-23T12:00:00Z, info: "Lorem ipsum sit dolor amet.")"#,
-        r#"ProvenanceMark(key: e6528cad9d939b515e78dd288b3915f9, hash: 8a9828a898c97d76e114d00af3bcdc1d, chainID: 090bf2f8b55be45b4661b24b7e9c340c, seq: 4, date: 2023-06-24T12:00:00Z, info: "Lorem ipsum sit dolor amet.")"#,
-        r#"ProvenanceMark(key: 7fc7b276b810e4fc14a72ac53b5cd9b5, hash: 50aeeb375a05e2f870f302b221972dd2, chainID: 090bf2f8b55be45b4661b24b7e9c340c, seq: 5, date: 2023-06-25T12:00:00Z, info: "Lorem ipsum sit dolor amet.")"#,
-        r#"ProvenanceMark(key: 445dbfc1264fd6de95b5ca8c060c24ba, hash: 84e2627277ea03eb2384ce167bba24a3, chainID: 090bf2f8b55be45b4661b24b7e9c340c, seq: 6, date: 2023-06-26T12:00:00Z, info: "Lorem ipsum sit dolor amet.")"#,
-        r#"ProvenanceMark(key: 1dadf8aa6a2b2fa657fc7a0225d1880e, hash: 25c8850f0d4f74aefaea5460f5990317, chainID: 090bf2f8b55be45b4661b24b7e9c340c, seq: 7, date: 2023-06-27T12:00:00Z, info: "Lorem ipsum sit dolor amet.")"#,
-        r#"ProvenanceMark(key: 5946b5fd32e588c593923750478d74d4, hash: 9ade9daaa70c28c38b6de6b5e93f7174, chainID: 090bf2f8b55be45b4661b24b7e9c340c, seq: 8, date: 2023-06-28T12:00:00Z, info: "Lorem ipsum sit dolor amet.")"#,
-        r#"ProvenanceMark(key: 469cbdca128f2d85d803e81b3e0e3a7d, hash: 1c0b6f5e7521a6ebd2d2e96adae4df25, chainID: 090bf2f8b55be45b4661b24b7e9c340c, seq: 9, date: 2023-06-29T12:00:00Z, info: "Lorem ipsum sit dolor amet.")"#,
+        r#"ProvenanceMark(key: 090bf2f8b55be45b4661b24b7e9c340c, hash: 5bbcccab578f10c794a2a66fd42abff1, chainID: 090bf2f8b55be45b4661b24b7e9c340c, seq: 0, date: 2023-06-20T12:00:00.000Z, info: "Lorem ipsum sit dolor amet.")"#,
+        r#"ProvenanceMark(key: 558dbfc6536b296875bb47d085cf746e, hash: 9b1f7fe61f3f9a2fbfc5f896b5e1bb5c, chainID: 090bf2f8b55be45b4661b24b7e9c340c, seq: 1, date: 2023-06-21T12:00:00.000Z, info: "Lorem ipsum sit dolor amet.")"#,
+        r#"ProvenanceMark(key: ca274110de5cbde40f34e1b651372ca2, hash: 89272fb4945aa03ce0efe8bb7d454773, chainID: 090bf2f8b55be45b4661b24b7e9c340c, seq: 2, date: 2023-06-22T12:00:00.000Z, info: "Lorem ipsum sit dolor amet.")"#,
+        r#"ProvenanceMark(key: abc6aa642861a61a42c751c2012df374, hash: 363c21c2ca486cd34922912670a6c525, chainID: 090bf2f8b55be45b4661b24b7e9c340c, seq: 3, date: 2023-06-23T12:00:00.000Z, info: "Lorem ipsum sit dolor amet.")"#,
+        r#"ProvenanceMark(key: e6528cad9d939b515e78dd288b3915f9, hash: 8a9828a898c97d76e114d00af3bcdc1d, chainID: 090bf2f8b55be45b4661b24b7e9c340c, seq: 4, date: 2023-06-24T12:00:00.000Z, info: "Lorem ipsum sit dolor amet.")"#,
+        r#"ProvenanceMark(key: 7fc7b276b810e4fc14a72ac53b5cd9b5, hash: 50aeeb375a05e2f870f302b221972dd2, chainID: 090bf2f8b55be45b4661b24b7e9c340c, seq: 5, date: 2023-06-25T12:00:00.000Z, info: "Lorem ipsum sit dolor amet.")"#,
+        r#"ProvenanceMark(key: 445dbfc1264fd6de95b5ca8c060c24ba, hash: 84e2627277ea03eb2384ce167bba24a3, chainID: 090bf2f8b55be45b4661b24b7e9c340c, seq: 6, date: 2023-06-26T12:00:00.000Z, info: "Lorem ipsum sit dolor amet.")"#,
+        r#"ProvenanceMark(key: 1dadf8aa6a2b2fa657fc7a0225d1880e, hash: 25c8850f0d4f74aefaea5460f5990317, chainID: 090bf2f8b55be45b4661b24b7e9c340c, seq: 7, date: 2023-06-27T12:00:00.000Z, info: "Lorem ipsum sit dolor amet.")"#,
+        r#"ProvenanceMark(key: 5946b5fd32e588c593923750478d74d4, hash: 9ade9daaa70c28c38b6de6b5e93f7174, chainID: 090bf2f8b55be45b4661b24b7e9c340c, seq: 8, date: 2023-06-28T12:00:00.000Z, info: "Lorem ipsum sit dolor amet.")"#,
+        r#"ProvenanceMark(key: 469cbdca128f2d85d803e81b3e0e3a7d, hash: 1c0b6f5e7521a6ebd2d2e96adae4df25, chainID: 090bf2f8b55be45b4661b24b7e9c340c, seq: 9, date: 2023-06-29T12:00:00.000Z, info: "Lorem ipsum sit dolor amet.")"#,
     ];
     let expected_bytewords = [
         "axis bald whiz yoga race help vibe help frog huts purr gear knob news edge barn inky jump mild warm warm pose obey ruby very hill yank song frog into maze work days hawk puff huts yurt limp limp mint keep echo free eyes ugly kiwi trip mild menu peck fern fizz item cost rich mild nail omit drum meow sets work zinc edge beta zero draw redo king math real dice onyx cats help iris join oboe nail slot barn news jury fact miss eyes love jury soap surf nail",
@@ -778,16 +809,16 @@ fn test_high() {
         "ProvenanceMark(71500811)",
     ];
     let expected_debug = [
-        r#"ProvenanceMark(key: 090bf2f8b55be45b4661b24b7e9c340cf9464c5fe95c84f580954aaabe085e7c, hash: 8cd8e51f2885aeca2c644280b53ee75aa12b5a15f4d84068c8c918f8c0d85878, chainID: 090bf2f8b55be45b4661b24b7e9c340cf9464c5fe95c84f580954aaabe085e7c, seq: 0, date: 2023-06-20T12:00:00Z)"#,
-        r#"ProvenanceMark(key: 558dbfc6536b296875bb47d085cf746eca274110de5cbde40f34e1b651372ca2, hash: c5c043de88786aaed762e671a08f6805418fd9179179d325f7b2dc8ac115ca9a, chainID: 090bf2f8b55be45b4661b24b7e9c340cf9464c5fe95c84f580954aaabe085e7c, seq: 1, date: 2023-06-21T12:00:00Z)"#,
-        r#"ProvenanceMark(key: abc6aa642861a61a42c751c2012df374e6528cad9d939b515e78dd288b3915f9, hash: 211f55707df73f0c8e7404a2b137d4417080a6bb523ba4e4baac409995be05ba, chainID: 090bf2f8b55be45b4661b24b7e9c340cf9464c5fe95c84f580954aaabe085e7c, seq: 2, date: 2023-06-22T12:00:00Z)"#,
-        r#"ProvenanceMark(key: 7fc7b276b810e4fc14a72ac53b5cd9b5445dbfc1264fd6de95b5ca8c060c24ba, hash: fb67f81adba64c5c0015b2608edc913a96fe94718f80fd721490cbafd55b3dcb, chainID: 090bf2f8b55be45b4661b24b7e9c340cf9464c5fe95c84f580954aaabe085e7c, seq: 3, date: 2023-06-23T12:00:00Z)"#,
-        r#"ProvenanceMark(key: 1dadf8aa6a2b2fa657fc7a0225d1880e5946b5fd32e588c593923750478d74d4, hash: 33cf40295c4c06ac57eb89ef145779d6d65892741539441d93128f95ca194877, chainID: 090bf2f8b55be45b4661b24b7e9c340cf9464c5fe95c84f580954aaabe085e7c, seq: 4, date: 2023-06-24T12:00:00Z)"#,
-        r#"ProvenanceMark(key: 469cbdca128f2d85d803e81b3e0e3a7d64f15911e1854210ef5d1b22614a2006, hash: 96fe484af28c77c66b9d5d88dfc7e6b74950cbe9a01c959f2f1fa0e510a9b8c6, chainID: 090bf2f8b55be45b4661b24b7e9c340cf9464c5fe95c84f580954aaabe085e7c, seq: 5, date: 2023-06-25T12:00:00Z)"#,
-        r#"ProvenanceMark(key: 46b5294ba2787ed96dc10f79a3de28885d37a6dc0981dca240e7f1324e94a598, hash: 99356fddf4d51ce78887e24b59f86d3a150f0372e098dc8dc47cc57f09e90f7f, chainID: 090bf2f8b55be45b4661b24b7e9c340cf9464c5fe95c84f580954aaabe085e7c, seq: 6, date: 2023-06-26T12:00:00Z)"#,
-        r#"ProvenanceMark(key: 2e557fea3bc15ed4de848fc9929ab9b059fa7d30a1436b1fd9a6bc3dd4c08a06, hash: a91218f2e7addb84d58d6c20d869e4cd684b6e7809d2dc7a49b3b1f3c7c3c9a1, chainID: 090bf2f8b55be45b4661b24b7e9c340cf9464c5fe95c84f580954aaabe085e7c, seq: 7, date: 2023-06-27T12:00:00Z)"#,
-        r#"ProvenanceMark(key: 53a5f76321139ef9f174b27b088a66c4803128e09eb4c3d97ca51f0c369dca63, hash: a79faa3fca4d8d01a0206aac4c706d21d74e373b277e75a2eafa83802047a1f0, chainID: 090bf2f8b55be45b4661b24b7e9c340cf9464c5fe95c84f580954aaabe085e7c, seq: 8, date: 2023-06-28T12:00:00Z)"#,
-        r#"ProvenanceMark(key: 3e095176523550db5ea446af664feab86dba35dbfd168b78e589999b09a702eb, hash: 7150081136c8ec9364c000661180ce566059bf7d2778ff6e6693aac43d800df7, chainID: 090bf2f8b55be45b4661b24b7e9c340cf9464c5fe95c84f580954aaabe085e7c, seq: 9, date: 2023-06-29T12:00:00Z)"#,
+        r#"ProvenanceMark(key: 090bf2f8b55be45b4661b24b7e9c340cf9464c5fe95c84f580954aaabe085e7c, hash: 8cd8e51f2885aeca2c644280b53ee75aa12b5a15f4d84068c8c918f8c0d85878, chainID: 090bf2f8b55be45b4661b24b7e9c340cf9464c5fe95c84f580954aaabe085e7c, seq: 0, date: 2023-06-20T12:00:00.000Z)"#,
+        r#"ProvenanceMark(key: 558dbfc6536b296875bb47d085cf746eca274110de5cbde40f34e1b651372ca2, hash: c5c043de88786aaed762e671a08f6805418fd9179179d325f7b2dc8ac115ca9a, chainID: 090bf2f8b55be45b4661b24b7e9c340cf9464c5fe95c84f580954aaabe085e7c, seq: 1, date: 2023-06-21T12:00:00.000Z)"#,
+        r#"ProvenanceMark(key: abc6aa642861a61a42c751c2012df374e6528cad9d939b515e78dd288b3915f9, hash: 211f55707df73f0c8e7404a2b137d4417080a6bb523ba4e4baac409995be05ba, chainID: 090bf2f8b55be45b4661b24b7e9c340cf9464c5fe95c84f580954aaabe085e7c, seq: 2, date: 2023-06-22T12:00:00.000Z)"#,
+        r#"ProvenanceMark(key: 7fc7b276b810e4fc14a72ac53b5cd9b5445dbfc1264fd6de95b5ca8c060c24ba, hash: fb67f81adba64c5c0015b2608edc913a96fe94718f80fd721490cbafd55b3dcb, chainID: 090bf2f8b55be45b4661b24b7e9c340cf9464c5fe95c84f580954aaabe085e7c, seq: 3, date: 2023-06-23T12:00:00.000Z)"#,
+        r#"ProvenanceMark(key: 1dadf8aa6a2b2fa657fc7a0225d1880e5946b5fd32e588c593923750478d74d4, hash: 33cf40295c4c06ac57eb89ef145779d6d65892741539441d93128f95ca194877, chainID: 090bf2f8b55be45b4661b24b7e9c340cf9464c5fe95c84f580954aaabe085e7c, seq: 4, date: 2023-06-24T12:00:00.000Z)"#,
+        r#"ProvenanceMark(key: 469cbdca128f2d85d803e81b3e0e3a7d64f15911e1854210ef5d1b22614a2006, hash: 96fe484af28c77c66b9d5d88dfc7e6b74950cbe9a01c959f2f1fa0e510a9b8c6, chainID: 090bf2f8b55be45b4661b24b7e9c340cf9464c5fe95c84f580954aaabe085e7c, seq: 5, date: 2023-06-25T12:00:00.000Z)"#,
+        r#"ProvenanceMark(key: 46b5294ba2787ed96dc10f79a3de28885d37a6dc0981dca240e7f1324e94a598, hash: 99356fddf4d51ce78887e24b59f86d3a150f0372e098dc8dc47cc57f09e90f7f, chainID: 090bf2f8b55be45b4661b24b7e9c340cf9464c5fe95c84f580954aaabe085e7c, seq: 6, date: 2023-06-26T12:00:00.000Z)"#,
+        r#"ProvenanceMark(key: 2e557fea3bc15ed4de848fc9929ab9b059fa7d30a1436b1fd9a6bc3dd4c08a06, hash: a91218f2e7addb84d58d6c20d869e4cd684b6e7809d2dc7a49b3b1f3c7c3c9a1, chainID: 090bf2f8b55be45b4661b24b7e9c340cf9464c5fe95c84f580954aaabe085e7c, seq: 7, date: 2023-06-27T12:00:00.000Z)"#,
+        r#"ProvenanceMark(key: 53a5f76321139ef9f174b27b088a66c4803128e09eb4c3d97ca51f0c369dca63, hash: a79faa3fca4d8d01a0206aac4c706d21d74e373b277e75a2eafa83802047a1f0, chainID: 090bf2f8b55be45b4661b24b7e9c340cf9464c5fe95c84f580954aaabe085e7c, seq: 8, date: 2023-06-28T12:00:00.000Z)"#,
+        r#"ProvenanceMark(key: 3e095176523550db5ea446af664feab86dba35dbfd168b78e589999b09a702eb, hash: 7150081136c8ec9364c000661180ce566059bf7d2778ff6e6693aac43d800df7, chainID: 090bf2f8b55be45b4661b24b7e9c340cf9464c5fe95c84f580954aaabe085e7c, seq: 9, date: 2023-06-29T12:00:00.000Z)"#,
     ];
     let expected_bytewords = [
         "axis bald whiz yoga race help vibe help frog huts purr gear knob news edge barn yurt frog gems hope wall high liar yank lava mild game peck ruin away holy kite open fizz dark data lava brew gyro curl glow eyes body ramp epic tiny high item join navy solo gala away view acid real ruin open keys body dice stub fair inch kick waxy waxy tent item code toil whiz wave kept good twin limp cyan lion easy chef kept axis roof work exit nail yank task very lazy gear oboe oboe junk obey hang bald menu draw curl noon jolt game legs void news echo inky surf",
@@ -878,16 +909,16 @@ fn test_high_with_info() {
         "ProvenanceMark(1e5bd360)",
     ];
     let expected_debug = [
-        r#"ProvenanceMark(key: 090bf2f8b55be45b4661b24b7e9c340cf9464c5fe95c84f580954aaabe085e7c, hash: 0c6b0c1b5456dc960c9030f60474b317eade25621cd2fe5c6e70d5dd235c9480, chainID: 090bf2f8b55be45b4661b24b7e9c340cf9464c5fe95c84f580954aaabe085e7c, seq: 0, date: 2023-06-20T12:00:00Z, info: "Lorem ipsum sit dolor amet.")"#,
-        r#"ProvenanceMark(key: 558dbfc6536b296875bb47d085cf746eca274110de5cbde40f34e1b651372ca2, hash: f172222a21b23fb87eb3a6f50eda0b7e2c2e1a3d9f7ce9ad37d69049742f53bb, chainID: 090bf2f8b55be45b4661b24b7e9c340cf9464c5fe95c84f580954aaabe085e7c, seq: 1, date: 2023-06-21T12:00:00Z, info: "Lorem ipsum sit dolor amet.")"#,
-        r#"ProvenanceMark(key: abc6aa642861a61a42c751c2012df374e6528cad9d939b515e78dd288b3915f9, hash: 6da569b9f34ab28134d1417c4167293ec75bd825e773940208be0e378ab3591a, chainID: 090bf2f8b55be45b4661b24b7e9c340cf9464c5fe95c84f580954aaabe085e7c, seq: 2, date: 2023-06-22T12:00:00Z, info: "Lorem ipsum sit dolor amet.")"#,
-        r#"ProvenanceMark(key: 7fc7b276b810e4fc14a72ac53b5cd9b5445dbfc1264fd6de95b5ca8c060c24ba, hash: 10e6c12f1d64129ad8decf1e568a4ee974a4e8182673253ca8f657b5619cddee, chainID: 090bf2f8b55be45b4661b24b7e9c340cf9464c5fe95c84f580954aaabe085e7c, seq: 3, date: 2023-06-23T12:00:00Z, info: "Lorem ipsum sit dolor amet.")"#,
-        r#"ProvenanceMark(key: 1dadf8aa6a2b2fa657fc7a0225d1880e5946b5fd32e588c593923750478d74d4, hash: 6e4641df3c26057c5edff653bdfca67bfdc9a9fe74e6e6a5746af48e4fd78ee2, chainID: 090bf2f8b55be45b4661b24b7e9c340cf9464c5fe95c84f580954aaabe085e7c, seq: 4, date: 2023-06-24T12:00:00Z, info: "Lorem ipsum sit dolor amet.")"#,
-        r#"ProvenanceMark(key: 469cbdca128f2d85d803e81b3e0e3a7d64f15911e1854210ef5d1b22614a2006, hash: d99c321d11167179a24d4a57351c032c134f03df761b83fc74662b9805450367, chainID: 090bf2f8b55be45b4661b24b7e9c340cf9464c5fe95c84f580954aaabe085e7c, seq: 5, date: 2023-06-25T12:00:00Z, info: "Lorem ipsum sit dolor amet.")"#,
-        r#"ProvenanceMark(key: 46b5294ba2787ed96dc10f79a3de28885d37a6dc0981dca240e7f1324e94a598, hash: 1af31098e6b27a35c492d5fc0b6106108183627a3d8a97d8eeb1271bbcadbfb4, chainID: 090bf2f8b55be45b4661b24b7e9c340cf9464c5fe95c84f580954aaabe085e7c, seq: 6, date: 2023-06-26T12:00:00Z, info: "Lorem ipsum sit dolor amet.")"#,
-        r#"ProvenanceMark(key: 2e557fea3bc15ed4de848fc9929ab9b059fa7d30a1436b1fd9a6bc3dd4c08a06, hash: 111904ac8f50db2bd1e482b415562aec37fd7f022618a73593973046ae5007b0, chainID: 090bf2f8b55be45b4661b24b7e9c340cf9464c5fe95c84f580954aaabe085e7c, seq: 7, date: 2023-06-27T12:00:00Z, info: "Lorem ipsum sit dolor amet.")"#,
-        r#"ProvenanceMark(key: 53a5f76321139ef9f174b27b088a66c4803128e09eb4c3d97ca51f0c369dca63, hash: 4cfc564c0d863a843ddb014681a813e98cc7126985f6958bdc14bf01acc38997, chainID: 090bf2f8b55be45b4661b24b7e9c340cf9464c5fe95c84f580954aaabe085e7c, seq: 8, date: 2023-06-28T12:00:00Z, info: "Lorem ipsum sit dolor amet.")"#,
-        r#"ProvenanceMark(key: 3e095176523550db5ea446af664feab86dba35dbfd168b78e589999b09a702eb, hash: 1e5bd360d448c43b08941028e5154aae2c29d6d3c3bc08869f298465ba77d77a, chainID: 090bf2f8b55be45b4661b24b7e9c340cf9464c5fe95c84f580954aaabe085e7c, seq: 9, date: 2023-06-29T12:00:00Z, info: "Lorem ipsum sit dolor amet.")"#,
+        r#"ProvenanceMark(key: 090bf2f8b55be45b4661b24b7e9c340cf9464c5fe95c84f580954aaabe085e7c, hash: 0c6b0c1b5456dc960c9030f60474b317eade25621cd2fe5c6e70d5dd235c9480, chainID: 090bf2f8b55be45b4661b24b7e9c340cf9464c5fe95c84f580954aaabe085e7c, seq: 0, date: 2023-06-20T12:00:00.000Z, info: "Lorem ipsum sit dolor amet.")"#,
+        r#"ProvenanceMark(key: 558dbfc6536b296875bb47d085cf746eca274110de5cbde40f34e1b651372ca2, hash: f172222a21b23fb87eb3a6f50eda0b7e2c2e1a3d9f7ce9ad37d69049742f53bb, chainID: 090bf2f8b55be45b4661b24b7e9c340cf9464c5fe95c84f580954aaabe085e7c, seq: 1, date: 2023-06-21T12:00:00.000Z, info: "Lorem ipsum sit dolor amet.")"#,
+        r#"ProvenanceMark(key: abc6aa642861a61a42c751c2012df374e6528cad9d939b515e78dd288b3915f9, hash: 6da569b9f34ab28134d1417c4167293ec75bd825e773940208be0e378ab3591a, chainID: 090bf2f8b55be45b4661b24b7e9c340cf9464c5fe95c84f580954aaabe085e7c, seq: 2, date: 2023-06-22T12:00:00.000Z, info: "Lorem ipsum sit dolor amet.")"#,
+        r#"ProvenanceMark(key: 7fc7b276b810e4fc14a72ac53b5cd9b5445dbfc1264fd6de95b5ca8c060c24ba, hash: 10e6c12f1d64129ad8decf1e568a4ee974a4e8182673253ca8f657b5619cddee, chainID: 090bf2f8b55be45b4661b24b7e9c340cf9464c5fe95c84f580954aaabe085e7c, seq: 3, date: 2023-06-23T12:00:00.000Z, info: "Lorem ipsum sit dolor amet.")"#,
+        r#"ProvenanceMark(key: 1dadf8aa6a2b2fa657fc7a0225d1880e5946b5fd32e588c593923750478d74d4, hash: 6e4641df3c26057c5edff653bdfca67bfdc9a9fe74e6e6a5746af48e4fd78ee2, chainID: 090bf2f8b55be45b4661b24b7e9c340cf9464c5fe95c84f580954aaabe085e7c, seq: 4, date: 2023-06-24T12:00:00.000Z, info: "Lorem ipsum sit dolor amet.")"#,
+        r#"ProvenanceMark(key: 469cbdca128f2d85d803e81b3e0e3a7d64f15911e1854210ef5d1b22614a2006, hash: d99c321d11167179a24d4a57351c032c134f03df761b83fc74662b9805450367, chainID: 090bf2f8b55be45b4661b24b7e9c340cf9464c5fe95c84f580954aaabe085e7c, seq: 5, date: 2023-06-25T12:00:00.000Z, info: "Lorem ipsum sit dolor amet.")"#,
+        r#"ProvenanceMark(key: 46b5294ba2787ed96dc10f79a3de28885d37a6dc0981dca240e7f1324e94a598, hash: 1af31098e6b27a35c492d5fc0b6106108183627a3d8a97d8eeb1271bbcadbfb4, chainID: 090bf2f8b55be45b4661b24b7e9c340cf9464c5fe95c84f580954aaabe085e7c, seq: 6, date: 2023-06-26T12:00:00.000Z, info: "Lorem ipsum sit dolor amet.")"#,
+        r#"ProvenanceMark(key: 2e557fea3bc15ed4de848fc9929ab9b059fa7d30a1436b1fd9a6bc3dd4c08a06, hash: 111904ac8f50db2bd1e482b415562aec37fd7f022618a73593973046ae5007b0, chainID: 090bf2f8b55be45b4661b24b7e9c340cf9464c5fe95c84f580954aaabe085e7c, seq: 7, date: 2023-06-27T12:00:00.000Z, info: "Lorem ipsum sit dolor amet.")"#,
+        r#"ProvenanceMark(key: 53a5f76321139ef9f174b27b088a66c4803128e09eb4c3d97ca51f0c369dca63, hash: 4cfc564c0d863a843ddb014681a813e98cc7126985f6958bdc14bf01acc38997, chainID: 090bf2f8b55be45b4661b24b7e9c340cf9464c5fe95c84f580954aaabe085e7c, seq: 8, date: 2023-06-28T12:00:00.000Z, info: "Lorem ipsum sit dolor amet.")"#,
+        r#"ProvenanceMark(key: 3e095176523550db5ea446af664feab86dba35dbfd168b78e589999b09a702eb, hash: 1e5bd360d448c43b08941028e5154aae2c29d6d3c3bc08869f298465ba77d77a, chainID: 090bf2f8b55be45b4661b24b7e9c340cf9464c5fe95c84f580954aaabe085e7c, seq: 9, date: 2023-06-29T12:00:00.000Z, info: "Lorem ipsum sit dolor amet.")"#,
     ];
     let expected_bytewords = [
         "axis bald whiz yoga race help vibe help frog huts purr gear knob news edge barn yurt frog gems hope wall high liar yank lava mild game peck ruin away holy kite open fizz dark data lava brew gyro curl glow eyes body ramp epic tiny high item join navy solo gala away view acid real ruin open keys body dice stub fair inch yurt hill aunt toil calm task owls pool swan legs cusp plus edge iron tied lamb grim leaf keno stub code easy deli safe iron hard gems jolt flap days runs hawk hang bald menu draw curl noon jolt game legs void solo lazy zone unit judo limp yoga luck kick numb undo oboe code edge zone yank time note surf king blue waxy exit item gear holy zest good good pool wasp tomb huts",
@@ -973,6 +1004,8 @@ fn test_html_root_url() {
     version_sync::assert_html_root_url_updated!("src/lib.rs");
 }
 
+#[cfg(feature = "envelope")]
+use bc_envelope::FormatContext;
 #[cfg(feature = "envelope")]
 use bc_envelope::prelude::*;
 #[cfg(feature = "envelope")]
@@ -992,7 +1025,7 @@ fn test_envelope() {
     );
     let mark = generator.next(date, Some("Info field content"));
 
-    let gen_envelope = generator.clone().into_envelope();
+    let gen_envelope = generator.dangerous_clone_for_fork().into_envelope();
     // expected-text-output-rubric:
     #[rustfmt::skip]
     let expected = indoc! {r#"
@@ -1016,7 +1049,983 @@ fn test_envelope() {
     // expected-text-output-rubric:
     #[rustfmt::skip]
     let expected = indoc! {r#"
-        ProvenanceMark(key: b16a7cbd178ee0d41cadb0dcefdbe87d6a41c85b41c551134ae8307f9203babc, hash: 59def089a4d373a2d3f6a449c6758f62ba55cda64c7faf01c1c74a1130d3c1ee, chainID: b16a7cbd178ee0d41cadb0dcefdbe87d6a41c85b41c551134ae8307f9203babc, seq: 0, date: 2025-10-26, info: "Info field content")
+        ProvenanceMark(key: b16a7cbd178ee0d41cadb0dcefdbe87d6a41c85b41c551134ae8307f9203babc, hash: 59def089a4d373a2d3f6a449c6758f62ba55cda64c7faf01c1c74a1130d3c1ee, chainID: b16a7cbd178ee0d41cadb0dcefdbe87d6a41c85b41c551134ae8307f9203babc, seq: 0, date: 2025-10-26T00:00:00.000Z, info: "Info field content")
     "#}.trim();
     assert_eq!(format!("{:?}", mark), expected);
 }
+
+#[test]
+#[cfg(feature = "envelope")]
+fn test_register_tags_idempotent() {
+    provenance_mark::register_tags();
+    provenance_mark::register_tags();
+    assert!(provenance_mark::tags_registered());
+
+    let mut generator = ProvenanceMarkGenerator::new_with_passphrase(
+        ProvenanceMarkResolution::Low,
+        "Wolf",
+    );
+    let mark =
+        generator.next(Date::from_string("2023-06-20").unwrap(), None::<&str>);
+    let envelope =
+        Envelope::new("Essay").add_assertion("provenance", mark.clone());
+    let formatted = provenance_mark::with_registered_tags(|| envelope.format());
+    assert_eq!(
+        formatted.matches(&mark.to_string()).count(),
+        1,
+        "{formatted}"
+    );
+}
+
+#[test]
+#[cfg(feature = "envelope")]
+fn test_experimental_tags_not_registered_by_default() {
+    let experimental = [
+        TAG_PROVENANCE_GENERATOR,
+        TAG_CHAIN_TERMINATION,
+        TAG_ENCRYPTED_INFO,
+        TAG_CHAIN_CONTINUATION,
+        TAG_OWNERSHIP_CHALLENGE,
+        TAG_OWNERSHIP_RESPONSE,
+    ];
+
+    let mut context = FormatContext::default();
+    provenance_mark::register_tags_in(&mut context);
+    assert!(
+        context
+            .tags()
+            .tag_for_value(bc_tags::TAG_PROVENANCE_MARK)
+            .is_some()
+    );
+    for tag in experimental {
+        assert!(context.tags().tag_for_value(tag).is_none(), "{tag}");
+    }
+
+    provenance_mark::register_experimental_tags_in(&mut context);
+    for tag in experimental {
+        assert!(context.tags().tag_for_value(tag).is_some(), "{tag}");
+    }
+}
+
+#[test]
+#[cfg(feature = "json")]
+fn test_self_consistent() {
+    let mut generator = ProvenanceMarkGenerator::new_with_passphrase(
+        ProvenanceMarkResolution::Low,
+        "Wolf",
+    );
+    let date = Date::from_string("2023-06-20").unwrap();
+    let genesis = generator.next(date, None::<&str>);
+    let mark = generator.next(date, Some("Lorem ipsum sit dolor amet."));
+    assert_eq!(genesis.self_consistent(), Ok(()));
+    assert_eq!(mark.self_consistent(), Ok(()));
+
+    // A genesis mark whose seq was edited without touching the key or hash
+    let json = serde_json::to_string(&genesis).unwrap();
+    let edited = json.replace(r#""seq":0"#, r#""seq":3"#);
+    assert_ne!(json, edited);
+    let err = serde_json::from_str::<ProvenanceMark>(&edited).unwrap_err();
+    assert!(
+        err.to_string().contains(
+            "inconsistent provenance mark: key equals chain ID at non-zero sequence number"
+        ),
+        "unexpected error: {err}"
+    );
+
+    // A non-genesis mark whose seq was edited is re-derived from the new seq,
+    // so it deserializes as a different, self-consistent mark that only
+    // validation against its neighbors exposes
+    let third = generator.next(date, None::<&str>);
+    let json = serde_json::to_string(&mark).unwrap();
+    let edited = json.replace(r#""seq":1"#, r#""seq":5"#);
+    assert_ne!(json, edited);
+    let tampered = serde_json::from_str::<ProvenanceMark>(&edited).unwrap();
+    assert_eq!(tampered.seq(), 5);
+    assert_eq!(tampered.self_consistent(), Ok(()));
+    assert_ne!(tampered, mark);
+    assert!(!genesis.precedes(&tampered));
+    assert!(!tampered.precedes(&third));
+    assert!(genesis.precedes(&mark) && mark.precedes(&third));
+
+    // A date more precise than the Low resolution can represent
+    let edited = json.replace(r#""2023-06-20""#, r#""2023-06-20T12:34:56Z""#);
+    assert_ne!(json, edited);
+    let err = serde_json::from_str::<ProvenanceMark>(&edited).unwrap_err();
+    assert!(
+        err.to_string().contains("date is more precise"),
+        "unexpected error: {err}"
+    );
+}
+
+#[test]
+fn test_from_fields() {
+    let mut generator = ProvenanceMarkGenerator::new_with_passphrase(
+        ProvenanceMarkResolution::Quartile,
+        "Wolf",
+    );
+    let date = Date::from_string("2023-06-20").unwrap();
+    let marks = [
+        generator.next(date, None::<&str>),
+        generator.next(date, Some("Lorem ipsum sit dolor amet.")),
+    ];
+    for mark in marks {
+        let info_bytes = mark
+            .info()
+            .map(|info| info.to_cbor_data())
+            .unwrap_or_default();
+        let rebuilt = ProvenanceMark::from_fields(
+            mark.res(),
+            mark.key().clone(),
+            mark.hash().clone(),
+            mark.chain_id().clone(),
+            mark.seq().into(),
+            mark.date(),
+            info_bytes,
+        )
+        .unwrap();
+        assert_eq!(rebuilt.message(), mark.message());
+        assert_eq!(rebuilt.ur_string(), mark.ur_string());
+        assert_eq!(rebuilt, mark);
+    }
+}
+
+#[test]
+#[allow(deprecated)]
+fn test_from_byte_fields_errors() {
+    let mut generator = ProvenanceMarkGenerator::new_with_passphrase(
+        ProvenanceMarkResolution::Low,
+        "Wolf",
+    );
+    let mark =
+        generator.next(Date::from_string("2023-06-20").unwrap(), None::<&str>);
+    let from_fields = |key: &[u8], hash: &[u8], date, info_bytes: &[u8]| {
+        ProvenanceMark::from_byte_fields(
+            mark.res(),
+            key.to_vec(),
+            hash.to_vec(),
+            mark.chain_id().to_vec(),
+            mark.seq().into(),
+            date,
+            info_bytes.to_vec(),
+        )
+    };
+
+    assert!(matches!(
+        from_fields(&[0; 5], mark.hash(), mark.date(), &[]),
+        Err(Error::InvalidKeyLength { expected: 4, actual: 5 })
+    ));
+    assert!(matches!(
+        from_fields(mark.key(), &[0; 3], mark.date(), &[]),
+        Err(Error::InvalidHashLength { expected: 4, actual: 3 })
+    ));
+
+    // Low resolution dates have no time of day
+    let precise = Date::from_string("2023-06-20T12:34:56Z").unwrap();
+    assert!(matches!(
+        from_fields(mark.key(), mark.hash(), precise, &[]),
+        Err(Error::InvalidDate { .. })
+    ));
+    let early = Date::from_string("2020-01-01").unwrap();
+    assert!(from_fields(mark.key(), mark.hash(), early, &[]).is_err());
+
+    assert!(matches!(
+        from_fields(mark.key(), mark.hash(), mark.date(), &[0xff]),
+        Err(Error::InvalidInfoCbor)
+    ));
+}
+
+#[test]
+fn test_payload() {
+    let mut generator = ProvenanceMarkGenerator::new_with_passphrase(
+        ProvenanceMarkResolution::Low,
+        "test",
+    );
+    // (message, payload)
+    let expected = [
+        (
+            "b16a7cbd27610028d06a96d85092192d",
+            "b16a7cbdf057c8c4000000d4",
+        ),
+        (
+            "354e29c31446cb3849fb3601bed2c076",
+            "b16a7cbd1b806d6c000100d5",
+        ),
+        (
+            "1307a9b4a1bb2ea9e6c13c3eadadbca1",
+            "b16a7cbdb292f357000200d6",
+        ),
+        (
+            "61e08a3880d794ea9b8e6f03a5cc28eb",
+            "b16a7cbd761a5e74000300d7",
+        ),
+        (
+            "bb7d8cf7c3362e7a633474b99b81e42b",
+            "b16a7cbd42d12de5000400d8",
+        ),
+    ];
+    for (i, (message, payload)) in expected.into_iter().enumerate() {
+        let date = dcbor::Date::from_datetime(
+            chrono::Utc
+                .with_ymd_and_hms(2023, 6, 20 + i as u32, 12, 0, 0)
+                .single()
+                .unwrap(),
+        );
+        let mark = generator.next(date, None::<String>);
+        assert_eq!(mark.message_hex(), message);
+        assert_eq!(mark.payload_hex(), payload);
+
+        // The payload never includes the key
+        let payload = mark.payload();
+        assert_eq!(&payload[..4], mark.chain_id());
+        assert_eq!(&payload[4..8], mark.hash());
+        assert_eq!(&mark.message()[..4], mark.key());
+    }
+}
+
+proptest::proptest! {
+    #[test]
+    fn test_from_message_roundtrip(
+        res in 0u8..4,
+        seed in proptest::prelude::any::<[u8; 32]>(),
+        seq in proptest::prelude::any::<u32>(),
+        // 2023-01-01 through 2099-12-31
+        timestamp in 1_672_531_200i64..4_102_444_800,
+        info in proptest::option::of(".{0,40}"),
+    ) {
+        let res = ProvenanceMarkResolution::try_from(res).unwrap();
+        let seq = match res {
+            ProvenanceMarkResolution::Low => seq % (u16::MAX as u32 + 1),
+            _ => seq,
+        };
+        let link = res.link_length();
+        let next_key = provenance_mark::crypto_utils::sha256(seed);
+        let chain_id = provenance_mark::crypto_utils::sha256(next_key);
+        let date = dcbor::Date::from_datetime(
+            chrono::Utc.timestamp_opt(timestamp, 0).unwrap(),
+        );
+
+        let mark = ProvenanceMark::new(
+            res,
+            LinkKey::new(res, &seed[..link]).unwrap(),
+            LinkKey::new(res, &next_key[..link]).unwrap(),
+            ChainId::new(res, &chain_id[..link]).unwrap(),
+            seq.into(),
+            date,
+            info,
+        )
+        .unwrap();
+        let decoded =
+            ProvenanceMark::from_message(res, mark.message()).unwrap();
+        proptest::prop_assert_eq!(&decoded, &mark);
+        proptest::prop_assert_eq!(decoded.key(), mark.key());
+        proptest::prop_assert_eq!(decoded.hash(), mark.hash());
+        proptest::prop_assert_eq!(decoded.chain_id(), mark.chain_id());
+        proptest::prop_assert_eq!(decoded.seq(), mark.seq());
+        proptest::prop_assert_eq!(decoded.date(), mark.date());
+        proptest::prop_assert_eq!(decoded.info(), mark.info());
+        proptest::prop_assert_eq!(decoded.payload(), mark.payload());
+    }
+}
+
+#[test]
+#[cfg(feature = "url")]
+fn test_deep_links() {
+    let mut generator = ProvenanceMarkGenerator::new_with_passphrase(
+        ProvenanceMarkResolution::Low,
+        "Wolf",
+    );
+    let date = Date::from_string("2023-06-20").unwrap();
+    let mark = generator.next(date, None::<&str>);
+    let other = generator.next(date, None::<&str>);
+    let segment = mark.to_path_segment();
+    assert!(segment.chars().all(|c| c.is_ascii_lowercase()));
+    assert_eq!(
+        ProvenanceMark::from_path_segment(&segment.to_uppercase()).unwrap(),
+        mark
+    );
+
+    // With or without a trailing slash, the mark becomes the last segment
+    let expected = format!("https://registry.example/m/{segment}");
+    for base in ["https://registry.example/m", "https://registry.example/m/"] {
+        let base = url::Url::parse(base).unwrap();
+        let link = mark.to_deep_link(&base).unwrap();
+        assert_eq!(link.as_str(), expected);
+        assert_eq!(ProvenanceMark::from_deep_link(&link).unwrap(), mark);
+    }
+
+    // A trailing slash after the mark is tolerated
+    let link = url::Url::parse(&format!("{expected}/")).unwrap();
+    assert_eq!(ProvenanceMark::from_deep_link(&link).unwrap(), mark);
+
+    // Links using the query parameter are still understood
+    let link = mark.to_url("https://registry.example/m");
+    assert_eq!(ProvenanceMark::from_deep_link(&link).unwrap(), mark);
+
+    // The same mark in both places is accepted, but different ones are not
+    let link =
+        url::Url::parse(&format!("{expected}?provenance={segment}")).unwrap();
+    assert_eq!(ProvenanceMark::from_deep_link(&link).unwrap(), mark);
+    let link = url::Url::parse(&format!(
+        "{expected}?provenance={}",
+        other.to_url_encoding()
+    ))
+    .unwrap();
+    assert!(matches!(
+        ProvenanceMark::from_deep_link(&link),
+        Err(Error::AmbiguousDeepLink { .. })
+    ));
+
+    // Neither a path token nor a query parameter
+    let link = url::Url::parse("https://registry.example/m/about").unwrap();
+    assert!(matches!(
+        ProvenanceMark::from_deep_link(&link),
+        Err(Error::MissingUrlParameter { .. })
+    ));
+
+    let base = url::Url::parse("mailto:registry@example.com").unwrap();
+    assert!(matches!(
+        mark.to_deep_link(&base),
+        Err(Error::InvalidDeepLinkBase { .. })
+    ));
+}
+
+#[test]
+#[cfg(feature = "json")]
+fn test_display_json() {
+    let mut generator = ProvenanceMarkGenerator::new_with_passphrase(
+        ProvenanceMarkResolution::Low,
+        "Wolf",
+    );
+    let date = Date::from_string("2023-06-20").unwrap();
+    let mark = generator.next(date, None::<&str>);
+    let display = mark.to_display_json();
+
+    // Everything in the serde form is kept
+    let serde_form = serde_json::to_value(&mark).unwrap();
+    for (field, value) in serde_form.as_object().unwrap() {
+        assert_eq!(&display[field], value, "{field}");
+    }
+    assert_eq!(display["ur"], mark.ur_string());
+    assert_eq!(display["identifier"], mark.id_hex());
+    assert_eq!(display["bytewords_identifier"], mark.id_bytewords(4, true));
+    assert_eq!(display["bytemoji_identifier"], mark.id_bytemoji(4, true));
+    assert_eq!(display["resolution_name"], "low");
+    assert_eq!(display["is_genesis"], true);
+    assert_eq!(ProvenanceMark::from_display_json(&display).unwrap(), mark);
+
+    // Only the UR is required
+    let minimal = serde_json::json!({ "ur": mark.ur_string() });
+    assert_eq!(ProvenanceMark::from_display_json(&minimal).unwrap(), mark);
+
+    // A redundant field that disagrees with the UR is rejected
+    let mut edited = display.clone();
+    edited["bytewords_identifier"] = "🅟 ABLE ACID ALSO APEX".into();
+    let err = ProvenanceMark::from_display_json(&edited).unwrap_err();
+    assert!(matches!(
+        &err,
+        Error::DisplayJsonMismatch { field } if field == "bytewords_identifier"
+    ));
+    assert!(
+        err.to_string().contains("\"bytewords_identifier\""),
+        "{err}"
+    );
+
+    let mut edited = display.clone();
+    edited["seq"] = 1.into();
+    assert!(matches!(
+        ProvenanceMark::from_display_json(&edited),
+        Err(Error::DisplayJsonMismatch { field }) if field == "seq"
+    ));
+
+    let mut missing = display;
+    missing.as_object_mut().unwrap().remove("ur");
+    assert!(matches!(
+        ProvenanceMark::from_display_json(&missing),
+        Err(Error::MissingDisplayJsonUr)
+    ));
+}
+
+#[test]
+#[cfg(feature = "json")]
+fn test_serde_field_order() {
+    let date = Date::from_string("2023-06-20T12:00:00Z").unwrap();
+    let genesis = |res: ProvenanceMarkResolution, info: Option<&str>| {
+        ProvenanceMarkGenerator::new_with_passphrase(res, "Wolf")
+            .next(date, info)
+    };
+
+    let expected = [
+        (
+            ProvenanceMarkResolution::Low,
+            r#"{"chainID":"CQvy+A==","date":"2023-06-20","hash":"W9zsgQ==","key":"CQvy+A==","res":0,"seq":0}"#,
+        ),
+        (
+            ProvenanceMarkResolution::Medium,
+            r#"{"chainID":"CQvy+LVb5Fs=","date":"2023-06-20T12:00:00Z","hash":"GI1r2a2LxPM=","key":"CQvy+LVb5Fs=","res":1,"seq":0}"#,
+        ),
+        (
+            ProvenanceMarkResolution::Quartile,
+            r#"{"chainID":"CQvy+LVb5FtGYbJLfpw0DA==","date":"2023-06-20T12:00:00Z","hash":"Sgc4oxo+kHPxwBmZzQH/Cg==","key":"CQvy+LVb5FtGYbJLfpw0DA==","res":2,"seq":0}"#,
+        ),
+        (
+            ProvenanceMarkResolution::High,
+            r#"{"chainID":"CQvy+LVb5FtGYbJLfpw0DPlGTF/pXIT1gJVKqr4IXnw=","date":"2023-06-20T12:00:00Z","hash":"jNjlHyiFrsosZEKAtT7nWqErWhX02EBoyMkY+MDYWHg=","key":"CQvy+LVb5FtGYbJLfpw0DPlGTF/pXIT1gJVKqr4IXnw=","res":3,"seq":0}"#,
+        ),
+    ];
+    for (res, json) in expected {
+        let mark = genesis(res, None);
+        assert_eq!(serde_json::to_string(&mark).unwrap(), json, "{res:?}");
+        assert_eq!(serde_json::from_str::<ProvenanceMark>(json).unwrap(), mark);
+    }
+
+    // Info follows the hash
+    let mark = genesis(
+        ProvenanceMarkResolution::Low,
+        Some("Lorem ipsum sit dolor amet."),
+    );
+    let json = r#"{"chainID":"CQvy+A==","date":"2023-06-20","hash":"uu40wg==","infoBytes":"eBtMb3JlbSBpcHN1bSBzaXQgZG9sb3IgYW1ldC4=","key":"CQvy+A==","res":0,"seq":0}"#;
+    assert_eq!(serde_json::to_string(&mark).unwrap(), json);
+
+    // Keys are accepted in any order and with either spelling
+    let reordered = r#"{"seq":0,"res":0,"key":"CQvy+A==","info_bytes":"eBtMb3JlbSBpcHN1bSBzaXQgZG9sb3IgYW1ldC4=","hash":"uu40wg==","date":"2023-06-20","chain_id":"CQvy+A=="}"#;
+    assert_eq!(
+        serde_json::from_str::<ProvenanceMark>(reordered).unwrap(),
+        mark
+    );
+}
+
+#[test]
+fn test_self_check() {
+    let mut generator = ProvenanceMarkGenerator::new_with_passphrase(
+        ProvenanceMarkResolution::Medium,
+        "Wolf",
+    );
+    let date = Date::from_string("2023-06-20").unwrap();
+    let genesis = generator.next(date, None::<&str>);
+    let mark = generator.next(date, Some("Lorem ipsum sit dolor amet."));
+    assert_eq!(genesis.self_check(), Ok(()));
+    assert!(mark.passes_self_check());
+
+    // `from_fields` takes the key and sequence number as given, so it can
+    // build marks that break the genesis invariant in either direction.
+    let rebuild = |source: &ProvenanceMark, key: &LinkKey, seq: u32| {
+        ProvenanceMark::from_fields(
+            source.res(),
+            key.clone(),
+            source.hash().clone(),
+            source.chain_id().clone(),
+            seq.into(),
+            source.date(),
+            Vec::new(),
+        )
+        .unwrap()
+    };
+    let genesis_key_later = rebuild(&genesis, genesis.key(), 3);
+    assert_eq!(
+        genesis_key_later.self_check(),
+        Err(vec![SelfCheckFailure::GenesisKeyAtNonZeroSeq])
+    );
+    let other_key_at_zero = rebuild(&mark, mark.key(), 0);
+    assert_eq!(
+        other_key_at_zero.self_check(),
+        Err(vec![SelfCheckFailure::NonGenesisKeyAtZeroSeq])
+    );
+    assert!(!other_key_at_zero.passes_self_check());
+    assert_eq!(
+        SelfCheckFailure::NonGenesisKeyAtZeroSeq.to_string(),
+        "key differs from chain ID at sequence number 0"
+    );
+}
+
+fn decode_untagged(data: &[u8]) -> dcbor::Result<ProvenanceMark> {
+    CBOR::try_from_data(data).and_then(ProvenanceMark::from_untagged_cbor)
+}
+
+fn untagged_mark(res: CBOR, message: CBOR) -> CBOR {
+    CBOR::from(vec![res, message])
+}
+
+#[test]
+fn test_strict_resolution_decoding() {
+    let mut generator = ProvenanceMarkGenerator::new_with_passphrase(
+        ProvenanceMarkResolution::Quartile,
+        "Wolf",
+    );
+    let mark =
+        generator.next(Date::from_string("2023-06-20").unwrap(), Some("info"));
+    let message = CBOR::to_byte_string(mark.message());
+    assert_eq!(
+        ProvenanceMark::from_untagged_cbor(untagged_mark(
+            CBOR::from(2),
+            message.clone()
+        ))
+        .unwrap(),
+        mark
+    );
+
+    let invalid = [
+        (CBOR::from(4), "unsigned integer 4"),
+        (CBOR::from(256), "unsigned integer 256"),
+        (
+            CBOR::from(u64::MAX),
+            "unsigned integer 18446744073709551615",
+        ),
+        (CBOR::from(-1), "negative integer"),
+        (CBOR::from(2.5), "float"),
+        (CBOR::from("2"), "text string"),
+        (CBOR::to_byte_string([2]), "byte string"),
+        (CBOR::from(vec![2]), "array"),
+        (
+            CBOR::to_tagged_value(2, CBOR::to_byte_string([2])),
+            "tagged value",
+        ),
+        (CBOR::null(), "simple value"),
+        (CBOR::from(true), "simple value"),
+    ];
+    for (res, found) in invalid {
+        let error = ProvenanceMark::from_untagged_cbor(untagged_mark(
+            res,
+            message.clone(),
+        ))
+        .unwrap_err()
+        .to_string();
+        assert!(error.contains("invalid resolution encoding"), "{error}");
+        assert!(error.ends_with(&format!("got {found}")), "{error}");
+    }
+
+    // A float is never accepted in place of an integer: the canonical
+    // encoding of 2.0 is the integer 2, and dCBOR rejects the float encoding
+    let mut data = untagged_mark(CBOR::from(2), message).to_cbor_data();
+    assert_eq!(&data[..2], &[0x82, 0x02]);
+    data.splice(1..2, [0xf9, 0x40, 0x00]);
+    assert!(decode_untagged(&data).is_err());
+}
+
+#[test]
+fn test_strict_message_decoding() {
+    for res in [
+        ProvenanceMarkResolution::Low,
+        ProvenanceMarkResolution::Medium,
+        ProvenanceMarkResolution::Quartile,
+        ProvenanceMarkResolution::High,
+    ] {
+        let res_cbor = CBOR::from(res);
+        for len in [0, 1, res.fixed_length() - 1] {
+            let error = ProvenanceMark::from_untagged_cbor(untagged_mark(
+                res_cbor.clone(),
+                CBOR::to_byte_string(vec![0; len]),
+            ))
+            .unwrap_err()
+            .to_string();
+            assert!(
+                error.contains(&format!(
+                    "expected at least {}, got {}",
+                    res.fixed_length(),
+                    len
+                )),
+                "{error}"
+            );
+        }
+        for message in [CBOR::from("message"), CBOR::from(0), CBOR::null()] {
+            assert!(
+                ProvenanceMark::from_untagged_cbor(untagged_mark(
+                    res_cbor.clone(),
+                    message
+                ))
+                .is_err()
+            );
+        }
+    }
+
+    for cbor in [
+        CBOR::from(Vec::<CBOR>::new()),
+        CBOR::from(vec![CBOR::from(0)]),
+        CBOR::from(vec![
+            CBOR::from(0),
+            CBOR::to_byte_string([0; 16]),
+            0.into(),
+        ]),
+        CBOR::from(0),
+        CBOR::to_byte_string([0; 16]),
+    ] {
+        assert!(ProvenanceMark::from_untagged_cbor(cbor).is_err());
+    }
+}
+
+proptest::proptest! {
+    // Arbitrary and corrupted encodings decode to a mark or a clean error,
+    // never a panic
+    #[test]
+    fn test_decode_malformed_cbor(
+        data in proptest::collection::vec(
+            proptest::prelude::any::<u8>(),
+            0..80,
+        ),
+        res in 0u8..4,
+        index in proptest::prelude::any::<usize>(),
+        byte in proptest::prelude::any::<u8>(),
+    ) {
+        let _ = decode_untagged(&data);
+        let _ = ProvenanceMark::from_tagged_cbor_data(&data);
+
+        let res = ProvenanceMarkResolution::try_from(res).unwrap();
+        let mut generator =
+            ProvenanceMarkGenerator::new_with_passphrase(res, "Wolf");
+        let mark = generator
+            .next(Date::from_string("2023-06-20").unwrap(), Some("info"));
+        let mut data = mark.tagged_cbor().to_cbor_data();
+        let index = index % data.len();
+        data[index] = byte;
+        let _ = ProvenanceMark::from_tagged_cbor_data(&data);
+        data.truncate(index);
+        let _ = ProvenanceMark::from_tagged_cbor_data(&data);
+    }
+}
+
+#[test]
+fn test_ur_type_and_cbor_tag() {
+    let mut generator = ProvenanceMarkGenerator::new_with_passphrase(
+        ProvenanceMarkResolution::Low,
+        "Wolf",
+    );
+    let mark = generator
+        .next(Date::from_string("2023-06-20").unwrap(), None::<String>);
+
+    assert_eq!(CBOR_TAG, 1347571542);
+    let tagged = mark.tagged_cbor();
+    let CBORCase::Tagged(tag, _) = tagged.as_case() else {
+        panic!("untagged: {tagged}");
+    };
+    assert_eq!(tag.value(), CBOR_TAG);
+
+    assert_eq!(UR_TYPE, "provenance");
+    let ur = mark.ur();
+    assert_eq!(ur.ur_type_str(), UR_TYPE);
+    assert!(mark.ur_string().starts_with(&format!("ur:{UR_TYPE}/")));
+
+    let cddl = cddl();
+    assert!(cddl.contains(&format!("provenance-mark = #6.{CBOR_TAG}(")));
+    assert!(
+        cddl.contains(
+            "resolution = &(low: 0, medium: 1, quartile: 2, high: 3)"
+        )
+    );
+}
+
+#[test]
+fn test_ur_type_matches() {
+    let mark = ProvenanceMarkGenerator::new_with_passphrase(
+        ProvenanceMarkResolution::Low,
+        "Wolf",
+    )
+    .next(Date::from_string("2023-06-20").unwrap(), None::<String>);
+    let seed = UR::new("seed", CBOR::to_byte_string([7u8; 16])).unwrap();
+    let decoy = UR::from_ur_string(seed.string()).unwrap();
+
+    let urs = [mark.ur(), decoy];
+    let marks: Vec<ProvenanceMark> = urs
+        .iter()
+        .filter(|ur| ProvenanceMark::ur_type_matches(ur))
+        .map(|ur| ProvenanceMark::from_ur(ur).unwrap())
+        .collect();
+    assert_eq!(marks, [mark]);
+    assert!(!ProvenanceMark::ur_type_matches(&urs[1]));
+}
+
+#[test]
+fn test_compact_string_round_trip() {
+    let date = Date::from_datetime(
+        chrono::Utc
+            .with_ymd_and_hms(2023, 6, 20, 12, 34, 56)
+            .single()
+            .unwrap(),
+    );
+    for res in [
+        ProvenanceMarkResolution::Low,
+        ProvenanceMarkResolution::Medium,
+        ProvenanceMarkResolution::Quartile,
+        ProvenanceMarkResolution::High,
+    ] {
+        let mut generator =
+            ProvenanceMarkGenerator::new_with_passphrase(res, "Wolf");
+        for info in [None, Some("Lorem ipsum sit dolor amet.")] {
+            let mark = generator.next(date, info);
+            let compact = mark.to_compact_string();
+            assert!(!compact.contains('\n'));
+            assert!(compact.starts_with(&format!(
+                "pm1:{res}:seq={}:date={}:ur=ur:provenance/",
+                mark.seq(),
+                mark.date()
+            )));
+            assert_eq!(
+                ProvenanceMark::from_compact_string(&format!(" {compact}\n"))
+                    .unwrap(),
+                mark
+            );
+        }
+    }
+}
+
+#[test]
+fn test_compact_string_grammar() {
+    let mark = ProvenanceMarkGenerator::new_with_passphrase(
+        ProvenanceMarkResolution::Low,
+        "test",
+    )
+    .next(Date::from_string("2023-06-20").unwrap(), None::<String>);
+    let compact = "pm1:low:seq=0:date=2023-06-20:ur=ur:provenance/lfaegdpaimkerydihsaedetiimmttpgdmocfdpbnhlasba";
+    assert_eq!(mark.to_compact_string(), compact);
+    assert_eq!(ProvenanceMark::from_compact_string(compact).unwrap(), mark);
+    // `Display` is unchanged
+    assert_eq!(
+        mark.to_string(),
+        format!("ProvenanceMark({})", mark.id_hex())
+    );
+}
+
+#[test]
+fn test_compact_string_rejects_tampering() {
+    let compact = "pm1:low:seq=0:date=2023-06-20:ur=ur:provenance/lfaegdpaimkerydihsaedetiimmttpgdmocfdpbnhlasba";
+    let reject = |s: &str| match ProvenanceMark::from_compact_string(s) {
+        Err(Error::InvalidCompactString { details }) => details,
+        result => panic!("unexpected result for {s}: {result:?}"),
+    };
+
+    assert_eq!(
+        reject(&compact.replace("seq=0", "seq=1")),
+        r#"seq "1" does not match the mark's "0""#
+    );
+    assert_eq!(
+        reject(&compact.replace(":low:", ":high:")),
+        r#"resolution "high" does not match the mark's "low""#
+    );
+    assert_eq!(
+        reject(&compact.replace("2023-06-20", "2023-06-21")),
+        r#"date "2023-06-21" does not match the mark's "2023-06-20""#
+    );
+    assert_eq!(
+        reject(&compact.replace("pm1:", "pm2:")),
+        r#"expected "pm1:""#
+    );
+    assert_eq!(reject(&compact.replace(":ur=", ":")), r#"expected ":ur=""#);
+    assert!(
+        ProvenanceMark::from_compact_string(
+            &compact.replace("lfaegd", "lfaegf")
+        )
+        .is_err()
+    );
+}
+
+#[test]
+fn test_date_precision() {
+    let date = Date::from_datetime(
+        chrono::Utc
+            .with_ymd_and_hms(2023, 6, 20, 12, 34, 56)
+            .single()
+            .unwrap()
+            + chrono::Duration::milliseconds(789),
+    );
+    let expected = [
+        (
+            ProvenanceMarkResolution::Low,
+            DatePrecision::Day,
+            "2023-06-20",
+        ),
+        (
+            ProvenanceMarkResolution::Medium,
+            DatePrecision::Second,
+            "2023-06-20T12:34:56Z",
+        ),
+        (
+            ProvenanceMarkResolution::Quartile,
+            DatePrecision::Millisecond,
+            "2023-06-20T12:34:56.789Z",
+        ),
+        (
+            ProvenanceMarkResolution::High,
+            DatePrecision::Millisecond,
+            "2023-06-20T12:34:56.789Z",
+        ),
+    ];
+    for (res, precision, display) in expected {
+        let mark = ProvenanceMarkGenerator::new_with_passphrase(res, "Wolf")
+            .next(date, None::<String>);
+        assert_eq!(mark.date_precision(), precision, "{res}");
+        assert_eq!(mark.date_display(), display, "{res}");
+        assert!(
+            format!("{:?}", mark).ends_with(&format!("date: {display})")),
+            "{res}"
+        );
+    }
+
+    // Whole seconds still show their milliseconds at millisecond precision
+    let midnight = Date::from_string("2023-06-20").unwrap();
+    assert_eq!(
+        DatePrecision::Second.format(&midnight),
+        "2023-06-20T00:00:00Z"
+    );
+    assert_eq!(
+        DatePrecision::Millisecond.format(&midnight),
+        "2023-06-20T00:00:00.000Z"
+    );
+}
+
+#[test]
+fn test_extended_key_cache_does_not_affect_identity() {
+    use std::{
+        collections::{HashSet, hash_map::DefaultHasher},
+        hash::{Hash, Hasher},
+    };
+
+    use provenance_mark::crypto_utils::{
+        extend_key, obfuscate, obfuscate_with_extended_key,
+    };
+
+    fn hash_of(mark: &ProvenanceMark) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        mark.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    let mark = ProvenanceMarkGenerator::new_with_passphrase(
+        ProvenanceMarkResolution::Quartile,
+        "Wolf",
+    )
+    .next(Date::from_string("2023-06-20").unwrap(), Some("info"));
+    let info_bytes = mark.info().unwrap().to_cbor_data();
+
+    // The same mark built each way, with its cache filled or not
+    let decoded =
+        ProvenanceMark::from_message(mark.res(), mark.message()).unwrap();
+    let reassembled = ProvenanceMark::from_fields(
+        mark.res(),
+        mark.key().clone(),
+        mark.hash().clone(),
+        mark.chain_id().clone(),
+        mark.seq().into(),
+        mark.date(),
+        info_bytes,
+    )
+    .unwrap();
+    let expected_message =
+        [mark.key().to_vec(), obfuscate(mark.key(), mark.payload())].concat();
+    for copy in [&decoded, &reassembled, &reassembled.clone()] {
+        assert_eq!(copy, &mark);
+        assert_eq!(hash_of(copy), hash_of(&mark));
+        assert_eq!(copy.message(), expected_message);
+        assert_eq!(copy.ur_string(), mark.ur_string());
+        assert_eq!(copy.to_cbor_data(), mark.to_cbor_data());
+        assert_eq!(format!("{:?}", copy), format!("{:?}", mark));
+        #[cfg(feature = "json")]
+        assert_eq!(
+            serde_json::to_string(copy).unwrap(),
+            serde_json::to_string(&mark).unwrap()
+        );
+    }
+    assert_eq!(HashSet::from([mark.clone(), decoded, reassembled]).len(), 1);
+
+    let key = extend_key(mark.key());
+    assert_eq!(
+        obfuscate_with_extended_key(&key, mark.payload()),
+        obfuscate(mark.key(), mark.payload())
+    );
+    assert!(obfuscate_with_extended_key(&key, b"").is_empty());
+}
+
+#[test]
+fn test_seq_out_of_range() {
+    let date = Date::from_string("2023-06-20").unwrap();
+    let mark = |res: ProvenanceMarkResolution, seq: u64| {
+        let source = ProvenanceMarkGenerator::new_with_passphrase(res, "test")
+            .next(date, None::<String>);
+        ProvenanceMark::new(
+            res,
+            LinkKey::new(res, source.hash().as_bytes()).unwrap(),
+            LinkKey::new(res, source.hash().as_bytes()).unwrap(),
+            source.chain_id().clone(),
+            seq,
+            date,
+            None::<String>,
+        )
+    };
+    let out_of_range =
+        |result: provenance_mark::Result<ProvenanceMark>| match result {
+            Err(Error::SequenceOutOfRange { value, max }) => (value, max),
+            result => panic!("unexpected result: {result:?}"),
+        };
+
+    let low = ProvenanceMarkResolution::Low;
+    assert_eq!(mark(low, 65535).unwrap().seq(), 65535);
+    assert_eq!(out_of_range(mark(low, 65536)), (65536, 65535));
+
+    let medium = ProvenanceMarkResolution::Medium;
+    let last = mark(medium, u32::MAX.into()).unwrap();
+    assert_eq!(last.seq(), u32::MAX);
+    assert_eq!(out_of_range(mark(medium, 1 << 32)), (4294967296, u32::MAX));
+
+    // `from_fields` is checked the same way, never truncating
+    let rebuilt = |seq: u64| {
+        ProvenanceMark::from_fields(
+            low,
+            LinkKey::new(low, [1u8; 4]).unwrap(),
+            LinkHash::new(low, [2u8; 4]).unwrap(),
+            ChainId::new(low, [3u8; 4]).unwrap(),
+            seq,
+            date,
+            Vec::new(),
+        )
+    };
+    assert_eq!(rebuilt(65535).unwrap().seq(), 65535);
+    assert_eq!(out_of_range(rebuilt(65536)), (65536, 65535));
+    assert_eq!(out_of_range(rebuilt(1 << 32)), (4294967296, 65535));
+
+    // Generator state is checked against `u32` only, since a generator that
+    // has issued its last mark is one past its resolution's maximum
+    let continuation = |next_seq: u64| {
+        ChainContinuation::new(
+            low,
+            vec![3; 4],
+            next_seq,
+            RngState::from([0u8; 32]),
+        )
+    };
+    assert_eq!(continuation(65536).unwrap().next_seq(), 65536);
+    assert!(matches!(
+        continuation(1 << 32),
+        Err(Error::SequenceOutOfRange { value: 4294967296, max: u32::MAX })
+    ));
+    assert!(matches!(
+        ProvenanceMarkGenerator::new(
+            medium,
+            ProvenanceSeed::new_with_passphrase("test"),
+            vec![3; 8],
+            1 << 32,
+            RngState::from([0u8; 32]),
+        ),
+        Err(Error::SequenceOutOfRange { value: 4294967296, max: u32::MAX })
+    ));
+}
+
+#[test]
+#[cfg(feature = "json")]
+fn test_seq_out_of_range_json() {
+    let date = Date::from_string("2023-06-20").unwrap();
+    for (res, seq, max) in [
+        (ProvenanceMarkResolution::Low, 65536u64, 65535u32),
+        (ProvenanceMarkResolution::Medium, 1 << 32, u32::MAX),
+    ] {
+        let mark = ProvenanceMarkGenerator::new_with_passphrase(res, "test")
+            .next(date, None::<String>);
+        let mut json = serde_json::to_value(&mark).unwrap();
+        json["seq"] = seq.into();
+        let err = serde_json::from_value::<ProvenanceMark>(json)
+            .unwrap_err()
+            .to_string();
+        assert!(
+            err.contains(&format!(
+                "sequence number {seq} out of range (max {max})"
+            )),
+            "{err}"
+        );
+    }
+}
@@ -0,0 +1,77 @@
+#![cfg(feature = "json")]
+
+use bc_ur::UREncodable;
+use dcbor::Date;
+use provenance_mark::*;
+use serde_json::{Value, json};
+
+fn info() -> ProvenanceMarkInfo {
+    let mut generator = ProvenanceMarkGenerator::new_with_passphrase(
+        ProvenanceMarkResolution::Medium,
+        "test",
+    );
+    let date = Date::from_string("2023-06-20").unwrap();
+    ProvenanceMarkInfo::new(generator.next(date, Some("Essay")), "An essay.")
+}
+
+/// The JSON of `info` with its `ur` field replaced by `ur`.
+fn with_ur(info: &ProvenanceMarkInfo, ur: Value) -> Value {
+    let mut json = serde_json::to_value(info).unwrap();
+    json["ur"] = ur;
+    json
+}
+
+#[test]
+fn test_mark_info_lenient_ur() {
+    let info = info();
+    let ur = info.mark().ur_string();
+    let variants = [
+        ur.to_uppercase(),
+        format!("UR:{}", &ur["ur:".len()..]),
+        format!("  {}\n", ur),
+        format!(" {} ", ur.to_uppercase()),
+        ur["ur:".len()..].to_string(),
+    ];
+    for variant in variants {
+        let decoded: ProvenanceMarkInfo =
+            serde_json::from_value(with_ur(&info, json!(variant))).unwrap();
+        assert_eq!(decoded.mark(), info.mark(), "{variant:?}");
+        assert_eq!(decoded.ur().to_string(), ur);
+        assert_eq!(decoded.comment(), "An essay.");
+    }
+}
+
+#[test]
+fn test_mark_info_lenient_ur_error() {
+    let info = info();
+    let err = serde_json::from_value::<ProvenanceMarkInfo>(with_ur(
+        &info,
+        json!(" UR:NOT/A-MARK "),
+    ))
+    .unwrap_err()
+    .to_string();
+    assert!(
+        err.contains(
+            r#"invalid UR " UR:NOT/A-MARK " (tried as given, trimmed, lowercased)"#
+        ),
+        "{err}"
+    );
+}
+
+#[test]
+fn test_mark_info_without_ur() {
+    let info = info();
+    let mut json = serde_json::to_value(&info).unwrap();
+    json.as_object_mut().unwrap().remove("ur");
+    let decoded: ProvenanceMarkInfo = serde_json::from_value(json).unwrap();
+    assert_eq!(decoded.mark(), info.mark());
+    assert_eq!(decoded.ur(), info.ur());
+
+    // Neither the UR nor the mark
+    let mut json = serde_json::to_value(&info).unwrap();
+    let object = json.as_object_mut().unwrap();
+    object.remove("ur");
+    object.remove("mark");
+    let err = serde_json::from_value::<ProvenanceMarkInfo>(json).unwrap_err();
+    assert!(err.to_string().contains("missing field `ur`"), "{err}");
+}
@@ -0,0 +1,95 @@
+#![cfg(feature = "json")]
+
+use bc_ur::UREncodable;
+use dcbor::Date;
+use provenance_mark::*;
+
+fn infos() -> Vec<ProvenanceMarkInfo> {
+    let mut generator = ProvenanceMarkGenerator::new_with_passphrase(
+        ProvenanceMarkResolution::Medium,
+        "test",
+    );
+    let date = |day| Date::from_string(format!("2023-06-{day}")).unwrap();
+    vec![
+        ProvenanceMarkInfo::new(
+            generator.next(date(20), None::<String>),
+            "Genesis mark.",
+        ),
+        ProvenanceMarkInfo::new(generator.next(date(21), Some("Essay")), ""),
+    ]
+}
+
+#[test]
+fn test_mark_record_roundtrip() {
+    for info in infos() {
+        let mark = info.mark();
+        let record = MarkRecord::from(&info);
+        assert_eq!(record.ur, mark.ur_string());
+        assert_eq!(record.chain_id_hex, hex::encode(mark.chain_id()));
+        assert_eq!(record.seq, mark.seq());
+        assert_eq!(record.date_iso, mark.date().to_string());
+        assert_eq!(record.identifier, mark.id_hex());
+        assert_eq!(record.bytewords, info.bytewords());
+        assert_eq!(record.bytemoji, info.bytemoji());
+        assert_eq!(record.comment, info.comment());
+        assert_eq!(record.info_b64.is_some(), mark.info().is_some());
+
+        // Through serde, as a database layer would store it
+        let json = serde_json::to_string(&record).unwrap();
+        let loaded: MarkRecord = serde_json::from_str(&json).unwrap();
+        assert_eq!(loaded, record);
+
+        let restored = ProvenanceMarkInfo::try_from(loaded).unwrap();
+        assert_eq!(restored.mark(), mark);
+        assert_eq!(restored.comment(), info.comment());
+        assert_eq!(restored.bytewords(), info.bytewords());
+        assert_eq!(restored.bytemoji(), info.bytemoji());
+    }
+}
+
+#[test]
+fn test_mark_record_info_column() {
+    let infos = infos();
+    assert_eq!(MarkRecord::from(&infos[0]).info_b64, None);
+    // "Essay" as CBOR text: 65 45 73 73 61 79
+    assert_eq!(
+        MarkRecord::from(&infos[1]).info_b64.as_deref(),
+        Some("ZUVzc2F5")
+    );
+
+    let json = serde_json::to_value(MarkRecord::from(&infos[0])).unwrap();
+    assert!(json.get("info_b64").is_none());
+}
+
+#[test]
+fn test_mark_record_edited_column_rejected() {
+    let record = MarkRecord::from(&infos()[1]);
+    let mismatch =
+        |record: MarkRecord| match ProvenanceMarkInfo::try_from(record) {
+            Err(Error::MarkRecordMismatch { column }) => column,
+            Err(error) => panic!("unexpected error: {error}"),
+            Ok(_) => panic!("edited record was accepted"),
+        };
+
+    let mut edited = record.clone();
+    edited.seq = 7;
+    assert_eq!(mismatch(edited), "seq");
+
+    let mut edited = record.clone();
+    edited.date_iso = "2023-06-22T00:00:00Z".to_string();
+    assert_eq!(mismatch(edited), "date_iso");
+
+    let mut edited = record.clone();
+    edited.info_b64 = None;
+    assert_eq!(mismatch(edited), "info_b64");
+
+    // The comment is not derived from the mark, so it may be edited
+    let mut edited = record.clone();
+    edited.comment = "Revised".to_string();
+    let info = ProvenanceMarkInfo::try_from(edited).unwrap();
+    assert_eq!(info.comment(), "Revised");
+
+    let mut edited = record;
+    edited.ur = "ur:provenance/invalid".to_string();
+    assert!(ProvenanceMarkInfo::try_from(edited).is_err());
+}
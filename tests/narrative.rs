@@ -0,0 +1,72 @@
+use indoc::indoc;
+use provenance_mark::*;
+
+#[macro_use]
+mod common;
+use common::daily_marks;
+
+fn narrative(marks: Vec<ProvenanceMark>) -> String {
+    ProvenanceMark::validate(marks).chains()[0].narrative()
+}
+
+#[test]
+fn test_narrative_clean_chain() {
+    // expected-text-output-rubric:
+    #[rustfmt::skip]
+    assert_actual_expected!(narrative(daily_marks(ProvenanceMarkResolution::Low, "test", 5)), indoc! {r#"
+        This chain began on June 20, 2023. Over the following 4 days, 4 more
+        works were marked. The most recent mark, at sequence 4, is dated June
+        24, 2023.
+    "#}.trim().replace('\n', " "));
+
+    assert_eq!(
+        narrative(daily_marks(ProvenanceMarkResolution::Low, "test", 1)),
+        "This chain began on June 20, 2023. No other works have been marked."
+    );
+    assert_eq!(
+        narrative(daily_marks(ProvenanceMarkResolution::Low, "test", 2)),
+        "This chain began on June 20, 2023. On the following day, one more \
+         work was marked. The most recent mark, at sequence 1, is dated June \
+         21, 2023."
+    );
+}
+
+#[test]
+fn test_narrative_gap_chain() {
+    let marks = daily_marks(ProvenanceMarkResolution::Low, "test", 5);
+    let gapped = vec![
+        marks[0].clone(),
+        marks[1].clone(),
+        marks[3].clone(),
+        marks[4].clone(),
+    ];
+
+    // expected-text-output-rubric:
+    #[rustfmt::skip]
+    assert_actual_expected!(narrative(gapped), indoc! {r#"
+        This chain began on June 20, 2023. Over the following 4 days, 3 more
+        works were marked. There is a gap of one missing mark between June 21,
+        2023 and June 23, 2023. The most recent mark, at sequence 4, is dated
+        June 24, 2023.
+    "#}.trim().replace('\n', " "));
+
+    let gapped = vec![marks[0].clone(), marks[4].clone()];
+    assert!(narrative(gapped).contains(
+        "There is a gap of 3 missing marks between June 20, 2023 and \
+             June 24, 2023."
+    ));
+}
+
+#[test]
+fn test_narrative_missing_genesis() {
+    let marks = daily_marks(ProvenanceMarkResolution::Low, "test", 5);
+
+    // expected-text-output-rubric:
+    #[rustfmt::skip]
+    assert_actual_expected!(narrative(marks[1..].to_vec()), indoc! {r#"
+        This chain's genesis mark is missing. The earliest known mark, at
+        sequence 1, is dated June 21, 2023. Over the following 3 days, 3 more
+        works were marked. The most recent mark, at sequence 4, is dated June
+        24, 2023.
+    "#}.trim().replace('\n', " "));
+}
@@ -0,0 +1,102 @@
+use dcbor::{Date, prelude::*};
+use provenance_mark::{proof::*, *};
+
+fn generator(passphrase: &str) -> ProvenanceMarkGenerator {
+    ProvenanceMarkGenerator::new_with_passphrase(
+        ProvenanceMarkResolution::Low,
+        passphrase,
+    )
+}
+
+fn date(day: u32) -> Date {
+    Date::from_string(&format!("2023-06-{:02}", 20 + day)).unwrap()
+}
+
+/// A generator that has issued `count` marks, and the last of them.
+fn issued(
+    passphrase: &str,
+    count: u32,
+) -> (ProvenanceMarkGenerator, ProvenanceMark) {
+    let mut generator = generator(passphrase);
+    let mut last = None;
+    for day in 0..count {
+        last = Some(generator.next(date(day), None::<String>));
+    }
+    (generator, last.unwrap())
+}
+
+#[test]
+fn test_ownership_proof() {
+    let (mut generator, head) = issued("test", 3);
+    let challenge =
+        OwnershipChallenge::new(head.chain_id().clone(), b"registry nonce");
+
+    let snapshot = generator.snapshot();
+    let response = generator.respond(&challenge);
+    assert_eq!(generator.snapshot(), snapshot);
+    assert_eq!(response, generator.respond(&challenge));
+    assert_eq!(response.chain_id(), head.chain_id());
+    assert_eq!(response.seq(), 3);
+
+    let next = generator.next(date(3), None::<String>);
+    assert!(head.precedes(&next));
+    assert!(response.verify(&next, &challenge));
+
+    // The response only verifies against the mark that reveals its key
+    assert!(!response.verify(&head, &challenge));
+    let later = generator.next(date(4), None::<String>);
+    assert!(!response.verify(&later, &challenge));
+
+    // Or for the challenge it answered
+    let other =
+        OwnershipChallenge::new(head.chain_id().clone(), b"another nonce");
+    assert!(!response.verify(&next, &other));
+}
+
+#[test]
+fn test_ownership_proof_from_other_chain() {
+    let (mut generator, head) = issued("test", 3);
+    let (impostor, _) = issued("Wolf", 3);
+    let challenge = OwnershipChallenge::new(head.chain_id().clone(), [7u8; 16]);
+
+    let response = impostor.respond(&challenge);
+    assert_ne!(response.chain_id(), head.chain_id());
+    let next = generator.next(date(3), None::<String>);
+    assert!(!response.verify(&next, &challenge));
+}
+
+#[test]
+fn test_ownership_proof_at_genesis() {
+    let mut generator = generator("test");
+    let chain_id = generator.chain_id().clone();
+    let challenge = OwnershipChallenge::new(chain_id, [1u8, 2, 3]);
+    let response = generator.respond(&challenge);
+    assert_eq!(response.seq(), 0);
+
+    // Anyone could have made this response, since the genesis key is the
+    // chain ID
+    let genesis = generator.next(date(0), None::<String>);
+    assert!(!response.verify(&genesis, &challenge));
+}
+
+#[test]
+fn test_ownership_cbor() {
+    let (generator, head) = issued("test", 2);
+    let challenge =
+        OwnershipChallenge::new(head.chain_id().clone(), b"registry nonce");
+    let response = generator.respond(&challenge);
+
+    let cbor = CBOR::from(challenge.clone());
+    assert_eq!(OwnershipChallenge::try_from(cbor).unwrap(), challenge);
+    let cbor = CBOR::from(response.clone());
+    assert_eq!(OwnershipResponse::try_from(cbor).unwrap(), response);
+
+    let untagged = response.untagged_cbor();
+    assert!(OwnershipChallenge::from_untagged_cbor(untagged).is_err());
+    let short_id = vec![
+        CBOR::to_byte_string([0u8; 3]),
+        CBOR::to_byte_string(b"nonce"),
+    ]
+    .to_cbor();
+    assert!(OwnershipChallenge::from_untagged_cbor(short_id).is_err());
+}
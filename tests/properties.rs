@@ -0,0 +1,226 @@
+//! Property tests of the invariants the crate promises for every valid mark
+//! and chain, not just the example chains of the other tests.
+//!
+//! Runs use a fixed seed, so a failure in CI reproduces locally, and a
+//! bounded number of cases, so the suite stays fast.
+
+use bc_ur::prelude::*;
+use chrono::{TimeZone, Utc};
+use dcbor::Date;
+use proptest::{prelude::*, test_runner::RngSeed};
+use provenance_mark::*;
+
+fn config() -> ProptestConfig {
+    ProptestConfig {
+        cases: 64,
+        rng_seed: RngSeed::Fixed(0x7072_6f76),
+        failure_persistence: None,
+        ..ProptestConfig::default()
+    }
+}
+
+fn resolution() -> impl Strategy<Value = ProvenanceMarkResolution> {
+    (0u8..4).prop_map(|res| ProvenanceMarkResolution::try_from(res).unwrap())
+}
+
+/// Any date the resolution's date encoding represents exactly: whole days
+/// from 2023 through 2150 for Low, whole seconds from 2001 through early 2137
+/// for Medium, and whole milliseconds from 2001 through 9999 for Quartile and
+/// High.
+fn date(res: ProvenanceMarkResolution) -> BoxedStrategy<Date> {
+    let reference = Utc.with_ymd_and_hms(2001, 1, 1, 0, 0, 0).unwrap();
+    match res {
+        ProvenanceMarkResolution::Low => {
+            let first = Utc.with_ymd_and_hms(2023, 1, 1, 0, 0, 0).unwrap();
+            let last = Utc.with_ymd_and_hms(2150, 12, 31, 0, 0, 0).unwrap();
+            (0..=(last - first).num_days())
+                .prop_map(move |days| {
+                    Date::from_datetime(first + chrono::Duration::days(days))
+                })
+                .boxed()
+        }
+        ProvenanceMarkResolution::Medium => (0..=u32::MAX as i64)
+            .prop_map(move |seconds| {
+                Date::from_datetime(
+                    reference + chrono::Duration::seconds(seconds),
+                )
+            })
+            .boxed(),
+        ProvenanceMarkResolution::Quartile | ProvenanceMarkResolution::High => {
+            (0..=0xe594_0a78_a7ffi64)
+                .prop_map(move |millis| {
+                    Date::from_datetime(
+                        reference + chrono::Duration::milliseconds(millis),
+                    )
+                })
+                .boxed()
+        }
+    }
+}
+
+/// Any sequence number the resolution's sequence encoding represents.
+fn seq(res: ProvenanceMarkResolution) -> BoxedStrategy<u32> {
+    match res {
+        ProvenanceMarkResolution::Low => (0..=u16::MAX as u32).boxed(),
+        _ => any::<u32>().boxed(),
+    }
+}
+
+/// No info, or a small text, byte string, or integer.
+fn info() -> impl Strategy<Value = Option<CBOR>> {
+    proptest::option::of(prop_oneof![
+        ".{0,20}".prop_map(CBOR::from),
+        proptest::collection::vec(any::<u8>(), 0..20)
+            .prop_map(|bytes| CBOR::to_byte_string(bytes)),
+        any::<u64>().prop_map(CBOR::from),
+    ])
+}
+
+/// Any valid mark: a genesis mark whose key is its chain ID at sequence
+/// number 0, or a mark with any other key after it.
+fn mark() -> impl Strategy<Value = ProvenanceMark> {
+    resolution()
+        .prop_flat_map(|res| {
+            (
+                Just(res),
+                seq(res),
+                date(res),
+                any::<[u8; 32]>(),
+                any::<[u8; 32]>(),
+                any::<[u8; 32]>(),
+                info(),
+            )
+        })
+        .prop_filter(
+            "only a genesis mark's key equals its chain ID",
+            |(_, seq, _, key, _, chain_id, _)| *seq == 0 || key != chain_id,
+        )
+        .prop_map(|(res, seq, date, key, next_key, chain_id, info)| {
+            let link = res.link_length();
+            let chain_id = ChainId::new(res, &chain_id[..link]).unwrap();
+            let key = if seq == 0 {
+                chain_id.genesis_key()
+            } else {
+                LinkKey::new(res, &key[..link]).unwrap()
+            };
+            let next_key = LinkKey::new(res, &next_key[..link]).unwrap();
+            ProvenanceMark::new(
+                res,
+                key,
+                next_key,
+                chain_id,
+                seq.into(),
+                date,
+                info,
+            )
+            .unwrap()
+        })
+}
+
+/// A chain of one to eight marks issued by a generator from any seed, with
+/// nondecreasing dates.
+fn chain() -> impl Strategy<Value = Vec<ProvenanceMark>> {
+    resolution()
+        .prop_flat_map(|res| {
+            (
+                Just(res),
+                any::<[u8; 32]>(),
+                proptest::collection::vec(date(res), 1..=8),
+                proptest::collection::vec(info(), 8),
+            )
+        })
+        .prop_map(|(res, seed, mut dates, infos)| {
+            dates.sort_by_key(Date::datetime);
+            let mut generator = ProvenanceMarkGenerator::new_with_seed(
+                res,
+                ProvenanceSeed::from_bytes(seed),
+            );
+            dates
+                .into_iter()
+                .zip(infos)
+                .map(|(date, info)| generator.next(date, info))
+                .collect()
+        })
+}
+
+proptest! {
+    #![proptest_config(config())]
+
+    /// Every encoding of a mark decodes to the same mark: its UR, its
+    /// bytewords, its URL encoding, its tagged CBOR, and its base64 CBOR.
+    #[test]
+    fn prop_encodings_round_trip(mark in mark()) {
+        let res = mark.res();
+        prop_assert_eq!(
+            &ProvenanceMark::from_ur_string(mark.ur_string().as_str()).unwrap(),
+            &mark
+        );
+        prop_assert_eq!(
+            &ProvenanceMark::from_bytewords(res, &mark.to_bytewords())
+                .unwrap(),
+            &mark
+        );
+        prop_assert_eq!(
+            &ProvenanceMark::from_url_encoding(&mark.to_url_encoding())
+                .unwrap(),
+            &mark
+        );
+        prop_assert_eq!(
+            &ProvenanceMark::from_tagged_cbor_data(&mark.to_cbor_data())
+                .unwrap(),
+            &mark
+        );
+        #[cfg(feature = "json")]
+        prop_assert_eq!(
+            &ProvenanceMark::from_b64_cbor(&mark.to_b64_cbor()).unwrap(),
+            &mark
+        );
+    }
+
+    /// Parsing a mark's message at its resolution yields the same fields,
+    /// not just an equal mark.
+    #[test]
+    fn prop_message_round_trip(mark in mark()) {
+        let decoded =
+            ProvenanceMark::from_message(mark.res(), mark.message()).unwrap();
+        prop_assert_eq!(decoded.key(), mark.key());
+        prop_assert_eq!(decoded.hash(), mark.hash());
+        prop_assert_eq!(decoded.chain_id(), mark.chain_id());
+        prop_assert_eq!(decoded.seq(), mark.seq());
+        prop_assert_eq!(decoded.date(), mark.date());
+        prop_assert_eq!(decoded.info(), mark.info());
+        prop_assert!(decoded.passes_self_check());
+    }
+
+    /// Each mark a generator issues follows the one before it, so the whole
+    /// chain is a valid sequence.
+    #[test]
+    fn prop_generated_marks_link(chain in chain()) {
+        prop_assert!(chain[0].is_genesis());
+        for pair in chain.windows(2) {
+            prop_assert!(pair[0].precedes(&pair[1]));
+        }
+        prop_assert!(ProvenanceMark::is_sequence_valid(&chain));
+    }
+
+    /// Validating the marks of a chain, or any subset of them, gives the
+    /// same canonicalized report whatever order they are given in.
+    #[test]
+    #[cfg(feature = "json")]
+    fn prop_validation_is_order_independent(
+        (marks, shuffled) in chain().prop_flat_map(|chain| {
+            let len = chain.len();
+            proptest::sample::subsequence(chain, 1..=len).prop_flat_map(
+                |marks| (Just(marks.clone()), Just(marks).prop_shuffle()),
+            )
+        }),
+    ) {
+        let report = ProvenanceMark::validate(marks).canonicalize();
+        let shuffled = ProvenanceMark::validate(shuffled).canonicalize();
+        prop_assert!(report.is_order_invariant());
+        prop_assert_eq!(
+            report.format(ValidationReportFormat::JsonCompact),
+            shuffled.format(ValidationReportFormat::JsonCompact)
+        );
+    }
+}
@@ -0,0 +1,118 @@
+#![cfg(feature = "json")]
+
+use indoc::indoc;
+use provenance_mark::*;
+
+#[macro_use]
+mod common;
+use common::daily_marks;
+
+const BASE_IRI: &str = "https://example.com/marks/";
+
+fn iri(mark: &ProvenanceMark) -> String {
+    format!("{}{}", BASE_IRI, hex::encode(mark.fingerprint()))
+}
+
+#[test]
+fn test_prov_jsonld() {
+    let report = ProvenanceMark::validate(daily_marks(
+        ProvenanceMarkResolution::Low,
+        "test",
+        3,
+    ));
+    let jsonld = report.chains()[0].to_prov_jsonld(BASE_IRI);
+
+    // expected-text-output-rubric:
+    #[rustfmt::skip]
+    assert_actual_expected!(serde_json::to_string_pretty(&jsonld).unwrap(), indoc! {r#"
+        {
+          "@context": {
+            "prov": "http://www.w3.org/ns/prov#",
+            "xsd": "http://www.w3.org/2001/XMLSchema#"
+          },
+          "@graph": [
+            {
+              "@id": "https://example.com/marks/chain-b16a7cbd",
+              "@type": "prov:Collection",
+              "prov:hadMember": [
+                {
+                  "@id": "https://example.com/marks/9525bab54b76145f176e69cc320308db9e64f62225f8b2b9466da20fd27ccb44"
+                },
+                {
+                  "@id": "https://example.com/marks/c885f6a8aa31ccfbf6f02bf3b086862cfce36a326f3a4f8c1a01f3e5c7839a51"
+                },
+                {
+                  "@id": "https://example.com/marks/f24811dc0ba92f121ec1d3283ff11b1fd0ae2ea7ab6f7177c5ab8660cbef191d"
+                }
+              ]
+            },
+            {
+              "@id": "https://example.com/marks/9525bab54b76145f176e69cc320308db9e64f62225f8b2b9466da20fd27ccb44",
+              "@type": "prov:Entity",
+              "prov:generatedAtTime": {
+                "@type": "xsd:dateTime",
+                "@value": "2023-06-20T00:00:00Z"
+              }
+            },
+            {
+              "@id": "https://example.com/marks/c885f6a8aa31ccfbf6f02bf3b086862cfce36a326f3a4f8c1a01f3e5c7839a51",
+              "@type": "prov:Entity",
+              "prov:generatedAtTime": {
+                "@type": "xsd:dateTime",
+                "@value": "2023-06-21T00:00:00Z"
+              },
+              "prov:wasRevisionOf": {
+                "@id": "https://example.com/marks/9525bab54b76145f176e69cc320308db9e64f62225f8b2b9466da20fd27ccb44"
+              }
+            },
+            {
+              "@id": "https://example.com/marks/f24811dc0ba92f121ec1d3283ff11b1fd0ae2ea7ab6f7177c5ab8660cbef191d",
+              "@type": "prov:Entity",
+              "prov:generatedAtTime": {
+                "@type": "xsd:dateTime",
+                "@value": "2023-06-22T00:00:00Z"
+              },
+              "prov:wasRevisionOf": {
+                "@id": "https://example.com/marks/c885f6a8aa31ccfbf6f02bf3b086862cfce36a326f3a4f8c1a01f3e5c7839a51"
+              }
+            }
+          ]
+        }"#}.trim());
+}
+
+#[test]
+fn test_prov_jsonld_edges() {
+    let marks = daily_marks(ProvenanceMarkResolution::Low, "test", 5);
+    let present = vec![
+        marks[0].clone(),
+        marks[1].clone(),
+        marks[3].clone(),
+        marks[4].clone(),
+    ];
+    let report = ProvenanceMark::validate(present);
+    let jsonld = report.chains()[0].to_prov_jsonld(BASE_IRI);
+    let graph = jsonld["@graph"].as_array().unwrap();
+    let entity = |mark: &ProvenanceMark| {
+        graph
+            .iter()
+            .find(|node| node["@id"] == iri(mark).as_str())
+            .unwrap()
+    };
+
+    // Each newer mark is a revision of the one before it
+    assert_eq!(entity(&marks[0]).get("prov:wasRevisionOf"), None);
+    assert_eq!(
+        entity(&marks[1])["prov:wasRevisionOf"]["@id"],
+        iri(&marks[0])
+    );
+    assert_eq!(
+        entity(&marks[4])["prov:wasRevisionOf"]["@id"],
+        iri(&marks[3])
+    );
+
+    // No edge crosses the gap
+    assert_eq!(entity(&marks[3]).get("prov:wasRevisionOf"), None);
+
+    // The collection still has every mark
+    assert_eq!(graph[0]["prov:hadMember"].as_array().unwrap().len(), 4);
+}
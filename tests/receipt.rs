@@ -0,0 +1,69 @@
+use bc_ur::UREncodable;
+use dcbor::Date;
+use provenance_mark::*;
+
+fn receipt(file: &[u8]) -> (ProvenanceMark, WorkReceipt) {
+    let mut generator = ProvenanceMarkGenerator::new_with_passphrase(
+        ProvenanceMarkResolution::Low,
+        "Wolf",
+    );
+    let mark =
+        generator.next(Date::from_string("2023-06-20").unwrap(), None::<&str>);
+    let receipt = WorkReceipt::new(&mark, file, "cover.png", "image/png");
+    (mark, receipt)
+}
+
+#[test]
+#[cfg(feature = "markdown")]
+fn test_receipt_verifies_matching_file() {
+    let file = b"the quick brown fox";
+    let (mark, receipt) = receipt(file);
+    assert_eq!(receipt.mark().unwrap(), mark);
+    assert_eq!(receipt.size(), file.len() as u64);
+    assert_eq!(
+        receipt.verify(file),
+        ReceiptVerification { mark_ok: true, digest_ok: true }
+    );
+    assert!(receipt.verify(file).is_ok());
+
+    let json = serde_json::to_string(&receipt).unwrap();
+    let decoded: WorkReceipt = serde_json::from_str(&json).unwrap();
+    assert_eq!(decoded, receipt);
+    assert!(decoded.verify(file).is_ok());
+
+    let markdown = receipt.markdown();
+    assert!(markdown.contains("#### `cover.png`"));
+    assert!(markdown.contains("image/png, 19 bytes"));
+    assert!(markdown.contains(&format!("#### {}", mark.ur_string())));
+    assert!(markdown.contains(&mark.id_bytewords(4, true)));
+}
+
+#[test]
+fn test_receipt_rejects_modified_file() {
+    let (_, receipt) = receipt(b"the quick brown fox");
+    assert_eq!(
+        receipt.verify(b"the quick brown box"),
+        ReceiptVerification { mark_ok: true, digest_ok: false }
+    );
+    assert!(!receipt.verify(b"the quick brown fox!").digest_ok);
+}
+
+#[test]
+fn test_receipt_rejects_corrupted_ur() {
+    let file = b"the quick brown fox";
+    let (_, receipt) = receipt(file);
+    let mut json: serde_json::Value = serde_json::to_value(&receipt).unwrap();
+    let ur = json["ur"].as_str().unwrap().to_string();
+    // Change one character of the bytewords body, breaking its checksum
+    let mut corrupted = ur.into_bytes();
+    let i = corrupted.len() - 5;
+    corrupted[i] = if corrupted[i] == b'a' { b'b' } else { b'a' };
+    json["ur"] = String::from_utf8(corrupted).unwrap().into();
+
+    let receipt: WorkReceipt = serde_json::from_value(json).unwrap();
+    assert!(receipt.mark().is_err());
+    assert_eq!(
+        receipt.verify(file),
+        ReceiptVerification { mark_ok: false, digest_ok: true }
+    );
+}
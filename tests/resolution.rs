@@ -0,0 +1,83 @@
+use bc_ur::UREncodable;
+use dcbor::Date;
+use provenance_mark::*;
+
+const RESOLUTIONS: [ProvenanceMarkResolution; 4] = [
+    ProvenanceMarkResolution::Low,
+    ProvenanceMarkResolution::Medium,
+    ProvenanceMarkResolution::Quartile,
+    ProvenanceMarkResolution::High,
+];
+
+#[test]
+fn test_estimate_ur_len() {
+    let date = Date::from_string("2023-06-20T12:00:00Z").unwrap();
+    for res in RESOLUTIONS {
+        let mut generator =
+            ProvenanceMarkGenerator::new_with_passphrase(res, "test");
+        // Info padded to cross each byte string header size
+        for padding in [0, 1, 5, 22, 100, 200, 260, 1000] {
+            let info = "x".repeat(padding);
+            let mark = if padding == 0 {
+                generator.next(date, None::<String>)
+            } else {
+                generator.next(date, Some(info))
+            };
+            let info_len =
+                mark.info().map_or(0, |info| info.to_cbor_data().len());
+            let estimate = res.estimate_ur_len(info_len);
+            let actual = mark.ur_string().len();
+            assert!(
+                estimate.abs_diff(actual) <= 2,
+                "{res:?} with {info_len} bytes of info: estimated {estimate}, \
+                 actual {actual}"
+            );
+        }
+    }
+}
+
+#[test]
+fn test_best_fitting() {
+    // A version-10 QR code at medium error correction holds 311 alphanumeric
+    // characters.
+    assert_eq!(
+        ProvenanceMarkResolution::best_fitting(311, 40),
+        Some(ProvenanceMarkResolution::Quartile)
+    );
+    assert_eq!(ProvenanceMarkResolution::best_fitting(311, 200), None);
+    assert_eq!(
+        ProvenanceMarkResolution::best_fitting(usize::MAX, 200),
+        Some(ProvenanceMarkResolution::High)
+    );
+
+    let low = ProvenanceMarkResolution::Low.estimate_ur_len(0);
+    assert_eq!(low, 60);
+    assert_eq!(
+        ProvenanceMarkResolution::best_fitting(low, 0),
+        Some(ProvenanceMarkResolution::Low)
+    );
+    assert_eq!(ProvenanceMarkResolution::best_fitting(low - 1, 0), None);
+}
+
+#[test]
+fn test_checked_seq() {
+    let low = ProvenanceMarkResolution::Low;
+    assert_eq!(low.max_seq(), 65535);
+    assert_eq!(low.checked_seq(65535).unwrap(), 65535);
+    assert!(matches!(
+        low.checked_seq(65536),
+        Err(Error::SequenceOutOfRange { value: 65536, max: 65535 })
+    ));
+    for res in &RESOLUTIONS[1..] {
+        assert_eq!(res.max_seq(), u32::MAX);
+        assert_eq!(res.checked_seq(u32::MAX.into()).unwrap(), u32::MAX);
+        assert!(matches!(
+            res.checked_seq(u64::from(u32::MAX) + 1),
+            Err(Error::SequenceOutOfRange { value: 4294967296, max: u32::MAX })
+        ));
+    }
+    assert!(matches!(
+        low.serialize_seq(65536),
+        Err(Error::SequenceOutOfRange { value: 65536, max: 65535 })
+    ));
+}
@@ -0,0 +1,65 @@
+use std::sync::Arc;
+
+use bc_ur::UREncodable;
+use provenance_mark::{scan::*, *};
+
+mod common;
+use common::same_day_marks;
+
+#[test]
+fn test_extract_marks() {
+    let marks = same_day_marks(ProvenanceMarkResolution::Low, "Wolf", 4);
+    let wrapped = marks[1].ur_string();
+    let (first_line, second_line) = wrapped.split_at(30);
+    let text = format!(
+        "Hi,\n\n\
+         The first essay is marked {}\n\
+         and the second, wrapped by my mail client, {}\n\
+         {}\n\
+         Also see {}. Please keep this seed safe:\n\
+         ur:crypto-seed/oyadgdhkwzdtfthptokigtvwnnjsqzcxknsktdhpyljeda\n\
+         and the URL parameter {}\n\
+         Not this one: ur:provenance/lfaegdtokebz, thanks",
+        marks[0].ur_string().to_uppercase(),
+        first_line,
+        second_line,
+        marks[2].ur_string(),
+        marks[3].to_url_encoding(),
+    );
+
+    let results = extract_marks(&text);
+    assert_eq!(results.len(), 5);
+
+    let offset = |needle: &str| text.find(needle).unwrap();
+    let expected = [
+        (offset(&marks[0].ur_string().to_uppercase()), &marks[0]),
+        (offset(first_line), &marks[1]),
+        (offset(&marks[2].ur_string()), &marks[2]),
+        (offset(&marks[3].to_url_encoding()), &marks[3]),
+    ];
+    for (result, (offset, mark)) in results.iter().zip(expected) {
+        assert_eq!(result.0, offset);
+        assert_eq!(result.1.as_ref().unwrap(), mark);
+    }
+    assert_eq!(results[4].0, offset("ur:provenance/lfaegdtokebz"));
+    assert!(results[4].1.is_err());
+
+    let found: Vec<ProvenanceMark> = results
+        .into_iter()
+        .filter_map(|(_, result)| result.ok())
+        .collect();
+    let report = ProvenanceMark::validate(found);
+    assert!(!report.has_issues());
+    let chain_marks: Vec<&ProvenanceMark> =
+        report.chains()[0].marks().iter().map(Arc::as_ref).collect();
+    assert_eq!(chain_marks, marks.iter().collect::<Vec<_>>());
+}
+
+#[test]
+fn test_extract_marks_without_marks() {
+    assert!(extract_marks("").is_empty());
+    assert!(
+        extract_marks("Nothing to see here: ur:bytes/hdcxlkahssqzwfvslo")
+            .is_empty()
+    );
+}
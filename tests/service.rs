@@ -0,0 +1,123 @@
+use std::thread;
+
+use dcbor::Date;
+use provenance_mark::*;
+
+fn chain(passphrase: &str, count: u32) -> Vec<ProvenanceMark> {
+    let mut generator = ProvenanceMarkGenerator::new_with_passphrase(
+        ProvenanceMarkResolution::Low,
+        passphrase,
+    );
+    (0..count)
+        .map(|i| {
+            let date =
+                Date::from_string(format!("2023-06-{:02}", 1 + i)).unwrap();
+            generator.next(date, None::<&str>)
+        })
+        .collect()
+}
+
+#[test]
+fn test_service_is_send_and_sync() {
+    fn assert_send_sync<T: Send + Sync>() {}
+    assert_send_sync::<SharedProvenanceService>();
+    assert_send_sync::<ValidationReport>();
+}
+
+#[test]
+fn test_service_submit_and_verdict() {
+    let service = SharedProvenanceService::new();
+    assert_eq!(service.verdict(), Verdict::Empty);
+
+    let marks = chain("alice", 3);
+    assert_eq!(service.submit(marks[0].clone()), InsertOutcome::Inserted);
+    assert_eq!(service.submit(marks[0].clone()), InsertOutcome::Duplicate);
+    assert_eq!(service.submit(marks[2].clone()), InsertOutcome::Inserted);
+    assert_eq!(service.len(), 2);
+    assert_eq!(service.verdict(), Verdict::Issues);
+
+    service.submit(marks[1].clone());
+    assert_eq!(service.verdict(), Verdict::Valid);
+
+    // Another valid chain does not make the verdict worse
+    for mark in chain("bob", 2) {
+        service.submit(mark);
+    }
+    assert_eq!(service.verdict(), Verdict::Valid);
+
+    let report = service.report_for_chain(marks[0].chain_id()).unwrap();
+    assert_eq!(report.marks().len(), 3);
+    assert!(service.report_for_chain(&[0; 4]).is_none());
+
+    // The cached report is shared until the next submission
+    let first = service.snapshot_report();
+    assert!(std::sync::Arc::ptr_eq(&first, &service.snapshot_report()));
+}
+
+#[test]
+fn test_service_verdict_with_expected_chains() {
+    let test = chain("test", 3);
+    let alice = chain("alice", 2);
+    let verdict = |expected: &[&ProvenanceMark], marks: &[ProvenanceMark]| {
+        let options = ValidationOptions::new()
+            .with_expected_chains(expected.iter().map(|mark| mark.chain_id()));
+        let service = SharedProvenanceService::new().with_options(options);
+        for mark in marks {
+            service.submit(mark.clone());
+        }
+        service.verdict()
+    };
+
+    assert_eq!(verdict(&[&test[0]], &test), Verdict::Valid);
+    // An expected chain is missing
+    assert_eq!(verdict(&[&test[0], &alice[0]], &test), Verdict::Issues);
+    // No marks have been submitted for an expected chain
+    assert_eq!(verdict(&[&test[0]], &[]), Verdict::Issues);
+    assert_eq!(verdict(&[], &[]), Verdict::Empty);
+    // A chain is unexpected
+    assert_eq!(
+        verdict(&[&test[0]], &[test.clone(), alice.clone()].concat()),
+        Verdict::Issues
+    );
+}
+
+#[test]
+fn test_service_concurrent() {
+    let chains: Vec<_> = ["alice", "bob", "carol", "dave"]
+        .iter()
+        .map(|passphrase| chain(passphrase, 20))
+        .collect();
+    let service = SharedProvenanceService::new();
+
+    thread::scope(|scope| {
+        for marks in &chains {
+            let service = service.clone();
+            scope.spawn(move || {
+                // Submit every mark twice, in reverse order
+                for mark in marks.iter().rev().chain(marks) {
+                    service.submit(mark.clone());
+                }
+            });
+        }
+        for _ in 0..4 {
+            let service = service.clone();
+            scope.spawn(move || {
+                for _ in 0..20 {
+                    let report = service.snapshot_report();
+                    assert!(report.marks().len() <= 80);
+                    service.verdict();
+                }
+            });
+        }
+    });
+
+    let reference = ValidationReport::validate(chains.concat()).canonicalize();
+    let report = service.snapshot_report();
+    assert_eq!(report.marks().len(), 80);
+    assert_eq!(report.digest(), reference.digest());
+    assert_eq!(
+        serde_json::to_value(&*report).unwrap(),
+        serde_json::to_value(&reference).unwrap()
+    );
+    assert_eq!(service.verdict(), Verdict::Valid);
+}
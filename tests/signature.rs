@@ -0,0 +1,160 @@
+use dcbor::{Date, prelude::*};
+use provenance_mark::{crypto_utils::sha256, *};
+
+const KEY: &[u8] = b"publisher verifying key";
+
+fn date(day: u32) -> Date {
+    Date::from_string(&format!("2023-06-{day:02}")).unwrap()
+}
+
+/// A stand-in signature scheme: SHA-256 of the key followed by the data.
+fn dummy_sign(key: &[u8], data: &[u8]) -> Vec<u8> {
+    sha256([key, data].concat()).to_vec()
+}
+
+fn dummy_verify(key: &[u8], data: &[u8], signature: &[u8]) -> bool {
+    dummy_sign(key, data) == signature
+}
+
+fn signed(content: &str) -> SignedInfo {
+    let signature = dummy_sign(KEY, &content.to_cbor_data());
+    SignedInfo::new(content, signature)
+}
+
+fn declared_chain() -> Vec<ProvenanceMark> {
+    let mut generator = ProvenanceMarkGenerator::new_with_passphrase(
+        ProvenanceMarkResolution::Quartile,
+        "publisher",
+    );
+    let policy = GenesisPolicy::new().with_verifying_key(KEY);
+    let genesis = generator
+        .genesis_with_policy(date(20), &policy, Some("Genesis"))
+        .unwrap();
+    vec![
+        genesis,
+        generator.next(date(21), Some(CBOR::from(signed("Chapter 1")))),
+        generator.next(date(22), None::<&str>),
+        generator.next(date(23), Some(CBOR::from(signed("Chapter 2")))),
+    ]
+}
+
+#[test]
+fn test_declared_verifying_key() {
+    let marks = declared_chain();
+    assert_eq!(marks[0].declared_verifying_key(), Some(KEY.to_vec()));
+    assert!(
+        marks[1..]
+            .iter()
+            .all(|m| m.declared_verifying_key().is_none())
+    );
+
+    let signed_info = marks[1].signed_info().unwrap();
+    assert_eq!(signed_info, signed("Chapter 1"));
+    assert_eq!(signed_info.content(), &CBOR::from("Chapter 1"));
+    assert!(marks[2].signed_info().is_none());
+
+    let policy = ValidationPolicy::new().with_signature_verifier(dummy_verify);
+    let report = ProvenanceMark::validate_with_policy(marks, &policy);
+    assert!(report.chains()[0].declares_key());
+    assert!(!report.has_issues());
+}
+
+#[test]
+fn test_undeclared_chain() {
+    let mut generator = ProvenanceMarkGenerator::new_with_passphrase(
+        ProvenanceMarkResolution::Quartile,
+        "publisher",
+    );
+    let genesis = generator
+        .genesis_with_policy(date(20), &GenesisPolicy::new(), Some("Genesis"))
+        .unwrap();
+    assert_eq!(genesis.info(), Some(CBOR::from("Genesis")));
+    assert_eq!(genesis.declared_verifying_key(), None);
+    let marks = vec![genesis, generator.next(date(21), Some("Chapter 1"))];
+
+    let policy =
+        ValidationPolicy::new().with_signature_verifier(|_, _, _| false);
+    let report = ProvenanceMark::validate_with_policy(marks, &policy);
+    assert!(!report.chains()[0].declares_key());
+    assert!(!report.has_issues());
+}
+
+#[test]
+fn test_failing_verifier() {
+    let mut marks = declared_chain();
+    // Unsigned info on a chain that declares a key
+    let mut generator = ProvenanceMarkGenerator::new_with_passphrase(
+        ProvenanceMarkResolution::Quartile,
+        "publisher",
+    );
+    for mark in &marks {
+        generator.commit_speculation(mark).unwrap();
+    }
+    marks.push(generator.next(date(24), Some("Chapter 3")));
+
+    let policy =
+        ValidationPolicy::new().with_signature_verifier(|_, _, _| false);
+    let report = ProvenanceMark::validate_with_policy(marks.clone(), &policy);
+    let chain = &report.chains()[0];
+    assert!(chain.declares_key());
+    // Signature problems do not break the sequence
+    assert_eq!(chain.sequences().len(), 1);
+    let issues: Vec<&[ValidationIssue]> = chain.sequences()[0]
+        .marks()
+        .iter()
+        .map(|m| m.issues())
+        .collect();
+    assert_eq!(
+        issues,
+        vec![
+            &[][..],
+            &[ValidationIssue::InvalidSignature][..],
+            &[][..],
+            &[ValidationIssue::InvalidSignature][..],
+            &[ValidationIssue::MissingSignature][..],
+        ]
+    );
+    let text = report.format(ValidationReportFormat::Text);
+    assert!(text.contains("(invalid signature)"));
+    assert!(text.contains("(missing signature)"));
+
+    // Without a verifier, signatures are not checked
+    let report = ProvenanceMark::validate(marks);
+    assert!(report.chains()[0].declares_key());
+    assert!(!report.has_issues());
+}
+
+#[test]
+fn test_genesis_with_policy_after_genesis() {
+    let mut generator = ProvenanceMarkGenerator::new_with_passphrase(
+        ProvenanceMarkResolution::Low,
+        "publisher",
+    );
+    generator.next(date(20), None::<&str>);
+    let error = generator
+        .genesis_with_policy(date(21), &GenesisPolicy::new(), None::<&str>)
+        .unwrap_err();
+    assert!(matches!(error, Error::NotAtGenesis { next_seq: 1 }));
+}
+
+#[test]
+fn test_genesis_with_policy_date_out_of_range() {
+    let mut generator = ProvenanceMarkGenerator::new_with_passphrase(
+        ProvenanceMarkResolution::Low,
+        "publisher",
+    );
+    let policy = GenesisPolicy::new().with_verifying_key(KEY);
+    let too_late = Date::from_string("2200-01-01").unwrap();
+    assert!(
+        generator
+            .genesis_with_policy(too_late, &policy, None::<&str>)
+            .is_err()
+    );
+
+    // The generator is left at genesis
+    assert_eq!(generator.next_seq(), 0);
+    let genesis = generator
+        .genesis_with_policy(date(20), &policy, None::<&str>)
+        .unwrap();
+    assert!(genesis.is_genesis());
+}
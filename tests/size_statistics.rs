@@ -0,0 +1,167 @@
+use std::collections::HashSet;
+
+use bc_ur::UREncodable;
+use dcbor::Date;
+use provenance_mark::*;
+
+fn marks(
+    res: ProvenanceMarkResolution,
+    count: usize,
+    info: Option<&str>,
+) -> Vec<ProvenanceMark> {
+    let mut generator =
+        ProvenanceMarkGenerator::new_with_passphrase(res, &format!("{res}"));
+    (0..count)
+        .map(|i| {
+            let date =
+                Date::from_string(&format!("2023-06-{:02}", 20 + i)).unwrap();
+            generator.next(date, info)
+        })
+        .collect()
+}
+
+#[test]
+fn test_mark_lengths() {
+    for res in [
+        ProvenanceMarkResolution::Low,
+        ProvenanceMarkResolution::Medium,
+        ProvenanceMarkResolution::Quartile,
+        ProvenanceMarkResolution::High,
+    ] {
+        for info in [None, Some("Lorem ipsum sit dolor amet.")] {
+            let mark = &marks(res, 2, info)[1];
+            assert_eq!(mark.message_len(), mark.message().len(), "{res}");
+            assert_eq!(mark.ur_len(), mark.ur_string().len(), "{res}");
+            assert_eq!(
+                mark.info_len(),
+                mark.info().map_or(0, |info| info.to_cbor_data().len()),
+                "{res}"
+            );
+        }
+    }
+}
+
+#[test]
+fn test_ur_len_cache_does_not_affect_identity() {
+    let mark = marks(ProvenanceMarkResolution::Low, 1, None).remove(0);
+    let fresh = mark.clone();
+    let ur_len = mark.ur_len();
+    assert_eq!(mark.ur_len(), ur_len);
+    assert_eq!(mark.clone().ur_len(), ur_len);
+
+    assert_eq!(mark, fresh);
+    let set = HashSet::from([fresh]);
+    assert!(set.contains(&mark));
+}
+
+#[test]
+#[cfg(feature = "json")]
+fn test_mark_info_ur_len() {
+    let mark = marks(ProvenanceMarkResolution::High, 1, Some("info")).remove(0);
+    let info = ProvenanceMarkInfo::new(mark.clone(), "");
+    assert_eq!(info.ur_len(), mark.ur_string().len());
+    assert_eq!(info.ur_len(), info.ur().string().len());
+}
+
+#[test]
+fn test_report_size_statistics() {
+    let low = marks(ProvenanceMarkResolution::Low, 3, None);
+    let high = marks(ProvenanceMarkResolution::High, 2, Some("a longer info"));
+    let all: Vec<ProvenanceMark> = low.iter().chain(&high).cloned().collect();
+    let report = ProvenanceMark::validate(all.clone());
+
+    let overall = report.size_statistics();
+    assert_eq!(overall.mark_count(), 5);
+    assert_eq!(
+        overall.total_bytes(),
+        all.iter().map(|mark| mark.message().len()).sum::<usize>()
+    );
+    assert_eq!(overall.min_ur_len(), low[0].ur_string().len());
+    assert_eq!(overall.max_ur_len(), high[0].ur_string().len());
+    let mean = all
+        .iter()
+        .map(|mark| mark.ur_string().len() as f64)
+        .sum::<f64>()
+        / 5.0;
+    assert!((overall.mean_ur_len() - mean).abs() < 1e-9);
+
+    for chain in report.chains() {
+        let marks = if chain.chain_id() == low[0].chain_id() {
+            &low
+        } else {
+            &high
+        };
+        let sizes = chain.size_statistics();
+        let ur_lens: Vec<usize> =
+            marks.iter().map(|mark| mark.ur_string().len()).collect();
+        assert_eq!(sizes.mark_count(), marks.len());
+        assert_eq!(
+            sizes.total_bytes(),
+            marks.iter().map(|mark| mark.message().len()).sum::<usize>()
+        );
+        assert_eq!(sizes.min_ur_len(), *ur_lens.iter().min().unwrap());
+        assert_eq!(sizes.max_ur_len(), *ur_lens.iter().max().unwrap());
+    }
+
+    let options = FormatOptions::new()
+        .with_always_emit(true)
+        .with_statistics(true);
+    let text =
+        report.format_with_options(ValidationReportFormat::Text, &options);
+    assert!(
+        text.contains(&format!("\nTotal bytes: {}\n", overall.total_bytes())),
+        "{text}"
+    );
+    for chain in report.chains() {
+        let line = format!("\n  Size: {}", chain.size_statistics());
+        assert!(text.contains(&line), "{text}");
+    }
+    assert!(
+        !report
+            .format_with_options(
+                ValidationReportFormat::Text,
+                &FormatOptions::new().with_always_emit(true),
+            )
+            .contains("Size:")
+    );
+
+    let empty = ProvenanceMark::validate(vec![]).size_statistics();
+    assert_eq!(empty.mark_count(), 0);
+    assert_eq!(empty.mean_ur_len(), 0.0);
+}
+
+#[test]
+#[cfg(feature = "json")]
+fn test_report_size_statistics_json() {
+    let low = marks(ProvenanceMarkResolution::Low, 3, None);
+    let high = marks(ProvenanceMarkResolution::High, 2, Some("a longer info"));
+    let report =
+        ProvenanceMark::validate(low.iter().chain(&high).cloned().collect());
+
+    let options = FormatOptions::default().with_statistics(true);
+    let json: serde_json::Value = serde_json::from_str(
+        &report
+            .format_with_options(ValidationReportFormat::JsonCompact, &options),
+    )
+    .unwrap();
+    let statistics = &json["statistics"];
+    assert_eq!(
+        statistics["total_bytes"],
+        report.size_statistics().total_bytes()
+    );
+    let chain_sizes = statistics["chain_sizes"].as_array().unwrap();
+    assert_eq!(chain_sizes.len(), 2);
+    for (chain, sizes) in report.chains().iter().zip(chain_sizes) {
+        let expected = chain.size_statistics();
+        assert_eq!(sizes["chain_id"], chain.chain_id_hex());
+        assert_eq!(sizes["mark_count"], expected.mark_count());
+        assert_eq!(sizes["total_bytes"], expected.total_bytes());
+        assert_eq!(sizes["min_ur_len"], expected.min_ur_len());
+        assert_eq!(sizes["mean_ur_len"], expected.mean_ur_len());
+        assert_eq!(sizes["max_ur_len"], expected.max_ur_len());
+    }
+
+    let validator =
+        jsonschema::validator_for(&ValidationReport::json_schema()).unwrap();
+    assert!(validator.is_valid(&json));
+}
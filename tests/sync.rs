@@ -0,0 +1,119 @@
+use provenance_mark::*;
+
+mod common;
+use common::daily_marks;
+
+/// `marks` with mark 1 replaced by one whose hash does not commit to the key
+/// of mark 2, breaking the chain between them.
+fn with_hash_mismatch(marks: &[ProvenanceMark]) -> Vec<ProvenanceMark> {
+    let mark = &marks[1];
+    let broken = ProvenanceMark::new(
+        mark.res(),
+        mark.key().clone(),
+        LinkKey::new(mark.res(), marks[0].key().as_bytes()).unwrap(),
+        mark.chain_id().clone(),
+        1,
+        mark.date(),
+        None::<String>,
+    )
+    .unwrap();
+    let mut marks = marks.to_vec();
+    marks[1] = broken;
+    marks
+}
+
+#[test]
+fn test_marks_after() {
+    let marks = daily_marks(ProvenanceMarkResolution::Low, "alice", 5);
+    let report = ProvenanceMark::validate(marks.clone());
+    let chain = &report.chains()[0];
+
+    let after = chain.marks_after(&marks[1]).unwrap();
+    assert_eq!(after, marks[2..].iter().collect::<Vec<_>>());
+    assert_eq!(chain.marks_after(&marks[0]).unwrap().len(), 4);
+
+    // A mark of another chain
+    let other = daily_marks(ProvenanceMarkResolution::Low, "bob", 1);
+    assert_eq!(chain.marks_after(&other[0]), Err(SyncError::UnknownMark));
+
+    // A subscriber with the latest mark
+    assert_eq!(
+        chain.marks_after(&marks[4]),
+        Err(SyncError::AlreadyUpToDate)
+    );
+}
+
+#[test]
+fn test_marks_after_broken_link() {
+    let marks = with_hash_mismatch(&daily_marks(
+        ProvenanceMarkResolution::Low,
+        "alice",
+        5,
+    ));
+    let report = ProvenanceMark::validate(marks.clone());
+    let chain = &report.chains()[0];
+    assert_eq!(chain.sequences().len(), 2);
+
+    // Marks after the break cannot be verified from a mark before it
+    assert_eq!(chain.marks_after(&marks[0]).unwrap(), vec![&marks[1]]);
+    assert_eq!(
+        chain.marks_after(&marks[1]),
+        Err(SyncError::AlreadyUpToDate)
+    );
+
+    // A mark after the break is not on the verified path
+    for known in &marks[2..] {
+        assert_eq!(
+            chain.marks_after(known),
+            Err(SyncError::MarkOnBrokenBranch)
+        );
+    }
+}
+
+#[test]
+fn test_sync_plan() {
+    let alice = daily_marks(ProvenanceMarkResolution::Low, "alice", 4);
+    let bob = daily_marks(ProvenanceMarkResolution::Low, "bob", 3);
+    let carol = daily_marks(ProvenanceMarkResolution::Low, "carol", 2);
+    let report = ProvenanceMark::validate(
+        alice.iter().chain(&bob).chain(&carol).cloned().collect(),
+    );
+
+    let known_heads = vec![
+        (alice[0].chain_id().to_vec(), 1),
+        (bob[0].chain_id().to_vec(), 2),
+        (vec![0; 4], 7),
+    ];
+    let plan = report.sync_plan(&known_heads);
+    assert_eq!(plan.len(), 3);
+
+    for (chain, item) in report.chains().iter().zip(&plan) {
+        assert_eq!(item.chain_id(), chain.chain_id());
+        let fetched: Vec<ProvenanceMark> = item
+            .marks()
+            .iter()
+            .map(|mark| mark.as_ref().clone())
+            .collect();
+        if chain.chain_id() == alice[0].chain_id() {
+            assert_eq!(item.known_seq(), Some(1));
+            assert_eq!(fetched, &alice[2..]);
+            assert_eq!(item.error(), None);
+        } else if chain.chain_id() == bob[0].chain_id() {
+            assert_eq!(item.known_seq(), Some(2));
+            assert!(fetched.is_empty());
+            assert_eq!(item.error(), Some(SyncError::AlreadyUpToDate));
+        } else {
+            // Not followed yet, so the whole chain is fetched
+            assert_eq!(item.known_seq(), None);
+            assert_eq!(fetched, carol);
+            assert_eq!(item.error(), None);
+        }
+    }
+
+    // A known head past a break
+    let broken = with_hash_mismatch(&alice);
+    let plan = ProvenanceMark::validate(broken)
+        .sync_plan(&[(alice[0].chain_id().to_vec(), 2)]);
+    assert_eq!(plan[0].error(), Some(SyncError::MarkOnBrokenBranch));
+    assert!(plan[0].marks().is_empty());
+}
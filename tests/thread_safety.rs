@@ -0,0 +1,49 @@
+//! The core read-only types can be shared between threads. A type that gains
+//! a field that is not `Send` or `Sync` fails to compile here.
+
+use bc_ur::UREncodable;
+use provenance_mark::*;
+
+const fn assert_thread_safe<T: Send + Sync + 'static>() {}
+
+const _: () = {
+    assert_thread_safe::<ProvenanceMark>();
+    assert_thread_safe::<ProvenanceMarkResolution>();
+    assert_thread_safe::<ValidationReport>();
+    assert_thread_safe::<ChainReport>();
+    assert_thread_safe::<SequenceReport>();
+    assert_thread_safe::<FlaggedMark>();
+};
+
+#[cfg(feature = "json")]
+const _: () = assert_thread_safe::<ProvenanceMarkInfo>();
+
+#[test]
+fn test_report_shared_across_threads() {
+    let mut generator = ProvenanceMarkGenerator::new_with_passphrase(
+        ProvenanceMarkResolution::Low,
+        "test",
+    );
+    let marks: Vec<ProvenanceMark> = (0..3)
+        .map(|i| {
+            let date = dcbor::Date::from_string(&format!("2023-06-{}", 20 + i))
+                .unwrap();
+            generator.next(date, None::<String>)
+        })
+        .collect();
+    let report = std::sync::Arc::new(ProvenanceMark::validate(marks.clone()));
+
+    let handles: Vec<_> = (0..4)
+        .map(|_| {
+            let report = report.clone();
+            std::thread::spawn(move || {
+                // Caches are filled on first use from whichever thread gets
+                // there first
+                (report.marks()[0].ur_len(), report.chains().len())
+            })
+        })
+        .collect();
+    for handle in handles {
+        assert_eq!(handle.join().unwrap(), (marks[0].ur_string().len(), 1));
+    }
+}
@@ -0,0 +1,128 @@
+#![cfg(feature = "tracing")]
+
+use std::{
+    collections::BTreeMap,
+    fmt::Debug,
+    sync::{Arc, Mutex},
+};
+
+use provenance_mark::*;
+use tracing::{
+    Event, Level, Subscriber,
+    field::{Field, Visit},
+};
+use tracing_subscriber::{
+    Layer, Registry,
+    layer::{Context, SubscriberExt},
+};
+
+mod common;
+use common::same_day_marks;
+
+#[derive(Debug, Clone)]
+struct CapturedEvent {
+    level: Level,
+    fields: BTreeMap<String, String>,
+}
+
+impl CapturedEvent {
+    fn field(&self, name: &str) -> Option<&str> {
+        self.fields.get(name).map(String::as_str)
+    }
+}
+
+#[derive(Default)]
+struct FieldVisitor(BTreeMap<String, String>);
+
+impl Visit for FieldVisitor {
+    fn record_debug(&mut self, field: &Field, value: &dyn Debug) {
+        self.0
+            .insert(field.name().to_string(), format!("{:?}", value));
+    }
+
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.0.insert(field.name().to_string(), value.to_string());
+    }
+}
+
+#[derive(Clone, Default)]
+struct Capture(Arc<Mutex<Vec<CapturedEvent>>>);
+
+impl<S: Subscriber> Layer<S> for Capture {
+    fn on_event(&self, event: &Event<'_>, _ctx: Context<'_, S>) {
+        let mut visitor = FieldVisitor::default();
+        event.record(&mut visitor);
+        self.0.lock().unwrap().push(CapturedEvent {
+            level: *event.metadata().level(),
+            fields: visitor.0,
+        });
+    }
+}
+
+fn capture<R>(f: impl FnOnce() -> R) -> (R, Vec<CapturedEvent>) {
+    let capture = Capture::default();
+    let subscriber = Registry::default().with(capture.clone());
+    let result = tracing::subscriber::with_default(subscriber, f);
+    let events = capture.0.lock().unwrap().clone();
+    (result, events)
+}
+
+#[test]
+fn test_validation_events() {
+    let marks = same_day_marks(ProvenanceMarkResolution::Low, "test", 3);
+    let (report, events) = capture(|| {
+        ProvenanceMark::validate(vec![marks[0].clone(), marks[2].clone()])
+    });
+    assert!(report.has_issues());
+
+    let warnings: Vec<_> =
+        events.iter().filter(|e| e.level == Level::WARN).collect();
+    assert_eq!(warnings.len(), 1, "{events:?}");
+    assert_eq!(warnings[0].field("chain_id"), Some("b16a7cbd"));
+    assert_eq!(warnings[0].field("seq"), Some("2"));
+    assert_eq!(warnings[0].field("issue"), Some("SequenceGap"));
+
+    let chains: Vec<_> = events
+        .iter()
+        .filter(|e| e.level == Level::INFO)
+        .filter(|e| e.field("message") == Some("validated chain"))
+        .collect();
+    assert_eq!(chains.len(), 1, "{events:?}");
+    assert_eq!(chains[0].field("chain_id"), Some("b16a7cbd"));
+    assert_eq!(chains[0].field("sequences"), Some("2"));
+}
+
+#[test]
+fn test_clean_validation_has_no_warnings() {
+    let marks = same_day_marks(ProvenanceMarkResolution::Low, "test", 3);
+    let (_, events) = capture(|| ProvenanceMark::validate(marks));
+    assert!(events.iter().all(|e| e.level != Level::WARN), "{events:?}");
+}
+
+#[test]
+fn test_generator_events() {
+    let (mark, events) = capture(|| {
+        same_day_marks(ProvenanceMarkResolution::Low, "test", 1).remove(0)
+    });
+    let issued: Vec<_> = events
+        .iter()
+        .filter(|e| e.field("message") == Some("issued mark"))
+        .collect();
+    assert_eq!(issued.len(), 1, "{events:?}");
+    assert_eq!(issued[0].field("id"), Some(mark.id_hex().as_str()));
+}
+
+#[test]
+#[cfg(feature = "url")]
+fn test_ur_parse_failure_event() {
+    let html =
+        r#"<meta name="provenance" content="ur:provenance/lfaegdtokebz">"#;
+    let (results, events) = capture(|| web::extract_marks_from_html(html));
+    assert!(results[0].is_err());
+    let warning = events
+        .iter()
+        .find(|e| e.level == Level::WARN)
+        .expect("no warning");
+    assert_eq!(warning.field("ur"), Some("ur:provenance/lfaegdtokebz"));
+    assert!(warning.field("error").is_some());
+}
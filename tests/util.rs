@@ -0,0 +1,127 @@
+use dcbor::Date;
+use provenance_mark::util::*;
+
+fn utc(s: &str) -> Date { Date::from_string(s).unwrap() }
+
+fn parse(s: &str) -> Date {
+    let now = utc("2024-03-05T14:30:00Z");
+    parse_date_flexible(s, Some(now))
+        .unwrap_or_else(|e| panic!("{s:?} was rejected: {e}"))
+}
+
+#[test]
+fn test_parse_date_flexible_iso_dates() {
+    let expected = utc("2024-03-05");
+    for input in ["2024-03-05", "2024-3-5", "2024-03-5", " 2024-3-05 "] {
+        assert_eq!(parse(input), expected, "{input}");
+    }
+}
+
+#[test]
+fn test_parse_date_flexible_times() {
+    let expected = utc("2024-03-05T14:00:00Z");
+    for input in [
+        "2024-03-05 14:00",
+        "2024-03-05T14:00",
+        "2024-3-5 14:00:00",
+        "2024-03-05T14:00:00Z",
+        "2024-03-05 14:00z",
+        "2024-03-05 9:00",
+    ] {
+        let expected = if input.ends_with("9:00") {
+            utc("2024-03-05T09:00:00Z")
+        } else {
+            expected
+        };
+        assert_eq!(parse(input), expected, "{input}");
+    }
+
+    assert_eq!(
+        parse("2024-03-05T14:00:00.250Z"),
+        Date::from_datetime(
+            utc("2024-03-05T14:00:00Z").datetime()
+                + chrono::Duration::milliseconds(250)
+        )
+    );
+}
+
+#[test]
+fn test_parse_date_flexible_offsets() {
+    let expected = utc("2024-03-05T12:00:00Z");
+    for input in [
+        "2024-03-05T14:00+02:00",
+        "2024-03-05 14:00 +02:00",
+        "2024-03-05 14:00+0200",
+        "2024-03-05 14:00+02",
+        "2024-03-05T07:00:00-05:00",
+        "2024-03-05 06:30-0530",
+    ] {
+        assert_eq!(parse(input), expected, "{input}");
+    }
+
+    // An offset can move the date across midnight
+    assert_eq!(parse("2024-03-05 01:00+03:00"), utc("2024-03-04T22:00:00Z"));
+}
+
+#[test]
+fn test_parse_date_flexible_month_names() {
+    let expected = utc("2024-03-05");
+    for input in [
+        "March 5 2024",
+        "March 5, 2024",
+        "march 05 2024",
+        "Mar 5 2024",
+        "5 March 2024",
+        "5 MAR 2024",
+    ] {
+        assert_eq!(parse(input), expected, "{input}");
+    }
+}
+
+#[test]
+fn test_parse_date_flexible_keywords() {
+    assert_eq!(parse("today"), utc("2024-03-05"));
+    assert_eq!(parse(" Today "), utc("2024-03-05"));
+    assert_eq!(parse("YESTERDAY"), utc("2024-03-04"));
+
+    // Yesterday crosses month boundaries
+    let now = utc("2024-03-01T00:00:00Z");
+    assert_eq!(
+        parse_date_flexible("yesterday", Some(now)).unwrap(),
+        utc("2024-02-29")
+    );
+
+    // Without a reference, the current date is used
+    let today = parse_date_flexible("today", None).unwrap();
+    let now = Date::now().datetime();
+    assert!(now - today.datetime() < chrono::Duration::days(1));
+}
+
+#[test]
+fn test_parse_date_flexible_rejects() {
+    for input in [
+        "",
+        "tomorrow",
+        "2024-02-30",
+        "2024-13-01",
+        "2024/03/05",
+        "2024-03-05 25:00",
+        "2024-03-05 14:00+2:00:00",
+        "2024-03-05 14:00+24:00",
+        "Marchember 5 2024",
+    ] {
+        let error = parse_date_flexible(input, None).unwrap_err();
+        assert_eq!(error.input(), input);
+        assert_eq!(error.accepted_formats(), ACCEPTED_DATE_FORMATS);
+        let message = error.to_string();
+        assert!(message.contains("YYYY-MM-DD"), "{message}");
+        assert!(message.contains("yesterday"), "{message}");
+    }
+}
+
+#[test]
+fn test_parse_date_is_strict() {
+    assert!(parse_date("2024-03-05").is_ok());
+    assert!(parse_date("March 5 2024").is_err());
+    assert!(parse_date("today").is_err());
+}
@@ -1,3 +1,6 @@
+use std::sync::Arc;
+
+use bc_ur::UREncodable;
 use chrono::TimeZone;
 use dcbor::Date;
 use indoc::indoc;
@@ -5,6 +8,7 @@ use provenance_mark::*;
 
 #[macro_use]
 mod common;
+use common::daily_marks;
 
 fn create_test_marks(
     count: usize,
@@ -14,26 +18,11 @@ fn create_test_marks(
     #[cfg(feature = "envelope")]
     provenance_mark::register_tags();
 
-    let mut generator =
-        ProvenanceMarkGenerator::new_with_passphrase(resolution, passphrase);
-    let calendar = chrono::Utc;
-
-    (0..count)
-        .map(|i| {
-            let date = Date::from_datetime(
-                calendar
-                    .with_ymd_and_hms(2023, 6, 20, 12, 0, 0)
-                    .single()
-                    .unwrap()
-                    .checked_add_signed(chrono::Duration::days(i as i64))
-                    .unwrap(),
-            );
-            generator.next(date, None::<String>)
-        })
-        .collect()
+    daily_marks(resolution, passphrase, count)
 }
 
 #[test]
+#[cfg(feature = "json")]
 fn test_validate_empty() {
     let report = ProvenanceMark::validate(vec![]);
 
@@ -43,19 +32,21 @@ fn test_validate_empty() {
     assert_actual_expected!(json, indoc! {r#"
         {
           "marks": [],
-          "chains": []
+          "chains": [],
+          "digest": "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"
         }"#}.trim());
 
     // Test compact JSON format
     let json_compact = report.format(ValidationReportFormat::JsonCompact);
     #[rustfmt::skip]
-    assert_actual_expected!(json_compact, r#"{"marks":[],"chains":[]}"#);
+    assert_actual_expected!(json_compact, r#"{"marks":[],"chains":[],"digest":"e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"}"#);
 
     // Format should return empty string for empty report
     assert_actual_expected!(report.format(ValidationReportFormat::Text), "");
 }
 
 #[test]
+#[cfg(feature = "json")]
 fn test_validate_single_mark() {
     let marks = create_test_marks(1, ProvenanceMarkResolution::Low, "test");
     let report = ProvenanceMark::validate(marks.clone());
@@ -71,6 +62,7 @@ fn test_validate_single_mark() {
           "chains": [
             {
               "chain_id": "b16a7cbd",
+              "chain_fingerprint": "262b5561638371c84e0ea776ffdb69b6f6fa5627325187f8607f6197aa114ec9",
               "has_genesis": true,
               "marks": [
                 "ur:provenance/lfaegdpaimkerydihsaedetiimmttpgdmocfdpbnhlasba"
@@ -84,23 +76,27 @@ fn test_validate_single_mark() {
                       "mark": "ur:provenance/lfaegdpaimkerydihsaedetiimmttpgdmocfdpbnhlasba",
                       "issues": []
                     }
-                  ]
+                  ],
+                  "digest": "c210ccae40019ec3f56e031c5c08d2fe247727282f59d3d1104c795f4d02997c"
                 }
-              ]
+              ],
+              "digest": "0776002db3c90fb8994bd2fcb77d6a4f69f6c420a836461544cc46a7f498557a"
             }
-          ]
+          ],
+          "digest": "37440240c0ce8d1acf19251db31f0429a12da44066b6fcc3b9a0c7c7a18ceba3"
         }"#}.trim());
 
     // Test compact JSON format
     let json_compact = report.format(ValidationReportFormat::JsonCompact);
     #[rustfmt::skip]
-    assert_actual_expected!(json_compact, r#"{"marks":["ur:provenance/lfaegdpaimkerydihsaedetiimmttpgdmocfdpbnhlasba"],"chains":[{"chain_id":"b16a7cbd","has_genesis":true,"marks":["ur:provenance/lfaegdpaimkerydihsaedetiimmttpgdmocfdpbnhlasba"],"sequences":[{"start_seq":0,"end_seq":0,"marks":[{"mark":"ur:provenance/lfaegdpaimkerydihsaedetiimmttpgdmocfdpbnhlasba","issues":[]}]}]}]}"#);
+    assert_actual_expected!(json_compact, r#"{"marks":["ur:provenance/lfaegdpaimkerydihsaedetiimmttpgdmocfdpbnhlasba"],"chains":[{"chain_id":"b16a7cbd","chain_fingerprint":"262b5561638371c84e0ea776ffdb69b6f6fa5627325187f8607f6197aa114ec9","has_genesis":true,"marks":["ur:provenance/lfaegdpaimkerydihsaedetiimmttpgdmocfdpbnhlasba"],"sequences":[{"start_seq":0,"end_seq":0,"marks":[{"mark":"ur:provenance/lfaegdpaimkerydihsaedetiimmttpgdmocfdpbnhlasba","issues":[]}],"digest":"c210ccae40019ec3f56e031c5c08d2fe247727282f59d3d1104c795f4d02997c"}],"digest":"0776002db3c90fb8994bd2fcb77d6a4f69f6c420a836461544cc46a7f498557a"}],"digest":"37440240c0ce8d1acf19251db31f0429a12da44066b6fcc3b9a0c7c7a18ceba3"}"#);
 
     // Format should return empty string for single perfect chain
     assert_actual_expected!(report.format(ValidationReportFormat::Text), "");
 }
 
 #[test]
+#[cfg(feature = "json")]
 fn test_validate_valid_sequence() {
     let marks = create_test_marks(5, ProvenanceMarkResolution::Low, "test");
     let report = ProvenanceMark::validate(marks.clone());
@@ -121,6 +117,7 @@ fn test_validate_valid_sequence() {
           "chains": [
             {
               "chain_id": "b16a7cbd",
+              "chain_fingerprint": "262b5561638371c84e0ea776ffdb69b6f6fa5627325187f8607f6197aa114ec9",
               "has_genesis": true,
               "marks": [
                 "ur:provenance/lfaegdpaimkerydihsaedetiimmttpgdmocfdpbnhlasba",
@@ -154,18 +151,42 @@ fn test_validate_valid_sequence() {
                       "mark": "ur:provenance/lfaegdrkkilkylsrendmkniaeejyrhndlyvednzckpsbtk",
                       "issues": []
                     }
-                  ]
+                  ],
+                  "digest": "9cdd69b9413c86dff010d12581de04ed6383c22ecd7b6aec1c79c694a076e369"
                 }
-              ]
+              ],
+              "digest": "410fb5ae6623a5ef84fcdd5d4e499cad5fe340be11bd57cb71abbf5754d82998"
             }
-          ]
+          ],
+          "digest": "785a9844e490d8c7e70ad12ea14b5c613f8c8c19b70695a4298a00d656b67659"
         }"#}.trim());
 
     // Format should return empty string for single perfect chain
     assert_actual_expected!(report.format(ValidationReportFormat::Text), "");
+    assert!(!report.is_interesting());
+
+    // Unless asked to always emit the report
+    let text = report.format_with_options(
+        ValidationReportFormat::Text,
+        &FormatOptions::new().with_always_emit(true),
+    );
+    #[rustfmt::skip]
+    assert_actual_expected!(text, indoc! {r#"
+        Total marks: 5
+        Chains: 1
+        Status: valid
+
+        Chain 1: b16a7cbd
+          0: f057c8c4 (genesis mark)
+          1: 1b806d6c
+          2: b292f357
+          3: 761a5e74
+          4: 42d12de5
+    "#}.trim());
 }
 
 #[test]
+#[cfg(feature = "json")]
 fn test_validate_deduplication() {
     let marks = create_test_marks(3, ProvenanceMarkResolution::Low, "test");
 
@@ -188,9 +209,15 @@ fn test_validate_deduplication() {
             "ur:provenance/lfaegdecgldtsrbbfgsbetgazoenadrntdrtkoluwekerp",
             "ur:provenance/lfaegdbwatptqzoyrkdmptvasefnfmpmpmrfoywyptolfd"
           ],
+          "duplicates_removed": 3,
+          "duplicate_marks": [
+            "ur:provenance/lfaegdpaimkerydihsaedetiimmttpgdmocfdpbnhlasba",
+            "ur:provenance/lfaegdecgldtsrbbfgsbetgazoenadrntdrtkoluwekerp"
+          ],
           "chains": [
             {
               "chain_id": "b16a7cbd",
+              "chain_fingerprint": "262b5561638371c84e0ea776ffdb69b6f6fa5627325187f8607f6197aa114ec9",
               "has_genesis": true,
               "marks": [
                 "ur:provenance/lfaegdpaimkerydihsaedetiimmttpgdmocfdpbnhlasba",
@@ -214,19 +241,37 @@ fn test_validate_deduplication() {
                       "mark": "ur:provenance/lfaegdbwatptqzoyrkdmptvasefnfmpmpmrfoywyptolfd",
                       "issues": []
                     }
-                  ]
+                  ],
+                  "digest": "6e01a038bbcf050cbc2dc44a3a2d6f5396213fd8918c698153d52fa8f6acd887"
                 }
-              ]
+              ],
+              "digest": "a5eff2381fe615275c4fdc5d19afa33aee306409c4efccef9590ea905e3624c2"
             }
-          ]
+          ],
+          "digest": "d21c7af59839ca9125de2ed9e1ee26f3a138640c957a389bac0f935dab8b0848"
         }"#}.trim());
 
-    // Format should return empty string - single perfect chain after
-    // deduplication
-    assert_actual_expected!(report.format(ValidationReportFormat::Text), "");
+    assert_eq!(report.duplicates_removed(), 3);
+    let duplicate_marks: Vec<&ProvenanceMark> =
+        report.duplicate_marks().iter().map(Arc::as_ref).collect();
+    assert_eq!(duplicate_marks, marks[..2].iter().collect::<Vec<_>>());
+
+    // A single perfect chain, but the dropped duplicates are reported
+    #[rustfmt::skip]
+    assert_actual_expected!(report.format(ValidationReportFormat::Text), indoc! {r#"
+        Total marks: 3
+        Duplicates removed: 3
+        Chains: 1
+
+        Chain 1: b16a7cbd
+          0: f057c8c4 (genesis mark)
+          1: 1b806d6c
+          2: b292f357
+    "#}.trim());
 }
 
 #[test]
+#[cfg(feature = "json")]
 fn test_validate_multiple_chains() {
     let marks1 = create_test_marks(3, ProvenanceMarkResolution::Low, "alice");
     let marks2 = create_test_marks(3, ProvenanceMarkResolution::Low, "bob");
@@ -253,6 +298,7 @@ fn test_validate_multiple_chains() {
           "chains": [
             {
               "chain_id": "7a9c3f5e",
+              "chain_fingerprint": "003d2f66f73399c596d365a03ced07e2e9ef04e0dd2a22aa9f1b131176cad84c",
               "has_genesis": true,
               "marks": [
                 "ur:provenance/lfaegdknnsfhhylrgytdhtsnheskzepmctgrwnlyjeyngh",
@@ -276,12 +322,15 @@ fn test_validate_multiple_chains() {
                       "mark": "ur:provenance/lfaegdrevlpmticnmkbafsinmeonvycydphernwerppefs",
                       "issues": []
                     }
-                  ]
+                  ],
+                  "digest": "481146aa7aee5339e5d6152b7894cd6212e511f95e8fe527d8e5249111807c89"
                 }
-              ]
+              ],
+              "digest": "02921fd79f84127d36cf314286758d2a533ae1f506e30f77609af75a0931e2db"
             },
             {
               "chain_id": "a33e10de",
+              "chain_fingerprint": "525b8d629aff872f71ece553c77e5c0ab38005715d2cbcf24d5c37d54edd51a7",
               "has_genesis": true,
               "marks": [
                 "ur:provenance/lfaegdotfmbeuerniolpveenmowliegyfrfrwnfzntnbwe",
@@ -305,11 +354,14 @@ fn test_validate_multiple_chains() {
                       "mark": "ur:provenance/lfaegdenrdietbenskbesbdiiefgwkuoqzldbecpidhfrt",
                       "issues": []
                     }
-                  ]
+                  ],
+                  "digest": "28b495581a7c111d6a05624010d82c2198e8434b9bf94ebe26c200e9b44fee9e"
                 }
-              ]
+              ],
+              "digest": "057dd8e2f50bbebcbfd49ce79b9aad355be6315e847953cd305c2d81d90b0c2f"
             }
-          ]
+          ],
+          "digest": "8ba1bcb4eab6becf4c45112283c6ad9040803568834a61422bf0d4dddc3675fd"
         }"#}.trim());
 
     // Format should show both chains (interesting)
@@ -333,6 +385,7 @@ fn test_validate_multiple_chains() {
 }
 
 #[test]
+#[cfg(feature = "json")]
 fn test_validate_missing_genesis() {
     let marks = create_test_marks(5, ProvenanceMarkResolution::Low, "test");
 
@@ -356,6 +409,7 @@ fn test_validate_missing_genesis() {
           "chains": [
             {
               "chain_id": "b16a7cbd",
+              "chain_fingerprint": "262b5561638371c84e0ea776ffdb69b6f6fa5627325187f8607f6197aa114ec9",
               "has_genesis": false,
               "marks": [
                 "ur:provenance/lfaegdecgldtsrbbfgsbetgazoenadrntdrtkoluwekerp",
@@ -384,11 +438,14 @@ fn test_validate_missing_genesis() {
                       "mark": "ur:provenance/lfaegdrkkilkylsrendmkniaeejyrhndlyvednzckpsbtk",
                       "issues": []
                     }
-                  ]
+                  ],
+                  "digest": "e5f1d4e9b875a27105c3c7fb2b5a8a492ededc6bbb7531f28a0d946f53c27347"
                 }
-              ]
+              ],
+              "digest": "905aefe70c16f60fd4fa66afce5450afedbb14c671683da38c4fb733da3fa972"
             }
-          ]
+          ],
+          "digest": "f762a229d36b94eee6d3d38b1b5d0bfbc9dbe33653bc23d15501986b905725e2"
         }"#}.trim());
 
     // Format should show missing genesis warning
@@ -409,6 +466,7 @@ fn test_validate_missing_genesis() {
 }
 
 #[test]
+#[cfg(feature = "json")]
 fn test_validate_sequence_gap() {
     let marks = create_test_marks(5, ProvenanceMarkResolution::Low, "test");
 
@@ -422,6 +480,14 @@ fn test_validate_sequence_gap() {
 
     let report = ProvenanceMark::validate(marks_with_gap);
 
+    // The sequence after the gap names the mark before it
+    let sequences = report.chains()[0].sequences();
+    assert_eq!(sequences[0].preceded_by(), None);
+    assert_eq!(sequences[1].preceded_by(), Some("1b806d6c"));
+    assert_eq!((sequences[0].len(), sequences[1].len()), (2, 2));
+    assert!(sequences[0].is_clean());
+    assert!(!sequences[1].is_clean());
+
     // Test JSON serialization
     let json = report.format(ValidationReportFormat::JsonPretty);
     // expected-text-output-rubric:
@@ -437,6 +503,7 @@ fn test_validate_sequence_gap() {
           "chains": [
             {
               "chain_id": "b16a7cbd",
+              "chain_fingerprint": "262b5561638371c84e0ea776ffdb69b6f6fa5627325187f8607f6197aa114ec9",
               "has_genesis": true,
               "marks": [
                 "ur:provenance/lfaegdpaimkerydihsaedetiimmttpgdmocfdpbnhlasba",
@@ -457,11 +524,13 @@ fn test_validate_sequence_gap() {
                       "mark": "ur:provenance/lfaegdecgldtsrbbfgsbetgazoenadrntdrtkoluwekerp",
                       "issues": []
                     }
-                  ]
+                  ],
+                  "digest": "bb81f438822e030be7f41422e3e3b0f6dda3c948390c3064b91b3fd81ab4dcca"
                 },
                 {
                   "start_seq": 3,
                   "end_seq": 4,
+                  "preceded_by": "1b806d6c",
                   "marks": [
                     {
                       "mark": "ur:provenance/lfaegdhsvtleetlatsmwwdndmnjlaxonsfdewmghpybzbg",
@@ -479,11 +548,14 @@ fn test_validate_sequence_gap() {
                       "mark": "ur:provenance/lfaegdrkkilkylsrendmkniaeejyrhndlyvednzckpsbtk",
                       "issues": []
                     }
-                  ]
+                  ],
+                  "digest": "b2aae9b352b64f8be12d243391c4397e89a0c4c5efe6ab8dae239bc52709db7e"
                 }
-              ]
+              ],
+              "digest": "46ccad9f580844a7f96933a8d2e1d096b1c85bfeca70cd4275a9f6840d1dbaf1"
             }
-          ]
+          ],
+          "digest": "f4aeb96f2f7198804dfbcaac761d3c7f86855044c2305979edeee5cb87f00f72"
         }"#}.trim());
 
     // Format should show gap issue and multiple sequences
@@ -496,13 +568,60 @@ fn test_validate_sequence_gap() {
         Chain 1: b16a7cbd
           0: f057c8c4 (genesis mark)
           1: 1b806d6c
-          3: 761a5e74 (gap: 2 missing)
+          3: 761a5e74 (gap: 2 missing, after 1b806d6c)
           4: 42d12de5
 
     "#}.trim());
 }
 
 #[test]
+fn test_validate_links() {
+    let marks = create_test_marks(6, ProvenanceMarkResolution::Low, "test");
+    // Seq 3 is missing
+    let gapped: Vec<_> = marks
+        .iter()
+        .filter(|mark| mark.seq() != 3)
+        .cloned()
+        .collect();
+    let report = ProvenanceMark::validate(gapped.clone());
+    let chain = &report.chains()[0];
+
+    let verified: Vec<_> = chain.verified_links().collect();
+    assert_eq!(
+        verified,
+        vec![
+            (&marks[0], &marks[1]),
+            (&marks[1], &marks[2]),
+            (&marks[4], &marks[5]),
+        ]
+    );
+    assert!(
+        verified
+            .iter()
+            .all(|(previous, next)| previous.precedes(next))
+    );
+    assert_eq!(report.verified_links().count(), 3);
+
+    let broken: Vec<_> = chain.broken_links().collect();
+    assert_eq!(
+        broken,
+        vec![(
+            &marks[2],
+            &marks[4],
+            &ValidationIssue::SequenceGap { expected: 3, actual: 4 }
+        )]
+    );
+
+    // A suppressed gap is not reported as a broken link
+    let options = ValidationOptions::new()
+        .suppress(SuppressionRule::new([0xb1, 0x6a], "SequenceGap"));
+    let report = ProvenanceMark::validate_opts(gapped, &options);
+    assert_eq!(report.chains()[0].verified_links().count(), 3);
+    assert_eq!(report.chains()[0].broken_links().count(), 0);
+}
+
+#[test]
+#[cfg(feature = "json")]
 fn test_validate_out_of_order() {
     let marks = create_test_marks(5, ProvenanceMarkResolution::Low, "test");
 
@@ -533,6 +652,7 @@ fn test_validate_out_of_order() {
           "chains": [
             {
               "chain_id": "b16a7cbd",
+              "chain_fingerprint": "262b5561638371c84e0ea776ffdb69b6f6fa5627325187f8607f6197aa114ec9",
               "has_genesis": true,
               "marks": [
                 "ur:provenance/lfaegdpaimkerydihsaedetiimmttpgdmocfdpbnhlasba",
@@ -566,11 +686,14 @@ fn test_validate_out_of_order() {
                       "mark": "ur:provenance/lfaegdrkkilkylsrendmkniaeejyrhndlyvednzckpsbtk",
                       "issues": []
                     }
-                  ]
+                  ],
+                  "digest": "9cdd69b9413c86dff010d12581de04ed6383c22ecd7b6aec1c79c694a076e369"
                 }
-              ]
+              ],
+              "digest": "410fb5ae6623a5ef84fcdd5d4e499cad5fe340be11bd57cb71abbf5754d82998"
             }
-          ]
+          ],
+          "digest": "785a9844e490d8c7e70ad12ea14b5c613f8c8c19b70695a4298a00d656b67659"
         }"#}.trim());
 
     // Format should return empty string - validation sorts by seq number
@@ -578,6 +701,7 @@ fn test_validate_out_of_order() {
 }
 
 #[test]
+#[cfg(feature = "json")]
 fn test_validate_hash_mismatch() {
     #[cfg(feature = "envelope")]
     provenance_mark::register_tags();
@@ -599,9 +723,10 @@ fn test_validate_hash_mismatch() {
     // This creates a hash mismatch since mark1.hash should be the prev
     let bad_mark = ProvenanceMark::new(
         mark1.res(),
-        mark1.key().to_vec(),
-        mark0.hash().to_vec(), // Wrong! Should be mark1.hash()
-        mark1.chain_id().to_vec(),
+        mark1.key().clone(),
+        // Wrong! Should be mark1.hash()
+        LinkKey::new(mark1.res(), mark0.hash().as_bytes()).unwrap(),
+        mark1.chain_id().clone(),
         2,
         date,
         None::<String>,
@@ -625,6 +750,7 @@ fn test_validate_hash_mismatch() {
           "chains": [
             {
               "chain_id": "b16a7cbd",
+              "chain_fingerprint": "262b5561638371c84e0ea776ffdb69b6f6fa5627325187f8607f6197aa114ec9",
               "has_genesis": true,
               "marks": [
                 "ur:provenance/lfaegdpaimkerydihsaedetiimmttpgdmocfdpbnhlasba",
@@ -642,13 +768,22 @@ fn test_validate_hash_mismatch() {
                     },
                     {
                       "mark": "ur:provenance/lfaegdecgldtsrbbfgsbetgazoenadrntdrtkoluwekerp",
-                      "issues": []
+                      "issues": [
+                        {
+                          "type": "ReusedKey",
+                          "data": {
+                            "other_mark": "09cca8210a3c4e64243e6e37b9bebb76fad6a01197c83954a225cef07bb1810a"
+                          }
+                        }
+                      ]
                     }
-                  ]
+                  ],
+                  "digest": "bb81f438822e030be7f41422e3e3b0f6dda3c948390c3064b91b3fd81ab4dcca"
                 },
                 {
                   "start_seq": 2,
                   "end_seq": 2,
+                  "preceded_by": "1b806d6c",
                   "marks": [
                     {
                       "mark": "ur:provenance/lfaegdecgldtsrbbfgsbethprlwfgsrnttrtkpgsttptwn",
@@ -659,14 +794,23 @@ fn test_validate_hash_mismatch() {
                             "expected": "d446017b",
                             "actual": "1b806d6c"
                           }
+                        },
+                        {
+                          "type": "ReusedKey",
+                          "data": {
+                            "other_mark": "1b806d6cc885f6a8aa31ccfbf6f02bf3b086862cfce36a326f3a4f8c1a01f3e5"
+                          }
                         }
                       ]
                     }
-                  ]
+                  ],
+                  "digest": "47cc3390b3e342b7870a1462fe6e456239fbfca38fba0b972d7b9fae5f902daa"
                 }
-              ]
+              ],
+              "digest": "49d19dcf87a6644c00dad4daa25e208b621dc3c399db17e6de4cf0b250facdc0"
             }
-          ]
+          ],
+          "digest": "707ba74317baf3b54ec85f0b6e8f850d9c6845cf0e52ec73c7b649d3e3da9443"
         }"#}.trim());
 
     // Format should show hash mismatch issue
@@ -678,12 +822,13 @@ fn test_validate_hash_mismatch() {
 
         Chain 1: b16a7cbd
           0: f057c8c4 (genesis mark)
-          1: 1b806d6c
-          2: 09cca821 (hash mismatch)
+          1: 1b806d6c (reused key)
+          2: 09cca821 (hash mismatch, reused key, after 1b806d6c)
     "#}.trim());
 }
 
 #[test]
+#[cfg(feature = "json")]
 fn test_validate_date_ordering_violation() {
     let marks = create_test_marks(3, ProvenanceMarkResolution::Low, "test");
 
@@ -707,6 +852,7 @@ fn test_validate_date_ordering_violation() {
           "chains": [
             {
               "chain_id": "b16a7cbd",
+              "chain_fingerprint": "262b5561638371c84e0ea776ffdb69b6f6fa5627325187f8607f6197aa114ec9",
               "has_genesis": true,
               "marks": [
                 "ur:provenance/lfaegdpaimkerydihsaedetiimmttpgdmocfdpbnhlasba",
@@ -730,15 +876,19 @@ fn test_validate_date_ordering_violation() {
                       "mark": "ur:provenance/lfaegdbwatptqzoyrkdmptvasefnfmpmpmrfoywyptolfd",
                       "issues": []
                     }
-                  ]
+                  ],
+                  "digest": "6e01a038bbcf050cbc2dc44a3a2d6f5396213fd8918c698153d52fa8f6acd887"
                 }
-              ]
+              ],
+              "digest": "a5eff2381fe615275c4fdc5d19afa33aee306409c4efccef9590ea905e3624c2"
             }
-          ]
+          ],
+          "digest": "d21c7af59839ca9125de2ed9e1ee26f3a138640c957a389bac0f935dab8b0848"
         }"#}.trim());
 }
 
 #[test]
+#[cfg(feature = "json")]
 fn test_validate_multiple_sequences_in_chain() {
     let marks = create_test_marks(7, ProvenanceMarkResolution::Low, "test");
 
@@ -769,6 +919,7 @@ fn test_validate_multiple_sequences_in_chain() {
           "chains": [
             {
               "chain_id": "b16a7cbd",
+              "chain_fingerprint": "262b5561638371c84e0ea776ffdb69b6f6fa5627325187f8607f6197aa114ec9",
               "has_genesis": true,
               "marks": [
                 "ur:provenance/lfaegdpaimkerydihsaedetiimmttpgdmocfdpbnhlasba",
@@ -790,11 +941,13 @@ fn test_validate_multiple_sequences_in_chain() {
                       "mark": "ur:provenance/lfaegdecgldtsrbbfgsbetgazoenadrntdrtkoluwekerp",
                       "issues": []
                     }
-                  ]
+                  ],
+                  "digest": "bb81f438822e030be7f41422e3e3b0f6dda3c948390c3064b91b3fd81ab4dcca"
                 },
                 {
                   "start_seq": 3,
                   "end_seq": 4,
+                  "preceded_by": "1b806d6c",
                   "marks": [
                     {
                       "mark": "ur:provenance/lfaegdhsvtleetlatsmwwdndmnjlaxonsfdewmghpybzbg",
@@ -812,11 +965,13 @@ fn test_validate_multiple_sequences_in_chain() {
                       "mark": "ur:provenance/lfaegdrkkilkylsrendmkniaeejyrhndlyvednzckpsbtk",
                       "issues": []
                     }
-                  ]
+                  ],
+                  "digest": "b2aae9b352b64f8be12d243391c4397e89a0c4c5efe6ab8dae239bc52709db7e"
                 },
                 {
                   "start_seq": 6,
                   "end_seq": 6,
+                  "preceded_by": "42d12de5",
                   "marks": [
                     {
                       "mark": "ur:provenance/lfaegdwkltwzolasuomobntaryinjzcyrocsfskkrtmyam",
@@ -830,11 +985,14 @@ fn test_validate_multiple_sequences_in_chain() {
                         }
                       ]
                     }
-                  ]
+                  ],
+                  "digest": "522947358fd8e157416e9f21c741bbc222a40e7ea751bf7cf54186094e53940c"
                 }
-              ]
+              ],
+              "digest": "d7d51236f5e0cd986ced9fa286a1e0e0191955c125e8899bf5fe9c45ca0a8628"
             }
-          ]
+          ],
+          "digest": "277723200e32f336d040466e85ff240852d8837dcf8388e67804fc1d5890aea1"
         }"#}.trim());
 
     // Format should show multiple sequences with gap annotations
@@ -847,9 +1005,9 @@ fn test_validate_multiple_sequences_in_chain() {
         Chain 1: b16a7cbd
           0: f057c8c4 (genesis mark)
           1: 1b806d6c
-          3: 761a5e74 (gap: 2 missing)
+          3: 761a5e74 (gap: 2 missing, after 1b806d6c)
           4: 42d12de5
-          6: 8a9b06e1 (gap: 5 missing)
+          6: 8a9b06e1 (gap: 5 missing, after 42d12de5)
 
     "#}.trim());
 }
@@ -883,6 +1041,7 @@ fn test_validate_chain_id_hex() {
 }
 
 #[test]
+#[cfg(feature = "json")]
 fn test_validate_with_info() {
     let mut generator = ProvenanceMarkGenerator::new_with_passphrase(
         ProvenanceMarkResolution::Low,
@@ -920,6 +1079,7 @@ fn test_validate_with_info() {
           "chains": [
             {
               "chain_id": "b16a7cbd",
+              "chain_fingerprint": "262b5561638371c84e0ea776ffdb69b6f6fa5627325187f8607f6197aa114ec9",
               "has_genesis": true,
               "marks": [
                 "ur:provenance/lfaehdcypaimkerydihsaedesbglvlrsgdmocfdpveksstlbrprscahlihyntoaxvtem",
@@ -943,15 +1103,19 @@ fn test_validate_with_info() {
                       "mark": "ur:provenance/lfaehdcybwatptqzoyrkdmptfntsjsqdpmpmrfoylewnlpjnhdwzadnycljncflozsfy",
                       "issues": []
                     }
-                  ]
+                  ],
+                  "digest": "a0297ef45526d9f79d4bb59a22532fc4a847c329211cccf11ba32e143e47423b"
                 }
-              ]
+              ],
+              "digest": "3a543e0bfd259549f4762eb24378e87782f2493c726a01cebf9cd43d3ed4358e"
             }
-          ]
+          ],
+          "digest": "5ce1c18860cc237cd18f9f1fb39f6dddc3645857661a65b6e4e05ca420f58b16"
         }"#}.trim());
 }
 
 #[test]
+#[cfg(feature = "json")]
 fn test_validate_sorted_chains() {
     // Create marks from different chains
     let marks1 = create_test_marks(2, ProvenanceMarkResolution::Low, "zebra");
@@ -981,6 +1145,7 @@ fn test_validate_sorted_chains() {
           "chains": [
             {
               "chain_id": "1eda2887",
+              "chain_fingerprint": "c277157b7dfa15aa6ac01c872bf1f5ff2ec65be6a56f92a35e137957d23cdb8d",
               "has_genesis": true,
               "marks": [
                 "ur:provenance/lfaegdcktndeltrtspprmhkptlfdwfgylsjljzwtahlpsf",
@@ -999,12 +1164,15 @@ fn test_validate_sorted_chains() {
                       "mark": "ur:provenance/lfaegdrslnurdeknftkscnlphnhgldcxnnahwddiaavyda",
                       "issues": []
                     }
-                  ]
+                  ],
+                  "digest": "9f49c56e189fda477461784fc4b2be639005f202220bc6e5125aaee1c1834eec"
                 }
-              ]
+              ],
+              "digest": "9dcff003924fff99e4453da9083ccccf3b539575e0adecc18a3e606af8d6032d"
             },
             {
               "chain_id": "44806f2a",
+              "chain_fingerprint": "6c0317c396e3517335153fd0e3754fab5ff050d523f31bc15e1663290e7ea176",
               "has_genesis": true,
               "marks": [
                 "ur:provenance/lfaegdfylajldrntasvyttgljtsbsoghdafzwfcawmgede",
@@ -1023,12 +1191,15 @@ fn test_validate_sorted_chains() {
                       "mark": "ur:provenance/lfaegdgrrtjorhmuzshlvsfdldchoxbntlsrstoyidjepm",
                       "issues": []
                     }
-                  ]
+                  ],
+                  "digest": "c2b9d12e0c85bd5ed1b78fdf320e73490e8f469ad412cbeaa24bd295b5d338a4"
                 }
-              ]
+              ],
+              "digest": "c6acfe1517be1956b8e675f2dd9c3b907546dd5b5039525b92d15d07f793f902"
             },
             {
               "chain_id": "47ce4d2e",
+              "chain_fingerprint": "bf156c96c54fc2a8871638641625a1de9e39d681cdac672830d728c84f7f9ed7",
               "has_genesis": true,
               "marks": [
                 "ur:provenance/lfaegdfltogtdmfpdphlttkilywyfntidsamrkmuioteid",
@@ -1047,15 +1218,19 @@ fn test_validate_sorted_chains() {
                       "mark": "ur:provenance/lfaegdntjopfzttddtsrkirkdytlkirhisiyidimdmwnkg",
                       "issues": []
                     }
-                  ]
+                  ],
+                  "digest": "8882961e98d87c301748ff5479075022b39c823ceba35444420a921b6fbc4376"
                 }
-              ]
+              ],
+              "digest": "f2ca1faf6b07c7c9bcf2b9595cd0434930f851fcf832fd637cbfbcd289f6c63d"
             }
-          ]
+          ],
+          "digest": "ec1d1b20eb04cdc80455052b34f251a3447a3eaf7552615f5a804c98cce7b531"
         }"#}.trim());
 }
 
 #[test]
+#[cfg(feature = "json")]
 fn test_validate_genesis_check() {
     let marks = create_test_marks(3, ProvenanceMarkResolution::Low, "test");
 
@@ -1076,6 +1251,7 @@ fn test_validate_genesis_check() {
           "chains": [
             {
               "chain_id": "b16a7cbd",
+              "chain_fingerprint": "262b5561638371c84e0ea776ffdb69b6f6fa5627325187f8607f6197aa114ec9",
               "has_genesis": true,
               "marks": [
                 "ur:provenance/lfaegdpaimkerydihsaedetiimmttpgdmocfdpbnhlasba",
@@ -1099,11 +1275,14 @@ fn test_validate_genesis_check() {
                       "mark": "ur:provenance/lfaegdbwatptqzoyrkdmptvasefnfmpmpmrfoywyptolfd",
                       "issues": []
                     }
-                  ]
+                  ],
+                  "digest": "6e01a038bbcf050cbc2dc44a3a2d6f5396213fd8918c698153d52fa8f6acd887"
                 }
-              ]
+              ],
+              "digest": "a5eff2381fe615275c4fdc5d19afa33aee306409c4efccef9590ea905e3624c2"
             }
-          ]
+          ],
+          "digest": "d21c7af59839ca9125de2ed9e1ee26f3a138640c957a389bac0f935dab8b0848"
         }"#}.trim());
 
     // Without genesis
@@ -1123,6 +1302,7 @@ fn test_validate_genesis_check() {
           "chains": [
             {
               "chain_id": "b16a7cbd",
+              "chain_fingerprint": "262b5561638371c84e0ea776ffdb69b6f6fa5627325187f8607f6197aa114ec9",
               "has_genesis": false,
               "marks": [
                 "ur:provenance/lfaegdecgldtsrbbfgsbetgazoenadrntdrtkoluwekerp",
@@ -1141,15 +1321,19 @@ fn test_validate_genesis_check() {
                       "mark": "ur:provenance/lfaegdbwatptqzoyrkdmptvasefnfmpmpmrfoywyptolfd",
                       "issues": []
                     }
-                  ]
+                  ],
+                  "digest": "53c4cd96c448c8a27311f794ab1dc7b2021aa66e96899d44279d27871082663a"
                 }
-              ]
+              ],
+              "digest": "c995d68c8a9a88d7c20e7a5475fa23207b941b7f2b4dd7972d26af1e8ba33304"
             }
-          ]
+          ],
+          "digest": "cd5495a75cd5434dd8efe5b43274f015d838aa46612edcfc7b15fb629c35202d"
         }"#}.trim());
 }
 
 #[test]
+#[cfg(feature = "json")]
 fn test_validate_date_ordering_violation_constructed() {
     #[cfg(feature = "envelope")]
     provenance_mark::register_tags();
@@ -1189,6 +1373,7 @@ fn test_validate_date_ordering_violation_constructed() {
           "chains": [
             {
               "chain_id": "b16a7cbd",
+              "chain_fingerprint": "262b5561638371c84e0ea776ffdb69b6f6fa5627325187f8607f6197aa114ec9",
               "has_genesis": true,
               "marks": [
                 "ur:provenance/lfaegdpaimkerydihsaedetiimmttpgdmocfdpbnhlasba",
@@ -1203,11 +1388,13 @@ fn test_validate_date_ordering_violation_constructed() {
                       "mark": "ur:provenance/lfaegdpaimkerydihsaedetiimmttpgdmocfdpbnhlasba",
                       "issues": []
                     }
-                  ]
+                  ],
+                  "digest": "c210ccae40019ec3f56e031c5c08d2fe247727282f59d3d1104c795f4d02997c"
                 },
                 {
                   "start_seq": 1,
                   "end_seq": 1,
+                  "preceded_by": "f057c8c4",
                   "marks": [
                     {
                       "mark": "ur:provenance/lfaegdecgldtsrbbfgsbetckchiatnrntdrtjohpbdeteo",
@@ -1221,15 +1408,19 @@ fn test_validate_date_ordering_violation_constructed() {
                         }
                       ]
                     }
-                  ]
+                  ],
+                  "digest": "babd284b65c4e705f2de5ee480ee89cb4e2b062644305b0a875752a4674f0a10"
                 }
-              ]
+              ],
+              "digest": "8fb6fee6c27a3ee738a8575568f4a35445dd70b3a3d67ed9207c1c2ca90ea95d"
             }
-          ]
+          ],
+          "digest": "00b978f5e3f2b9d2b66a4ff8b1a39b7998375abdb2f4f8131b7aaaf31a693200"
         }"#}.trim());
 }
 
 #[test]
+#[cfg(feature = "json")]
 fn test_validate_non_genesis_at_seq_zero() {
     #[cfg(feature = "envelope")]
     provenance_mark::register_tags();
@@ -1251,9 +1442,9 @@ fn test_validate_non_genesis_at_seq_zero() {
 
     let bad_mark = ProvenanceMark::new(
         mark1.res(),
-        mark1.key().to_vec(),
-        mark1.hash().to_vec(),
-        mark1.chain_id().to_vec(),
+        mark1.key().clone(),
+        LinkKey::new(mark1.res(), mark1.hash().as_bytes()).unwrap(),
+        mark1.chain_id().clone(),
         0, // Claim seq 0 but not genesis
         date,
         None::<String>,
@@ -1275,6 +1466,7 @@ fn test_validate_non_genesis_at_seq_zero() {
           "chains": [
             {
               "chain_id": "b16a7cbd",
+              "chain_fingerprint": "262b5561638371c84e0ea776ffdb69b6f6fa5627325187f8607f6197aa114ec9",
               "has_genesis": true,
               "marks": [
                 "ur:provenance/lfaegdpaimkerydihsaedetiimmttpgdmocfdpbnhlasba",
@@ -1289,11 +1481,13 @@ fn test_validate_non_genesis_at_seq_zero() {
                       "mark": "ur:provenance/lfaegdpaimkerydihsaedetiimmttpgdmocfdpbnhlasba",
                       "issues": []
                     }
-                  ]
+                  ],
+                  "digest": "c210ccae40019ec3f56e031c5c08d2fe247727282f59d3d1104c795f4d02997c"
                 },
                 {
                   "start_seq": 0,
                   "end_seq": 0,
+                  "preceded_by": "f057c8c4",
                   "marks": [
                     {
                       "mark": "ur:provenance/lfaegdecgldtsrbbfgsbetbahhgowzrntertkopkmyiowp",
@@ -1303,15 +1497,19 @@ fn test_validate_non_genesis_at_seq_zero() {
                         }
                       ]
                     }
-                  ]
+                  ],
+                  "digest": "e3bd543a3c77f78e0614f97ca2201daaf2e33cac4cebebc65fbd777bbfd446f6"
                 }
-              ]
+              ],
+              "digest": "29154e54ce74fb7fd8d6bc9aaecddca0b70066f0b4eebac58b78a3653e253d7a"
             }
-          ]
+          ],
+          "digest": "3cf8b7ac14147a51f792ec5b70d6a8e924ef3de9279ca1e94e6289b6644c71c0"
         }"#}.trim());
 }
 
 #[test]
+#[cfg(feature = "json")]
 fn test_validate_invalid_genesis_key_constructed() {
     #[cfg(feature = "envelope")]
     provenance_mark::register_tags();
@@ -1333,9 +1531,10 @@ fn test_validate_invalid_genesis_key_constructed() {
 
     let bad_mark = ProvenanceMark::new(
         mark1.res(),
-        mark1.chain_id().to_vec(), // key == chain_id (not allowed at seq > 0)
-        mark1.hash().to_vec(),
-        mark1.chain_id().to_vec(),
+        // key == chain_id (not allowed at seq > 0)
+        mark1.chain_id().genesis_key(),
+        LinkKey::new(mark1.res(), mark1.hash().as_bytes()).unwrap(),
+        mark1.chain_id().clone(),
         1, // seq 1
         date,
         None::<String>,
@@ -1357,6 +1556,7 @@ fn test_validate_invalid_genesis_key_constructed() {
           "chains": [
             {
               "chain_id": "b16a7cbd",
+              "chain_fingerprint": "262b5561638371c84e0ea776ffdb69b6f6fa5627325187f8607f6197aa114ec9",
               "has_genesis": true,
               "marks": [
                 "ur:provenance/lfaegdpaimkerydihsaedetiimmttpgdmocfdpbnhlasba",
@@ -1369,26 +1569,2260 @@ fn test_validate_invalid_genesis_key_constructed() {
                   "marks": [
                     {
                       "mark": "ur:provenance/lfaegdpaimkerydihsaedetiimmttpgdmocfdpbnhlasba",
-                      "issues": []
+                      "issues": [
+                        {
+                          "type": "ReusedKey",
+                          "data": {
+                            "other_mark": "d1cc93f7b447cfa07a72e92573a5edd92a086ef5a721fb295c40c93697a0914e"
+                          }
+                        }
+                      ]
                     }
-                  ]
+                  ],
+                  "digest": "c210ccae40019ec3f56e031c5c08d2fe247727282f59d3d1104c795f4d02997c"
                 },
                 {
                   "start_seq": 1,
                   "end_seq": 1,
+                  "preceded_by": "f057c8c4",
                   "marks": [
                     {
                       "mark": "ur:provenance/lfaegdpaimkerydihsaedewnwnsnwmgdmucfdwcpfxdtsr",
                       "issues": [
                         {
                           "type": "InvalidGenesisKey"
+                        },
+                        {
+                          "type": "ReusedKey",
+                          "data": {
+                            "other_mark": "f057c8c49525bab54b76145f176e69cc320308db9e64f62225f8b2b9466da20f"
+                          }
                         }
                       ]
                     }
-                  ]
+                  ],
+                  "digest": "07f96431955da2141a6008ba97a321cffb44a59cb938e22245f153e57b6cdf05"
                 }
-              ]
+              ],
+              "digest": "2b01029a4e8c5818a7929468465a6ba2a6dc2fb55a2adbab44d93645a397fd33"
             }
-          ]
+          ],
+          "digest": "4ca7098d0e406f35e4caf2ff0dd171f5b53b1cccb9875e880cbc3341894639a1"
         }"#}.trim());
 }
+
+#[test]
+#[cfg(feature = "json")]
+fn test_validate_report_format_names() {
+    use ValidationReportFormat::*;
+
+    for (name, format) in [
+        ("text", Text),
+        ("TEXT", Text),
+        ("json", JsonPretty),
+        ("Json", JsonPretty),
+        ("json-pretty", JsonPretty),
+        ("json-compact", JsonCompact),
+        ("JSON-Compact", JsonCompact),
+    ] {
+        assert_eq!(name.parse::<ValidationReportFormat>().unwrap(), format);
+    }
+
+    assert_eq!(
+        ValidationReportFormat::all(),
+        &[Text, JsonCompact, JsonPretty]
+    );
+    for &format in ValidationReportFormat::all() {
+        let name = format.to_string();
+        assert_eq!(name, format.name());
+        assert_eq!(name.parse::<ValidationReportFormat>().unwrap(), format);
+    }
+
+    let error = "yaml".parse::<ValidationReportFormat>().unwrap_err();
+    assert!(matches!(
+        &error,
+        Error::UnknownReportFormat { format, valid }
+            if format == "yaml" && valid == &["text", "json-compact", "json-pretty"]
+    ));
+    assert_eq!(
+        error.to_string(),
+        r#"unknown report format "yaml": expected one of text, json-compact, json-pretty"#
+    );
+}
+
+#[test]
+#[cfg(feature = "json")]
+fn test_validate_write_matches_format() {
+    let mut marks =
+        create_test_marks(3, ProvenanceMarkResolution::Low, "alice");
+    marks.extend(create_test_marks(3, ProvenanceMarkResolution::Low, "bob"));
+    let report = ProvenanceMark::validate(marks);
+
+    let mut compact = Vec::new();
+    report.write_json(&mut compact, false).unwrap();
+    assert_eq!(
+        String::from_utf8(compact).unwrap(),
+        serde_json::to_string(&report).unwrap()
+    );
+
+    let mut pretty = Vec::new();
+    report.write_json(&mut pretty, true).unwrap();
+    assert_eq!(
+        String::from_utf8(pretty).unwrap(),
+        serde_json::to_string_pretty(&report).unwrap()
+    );
+
+    let mut text = Vec::new();
+    report.write_text(&mut text).unwrap();
+    assert_eq!(
+        String::from_utf8(text).unwrap(),
+        report.format(ValidationReportFormat::Text)
+    );
+}
+
+struct FailingWriter;
+
+impl std::io::Write for FailingWriter {
+    fn write(&mut self, _buf: &[u8]) -> std::io::Result<usize> {
+        Err(std::io::Error::other("disk full"))
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> { Ok(()) }
+}
+
+#[test]
+#[cfg(feature = "json")]
+fn test_validate_write_failing_writer() {
+    let marks = create_test_marks(3, ProvenanceMarkResolution::Low, "test");
+    let marks_no_genesis: Vec<_> = marks.into_iter().skip(1).collect();
+    let report = ProvenanceMark::validate(marks_no_genesis);
+
+    for pretty in [false, true] {
+        let err = report.write_json(FailingWriter, pretty).unwrap_err();
+        assert!(matches!(err, Error::Io(_)), "unexpected error: {err}");
+        assert!(err.to_string().contains("disk full"));
+    }
+
+    let err = report.write_text(FailingWriter).unwrap_err();
+    assert!(matches!(err, Error::Io(_)), "unexpected error: {err}");
+}
+
+#[test]
+fn test_validate_staleness() {
+    let stale_marks =
+        create_test_marks(3, ProvenanceMarkResolution::Low, "alice");
+    let mut generator = ProvenanceMarkGenerator::new_with_passphrase(
+        ProvenanceMarkResolution::Low,
+        "bob",
+    );
+    let fresh_mark =
+        generator.next(Date::from_string("2024-08-06").unwrap(), None::<&str>);
+
+    let mut marks = stale_marks.clone();
+    marks.push(fresh_mark);
+    let report = ProvenanceMark::validate(marks);
+
+    let as_of = Date::from_string("2024-08-07").unwrap();
+    let stale_chain = report
+        .chains()
+        .iter()
+        .find(|c| c.chain_id() == stale_marks[0].chain_id())
+        .unwrap();
+    // The head of the Low resolution chain is dated 2023-06-22 (midnight)
+    assert_eq!(
+        stale_chain.staleness(&as_of),
+        Some(chrono::Duration::days(412))
+    );
+
+    let stale = report.stale_chains(&as_of, chrono::Duration::days(30));
+    assert_eq!(stale.len(), 1);
+    assert_eq!(stale[0].chain_id_hex(), "a33e10de");
+
+    // A head dated after `as_of` is not stale
+    let early = Date::from_string("2023-01-01").unwrap();
+    assert_eq!(
+        stale_chain.staleness(&early),
+        Some(chrono::Duration::zero())
+    );
+
+    let options = FormatOptions::new().with_as_of(as_of);
+    let text =
+        report.format_with_options(ValidationReportFormat::Text, &options);
+    let lines: Vec<&str> = text.lines().collect();
+    assert!(lines.contains(&"Chain 1: 7a9c3f5e"), "{text}");
+    assert!(
+        lines.contains(&"Chain 2: a33e10de (stale: 412 days)"),
+        "{text}"
+    );
+
+    // Without `as_of`, no annotation is shown
+    assert!(
+        !report
+            .format(ValidationReportFormat::Text)
+            .contains("stale")
+    );
+}
+
+#[test]
+fn test_validate_marks_between() {
+    // Low marks are dated 2023-06-20, 21, and 22; High marks are at noon on
+    // the same days
+    let low = create_test_marks(3, ProvenanceMarkResolution::Low, "alice");
+    let high = create_test_marks(3, ProvenanceMarkResolution::High, "bob");
+    let mut marks = low.clone();
+    marks.extend(high.clone());
+    let report = ProvenanceMark::validate(marks);
+    let chain = |mark: &ProvenanceMark| {
+        report
+            .chains()
+            .iter()
+            .find(|chain| chain.chain_id() == mark.chain_id())
+            .unwrap()
+    };
+    let at = |day, hour, min, sec| {
+        Date::from_datetime(
+            chrono::Utc
+                .with_ymd_and_hms(2023, 6, day, hour, min, sec)
+                .single()
+                .unwrap(),
+        )
+    };
+
+    // A single instant matches the Low mark for the whole day and the High
+    // mark at exactly that instant
+    let noon = at(21, 12, 0, 0);
+    assert_eq!(chain(&low[0]).marks_between(&noon, &noon), [&low[1]]);
+    assert_eq!(chain(&high[0]).marks_between(&noon, &noon), [&high[1]]);
+
+    // A date-only mark matches an end bound later that day, but not a start
+    // bound on the following day
+    assert_eq!(
+        chain(&low[0]).marks_between(&at(19, 0, 0, 0), &at(20, 10, 0, 0)),
+        [&low[0]]
+    );
+    assert_eq!(
+        chain(&low[0]).marks_between(&at(21, 0, 0, 0), &at(21, 23, 59, 59)),
+        [&low[1]]
+    );
+
+    // Just after noon until midnight: the Low marks for both days touched,
+    // but no High mark
+    let start = at(21, 12, 0, 1);
+    let end = at(22, 0, 0, 0);
+    assert_eq!(
+        chain(&low[0]).marks_between(&start, &end),
+        [&low[1], &low[2]]
+    );
+    assert!(chain(&high[0]).marks_between(&start, &end).is_empty());
+
+    // The report aggregates across chains in chain order
+    let start = at(20, 12, 0, 0);
+    let end = at(21, 12, 0, 0);
+    let expected: Vec<&ProvenanceMark> = report
+        .chains()
+        .iter()
+        .flat_map(|chain| chain.marks_between(&start, &end))
+        .collect();
+    assert_eq!(expected.len(), 4);
+    assert_eq!(report.marks_between(&start, &end), expected);
+
+    // An empty range matches nothing
+    assert!(report.marks_between(&end, &start).is_empty());
+}
+
+#[test]
+fn test_validate_cadence() {
+    // 28 daily intervals and one 30-day silence
+    let mut generator = ProvenanceMarkGenerator::new_with_passphrase(
+        ProvenanceMarkResolution::Low,
+        "alice",
+    );
+    let start = Date::from_string("2023-06-20").unwrap().datetime();
+    let marks: Vec<_> = (0..30)
+        .map(|i| {
+            let days = if i < 20 { i } else { i + 29 };
+            let date = start + chrono::Duration::days(days);
+            generator.next(Date::from_datetime(date), None::<&str>)
+        })
+        .collect();
+    let mut bob = ProvenanceMarkGenerator::new_with_passphrase(
+        ProvenanceMarkResolution::Low,
+        "bob",
+    );
+    let single =
+        bob.next(Date::from_string("2023-06-20").unwrap(), None::<&str>);
+
+    let mut all_marks = marks.clone();
+    all_marks.push(single.clone());
+    let report = ProvenanceMark::validate(all_marks);
+    let chain = report
+        .chains()
+        .iter()
+        .find(|c| c.chain_id() == marks[0].chain_id())
+        .unwrap();
+
+    let intervals = chain.intervals();
+    assert_eq!(intervals.len(), 29);
+    assert_eq!(intervals[19], chrono::Duration::days(30));
+    let cadence = chain.cadence_summary().unwrap();
+    assert_eq!(
+        cadence,
+        CadenceSummary {
+            min: chrono::Duration::days(1),
+            median: chrono::Duration::days(1),
+            max: chrono::Duration::days(30),
+            mean: chrono::Duration::days(2),
+        }
+    );
+    assert_eq!(cadence.to_string(), "min 1d, median 1d, max 30d, mean 2d");
+
+    // A single-mark chain has no intervals
+    let single_chain = report
+        .chains()
+        .iter()
+        .find(|c| c.chain_id() == single.chain_id())
+        .unwrap();
+    assert!(single_chain.intervals().is_empty());
+    assert_eq!(single_chain.cadence_summary(), None);
+
+    let options = FormatOptions::new().with_cadence(true);
+    let text =
+        report.format_with_options(ValidationReportFormat::Text, &options);
+    let cadence_lines: Vec<&str> = text
+        .lines()
+        .filter(|line| line.contains("Cadence"))
+        .collect();
+    assert_eq!(
+        cadence_lines,
+        ["  Cadence: min 1d, median 1d, max 30d, mean 2d"],
+        "{text}"
+    );
+    assert!(
+        !report
+            .format(ValidationReportFormat::Text)
+            .contains("Cadence")
+    );
+}
+
+#[test]
+fn test_validate_cadence_same_date() {
+    let marks = long_chain(ProvenanceMarkResolution::Low, 3);
+    let report = ProvenanceMark::validate(marks);
+    let chain = &report.chains()[0];
+    assert_eq!(chain.intervals(), vec![chrono::Duration::zero(); 2]);
+    let cadence = chain.cadence_summary().unwrap();
+    assert_eq!(cadence.max, chrono::Duration::zero());
+    assert_eq!(cadence.to_string(), "min 0s, median 0s, max 0s, mean 0s");
+}
+
+fn schema_scenario_reports() -> Vec<ValidationReport> {
+    let marks = create_test_marks(5, ProvenanceMarkResolution::Low, "test");
+    let date = |day| {
+        Date::from_datetime(
+            chrono::Utc
+                .with_ymd_and_hms(2023, 6, day, 12, 0, 0)
+                .single()
+                .unwrap(),
+        )
+    };
+
+    // Hash mismatch: mark claiming to follow marks[1] with the wrong next key
+    let hash_mismatch = ProvenanceMark::new(
+        marks[1].res(),
+        marks[1].key().clone(),
+        LinkKey::new(marks[1].res(), marks[0].hash().as_bytes()).unwrap(),
+        marks[1].chain_id().clone(),
+        2,
+        date(22),
+        None::<String>,
+    )
+    .unwrap();
+
+    // Date ordering: second mark dated before the genesis
+    let mut generator = ProvenanceMarkGenerator::new_with_passphrase(
+        ProvenanceMarkResolution::Low,
+        "test",
+    );
+    let _ = generator.next(marks[0].date(), None::<String>);
+    let earlier = generator.next(date(19), None::<String>);
+
+    // Non-genesis mark at seq 0
+    let non_genesis = ProvenanceMark::new(
+        marks[1].res(),
+        marks[1].key().clone(),
+        LinkKey::new(marks[1].res(), marks[1].hash().as_bytes()).unwrap(),
+        marks[1].chain_id().clone(),
+        0,
+        date(21),
+        None::<String>,
+    )
+    .unwrap();
+
+    // Key equal to the chain ID after genesis
+    let genesis_key = ProvenanceMark::new(
+        marks[1].res(),
+        marks[1].chain_id().genesis_key(),
+        LinkKey::new(marks[1].res(), marks[1].hash().as_bytes()).unwrap(),
+        marks[1].chain_id().clone(),
+        1,
+        date(21),
+        None::<String>,
+    )
+    .unwrap();
+
+    let mut multiple_chains =
+        create_test_marks(3, ProvenanceMarkResolution::Low, "alice");
+    multiple_chains.extend(create_test_marks(
+        3,
+        ProvenanceMarkResolution::High,
+        "bob",
+    ));
+
+    let mut info_generator = ProvenanceMarkGenerator::new_with_passphrase(
+        ProvenanceMarkResolution::Quartile,
+        "info",
+    );
+    let with_info = vec![
+        info_generator.next(date(20), Some("Genesis")),
+        info_generator.next(date(21), Some("Second")),
+    ];
+
+    // Signature issues: one signed and one unsigned mark after a genesis mark
+    // declaring a key, checked with a verifier that rejects every signature
+    let mut signing_generator = ProvenanceMarkGenerator::new_with_passphrase(
+        ProvenanceMarkResolution::Quartile,
+        "signed",
+    );
+    let signed_chain = vec![
+        signing_generator
+            .genesis_with_policy(
+                date(20),
+                &GenesisPolicy::new().with_verifying_key(b"key"),
+                None::<String>,
+            )
+            .unwrap(),
+        signing_generator.next(
+            date(21),
+            Some(dcbor::CBOR::from(SignedInfo::new("Signed", b"signature"))),
+        ),
+        signing_generator.next(date(22), Some("Unsigned")),
+    ];
+
+    // Duplicate seq: a second mark at seq 2, dated differently
+    let mut fork_generator = ProvenanceMarkGenerator::new_with_passphrase(
+        ProvenanceMarkResolution::Low,
+        "test",
+    );
+    for mark in &marks[..2] {
+        fork_generator.commit_speculation(mark).unwrap();
+    }
+    let fork = fork_generator.next(date(23), None::<String>);
+
+    vec![
+        ProvenanceMark::validate(vec![]),
+        ProvenanceMark::validate(marks.clone()),
+        ProvenanceMark::validate(marks[1..].to_vec()),
+        ProvenanceMark::validate(vec![marks[0].clone(), marks[2].clone()]),
+        ProvenanceMark::validate(vec![
+            marks[0].clone(),
+            marks[1].clone(),
+            hash_mismatch.clone(),
+        ]),
+        ProvenanceMark::validate_with_policy(
+            vec![marks[0].clone(), marks[1].clone(), hash_mismatch],
+            &ValidationPolicy::new().with_include_evidence(true),
+        ),
+        ProvenanceMark::validate(vec![marks[0].clone(), earlier]),
+        ProvenanceMark::validate(vec![marks[0].clone(), non_genesis]),
+        ProvenanceMark::validate(vec![marks[0].clone(), genesis_key]),
+        ProvenanceMark::validate(vec![
+            marks[0].clone(),
+            marks[1].clone(),
+            marks[2].clone(),
+            fork,
+        ]),
+        ProvenanceMark::validate(multiple_chains),
+        ProvenanceMark::validate(with_info),
+        ProvenanceMark::validate_with_policy(
+            marks.clone(),
+            &ValidationPolicy::new().with_reference_time(date(21)),
+        ),
+        ProvenanceMark::validate_with_policy(
+            signed_chain,
+            &ValidationPolicy::new().with_signature_verifier(|_, _, _| false),
+        ),
+        ProvenanceMark::validate_opts(
+            marks.clone(),
+            &ValidationOptions::new()
+                .with_policy(
+                    ValidationPolicy::new()
+                        .with_future_tolerance(chrono::Duration::zero())
+                        .with_low_collision_threshold(2)
+                        .with_medium_collision_threshold(2),
+                )
+                .with_anchors(
+                    TrustAnchors::new().with_chain_id(marks[0].chain_id()),
+                )
+                .with_labels([(marks[0].chain_id(), "test")])
+                .with_reference_time(date(21)),
+        ),
+        ProvenanceMark::validate_opts(
+            vec![marks[0].clone(), marks[2].clone()],
+            &ValidationOptions::new().suppress(
+                SuppressionRule::new(marks[0].chain_id(), "SequenceGap")
+                    .with_seq_range(2..=2),
+            ),
+        ),
+    ]
+}
+
+#[test]
+#[cfg(feature = "json")]
+fn test_validate_json_schema() {
+    let schema = ValidationReport::json_schema();
+    let validator = jsonschema::validator_for(&schema).unwrap();
+
+    let mut issue_types = std::collections::HashSet::new();
+    for report in schema_scenario_reports() {
+        let json = report.format(ValidationReportFormat::JsonPretty);
+        let instance: serde_json::Value = serde_json::from_str(&json).unwrap();
+        if let Err(error) = validator.validate(&instance) {
+            panic!("report does not match schema: {error}\n{json}");
+        }
+        for chain in report.chains() {
+            for seq in chain.sequences() {
+                for mark in seq.marks() {
+                    for issue in mark.issues() {
+                        let value = serde_json::to_value(issue).unwrap();
+                        issue_types.insert(value["type"].to_string());
+                    }
+                }
+            }
+        }
+    }
+    // Every issue variant reachable through validation was exercised
+    assert_eq!(issue_types.len(), 10, "{issue_types:?}");
+
+    // Any change to the report shape must be reflected in the schema
+    let mut extra = serde_json::from_str::<serde_json::Value>(
+        &ProvenanceMark::validate(vec![])
+            .format(ValidationReportFormat::JsonCompact),
+    )
+    .unwrap();
+    extra["unexpected"] = serde_json::json!(true);
+    assert!(!validator.is_valid(&extra));
+}
+
+fn future_dated_chain() -> Vec<ProvenanceMark> {
+    let mut generator = ProvenanceMarkGenerator::new_with_passphrase(
+        ProvenanceMarkResolution::Medium,
+        "test",
+    );
+    let date = |year| {
+        Date::from_datetime(
+            chrono::Utc
+                .with_ymd_and_hms(year, 6, 20, 12, 0, 0)
+                .single()
+                .unwrap(),
+        )
+    };
+    vec![
+        generator.next(date(2023), None::<String>),
+        generator.next(date(2024), None::<String>),
+    ]
+}
+
+#[test]
+fn test_validate_future_dated() {
+    let marks = future_dated_chain();
+    let policy =
+        ValidationPolicy::new().with_reference_time(Date::from_datetime(
+            chrono::Utc
+                .with_ymd_and_hms(2023, 6, 20, 12, 1, 0)
+                .single()
+                .unwrap(),
+        ));
+    let report = ProvenanceMark::validate_with_policy(marks.clone(), &policy);
+
+    // Flagged, but the chain is still a single contiguous sequence
+    let chain = &report.chains()[0];
+    assert_eq!(chain.sequences().len(), 1);
+    let flagged = chain.sequences()[0].marks();
+    assert!(flagged[0].issues().is_empty());
+    assert_eq!(
+        flagged[1].issues(),
+        &[ValidationIssue::FutureDated {
+            date: marks[1].date(),
+            reference: policy.reference_time().unwrap(),
+        }]
+    );
+    assert!(report.has_issues());
+    assert!(
+        report
+            .format(ValidationReportFormat::Text)
+            .contains("(future-dated)")
+    );
+}
+
+#[test]
+fn test_validate_future_dated_within_tolerance() {
+    let marks = future_dated_chain();
+    let reference = Date::from_datetime(
+        chrono::Utc
+            .with_ymd_and_hms(2024, 6, 20, 11, 57, 0)
+            .single()
+            .unwrap(),
+    );
+
+    // Three minutes ahead is within the default five-minute tolerance
+    let policy = ValidationPolicy::new().with_reference_time(reference);
+    let report = ProvenanceMark::validate_with_policy(marks.clone(), &policy);
+    assert!(!report.has_issues());
+
+    let policy = policy.with_future_tolerance(chrono::Duration::minutes(1));
+    let report = ProvenanceMark::validate_with_policy(marks, &policy);
+    assert!(report.has_issues());
+}
+
+#[test]
+fn test_validate_without_reference_time() {
+    let report = ProvenanceMark::validate_with_policy(
+        future_dated_chain(),
+        &ValidationPolicy::default(),
+    );
+    assert!(!report.has_issues());
+    assert_eq!(report.format(ValidationReportFormat::Text), "");
+}
+
+#[test]
+#[cfg(feature = "json")]
+fn test_validate_opts() {
+    let essays = future_dated_chain();
+    let alice = create_test_marks(3, ProvenanceMarkResolution::Low, "alice");
+    let marks = [essays.clone(), alice.clone()].concat();
+    let reference = Date::from_datetime(
+        chrono::Utc
+            .with_ymd_and_hms(2023, 6, 21, 12, 0, 0)
+            .single()
+            .unwrap(),
+    );
+    let options = ValidationOptions::default()
+        .with_policy(ValidationPolicy::new().with_low_collision_threshold(2))
+        .with_anchors(TrustAnchors::new().with_chain_id(alice[0].chain_id()))
+        .with_labels([(essays[0].chain_id(), "Essays")])
+        .with_reference_time(reference);
+    let report = ProvenanceMark::validate_opts(marks, &options);
+
+    let essays_chain = report
+        .chains()
+        .iter()
+        .find(|chain| chain.chain_id() == essays[0].chain_id())
+        .unwrap();
+    let alice_chain = report
+        .chains()
+        .iter()
+        .find(|chain| chain.chain_id() == alice[0].chain_id())
+        .unwrap();
+
+    // Labels
+    assert_eq!(essays_chain.label(), Some("Essays"));
+    assert_eq!(alice_chain.label(), None);
+
+    // Trust anchors
+    assert!(alice_chain.is_anchored());
+    assert!(!essays_chain.is_anchored());
+
+    // Reference time
+    assert_eq!(
+        essays_chain.sequences()[0].marks()[1].issues(),
+        &[ValidationIssue::FutureDated { date: essays[1].date(), reference }]
+    );
+
+    // Policy
+    assert_eq!(
+        alice_chain.advisories(),
+        &[ChainAdvisory::CollisionRiskElevated {
+            resolution: ProvenanceMarkResolution::Low,
+            count: 3,
+            threshold: 2,
+        }]
+    );
+
+    let text = report.format(ValidationReportFormat::Text);
+    assert!(text.contains(&format!(
+        "{} \"Essays\"",
+        &essays_chain.chain_id_hex()[..8]
+    )));
+    assert!(
+        text.contains(&format!(
+            "{} (anchored)",
+            &alice_chain.chain_id_hex()[..8]
+        ))
+    );
+
+    // The active options are recorded in the report
+    let json: serde_json::Value = serde_json::from_str(
+        &report.format(ValidationReportFormat::JsonCompact),
+    )
+    .unwrap();
+    assert_eq!(
+        json["options"],
+        serde_json::json!({
+            "referenceTime": "2023-06-21T12:00:00Z",
+            "lowCollisionThreshold": 2,
+            "anchors": [alice_chain.chain_id_hex()],
+            "labels": { essays_chain.chain_id_hex(): "Essays" },
+        })
+    );
+}
+
+#[test]
+#[cfg(feature = "json")]
+fn test_validate_chain_aliases() {
+    let low = create_test_marks(3, ProvenanceMarkResolution::Low, "test");
+    let medium = create_test_marks(2, ProvenanceMarkResolution::Medium, "Wolf");
+    let alice = create_test_marks(3, ProvenanceMarkResolution::Low, "alice");
+    let marks = [low.clone(), medium.clone(), alice.clone()].concat();
+    let aliases =
+        ChainAlias::new().with_alias(medium[0].chain_id(), low[0].chain_id());
+    let options = ValidationOptions::new().with_aliases(aliases);
+    let report = ProvenanceMark::validate_opts(marks.clone(), &options);
+    let plain = ProvenanceMark::validate(marks);
+
+    // Chains are still validated individually
+    assert_eq!(report.chains().len(), 3);
+    assert_eq!(report.digest(), plain.digest());
+    assert!(plain.groups().is_empty());
+
+    let [group] = report.groups() else {
+        panic!("expected one group: {:?}", report.groups());
+    };
+    assert_eq!(group.canonical_chain_id(), low[0].chain_id().as_bytes());
+    assert_eq!(
+        group.chain_ids(),
+        [low[0].chain_id().to_vec(), medium[0].chain_id().to_vec()]
+    );
+    assert!(!group.contains(alice[0].chain_id()));
+
+    let json: serde_json::Value = serde_json::from_str(
+        &report.format(ValidationReportFormat::JsonCompact),
+    )
+    .unwrap();
+    assert_eq!(
+        json["groups"],
+        serde_json::json!([{
+            "canonical_chain_id": "b16a7cbd",
+            "chain_ids": ["b16a7cbd", medium[0].chain_id().to_string()],
+        }])
+    );
+    assert_eq!(
+        json["options"]["aliases"],
+        serde_json::json!({ medium[0].chain_id().to_string(): "b16a7cbd" })
+    );
+    let plain_json: serde_json::Value = serde_json::from_str(
+        &plain.format(ValidationReportFormat::JsonCompact),
+    )
+    .unwrap();
+    assert_eq!(json["chains"], plain_json["chains"]);
+    assert!(plain_json.get("groups").is_none());
+    let validator =
+        jsonschema::validator_for(&ValidationReport::json_schema()).unwrap();
+    assert!(validator.is_valid(&json));
+
+    // The alias is nested under its canonical chain in text reports, which
+    // otherwise list chains in order of chain ID
+    let text = report.format(ValidationReportFormat::Text);
+    let lines: Vec<&str> = text.lines().collect();
+    let canonical = lines
+        .iter()
+        .position(|line| {
+            line.starts_with("Chain ") && line.ends_with(": b16a7cbd")
+        })
+        .unwrap();
+    assert_eq!(
+        lines[canonical + 5],
+        "  Chain 3: 090bf2f8 (alias of b16a7cbd)",
+        "{text}"
+    );
+    assert!(lines[canonical + 6].starts_with("    0: "), "{text}");
+    assert!(lines[canonical + 7].starts_with("    1: "), "{text}");
+    let alice_header = lines
+        .iter()
+        .find(|line| line.contains(&alice[0].chain_id().to_string()[..8]))
+        .unwrap();
+    assert!(alice_header.starts_with("Chain "));
+    assert!(!alice_header.contains("alias"));
+    assert!(!plain.format(ValidationReportFormat::Text).contains("alias"));
+}
+
+#[test]
+#[cfg(feature = "json")]
+fn test_validate_expected_chains() {
+    let test = create_test_marks(3, ProvenanceMarkResolution::Low, "test");
+    let wolf = create_test_marks(2, ProvenanceMarkResolution::Medium, "Wolf");
+    let alice = create_test_marks(3, ProvenanceMarkResolution::Low, "alice");
+    let options = ValidationOptions::new().with_expected_chains(vec![
+        test[0].chain_id().to_vec(),
+        wolf[0].chain_id().to_vec(),
+    ]);
+    let report = ProvenanceMark::validate_opts(
+        [test.clone(), alice.clone()].concat(),
+        &options,
+    );
+
+    // Expected and present
+    let test_chain = report
+        .chains()
+        .iter()
+        .find(|chain| chain.chain_id() == test[0].chain_id().as_bytes())
+        .unwrap();
+    assert!(test_chain.advisories().is_empty());
+    // Expected and absent
+    assert_eq!(report.missing_chains(), [wolf[0].chain_id().to_vec()]);
+    // Present and unexpected
+    let alice_chain = report
+        .chains()
+        .iter()
+        .find(|chain| chain.chain_id() == alice[0].chain_id().as_bytes())
+        .unwrap();
+    assert_eq!(alice_chain.advisories(), [ChainAdvisory::UnexpectedChain]);
+
+    let text = report.format(ValidationReportFormat::Text);
+    assert!(
+        text.contains("\nChains: 2\nMissing chain: 090bf2f8b55be45b\n"),
+        "{text}"
+    );
+    assert!(text.contains(&format!(
+        ": {}\n  Advisory: chain is not one of the expected chains\n",
+        &alice[0].chain_id().to_string()
+    )));
+
+    let json: serde_json::Value = serde_json::from_str(
+        &report.format(ValidationReportFormat::JsonCompact),
+    )
+    .unwrap();
+    assert_eq!(
+        json["missing_chains"],
+        serde_json::json!(["090bf2f8b55be45b"])
+    );
+    assert_eq!(
+        json["options"]["expectedChains"],
+        serde_json::json!(["090bf2f8b55be45b", "b16a7cbd"])
+    );
+    let alice_json = json["chains"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|chain| chain["chain_id"] == alice_chain.chain_id_hex())
+        .unwrap();
+    assert_eq!(
+        alice_json["advisories"],
+        serde_json::json!([{ "type": "UnexpectedChain" }])
+    );
+    let validator =
+        jsonschema::validator_for(&ValidationReport::json_schema()).unwrap();
+    assert!(validator.is_valid(&json));
+
+    // Without an allowlist, every chain is expected
+    let report = ProvenanceMark::validate([test, alice].concat());
+    assert!(report.missing_chains().is_empty());
+    assert!(
+        report
+            .chains()
+            .iter()
+            .all(|chain| chain.advisories().is_empty())
+    );
+}
+
+#[test]
+#[cfg(feature = "json")]
+fn test_validate_opts_default() {
+    let marks = create_test_marks(3, ProvenanceMarkResolution::Low, "test");
+    let report =
+        ProvenanceMark::validate_opts(marks.clone(), &ValidationOptions::new());
+    assert!(report.options().is_default());
+    assert_eq!(
+        report.format(ValidationReportFormat::JsonCompact),
+        ProvenanceMark::validate(marks)
+            .format(ValidationReportFormat::JsonCompact)
+    );
+}
+
+fn long_chain(
+    resolution: ProvenanceMarkResolution,
+    count: usize,
+) -> Vec<ProvenanceMark> {
+    let mut generator =
+        ProvenanceMarkGenerator::new_with_passphrase(resolution, "test");
+    let date = Date::from_string("2023-06-20").unwrap();
+    (0..count)
+        .map(|_| generator.next(date, None::<String>))
+        .collect()
+}
+
+#[test]
+#[cfg(feature = "json")]
+fn test_validate_collision_advisory() {
+    let marks = long_chain(ProvenanceMarkResolution::Low, 10_000);
+    let report = ProvenanceMark::validate(marks.clone());
+
+    // Advisories do not make an otherwise valid chain invalid
+    assert!(!report.has_issues());
+    let advisory = ChainAdvisory::CollisionRiskElevated {
+        resolution: ProvenanceMarkResolution::Low,
+        count: 10_000,
+        threshold: ValidationPolicy::DEFAULT_LOW_COLLISION_THRESHOLD,
+    };
+    assert_eq!(report.chains()[0].advisories(), &[advisory.clone()]);
+
+    let text = report.format(ValidationReportFormat::Text);
+    assert!(text.contains(&format!("\n  Advisory: {advisory}\n")));
+    assert!(text.contains(
+        "10000 marks at low resolution exceeds the collision-safety threshold of 9300"
+    ));
+
+    let json: serde_json::Value = serde_json::from_str(
+        &report.format(ValidationReportFormat::JsonCompact),
+    )
+    .unwrap();
+    assert_eq!(
+        json["chains"][0]["advisories"],
+        serde_json::json!([{
+            "type": "CollisionRiskElevated",
+            "data": { "resolution": "low", "count": 10000, "threshold": 9300 }
+        }])
+    );
+    assert!(
+        jsonschema::validator_for(&ValidationReport::json_schema())
+            .unwrap()
+            .is_valid(&json)
+    );
+
+    // The threshold is configurable
+    let policy = ValidationPolicy::new().with_low_collision_threshold(10_000);
+    let report = ProvenanceMark::validate_with_policy(marks, &policy);
+    assert!(report.chains()[0].advisories().is_empty());
+    assert_eq!(report.format(ValidationReportFormat::Text), "");
+}
+
+#[test]
+fn test_validate_no_collision_advisory_at_high() {
+    let policy = ValidationPolicy::new()
+        .with_low_collision_threshold(0)
+        .with_medium_collision_threshold(0);
+
+    let report = ProvenanceMark::validate_with_policy(
+        long_chain(ProvenanceMarkResolution::Medium, 10),
+        &policy,
+    );
+    assert_eq!(report.chains()[0].advisories().len(), 1);
+
+    for resolution in [
+        ProvenanceMarkResolution::Quartile,
+        ProvenanceMarkResolution::High,
+    ] {
+        assert_eq!(policy.collision_threshold(resolution), None);
+        let report = ProvenanceMark::validate_with_policy(
+            long_chain(resolution, 10_000),
+            &policy,
+        );
+        assert!(report.chains()[0].advisories().is_empty());
+        assert!(!report.has_issues());
+    }
+}
+
+/// The marks of the "test" chain, plus a second mark at seq 2 issued from the
+/// same generator state with a later date.
+fn forked_test_marks() -> (Vec<ProvenanceMark>, ProvenanceMark) {
+    let marks = create_test_marks(4, ProvenanceMarkResolution::Low, "test");
+    let mut generator = ProvenanceMarkGenerator::new_with_passphrase(
+        ProvenanceMarkResolution::Low,
+        "test",
+    );
+    for mark in &marks[..2] {
+        generator.commit_speculation(mark).unwrap();
+    }
+    let fork = generator.next(marks[3].date(), None::<String>);
+    (marks, fork)
+}
+
+#[test]
+fn test_precedes_opt_sequence_issues() {
+    let (marks, fork) = forked_test_marks();
+    let issue =
+        |previous: &ProvenanceMark, next: &ProvenanceMark| match previous
+            .precedes_opt(next)
+        {
+            Err(Error::Validation(issue)) => Some(issue),
+            Err(error) => panic!("unexpected error: {error}"),
+            Ok(()) => None,
+        };
+
+    // The fork is a valid successor of seq 1
+    assert_eq!(issue(&marks[1], &fork), None);
+    assert_eq!(
+        issue(&marks[2], &fork),
+        Some(ValidationIssue::DuplicateSeq { seq: 2 })
+    );
+    assert_eq!(
+        issue(&marks[3], &marks[2]),
+        Some(ValidationIssue::SequenceRegression { previous: 3, next: 2 })
+    );
+    assert_eq!(
+        issue(&marks[0], &marks[2]),
+        Some(ValidationIssue::SequenceGap { expected: 1, actual: 2 })
+    );
+}
+
+#[test]
+fn test_precedes_opt_resolution_mismatch() {
+    let low = create_test_marks(2, ProvenanceMarkResolution::Low, "test");
+    let medium = create_test_marks(2, ProvenanceMarkResolution::Medium, "test");
+
+    let issue = match low[0].precedes_opt(&medium[1]) {
+        Err(Error::Validation(issue)) => issue,
+        result => panic!("unexpected result: {result:?}"),
+    };
+    assert_eq!(
+        issue,
+        ValidationIssue::ResolutionMismatch {
+            previous: ProvenanceMarkResolution::Low,
+            next: ProvenanceMarkResolution::Medium,
+        }
+    );
+    assert!(!low[0].precedes(&medium[1]));
+    assert!(!medium[0].precedes(&low[1]));
+
+    assert_eq!(issue.code(), "ResolutionMismatch");
+    assert_eq!(
+        issue.to_string(),
+        "resolution must match: previous is low, next is medium"
+    );
+    assert_eq!(
+        serde_json::to_value(&issue).unwrap(),
+        serde_json::json!({
+            "type": "ResolutionMismatch",
+            "data": { "previous": "low", "next": "medium" }
+        })
+    );
+}
+
+#[test]
+fn test_precedes_opt_chain_mismatch() {
+    let alice = create_test_marks(3, ProvenanceMarkResolution::Low, "alice");
+    let bob = create_test_marks(3, ProvenanceMarkResolution::Low, "bob");
+
+    // The marks would otherwise fail on the hash
+    let issue = match alice[0].precedes_opt(&bob[1]) {
+        Err(Error::Validation(issue)) => issue,
+        result => panic!("unexpected result: {result:?}"),
+    };
+    assert_eq!(
+        issue,
+        ValidationIssue::ChainMismatch {
+            previous_chain: "a33e10de".to_string(),
+            next_chain: "7a9c3f5e".to_string(),
+        }
+    );
+    assert!(!alice[1].precedes(&bob[2]));
+    assert!(alice[1].precedes(&alice[2]));
+
+    assert_eq!(issue.code(), "ChainMismatch");
+    assert_eq!(
+        issue.to_string(),
+        "chain must match: previous is a33e10de, next is 7a9c3f5e"
+    );
+    #[cfg(feature = "json")]
+    assert_eq!(
+        serde_json::to_value(&issue).unwrap(),
+        serde_json::json!({
+            "type": "ChainMismatch",
+            "data": { "previous_chain": "a33e10de", "next_chain": "7a9c3f5e" }
+        })
+    );
+
+    // Validation never compares marks across chains
+    let report = ProvenanceMark::validate([alice, bob].concat());
+    assert!(report.chains().iter().all(|chain| {
+        chain.sequences().len() == 1 && chain.sequences()[0].is_clean()
+    }));
+}
+
+#[test]
+fn test_validate_duplicate_seq() {
+    let (mut marks, fork) = forked_test_marks();
+    marks.push(fork.clone());
+    let report = ProvenanceMark::validate(marks.clone());
+
+    let sequences = report.chains()[0].sequences();
+    assert_eq!(sequences.len(), 2);
+    assert_eq!((sequences[0].start_seq(), sequences[0].end_seq()), (0, 2));
+    assert_eq!((sequences[1].start_seq(), sequences[1].end_seq()), (2, 3));
+    let flagged = &sequences[1].marks()[0];
+    assert_eq!(flagged.mark(), &fork);
+    // Both marks at seq 2 were issued from the same generator state
+    assert_eq!(
+        flagged.issues(),
+        &[
+            ValidationIssue::DuplicateSeq { seq: 2 },
+            ValidationIssue::ReusedKey { other_mark: marks[2].id_hex() },
+        ]
+    );
+
+    let text = report.format(ValidationReportFormat::Text);
+    assert!(text.contains(&format!(
+        "2: {} (duplicate seq, reused key, after {})",
+        &fork.id_hex()[..8],
+        &marks[2].id_hex()[..8]
+    )));
+}
+
+#[test]
+#[cfg(feature = "json")]
+fn test_validate_canonicalize_shuffled() {
+    let test_marks =
+        create_test_marks(5, ProvenanceMarkResolution::Low, "test");
+    let alice_marks =
+        create_test_marks(3, ProvenanceMarkResolution::Low, "alice");
+    let mut marks: Vec<ProvenanceMark> = test_marks
+        .iter()
+        .enumerate()
+        .filter(|(i, _)| *i != 2)
+        .map(|(_, mark)| mark.clone())
+        .chain(alice_marks)
+        .collect();
+    marks.push(test_marks[0].clone());
+
+    let mut reversed = marks.clone();
+    reversed.reverse();
+    let mut rotated = marks.clone();
+    rotated.rotate_left(3);
+    let orders = [marks, reversed, rotated];
+
+    let reports: Vec<ValidationReport> = orders
+        .iter()
+        .map(|order| ProvenanceMark::validate(order.clone()))
+        .collect();
+    // Without canonicalization the top-level marks follow input order.
+    assert_ne!(
+        reports[0].format(ValidationReportFormat::JsonCompact),
+        reports[1].format(ValidationReportFormat::JsonCompact)
+    );
+
+    let canonical: Vec<String> = reports
+        .into_iter()
+        .map(|report| {
+            assert!(report.is_order_invariant());
+            report
+                .canonicalize()
+                .format(ValidationReportFormat::JsonCompact)
+        })
+        .collect();
+    assert_eq!(canonical[0], canonical[1]);
+    assert_eq!(canonical[0], canonical[2]);
+}
+
+#[test]
+fn test_validate_duplicate_seq_not_order_invariant() {
+    let (mut marks, fork) = forked_test_marks();
+    marks.push(fork);
+    assert!(!ProvenanceMark::validate(marks).is_order_invariant());
+}
+
+#[test]
+#[cfg(feature = "json")]
+fn test_validate_digests() {
+    let mut marks = create_test_marks(5, ProvenanceMarkResolution::Low, "test");
+    marks.remove(2);
+    let report = ProvenanceMark::validate(marks.clone());
+    let again = ProvenanceMark::validate(marks.clone());
+    assert_eq!(report.digest(), again.digest());
+    assert_eq!(
+        report.chains()[0].sequences()[1].digest(),
+        again.chains()[0].sequences()[1].digest()
+    );
+    assert_ne!(
+        report.chains()[0].sequences()[0].digest(),
+        report.chains()[0].sequences()[1].digest()
+    );
+
+    let json = report.format(ValidationReportFormat::JsonPretty);
+    assert_eq!(
+        ValidationReport::recompute_digests(&json).unwrap(),
+        report.digest()
+    );
+
+    // Substitute a mark from another chain for one of the stored marks.
+    let other = create_test_marks(1, ProvenanceMarkResolution::Low, "alice");
+    let edited = json.replace(&marks[3].ur_string(), &other[0].ur_string());
+    let error = ValidationReport::recompute_digests(&edited).unwrap_err();
+    assert!(matches!(error, Error::DigestMismatch { .. }));
+    assert_eq!(
+        error.to_string(),
+        "digest mismatch at chains[0].sequences[1]"
+    );
+
+    // An edited digest is also detected.
+    let digest = hex::encode(report.digest());
+    let edited = json.replace(&digest, &digest.replace('a', "b"));
+    assert_eq!(
+        ValidationReport::recompute_digests(&edited)
+            .unwrap_err()
+            .to_string(),
+        "digest mismatch at report"
+    );
+}
+
+/// The lines listing the marks of the chain with the given short ID.
+fn chain_mark_lines<'a>(text: &'a str, short_chain_id: &str) -> Vec<&'a str> {
+    text.split("\n\n")
+        .find(|section| section.contains(&format!(": {short_chain_id}")))
+        .unwrap()
+        .lines()
+        .filter(|line| line.starts_with("  "))
+        .collect()
+}
+
+#[test]
+#[cfg(feature = "json")]
+fn test_validate_collapse_clean_runs() {
+    let marks = create_test_marks(50, ProvenanceMarkResolution::Low, "test");
+    let mut all_marks = marks.clone();
+    all_marks.extend(create_test_marks(
+        1,
+        ProvenanceMarkResolution::Low,
+        "alice",
+    ));
+    let report = ProvenanceMark::validate(all_marks);
+    let id = |seq: usize| marks[seq].id_hex()[..8].to_string();
+
+    let options = FormatOptions::new().with_collapse_clean_runs(true);
+    let text =
+        report.format_with_options(ValidationReportFormat::Text, &options);
+    assert_eq!(
+        chain_mark_lines(&text, "b16a7cbd"),
+        [
+            format!("  0: {} (genesis mark)", id(0)),
+            "  1–48: 48 verified marks".to_string(),
+            format!("  49: {}", id(49)),
+        ],
+        "{text}"
+    );
+
+    // Without collapsing, every mark is listed.
+    let text = report.format(ValidationReportFormat::Text);
+    assert_eq!(chain_mark_lines(&text, "b16a7cbd").len(), 50);
+
+    // JSON output is unaffected.
+    assert_eq!(
+        report
+            .format_with_options(ValidationReportFormat::JsonCompact, &options),
+        report.format(ValidationReportFormat::JsonCompact)
+    );
+}
+
+#[test]
+fn test_validate_collapse_around_issue() {
+    let mut marks =
+        create_test_marks(50, ProvenanceMarkResolution::Low, "test");
+    let id = |seq: usize| marks[seq].id_hex()[..8].to_string();
+    let expected = [
+        format!("  0: {} (genesis mark)", id(0)),
+        "  1–23: 23 verified marks".to_string(),
+        format!("  24: {}", id(24)),
+        format!("  26: {} (gap: 25 missing, after {})", id(26), id(24)),
+        "  27–48: 22 verified marks".to_string(),
+        format!("  49: {}", id(49)),
+    ];
+    marks.remove(25);
+    let report = ProvenanceMark::validate(marks);
+
+    let options = FormatOptions::new().with_collapse_clean_runs(true);
+    let text =
+        report.format_with_options(ValidationReportFormat::Text, &options);
+    assert_eq!(chain_mark_lines(&text, "b16a7cbd"), expected, "{text}");
+
+    let options = options.with_max_marks_per_chain(2);
+    let text =
+        report.format_with_options(ValidationReportFormat::Text, &options);
+    assert_eq!(
+        chain_mark_lines(&text, "b16a7cbd"),
+        [
+            expected[0].as_str(),
+            expected[1].as_str(),
+            "  ... 25 more marks"
+        ],
+        "{text}"
+    );
+}
+
+#[test]
+fn test_validate_aligned_text() {
+    let mut marks =
+        create_test_marks(12, ProvenanceMarkResolution::Low, "test");
+    let id = |seq: usize| marks[seq].id_hex()[..8].to_string();
+    let expected: Vec<String> = (0..12)
+        .filter(|seq| *seq != 5)
+        .map(|seq| match seq {
+            0 => format!(" 0: {} (genesis mark)", id(0)),
+            6 => format!(" 6: {} (gap: 5 missing, after {})", id(6), id(4)),
+            seq => format!("{:>2}: {}", seq, id(seq)),
+        })
+        .map(|line| format!("  {line}"))
+        .collect();
+    let collapsed = [
+        format!("     0: {} (genesis mark)", id(0)),
+        "   1–3: 3 verified marks".to_string(),
+        format!("     4: {}", id(4)),
+        format!("     6: {} (gap: 5 missing, after {})", id(6), id(4)),
+        "  7–10: 4 verified marks".to_string(),
+        format!("    11: {}", id(11)),
+    ];
+    marks.remove(5);
+    let report = ProvenanceMark::validate(marks);
+
+    let options = FormatOptions::new().with_aligned(true);
+    let text =
+        report.format_with_options(ValidationReportFormat::Text, &options);
+    assert_eq!(chain_mark_lines(&text, "b16a7cbd"), expected, "{text}");
+
+    // Collapsed runs widen the column
+    let options = options.with_collapse_clean_runs(true);
+    let text =
+        report.format_with_options(ValidationReportFormat::Text, &options);
+    assert_eq!(chain_mark_lines(&text, "b16a7cbd"), collapsed, "{text}");
+
+    // Alignment is off by default
+    let text = report.format(ValidationReportFormat::Text);
+    let lines = chain_mark_lines(&text, "b16a7cbd");
+    assert_eq!(lines[0], format!("  0: {} (genesis mark)", id(0)));
+    assert_eq!(lines[10], format!("  11: {}", id(11)));
+}
+
+#[test]
+fn test_validate_color_text() {
+    let mut marks =
+        create_test_marks(12, ProvenanceMarkResolution::Low, "test");
+    marks.remove(5);
+    let policy = ValidationPolicy::default().with_low_collision_threshold(5);
+    let report = ProvenanceMark::validate_with_policy(marks, &policy);
+    let advisory = report.chains()[0].advisories()[0].to_string();
+
+    let plain = report.format(ValidationReportFormat::Text);
+    assert!(!plain.contains('\x1b'));
+    let options = FormatOptions::new().with_color(true);
+    let text =
+        report.format_with_options(ValidationReportFormat::Text, &options);
+    assert!(text.contains("(\x1b[32mgenesis mark\x1b[0m)"), "{text}");
+    assert!(text.contains("(\x1b[31mgap: 5 missing\x1b[0m, after "));
+    assert!(
+        text.contains(&format!("Advisory: \x1b[33m{advisory}\x1b[0m\n")),
+        "{text}"
+    );
+    assert_eq!(text.matches('\x1b').count(), 6);
+
+    // The escape codes are the only difference
+    let stripped = ["\x1b[31m", "\x1b[32m", "\x1b[33m", "\x1b[0m"]
+        .iter()
+        .fold(text, |text, code| text.replace(code, ""));
+    assert_eq!(stripped, plain);
+}
+
+#[test]
+#[cfg(feature = "json")]
+fn test_validate_inline_mark_details() {
+    let marks = create_test_marks(5, ProvenanceMarkResolution::Low, "test");
+    let marks_with_gap = vec![
+        marks[0].clone(),
+        marks[1].clone(),
+        marks[3].clone(),
+        marks[4].clone(),
+    ];
+    let report = ProvenanceMark::validate(marks_with_gap);
+
+    let options = FormatOptions::new().with_inline_mark_details(true);
+    let json = report
+        .format_with_options(ValidationReportFormat::JsonPretty, &options);
+    // expected-text-output-rubric:
+    #[rustfmt::skip]
+    assert_actual_expected!(json, indoc! {r#"
+        {
+          "marks": [
+            {
+              "ur": "ur:provenance/lfaegdpaimkerydihsaedetiimmttpgdmocfdpbnhlasba",
+              "seq": 0,
+              "date": "2023-06-20",
+              "chain_id": "b16a7cbd",
+              "identifier": "WHAT HANG SOAP SETS",
+              "is_genesis": true,
+              "has_info": false
+            },
+            {
+              "ur": "ur:provenance/lfaegdecgldtsrbbfgsbetgazoenadrntdrtkoluwekerp",
+              "seq": 1,
+              "date": "2023-06-21",
+              "chain_id": "b16a7cbd",
+              "identifier": "CLAW LAVA JOIN JAZZ",
+              "is_genesis": false,
+              "has_info": false
+            },
+            {
+              "ur": "ur:provenance/lfaegdhsvtleetlatsmwwdndmnjlaxonsfdewmghpybzbg",
+              "seq": 3,
+              "date": "2023-06-23",
+              "chain_id": "b16a7cbd",
+              "identifier": "KENO CITY HOLY JURY",
+              "is_genesis": false,
+              "has_info": false
+            },
+            {
+              "ur": "ur:provenance/lfaegdrkkilkylsrendmkniaeejyrhndlyvednzckpsbtk",
+              "seq": 4,
+              "date": "2023-06-24",
+              "chain_id": "b16a7cbd",
+              "identifier": "FLEW TENT DROP VIEW",
+              "is_genesis": false,
+              "has_info": false
+            }
+          ],
+          "chains": [
+            {
+              "chain_id": "b16a7cbd",
+              "chain_fingerprint": "262b5561638371c84e0ea776ffdb69b6f6fa5627325187f8607f6197aa114ec9",
+              "has_genesis": true,
+              "marks": [
+                {
+                  "ur": "ur:provenance/lfaegdpaimkerydihsaedetiimmttpgdmocfdpbnhlasba",
+                  "seq": 0,
+                  "date": "2023-06-20",
+                  "chain_id": "b16a7cbd",
+                  "identifier": "WHAT HANG SOAP SETS",
+                  "is_genesis": true,
+                  "has_info": false
+                },
+                {
+                  "ur": "ur:provenance/lfaegdecgldtsrbbfgsbetgazoenadrntdrtkoluwekerp",
+                  "seq": 1,
+                  "date": "2023-06-21",
+                  "chain_id": "b16a7cbd",
+                  "identifier": "CLAW LAVA JOIN JAZZ",
+                  "is_genesis": false,
+                  "has_info": false
+                },
+                {
+                  "ur": "ur:provenance/lfaegdhsvtleetlatsmwwdndmnjlaxonsfdewmghpybzbg",
+                  "seq": 3,
+                  "date": "2023-06-23",
+                  "chain_id": "b16a7cbd",
+                  "identifier": "KENO CITY HOLY JURY",
+                  "is_genesis": false,
+                  "has_info": false
+                },
+                {
+                  "ur": "ur:provenance/lfaegdrkkilkylsrendmkniaeejyrhndlyvednzckpsbtk",
+                  "seq": 4,
+                  "date": "2023-06-24",
+                  "chain_id": "b16a7cbd",
+                  "identifier": "FLEW TENT DROP VIEW",
+                  "is_genesis": false,
+                  "has_info": false
+                }
+              ],
+              "sequences": [
+                {
+                  "start_seq": 0,
+                  "end_seq": 1,
+                  "marks": [
+                    {
+                      "mark": {
+                        "ur": "ur:provenance/lfaegdpaimkerydihsaedetiimmttpgdmocfdpbnhlasba",
+                        "seq": 0,
+                        "date": "2023-06-20",
+                        "chain_id": "b16a7cbd",
+                        "identifier": "WHAT HANG SOAP SETS",
+                        "is_genesis": true,
+                        "has_info": false
+                      },
+                      "issues": []
+                    },
+                    {
+                      "mark": {
+                        "ur": "ur:provenance/lfaegdecgldtsrbbfgsbetgazoenadrntdrtkoluwekerp",
+                        "seq": 1,
+                        "date": "2023-06-21",
+                        "chain_id": "b16a7cbd",
+                        "identifier": "CLAW LAVA JOIN JAZZ",
+                        "is_genesis": false,
+                        "has_info": false
+                      },
+                      "issues": []
+                    }
+                  ],
+                  "digest": "bb81f438822e030be7f41422e3e3b0f6dda3c948390c3064b91b3fd81ab4dcca"
+                },
+                {
+                  "start_seq": 3,
+                  "end_seq": 4,
+                  "preceded_by": "1b806d6c",
+                  "marks": [
+                    {
+                      "mark": {
+                        "ur": "ur:provenance/lfaegdhsvtleetlatsmwwdndmnjlaxonsfdewmghpybzbg",
+                        "seq": 3,
+                        "date": "2023-06-23",
+                        "chain_id": "b16a7cbd",
+                        "identifier": "KENO CITY HOLY JURY",
+                        "is_genesis": false,
+                        "has_info": false
+                      },
+                      "issues": [
+                        {
+                          "type": "SequenceGap",
+                          "data": {
+                            "expected": 2,
+                            "actual": 3
+                          }
+                        }
+                      ]
+                    },
+                    {
+                      "mark": {
+                        "ur": "ur:provenance/lfaegdrkkilkylsrendmkniaeejyrhndlyvednzckpsbtk",
+                        "seq": 4,
+                        "date": "2023-06-24",
+                        "chain_id": "b16a7cbd",
+                        "identifier": "FLEW TENT DROP VIEW",
+                        "is_genesis": false,
+                        "has_info": false
+                      },
+                      "issues": []
+                    }
+                  ],
+                  "digest": "b2aae9b352b64f8be12d243391c4397e89a0c4c5efe6ab8dae239bc52709db7e"
+                }
+              ],
+              "digest": "46ccad9f580844a7f96933a8d2e1d096b1c85bfeca70cd4275a9f6840d1dbaf1"
+            }
+          ],
+          "digest": "f4aeb96f2f7198804dfbcaac761d3c7f86855044c2305979edeee5cb87f00f72"
+        }"#}.trim());
+
+    let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+    let validator =
+        jsonschema::validator_for(&ValidationReport::json_schema()).unwrap();
+    assert!(validator.is_valid(&value));
+
+    // The default form is unchanged.
+    assert_eq!(
+        report.format_with_options(
+            ValidationReportFormat::JsonCompact,
+            &FormatOptions::new()
+        ),
+        report.format(ValidationReportFormat::JsonCompact)
+    );
+    assert!(
+        !report
+            .format(ValidationReportFormat::JsonCompact)
+            .contains("identifier")
+    );
+}
+
+#[test]
+#[cfg(feature = "json")]
+fn test_validate_inline_mark_details_date_precision() {
+    let options = FormatOptions::new().with_inline_mark_details(true);
+    for (res, expected) in [
+        (ProvenanceMarkResolution::Low, "2023-06-20"),
+        (ProvenanceMarkResolution::Medium, "2023-06-20T12:00:00Z"),
+        (
+            ProvenanceMarkResolution::Quartile,
+            "2023-06-20T12:00:00.000Z",
+        ),
+        (ProvenanceMarkResolution::High, "2023-06-20T12:00:00.000Z"),
+    ] {
+        let report =
+            ProvenanceMark::validate(create_test_marks(1, res, "test"));
+        let json: serde_json::Value =
+            serde_json::from_str(&report.format_with_options(
+                ValidationReportFormat::JsonCompact,
+                &options,
+            ))
+            .unwrap();
+        assert_eq!(json["marks"][0]["date"], expected, "{res:?}");
+    }
+}
+
+#[test]
+#[cfg(feature = "json")]
+fn test_validate_inline_mark_details_with_health() {
+    let marks = create_test_marks(3, ProvenanceMarkResolution::Low, "test");
+    let report = ProvenanceMark::validate(marks.clone());
+
+    let options = FormatOptions::new()
+        .with_inline_mark_details(true)
+        .with_health(true);
+    let json: serde_json::Value = serde_json::from_str(
+        &report
+            .format_with_options(ValidationReportFormat::JsonCompact, &options),
+    )
+    .unwrap();
+    let chain = &json["chains"][0];
+    assert_eq!(chain["marks"][1]["ur"], marks[1].ur_string());
+    assert_eq!(chain["marks"][1]["seq"], 1);
+    assert_eq!(
+        chain["sequences"][0]["marks"][2]["mark"]["ur"],
+        marks[2].ur_string()
+    );
+    assert!(chain["health"].is_object());
+
+    // The option is not carried into other serializations of the report
+    let plain = serde_json::to_value(&report).unwrap();
+    assert_eq!(plain["marks"][0], marks[0].ur_string());
+}
+
+#[test]
+#[cfg(feature = "json")]
+fn test_validate_inline_mark_details_leaves_other_strings() {
+    let marks = create_test_marks(2, ProvenanceMarkResolution::Low, "test");
+    // A label that happens to be the UR of one of the marks
+    let validation = ValidationOptions::new()
+        .with_labels([(marks[0].chain_id(), marks[0].ur_string())]);
+    let report = ProvenanceMark::validate_opts(marks.clone(), &validation);
+
+    let options = FormatOptions::new().with_inline_mark_details(true);
+    let json: serde_json::Value = serde_json::from_str(
+        &report
+            .format_with_options(ValidationReportFormat::JsonCompact, &options),
+    )
+    .unwrap();
+    let chain = &json["chains"][0];
+    assert_eq!(chain["label"], marks[0].ur_string());
+    assert_eq!(chain["marks"][0]["ur"], marks[0].ur_string());
+}
+
+#[test]
+#[cfg(feature = "json")]
+fn test_validate_reused_key() {
+    let alice = create_test_marks(2, ProvenanceMarkResolution::Low, "alice");
+    let bob = create_test_marks(1, ProvenanceMarkResolution::Low, "bob");
+
+    // A mark on bob's chain carrying the key of alice's second mark
+    let reused = ProvenanceMark::new(
+        bob[0].res(),
+        alice[1].key().clone(),
+        LinkKey::new(bob[0].res(), bob[0].hash().as_bytes()).unwrap(),
+        bob[0].chain_id().clone(),
+        1,
+        alice[1].date(),
+        None::<String>,
+    )
+    .unwrap();
+
+    let mut marks = alice.clone();
+    marks.extend(bob.clone());
+    marks.push(reused.clone());
+    let report = ProvenanceMark::validate(marks);
+
+    let issues = |mark: &ProvenanceMark| {
+        report
+            .chains()
+            .iter()
+            .flat_map(|chain| chain.sequences())
+            .flat_map(|seq| seq.marks())
+            .find(|flagged| flagged.mark() == mark)
+            .unwrap()
+            .issues()
+            .to_vec()
+    };
+    assert_eq!(
+        issues(&alice[1]),
+        [ValidationIssue::ReusedKey { other_mark: reused.id_hex() }]
+    );
+    assert!(issues(&reused).contains(&ValidationIssue::ReusedKey {
+        other_mark: alice[1].id_hex()
+    }));
+    assert!(issues(&alice[0]).is_empty());
+    assert!(issues(&bob[0]).is_empty());
+
+    // Reuse across chains is also noted for the report as a whole
+    let note = ReportNote::CrossChainKeyReuse {
+        key: alice[1].key().to_vec(),
+        chain_ids: vec![
+            bob[0].chain_id().to_vec(),
+            alice[0].chain_id().to_vec(),
+        ],
+    };
+    assert_eq!(report.notes(), &[note.clone()]);
+    assert_eq!(
+        note.to_string(),
+        format!(
+            "key {} is reused across chains 7a9c3f5e, a33e10de",
+            hex::encode(alice[1].key())
+        )
+    );
+
+    let text = report.format(ValidationReportFormat::Text);
+    assert!(text.contains(&format!("\nNote: {note}\n")), "{text}");
+    assert!(
+        text.contains(&format!("1: {} (reused key)", &alice[1].id_hex()[..8])),
+        "{text}"
+    );
+
+    let json: serde_json::Value = serde_json::from_str(
+        &report.format(ValidationReportFormat::JsonCompact),
+    )
+    .unwrap();
+    assert_eq!(json["notes"][0]["type"], "CrossChainKeyReuse");
+    assert!(
+        jsonschema::validator_for(&ValidationReport::json_schema())
+            .unwrap()
+            .is_valid(&json)
+    );
+}
+
+#[test]
+#[cfg(feature = "json")]
+fn test_validate_no_reused_keys() {
+    let mut marks = create_test_marks(5, ProvenanceMarkResolution::Low, "test");
+    marks.extend(create_test_marks(3, ProvenanceMarkResolution::Low, "alice"));
+    marks.extend(create_test_marks(3, ProvenanceMarkResolution::High, "bob"));
+    let report = ProvenanceMark::validate(marks);
+
+    assert!(report.notes().is_empty());
+    for chain in report.chains() {
+        for seq in chain.sequences() {
+            for mark in seq.marks() {
+                assert!(mark.issues().is_empty(), "{:?}", mark.issues());
+            }
+        }
+    }
+    assert!(
+        !report
+            .format(ValidationReportFormat::JsonCompact)
+            .contains("notes")
+    );
+}
+
+#[test]
+fn test_validate_shares_marks() {
+    let mut marks = create_test_marks(4, ProvenanceMarkResolution::Low, "test");
+    marks.extend(create_test_marks(3, ProvenanceMarkResolution::Low, "alice"));
+    marks.push(marks[1].clone());
+    let report = ProvenanceMark::validate(marks);
+
+    // Each distinct mark is stored once and shared by every part of the report
+    let shared = |mark: &Arc<ProvenanceMark>| {
+        report
+            .marks()
+            .iter()
+            .filter(|other| Arc::ptr_eq(other, mark))
+            .count()
+            == 1
+    };
+    assert_eq!(report.marks().len(), 7);
+    assert!(report.duplicate_marks().iter().all(shared));
+    for chain in report.chains() {
+        assert!(chain.marks().iter().all(shared));
+        let flagged: Vec<_> = chain
+            .sequences()
+            .iter()
+            .flat_map(|seq| seq.marks())
+            .map(FlaggedMark::shared_mark)
+            .collect();
+        assert_eq!(flagged.len(), chain.marks().len());
+        for (flagged, mark) in flagged.into_iter().zip(chain.marks()) {
+            assert!(Arc::ptr_eq(flagged, mark));
+        }
+    }
+
+    let first = &report.marks()[0];
+    let indexed = report.fingerprint_index().get(&first.fingerprint());
+    assert!(indexed.is_some_and(|mark| std::ptr::eq(mark, Arc::as_ref(first))));
+}
+
+#[test]
+#[cfg(feature = "json")]
+fn test_validate_suppression() {
+    let marks = create_test_marks(5, ProvenanceMarkResolution::Low, "test");
+    let gapped = vec![
+        marks[0].clone(),
+        marks[1].clone(),
+        marks[3].clone(),
+        marks[4].clone(),
+    ];
+    assert!(ProvenanceMark::validate(gapped.clone()).has_issues());
+
+    // The documented gap before seq 3, loaded from JSON
+    let rule: SuppressionRule = serde_json::from_str(
+        r#"{"chainIdPrefix": "b16a", "code": "SequenceGap",
+            "seqRange": {"start": 3, "end": 3}}"#,
+    )
+    .unwrap();
+    assert_eq!(
+        rule,
+        SuppressionRule::new([0xb1, 0x6a], "SequenceGap").with_seq_range(3..=3)
+    );
+
+    let options = ValidationOptions::new().suppress(rule);
+    let report = ProvenanceMark::validate_opts(gapped.clone(), &options);
+    assert!(!report.has_issues());
+    assert_eq!(report.chains()[0].sequences().len(), 2);
+    assert!(report.chains()[0].sequences()[1].is_clean());
+    assert_eq!(report.suppressed().len(), 1);
+    assert_eq!(report.suppressed()[0].mark(), &marks[3]);
+    assert_eq!(
+        report.suppressed()[0].issue(),
+        &ValidationIssue::SequenceGap { expected: 2, actual: 3 }
+    );
+
+    let json: serde_json::Value = serde_json::from_str(
+        &report.format(ValidationReportFormat::JsonCompact),
+    )
+    .unwrap();
+    assert_eq!(
+        json["suppressed"],
+        serde_json::json!([{
+            "mark": marks[3].ur_string(),
+            "issue": {
+                "type": "SequenceGap",
+                "data": { "expected": 2, "actual": 3 }
+            }
+        }])
+    );
+    assert_eq!(
+        json["options"]["suppressions"],
+        serde_json::json!([{
+            "chainIdPrefix": "b16a",
+            "code": "SequenceGap",
+            "seqRange": { "start": 3, "end": 3 }
+        }])
+    );
+
+    // expected-text-output-rubric:
+    #[rustfmt::skip]
+    assert_actual_expected!(report.format(ValidationReportFormat::Text), indoc! {r#"
+        Total marks: 4
+        Chains: 1
+
+        Chain 1: b16a7cbd
+          0: f057c8c4 (genesis mark)
+          1: 1b806d6c
+          3: 761a5e74 (after 1b806d6c)
+          4: 42d12de5
+
+        1 finding suppressed
+    "#}.trim());
+
+    // Rules for other sequence numbers, chains, or issues do not match
+    for rule in [
+        SuppressionRule::new([0xb1, 0x6a], "SequenceGap").with_seq_range(4..=9),
+        SuppressionRule::new([0xa3, 0x3e], "SequenceGap"),
+        SuppressionRule::new([0xb1, 0x6a], "HashMismatch"),
+    ] {
+        let options = ValidationOptions::new().suppress(rule);
+        let report = ProvenanceMark::validate_opts(gapped.clone(), &options);
+        assert!(report.has_issues());
+        assert!(report.suppressed().is_empty());
+    }
+}
+
+#[test]
+fn test_validate_display_id_disambiguates() {
+    let date = Date::from_string("2023-06-20").unwrap();
+    let genesis = |res: ProvenanceMarkResolution, chain_id: &[u8]| {
+        let chain_id = ChainId::new(res, chain_id).unwrap();
+        let next_key =
+            LinkKey::new(res, vec![0xff; res.link_length()]).unwrap();
+        ProvenanceMark::new(
+            res,
+            chain_id.genesis_key(),
+            next_key,
+            chain_id,
+            0,
+            date,
+            None::<&str>,
+        )
+        .unwrap()
+    };
+    let medium = ProvenanceMarkResolution::Medium;
+    let marks = vec![
+        genesis(medium, &[1, 2, 3, 4, 5, 6, 7, 8]),
+        genesis(medium, &[1, 2, 3, 4, 5, 9, 9, 9]),
+        genesis(ProvenanceMarkResolution::Low, &[1, 2, 3, 4]),
+        genesis(medium, &[9, 2, 3, 4, 5, 6, 7, 8]),
+    ];
+    let report = ProvenanceMark::validate(marks);
+
+    let display_ids: Vec<_> = report
+        .chains()
+        .iter()
+        .map(|chain| chain.display_id(&report))
+        .collect();
+    // A chain whose whole ID is shared is shown in full, chains sharing a
+    // prefix are extended until they differ, and the rest keep 4 bytes.
+    assert_eq!(
+        display_ids,
+        ["01020304", "010203040506", "010203040509", "09020304"]
+    );
+
+    let text = report.format(ValidationReportFormat::Text);
+    let headers: Vec<_> = text
+        .lines()
+        .filter(|line| line.starts_with("Chain "))
+        .collect();
+    assert_eq!(
+        headers,
+        [
+            "Chain 1: 01020304",
+            "Chain 2: 010203040506",
+            "Chain 3: 010203040509",
+            "Chain 4: 09020304",
+        ]
+    );
+
+    // JSON keeps the full chain IDs.
+    let json = serde_json::to_value(&report).unwrap();
+    assert_eq!(json["chains"][1]["chain_id"], "0102030405060708");
+}
+
+#[test]
+fn test_validate_mark_after_termination() {
+    let res = ProvenanceMarkResolution::Low;
+    let date =
+        |day: u32| Date::from_string(&format!("2023-06-{day:02}")).unwrap();
+    let mut generator =
+        ProvenanceMarkGenerator::new_with_passphrase(res, "test");
+    let mut marks = vec![
+        generator.next(date(20), None::<&str>),
+        generator.next(date(21), Some("Chapter 1")),
+    ];
+    marks.push(generator.terminate(date(22), None::<&str>).unwrap());
+
+    // A forger holding the seed replays the chain without terminating it.
+    // Keys do not depend on info, so its mark at seq 3 links to the
+    // termination mark.
+    let mut forger = ProvenanceMarkGenerator::new_with_passphrase(res, "test");
+    for day in 20..=22 {
+        forger.next(date(day), None::<&str>);
+    }
+    let forged = forger.next(date(23), Some("Chapter 2"));
+    assert!(marks[2].precedes(&forged));
+    marks.push(forged);
+
+    let report = ProvenanceMark::validate(marks);
+    let chain = &report.chains()[0];
+    assert_eq!(chain.sequences().len(), 1);
+    let flagged = &chain.sequences()[0].marks()[3];
+    assert_eq!(
+        flagged.issues(),
+        &[ValidationIssue::MarkAfterTermination { termination: 2 }]
+    );
+    assert!(report.has_issues());
+    assert_eq!(
+        flagged.issues()[0].to_string(),
+        "mark follows the chain's termination mark at sequence 2"
+    );
+    let text = report.format(ValidationReportFormat::Text);
+    assert!(text.contains("after termination"), "{text}");
+}
+
+#[test]
+fn test_validate_filter_chains() {
+    let alice = create_test_marks(3, ProvenanceMarkResolution::Low, "alice");
+    let bob = create_test_marks(3, ProvenanceMarkResolution::Low, "bob");
+    let carol = create_test_marks(2, ProvenanceMarkResolution::Low, "carol");
+    let mut all_marks = alice.clone();
+    all_marks.extend(bob.clone());
+    all_marks.push(bob[1].clone());
+    all_marks.extend(carol);
+    all_marks.push(alice[0].clone());
+    let report = ProvenanceMark::validate(all_marks);
+    assert_eq!(report.chains().len(), 3);
+    assert_eq!(report.duplicates_removed(), 2);
+
+    let mut bob_marks = bob.clone();
+    bob_marks.push(bob[1].clone());
+    let expected = ProvenanceMark::validate(bob_marks);
+
+    let filtered = report.filter_chain_id(bob[0].chain_id());
+    assert_eq!(filtered.chains().len(), 1);
+    assert_eq!(filtered.marks().len(), 3);
+    assert_eq!(filtered.duplicates_removed(), 1);
+    assert_eq!(filtered.digest(), expected.digest());
+    assert_eq!(
+        serde_json::to_value(&filtered).unwrap(),
+        serde_json::to_value(&expected).unwrap()
+    );
+    assert_eq!(
+        filtered.format(ValidationReportFormat::Text),
+        expected.format(ValidationReportFormat::Text)
+    );
+
+    let others =
+        report.filter_chains(|chain| chain.chain_id() != bob[0].chain_id());
+    assert_eq!(others.chains().len(), 2);
+    assert_eq!(others.marks().len(), 5);
+    assert_eq!(others.duplicates_removed(), 1);
+
+    assert!(report.filter_chain_id(&[0; 4]).chains().is_empty());
+}
+
+#[test]
+fn test_validate_self_check() {
+    let marks = create_test_marks(3, ProvenanceMarkResolution::Low, "test");
+    let options = ValidationOptions::default()
+        .with_policy(ValidationPolicy::default().with_self_check(true));
+    let report = ValidationReport::validate_opts(marks.clone(), &options);
+    assert!(!report.has_issues());
+    assert!(report.options().self_check());
+
+    // The second mark rebuilt with the genesis key, which is otherwise
+    // consistent but claims to be genesis at seq 1
+    let forged = ProvenanceMark::from_fields(
+        marks[1].res(),
+        marks[0].key().clone(),
+        marks[1].hash().clone(),
+        marks[1].chain_id().clone(),
+        marks[1].seq().into(),
+        marks[1].date(),
+        Vec::new(),
+    )
+    .unwrap();
+    let marks = vec![marks[0].clone(), forged, marks[2].clone()];
+
+    // Without the option, only the broken link is reported
+    let report = ValidationReport::validate(marks.clone());
+    assert!(report.chains()[0].sequences().iter().all(|sequence| {
+        sequence
+            .marks()
+            .iter()
+            .flat_map(|m| m.issues())
+            .all(|issue| {
+                !matches!(issue, ValidationIssue::SelfCheckFailed { .. })
+            })
+    }));
+
+    let report = ValidationReport::validate_opts(marks, &options);
+    let flagged = report.chains()[0]
+        .sequences()
+        .iter()
+        .flat_map(|sequence| sequence.marks())
+        .find(|flagged| flagged.mark().seq() == 1)
+        .unwrap();
+    let issue = ValidationIssue::SelfCheckFailed {
+        failures: vec![SelfCheckFailure::GenesisKeyAtNonZeroSeq],
+    };
+    assert!(flagged.issues().contains(&issue), "{:?}", flagged.issues());
+    assert_eq!(
+        issue.to_string(),
+        "mark fails self-check: key equals chain ID at non-zero sequence number"
+    );
+    let text = report.format(ValidationReportFormat::Text);
+    assert!(text.contains("self-check failed"), "{text}");
+}
+
+#[test]
+fn test_validate_date_encoding_check() {
+    let policy = ValidationPolicy::default().with_date_encoding_check(true);
+    let options = ValidationOptions::default().with_policy(policy);
+    for res in [
+        ProvenanceMarkResolution::Low,
+        ProvenanceMarkResolution::Medium,
+        ProvenanceMarkResolution::Quartile,
+        ProvenanceMarkResolution::High,
+    ] {
+        let marks = create_test_marks(4, res, "test");
+
+        // `from_fields` encodes the date itself, so a reassembled mark
+        // always carries conforming date bytes.
+        let rebuilt = ProvenanceMark::from_fields(
+            res,
+            marks[3].key().clone(),
+            marks[3].hash().clone(),
+            marks[3].chain_id().clone(),
+            marks[3].seq().into(),
+            marks[3].date(),
+            Vec::new(),
+        )
+        .unwrap();
+        assert_eq!(rebuilt.date_bytes(), marks[3].date_bytes());
+
+        let report = ValidationReport::validate_opts(marks, &options);
+        assert!(!report.has_issues(), "{:?}", res);
+        assert!(report.options().date_encoding_check());
+    }
+
+    let issue = ValidationIssue::DateEncodingMismatch {
+        stored: vec![0x2a, 0x41, 0xcc, 0x40],
+        reencoded: vec![0x2a, 0x41, 0xcc, 0x41],
+    };
+    assert_eq!(issue.code(), "DateEncodingMismatch");
+    assert_eq!(
+        issue.to_string(),
+        "date bytes 2a41cc40 do not match the date's encoding 2a41cc41"
+    );
+    assert_eq!(
+        serde_json::to_value(&issue).unwrap(),
+        serde_json::json!({
+            "type": "DateEncodingMismatch",
+            "data": { "stored": "2a41cc40", "reencoded": "2a41cc41" }
+        })
+    );
+}
+
+/// A mark that claims to follow `marks[1]` but commits to `marks[0]`'s hash.
+fn hash_mismatch_mark(marks: &[ProvenanceMark]) -> ProvenanceMark {
+    ProvenanceMark::new(
+        marks[1].res(),
+        marks[1].key().clone(),
+        LinkKey::new(marks[1].res(), marks[0].hash().as_bytes()).unwrap(),
+        marks[1].chain_id().clone(),
+        2,
+        Date::from_string("2023-06-22T12:00:00Z").unwrap(),
+        None::<String>,
+    )
+    .unwrap()
+}
+
+#[test]
+fn test_validate_hash_mismatch_evidence() {
+    let marks = create_test_marks(3, ProvenanceMarkResolution::Low, "test");
+    let bad_mark = hash_mismatch_mark(&marks);
+    // Out of order, so the positions differ from the sequence numbers
+    let input = vec![bad_mark, marks[0].clone(), marks[1].clone()];
+
+    let report = ProvenanceMark::validate(input.clone());
+    let flagged = &report.chains()[0].sequences()[1].marks()[0];
+    assert_eq!(flagged.issues().len(), 2);
+    assert!(flagged.evidence().is_empty());
+
+    let policy = ValidationPolicy::new().with_include_evidence(true);
+    let report = ProvenanceMark::validate_with_policy(input.clone(), &policy);
+    assert!(report.options().include_evidence());
+    let flagged = &report.chains()[0].sequences()[1].marks()[0];
+    let [evidence] = flagged.evidence() else {
+        panic!("{:?}", flagged.evidence());
+    };
+    assert_eq!(evidence.rule(), "HashMismatch");
+    assert_eq!(evidence.previous_mark(), &marks[1].id_hex()[..8]);
+    assert_eq!(evidence.previous_position(), 2);
+    assert_eq!(evidence.position(), 0);
+    assert_eq!(
+        evidence.values().iter().collect::<Vec<_>>(),
+        [
+            (&"actual_hash", &"1b806d6c".to_string()),
+            (&"expected_hash", &"d446017b".to_string()),
+        ]
+    );
+
+    let text = report.format(ValidationReportFormat::Text);
+    assert!(
+        text.contains(
+            "\n  2: 09cca821 (hash mismatch, reused key, after 1b806d6c)\n    \
+             why: \
+             HashMismatch against 1b806d6c (input 0 after input 2): \
+             actual_hash 1b806d6c, expected_hash d446017b"
+        ),
+        "{text}"
+    );
+
+    // Only linkage issues have evidence, which goes with the issue when it
+    // is suppressed
+    let options = ValidationOptions::new()
+        .with_policy(policy)
+        .suppress(SuppressionRule::new(marks[0].chain_id(), "HashMismatch"));
+    let report = ProvenanceMark::validate_opts(input, &options);
+    let flagged = &report.chains()[0].sequences()[1].marks()[0];
+    assert!(matches!(
+        flagged.issues(),
+        [ValidationIssue::ReusedKey { .. }]
+    ));
+    assert!(flagged.evidence().is_empty());
+}
+
+#[test]
+fn test_validate_date_ordering_evidence() {
+    let marks = create_test_marks(1, ProvenanceMarkResolution::Low, "test");
+    let mut generator = ProvenanceMarkGenerator::new_with_passphrase(
+        ProvenanceMarkResolution::Low,
+        "test",
+    );
+    generator.commit_speculation(&marks[0]).unwrap();
+    let earlier = generator
+        .next(Date::from_string("2023-06-19").unwrap(), None::<String>);
+
+    let report = ProvenanceMark::validate_with_policy(
+        vec![marks[0].clone(), earlier.clone()],
+        &ValidationPolicy::new().with_include_evidence(true),
+    );
+    let flagged = &report.chains()[0].sequences()[1].marks()[0];
+    assert!(matches!(
+        flagged.issues(),
+        [ValidationIssue::DateOrdering { .. }]
+    ));
+    let [evidence] = flagged.evidence() else {
+        panic!("{:?}", flagged.evidence());
+    };
+    assert_eq!(evidence.rule(), "DateOrdering");
+    assert_eq!(evidence.previous_mark(), &marks[0].id_hex()[..8]);
+    assert_eq!((evidence.previous_position(), evidence.position()), (0, 1));
+    assert_eq!(
+        evidence.values().get("previous_date"),
+        Some(&marks[0].date().to_string())
+    );
+    assert_eq!(
+        evidence.values().get("date"),
+        Some(&"2023-06-19".to_string())
+    );
+    assert_eq!(
+        evidence.to_string(),
+        format!(
+            "DateOrdering against {} (input 1 after input 0): date \
+             2023-06-19, previous_date {}",
+            &marks[0].id_hex()[..8],
+            marks[0].date()
+        )
+    );
+}
+
+#[test]
+#[cfg(feature = "json")]
+fn test_validate_evidence_json() {
+    let marks = create_test_marks(3, ProvenanceMarkResolution::Low, "test");
+    let input = vec![
+        marks[0].clone(),
+        marks[1].clone(),
+        hash_mismatch_mark(&marks),
+    ];
+
+    let json: serde_json::Value = serde_json::from_str(
+        &ProvenanceMark::validate(input.clone())
+            .format(ValidationReportFormat::JsonCompact),
+    )
+    .unwrap();
+    let flagged = &json["chains"][0]["sequences"][1]["marks"][0];
+    assert!(flagged.get("evidence").is_none());
+
+    let report = ProvenanceMark::validate_with_policy(
+        input,
+        &ValidationPolicy::new().with_include_evidence(true),
+    );
+    let json: serde_json::Value = serde_json::from_str(
+        &report.format(ValidationReportFormat::JsonCompact),
+    )
+    .unwrap();
+    assert_eq!(json["options"]["includeEvidence"], true);
+    assert_eq!(
+        json["chains"][0]["sequences"][1]["marks"][0]["evidence"],
+        serde_json::json!([{
+            "rule": "HashMismatch",
+            "previous_mark": "1b806d6c",
+            "previous_position": 1,
+            "position": 2,
+            "values": {
+                "actual_hash": "1b806d6c",
+                "expected_hash": "d446017b",
+            },
+        }])
+    );
+}
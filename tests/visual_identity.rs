@@ -0,0 +1,80 @@
+use chrono::TimeZone;
+use dcbor::Date;
+use provenance_mark::*;
+
+fn genesis_mark(
+    res: ProvenanceMarkResolution,
+    passphrase: &str,
+) -> ProvenanceMark {
+    let mut generator =
+        ProvenanceMarkGenerator::new_with_passphrase(res, passphrase);
+    let date = Date::from_datetime(
+        chrono::Utc
+            .with_ymd_and_hms(2023, 6, 20, 12, 0, 0)
+            .single()
+            .unwrap(),
+    );
+    generator.next(date, None::<&str>)
+}
+
+#[test]
+fn test_visual_identity_pinned_values() {
+    // These values are a stability guarantee: they must never change.
+    let cases = [
+        (
+            ProvenanceMarkResolution::Low,
+            "Wolf",
+            "090bf2f8",
+            [243, 254, 124],
+            "🐺",
+            "WOLF ZONE KITE MANY",
+        ),
+        (
+            ProvenanceMarkResolution::Quartile,
+            "Wolf",
+            "090bf2f8b55be45b4661b24b7e9c340c",
+            [140, 87, 86],
+            "🟩",
+            "LUCK HANG HALF TWIN",
+        ),
+        (
+            ProvenanceMarkResolution::High,
+            "Wolf",
+            "090bf2f8b55be45b4661b24b7e9c340cf9464c5fe95c84f580954aaabe085e7c",
+            [52, 253, 81],
+            "💪",
+            "EDGE ZINC GRAY WAND",
+        ),
+        (
+            ProvenanceMarkResolution::Low,
+            "test",
+            "b16a7cbd",
+            [72, 38, 76],
+            "🥝",
+            "FUND DAYS GEMS COLA",
+        ),
+    ];
+
+    for (res, passphrase, chain_id, color, emoji, words) in cases {
+        let mark = genesis_mark(res, passphrase);
+        assert_eq!(hex::encode(mark.chain_id()), chain_id);
+
+        let identity = mark.visual_identity();
+        assert_eq!(identity.color_rgb(), color);
+        assert_eq!(identity.emoji(), emoji);
+        assert_eq!(identity.bytewords(), words);
+    }
+}
+
+#[test]
+fn test_visual_identity_matches_chain_report() {
+    let mark = genesis_mark(ProvenanceMarkResolution::Medium, "Wolf");
+    let report = ProvenanceMark::validate(vec![mark.clone()]);
+    let chain = &report.chains()[0];
+    assert_eq!(chain.visual_identity(), mark.visual_identity());
+    assert_eq!(
+        chain.visual_identity(),
+        VisualIdentity::from_chain_id(mark.chain_id())
+    );
+    assert_eq!(mark.visual_identity().color_hex(), "#774ae3");
+}
@@ -0,0 +1,72 @@
+#![cfg(feature = "url")]
+
+use bc_ur::UREncodable;
+use provenance_mark::{web::*, *};
+
+mod common;
+use common::same_day_marks;
+
+#[test]
+fn test_extract_marks_from_html() {
+    let marks = same_day_marks(ProvenanceMarkResolution::Low, "Wolf", 4);
+    let url = marks[1]
+        .to_url("https://example.com/essay?lang=en")
+        .to_string()
+        .replace('&', "&amp;");
+    let encoded_ur = marks[3].ur_string().replace(':', "&#58;");
+    let html = format!(
+        r#"<!DOCTYPE html>
+<HTML lang=en>
+<head>
+  <meta charset="utf-8">
+  <meta name="description" content="ur:provenance/not-this-one">
+  <meta property='og:title' content='An Essay'>
+  <!-- <meta name="provenance" content="ur:provenance/commented-out"> -->
+  <meta name="provenance" content="{}" />
+  <link rel="stylesheet" href="/style.css?provenance=ignored">
+  <LINK REL='alternate Provenance' HREF='{}'>
+  <Meta Name=PROVENANCE Content={}>
+  <meta content="ur:provenance/lfaegdtokebznlahftbsnlaxps" name="provenance">
+  <meta
+    name = "provenance"
+    content = "{}">
+</head>
+<body><p>1 < 2 & 3 > 2</p></body>
+</HTML>"#,
+        marks[0].ur_string(),
+        url,
+        marks[2].ur_string().to_uppercase(),
+        encoded_ur,
+    );
+
+    let results = extract_marks_from_html(&html);
+    assert_eq!(results.len(), 5);
+    assert_eq!(results[0].as_ref().unwrap(), &marks[0]);
+    assert_eq!(results[1].as_ref().unwrap(), &marks[1]);
+    assert_eq!(results[2].as_ref().unwrap(), &marks[2]);
+    assert!(results[3].is_err());
+    assert_eq!(results[4].as_ref().unwrap(), &marks[3]);
+}
+
+#[test]
+fn test_extract_marks_from_relative_link() {
+    let mark = &same_day_marks(ProvenanceMarkResolution::Low, "Wolf", 4)[0];
+    let html = format!(
+        "<link href=\"?provenance={}\" rel=\"provenance\">",
+        mark.to_url_encoding()
+    );
+    let results = extract_marks_from_html(&html);
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0].as_ref().unwrap(), mark);
+}
+
+#[test]
+fn test_extract_marks_from_html_without_marks() {
+    assert!(extract_marks_from_html("").is_empty());
+    assert!(
+        extract_marks_from_html(
+            "<html><head><title>No marks</title></head></html>"
+        )
+        .is_empty()
+    );
+}